@@ -120,6 +120,12 @@ pub struct Context {
     /// Derives added to event structs and enums.
     extra_derives: Vec<Path>,
 
+    /// Whether to generate builders for the call structs.
+    call_builders: bool,
+
+    /// Whether to accept `impl Into<T>` for address and integer function parameters.
+    into_args: bool,
+
     /// Manually specified event aliases.
     event_aliases: BTreeMap<String, Ident>,
 
@@ -165,6 +171,9 @@ impl Context {
             // 7. The deploy method, only if the contract has a bytecode object
             let deployment_methods = self.deployment_methods();
 
+            // 8. The typed topic filters for the indexed event parameters
+            let event_topic_filters = self.event_topic_filters()?;
+
             let ethers_core = ethers_core_crate();
             let ethers_contract = ethers_contract_crate();
             let ethers_providers = ethers_providers_crate();
@@ -191,6 +200,8 @@ impl Context {
                         Self::new(contract.address(), contract.client())
                     }
                 }
+
+                #event_topic_filters
             }
         };
 
@@ -231,10 +242,15 @@ impl Context {
         let internal_structs = if human_readable {
             let mut internal_structs = InternalStructs::default();
             // the types in the abi_parser are already valid rust types so simply clone them to make
-            // it consistent with the `RawAbi` variant
-            internal_structs
-                .rust_type_names
-                .extend(abi_parser.function_params.values().map(|ty| (ty.clone(), ty.clone())));
+            // it consistent with the `RawAbi` variant. This includes the structs that are only
+            // nested in other structs, so they can be shared with the structs they're nested in
+            internal_structs.rust_type_names.extend(
+                abi_parser
+                    .function_params
+                    .values()
+                    .chain(abi_parser.structs.keys())
+                    .map(|ty| (ty.clone(), ty.clone())),
+            );
             internal_structs.function_params = abi_parser.function_params.clone();
             internal_structs.event_params = abi_parser.event_params.clone();
             internal_structs.outputs = abi_parser.outputs.clone();
@@ -243,6 +259,8 @@ impl Context {
         } else {
             match serde_json::from_str::<JsonAbi>(&abi_str)? {
                 JsonAbi::Object(obj) => {
+                    // unlinked bytecode is not valid hex, so it's dropped when deserializing and
+                    // no `deploy` function is generated for it
                     contract_bytecode = obj.bytecode;
                     contract_deployed_bytecode = obj.deployed_bytecode;
                     InternalStructs::new(obj.abi)
@@ -310,7 +328,13 @@ impl Context {
             method_aliases,
             error_aliases: Default::default(),
             event_aliases,
-            extra_derives: args.derives,
+            extra_derives: args
+                .derives
+                .into_iter()
+                .filter(|derive| !util::is_builtin_derive(derive))
+                .collect(),
+            call_builders: args.call_builders,
+            into_args: args.into_args,
         })
     }
 
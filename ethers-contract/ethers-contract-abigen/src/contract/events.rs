@@ -4,12 +4,12 @@ use super::{structs::expand_event_struct, types, Context};
 use crate::util;
 use ethers_core::{
     abi::{Event, EventExt},
-    macros::{ethers_contract_crate, ethers_core_crate},
+    macros::{ethers_contract_crate, ethers_core_crate, ethers_providers_crate},
 };
 use eyre::Result;
 use inflector::Inflector;
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use std::collections::BTreeMap;
 
 impl Context {
@@ -50,16 +50,108 @@ impl Context {
         })
     }
 
+    /// Generate the typed topic filters for all the events with indexed parameters
+    pub fn event_topic_filters(&self) -> Result<TokenStream> {
+        let sorted_events: BTreeMap<_, _> = self.abi.events.iter().collect();
+        let topic_filters = sorted_events
+            .values()
+            .flat_map(std::ops::Deref::deref)
+            .map(|event| self.expand_topic_filter(event))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(quote! {
+            #( #topic_filters )*
+        })
+    }
+
+    /// Expands into a trait with a `with_<param>` method for each indexed parameter of the event,
+    /// which sets the corresponding topic of an `Event` builder for this event.
+    ///
+    /// The prefix keeps parameters like `address` or `from_block` from clashing with the inherent
+    /// methods of `Event`.
+    ///
+    /// Returns `None` if the event has no indexed parameters.
+    fn expand_topic_filter(&self, event: &Event) -> Result<Option<TokenStream>> {
+        let alias = self.event_aliases.get(&event.abi_signature()).cloned();
+        let struct_name = event_struct_name(&event.name, alias);
+        let fields = types::expand_event_inputs(event, &self.internal_structs)?;
+
+        // topic0 is the event signature, unless the event is anonymous
+        let offset = usize::from(!event.anonymous);
+        let ethers_core = ethers_core_crate();
+        let (decls, impls): (Vec<_>, Vec<_>) = fields
+            .iter()
+            .zip(&event.inputs)
+            .filter(|((_, _, indexed), _)| *indexed)
+            .enumerate()
+            .map(|(idx, ((name, ty, _), input))| {
+                let topic = format_ident!("topic{}", idx + offset);
+                let method = format_ident!("with_{}", name.to_string());
+                let doc_str = if input.name.is_empty() {
+                    format!("Filters on the indexed parameter at position {idx}.")
+                } else {
+                    format!("Filters on the indexed `{}` parameter.", input.name)
+                };
+                let decl = quote! {
+                    #[doc = #doc_str]
+                    fn #method(self, #name: #ty) -> Self;
+                };
+                let imp = quote! {
+                    fn #method(self, #name: #ty) -> Self {
+                        let topic = #ethers_core::abi::encode(&[
+                            #ethers_core::abi::Tokenizable::into_token(#name),
+                        ]);
+                        self.#topic(#ethers_core::types::H256::from_slice(&topic))
+                    }
+                };
+                (decl, imp)
+            })
+            .unzip();
+
+        if decls.is_empty() {
+            return Ok(None)
+        }
+
+        let trait_name = format_ident!("{struct_name}Topics");
+        let doc_str = format!(
+            "Typed topic filters for the indexed parameters of the contract's `{}` event",
+            event.name
+        );
+        let ethers_contract = ethers_contract_crate();
+        let ethers_providers = ethers_providers_crate();
+
+        Ok(Some(quote! {
+            #[doc = #doc_str]
+            pub trait #trait_name {
+                #( #decls )*
+            }
+
+            impl<B, M> #trait_name for #ethers_contract::builders::Event<B, M, #struct_name>
+            where
+                B: ::std::borrow::Borrow<M>,
+                M: #ethers_providers::Middleware,
+            {
+                #( #impls )*
+            }
+        }))
+    }
+
     /// Generate an enum with a variant for each event
     fn expand_events_enum(&self) -> TokenStream {
-        let variants = self
+        let struct_name = |e: &Event| {
+            event_struct_name(&e.name, self.event_aliases.get(&e.abi_signature()).cloned())
+        };
+        let variants = self.abi.events.values().flatten().map(struct_name).collect::<Vec<_>>();
+        // anonymous events match any log with the right number and types of topics and data, so
+        // the events identified by their signature are decoded first
+        let decode_order = self
             .abi
             .events
             .values()
             .flatten()
-            .map(|e| {
-                event_struct_name(&e.name, self.event_aliases.get(&e.abi_signature()).cloned())
-            })
+            .filter(|e| !e.anonymous)
+            .chain(self.abi.events.values().flatten().filter(|e| e.anonymous))
+            .map(struct_name)
             .collect::<Vec<_>>();
 
         let enum_name = self.expand_event_enum_name();
@@ -82,8 +174,8 @@ impl Context {
             impl #ethers_contract::EthLogDecode for #enum_name {
                 fn decode_log(log: &#ethers_core::abi::RawLog) -> ::core::result::Result<Self, #ethers_core::abi::Error> {
                     #(
-                        if let Ok(decoded) = #variants::decode_log(log) {
-                            return Ok(#enum_name::#variants(decoded))
+                        if let Ok(decoded) = #decode_order::decode_log(log) {
+                            return Ok(#enum_name::#decode_order(decoded))
                         }
                     )*
                     Err(#ethers_core::abi::Error::InvalidData)
@@ -118,33 +210,48 @@ impl Context {
     /// Expands the `events` function that bundles all declared events of this contract
     fn expand_events_method(&self) -> Option<TokenStream> {
         let sorted_events: BTreeMap<_, _> = self.abi.events.clone().into_iter().collect();
+        let events = sorted_events.values().flatten().collect::<Vec<_>>();
+        if events.is_empty() {
+            return None
+        }
 
-        let mut iter = sorted_events.values().flatten();
         let ethers_contract = ethers_contract_crate();
+        let ethers_core = ethers_core_crate();
 
-        if let Some(event) = iter.next() {
-            let ty = if iter.next().is_some() {
-                self.expand_event_enum_name()
-            } else {
+        let struct_names = events
+            .iter()
+            .map(|event| {
                 event_struct_name(
                     &event.name,
                     self.event_aliases.get(&event.abi_signature()).cloned(),
                 )
-            };
-
-            Some(quote! {
-                /// Returns an `Event` builder for all the events of this contract.
-                pub fn events(&self) -> #ethers_contract::builders::Event<
-                    ::std::sync::Arc<M>,
-                    M,
-                    #ty,
-                > {
-                    self.0.event_with_filter(::core::default::Default::default())
-                }
             })
+            .collect::<Vec<_>>();
+        let ty =
+            if events.len() > 1 { self.expand_event_enum_name() } else { struct_names[0].clone() };
+
+        // anonymous events can't be matched by their signature, so `topic0` can only be
+        // restricted to the set of event signatures if all of them are regular events
+        let filter = if events.iter().any(|event| event.anonymous) {
+            quote!(::core::default::Default::default())
         } else {
-            None
-        }
+            quote! {
+                #ethers_core::types::Filter::new().topic0(::std::vec![
+                    #( <#struct_names as #ethers_contract::EthEvent>::signature(), )*
+                ])
+            }
+        };
+
+        Some(quote! {
+            /// Returns an `Event` builder for all the events of this contract.
+            pub fn events(&self) -> #ethers_contract::builders::Event<
+                ::std::sync::Arc<M>,
+                M,
+                #ty,
+            > {
+                self.0.event_with_filter(#filter)
+            }
+        })
     }
 
     /// Expands into a single method for contracting an event stream.
@@ -290,6 +397,59 @@ mod tests {
         });
     }
 
+    #[test]
+    fn expand_transfer_topic_filter() {
+        let event = Event {
+            name: "Transfer".into(),
+            inputs: vec![
+                EventParam { name: "from".into(), kind: ParamType::Address, indexed: true },
+                EventParam { name: "amount".into(), kind: ParamType::Uint(256), indexed: false },
+                EventParam { name: "to".into(), kind: ParamType::Address, indexed: true },
+            ],
+            anonymous: false,
+        };
+        let cx = test_context();
+        #[rustfmt::skip]
+        assert_quote!(cx.expand_topic_filter(&event).unwrap().unwrap(), {
+            #[doc = "Typed topic filters for the indexed parameters of the contract's `Transfer` event"]
+            pub trait TransferFilterTopics {
+                #[doc = "Filters on the indexed `from` parameter."]
+                fn with_from(self, from: ::ethers_core::types::Address) -> Self;
+                #[doc = "Filters on the indexed `to` parameter."]
+                fn with_to(self, to: ::ethers_core::types::Address) -> Self;
+            }
+
+            impl<B, M> TransferFilterTopics for ::ethers_contract::builders::Event<B, M, TransferFilter>
+            where
+                B: ::std::borrow::Borrow<M>,
+                M: ::ethers_providers::Middleware,
+            {
+                fn with_from(self, from: ::ethers_core::types::Address) -> Self {
+                    let topic = ::ethers_core::abi::encode(&[
+                        ::ethers_core::abi::Tokenizable::into_token(from),
+                    ]);
+                    self.topic1(::ethers_core::types::H256::from_slice(&topic))
+                }
+                fn with_to(self, to: ::ethers_core::types::Address) -> Self {
+                    let topic = ::ethers_core::abi::encode(&[
+                        ::ethers_core::abi::Tokenizable::into_token(to),
+                    ]);
+                    self.topic2(::ethers_core::types::H256::from_slice(&topic))
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn expand_topic_filter_without_indexed_params() {
+        let event = Event {
+            name: "Foo".into(),
+            inputs: vec![EventParam { name: "a".into(), kind: ParamType::Bool, indexed: false }],
+            anonymous: false,
+        };
+        assert!(test_context().expand_topic_filter(&event).unwrap().is_none());
+    }
+
     #[test]
     fn expand_data_struct_value() {
         let event = Event {
@@ -10,7 +10,7 @@ use ethers_core::{
 use eyre::{Context as _, Result};
 use inflector::Inflector;
 use proc_macro2::{Literal, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use std::collections::{btree_map::Entry, BTreeMap, HashMap, HashSet};
 use syn::Ident;
 
@@ -24,6 +24,7 @@ const NAME_ALIASING_OVERLOADED_FUNCTIONS_CAP: usize = 3;
 impl Context {
     /// Expands all method implementations
     pub(crate) fn methods_and_call_structs(&self) -> Result<(TokenStream, TokenStream)> {
+        self.check_selector_collisions()?;
         let aliases = self.get_method_aliases()?;
         let sorted_functions: BTreeMap<_, _> = self.abi.functions.iter().collect();
         let functions = sorted_functions
@@ -48,6 +49,30 @@ impl Context {
         Ok((function_impls, all_structs))
     }
 
+    /// Ensures that no two functions share the same selector.
+    ///
+    /// The generated calls enum dispatches on the selector, so from colliding functions, only
+    /// one could ever be decoded.
+    fn check_selector_collisions(&self) -> Result<()> {
+        let mut selectors: BTreeMap<Selector, String> = BTreeMap::new();
+        for function in self.abi.functions() {
+            let signature = function.abi_signature();
+            match selectors.entry(function.selector()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(signature);
+                }
+                // duplicate definitions of the same function are not a collision
+                Entry::Occupied(entry) if *entry.get() == signature => {}
+                Entry::Occupied(entry) => eyre::bail!(
+                    "functions `{}` and `{signature}` have the same selector 0x{}",
+                    entry.get(),
+                    hex::encode(entry.key())
+                ),
+            }
+        }
+        Ok(())
+    }
+
     /// Returns all deploy (constructor) implementations
     #[cfg(feature = "providers")]
     pub(crate) fn deployment_methods(&self) -> Option<TokenStream> {
@@ -129,11 +154,16 @@ impl Context {
 
         let ethers_contract = ethers_contract_crate();
 
+        let builder = (self.call_builders && !all_anonymous_fields && !fields.is_empty())
+            .then(|| expand_call_builder(&struct_name, &fields));
+
         Ok(quote! {
             #[doc = #doc_str]
             #[derive(Clone, #ethers_contract::EthCall, #ethers_contract::EthDisplay, #derives)]
             #[ethcall( name = #function_name, abi = #abi_signature )]
             pub #call_type_definition
+
+            #builder
         })
     }
 
@@ -218,9 +248,14 @@ impl Context {
             impl #ethers_core::abi::AbiDecode for #enum_name {
                 fn decode(data: impl AsRef<[u8]>) -> ::core::result::Result<Self, #ethers_core::abi::AbiError> {
                     let data = data.as_ref();
+                    if data.len() < 4 {
+                        return Err(#ethers_core::abi::Error::InvalidData.into())
+                    }
+                    let selector = &data[..4];
                     #(
-                        if let Ok(decoded) = <#struct_names as #ethers_core::abi::AbiDecode>::decode(data) {
-                            return Ok(Self::#variant_names(decoded))
+                        if selector == <#struct_names as #ethers_contract::EthCall>::selector() {
+                            return <#struct_names as #ethers_core::abi::AbiDecode>::decode(data)
+                                .map(Self::#variant_names)
                         }
                     )*
                     Err(#ethers_core::abi::Error::InvalidData.into())
@@ -335,8 +370,22 @@ impl Context {
         let selector_tokens = expand_selector(selector);
 
         let contract_args = self.expand_contract_call_args(function);
-        let function_params =
-            self.expand_input_params(function)?.into_iter().map(|(name, ty)| quote! { #name: #ty });
+        let mut conversions = Vec::new();
+        let function_params = self
+            .expand_input_params(function)?
+            .into_iter()
+            .zip(&function.inputs)
+            .map(|((name, ty), param)| {
+                if self.into_args && is_into_arg(&param.kind) {
+                    let param = quote! { #name: impl ::core::convert::Into<#ty> };
+                    conversions
+                        .push(quote! { let #name: #ty = ::core::convert::Into::into(#name); });
+                    param
+                } else {
+                    quote! { #name: #ty }
+                }
+            })
+            .collect::<Vec<_>>();
         let function_params = quote! { #( , #function_params )* };
 
         let outputs = {
@@ -359,6 +408,7 @@ impl Context {
         Ok(quote! {
             #[doc = #doc_str]
             pub fn #function_name(&self #function_params) -> #ethers_contract::builders::ContractCall<M, #outputs> {
+                #( #conversions )*
                 self.0.method_hash(#selector_tokens, #contract_args)
                     .expect("method not found (this should never happen)")
             }
@@ -578,6 +628,12 @@ fn expand_selector(selector: Selector) -> TokenStream {
     quote!([ #( #bytes ),* ])
 }
 
+/// Returns true if function parameters of this type are generated as `impl Into<T>` with the
+/// `into_args` option, i.e. addresses and integers
+fn is_into_arg(kind: &ParamType) -> bool {
+    matches!(kind, ParamType::Address | ParamType::Uint(_) | ParamType::Int(_))
+}
+
 /// Represents the aliases to use when generating method related elements
 #[derive(Debug, Clone)]
 pub struct MethodAlias {
@@ -602,6 +658,66 @@ fn expand_function_name(function: &Function, alias: Option<&MethodAlias>) -> Ide
     }
 }
 
+/// Expands to a builder for the call struct with the given named fields, and a `builder` function
+/// on the call struct returning it
+fn expand_call_builder(struct_name: &Ident, fields: &[(TokenStream, TokenStream)]) -> TokenStream {
+    let builder_name = format_ident!("{struct_name}Builder");
+    let ethers_contract = ethers_contract_crate();
+
+    let field_names = fields.iter().map(|(name, _)| name).collect::<Vec<_>>();
+    let field_types = fields.iter().map(|(_, ty)| ty).collect::<Vec<_>>();
+    // a setter named `build` would collide with the builder's `build` method
+    let setter_names = field_names.iter().map(|name| {
+        if name.to_string() == "build" {
+            quote!(build_)
+        } else {
+            quote!(#name)
+        }
+    });
+    let setter_docs = field_names.iter().map(|name| format!("Sets the `{name}` field"));
+    let unset_errors = field_names.iter().map(|name| {
+        let name = name.to_string();
+        name.strip_prefix("r#").map(str::to_string).unwrap_or(name)
+    });
+    let builder_doc = format!("Builder for [`{struct_name}`]");
+
+    quote! {
+        impl #struct_name {
+            #[doc = #builder_doc]
+            pub fn builder() -> #builder_name {
+                ::core::default::Default::default()
+            }
+        }
+
+        #[doc = #builder_doc]
+        #[derive(Clone, Default)]
+        pub struct #builder_name {
+            #( #field_names: ::core::option::Option<#field_types>, )*
+        }
+
+        impl #builder_name {
+            #(
+                #[doc = #setter_docs]
+                pub fn #setter_names(mut self, #field_names: #field_types) -> Self {
+                    self.#field_names = ::core::option::Option::Some(#field_names);
+                    self
+                }
+            )*
+
+            /// Builds the call, returning an error if one of its fields was not set
+            pub fn build(self) -> ::core::result::Result<#struct_name, #ethers_contract::UninitializedFieldError> {
+                ::core::result::Result::Ok(#struct_name {
+                    #(
+                        #field_names: self.#field_names.ok_or(
+                            #ethers_contract::UninitializedFieldError(#unset_errors),
+                        )?,
+                    )*
+                })
+            }
+        }
+    }
+}
+
 /// Expands the name of a struct by a postfix
 fn expand_struct_name_postfix(
     function: &Function,
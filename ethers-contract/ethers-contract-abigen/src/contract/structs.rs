@@ -48,22 +48,79 @@ impl Context {
         }
     }
 
+    /// Returns the type definition for the struct with the given identifier, named `rust_name`
+    /// instead of its own name. The fields of the struct that are structs whose Rust name is a key
+    /// of `renames` refer to the value instead.
+    ///
+    /// This is used to give distinct names to structs that share a name with structs of other
+    /// contracts but have different definitions.
+    pub fn renamed_struct_definition(
+        &self,
+        id: &str,
+        rust_name: &str,
+        renames: &HashMap<String, String>,
+    ) -> Result<TokenStream> {
+        if self.human_readable {
+            self.generate_human_readable_struct_as(id, rust_name, renames)
+        } else {
+            self.generate_internal_struct_as(id, Some(rust_name), renames)
+        }
+    }
+
+    /// Returns the canonical signature of the struct with the given identifier, including the
+    /// field names, like `Order(address maker,uint256 amount)`.
+    ///
+    /// This is used to tell apart structs with the same name but different definitions.
+    pub fn struct_signature(&self, id: &str) -> Option<String> {
+        let (sol_struct, types) = if self.human_readable {
+            (self.abi_parser.structs.get(id)?, self.abi_parser.struct_tuples.get(id)?.as_slice())
+        } else {
+            match self.internal_structs.struct_tuples.get(id)? {
+                ParamType::Tuple(types) => {
+                    (self.internal_structs.structs.get(id)?, types.as_slice())
+                }
+                _ => return None,
+            }
+        };
+        let fields = sol_struct
+            .fields()
+            .iter()
+            .zip(types)
+            .map(|(field, ty)| format!("{ty} {}", field.name()))
+            .collect::<Vec<_>>()
+            .join(",");
+        Some(format!("{}({fields})", sol_struct.name()))
+    }
+
     /// Generates the type definition for the name that matches the given identifier
     fn generate_internal_struct(&self, id: &str) -> Result<TokenStream> {
+        self.generate_internal_struct_as(id, None, &HashMap::new())
+    }
+
+    /// Generates the type definition for the given identifier, named `rust_name` if set
+    fn generate_internal_struct_as(
+        &self,
+        id: &str,
+        rust_name: Option<&str>,
+        renames: &HashMap<String, String>,
+    ) -> Result<TokenStream> {
         let sol_struct =
             self.internal_structs.structs.get(id).ok_or_else(|| eyre!("Struct not found"))?;
-        let struct_name = self
-            .internal_structs
-            .rust_type_names
-            .get(id)
-            .ok_or_else(|| eyre!("No types found for {id}"))?;
+        let struct_name = match rust_name {
+            Some(name) => name,
+            None => self
+                .internal_structs
+                .rust_type_names
+                .get(id)
+                .ok_or_else(|| eyre!("No types found for {id}"))?,
+        };
         let tuple = self
             .internal_structs
             .struct_tuples
             .get(id)
             .ok_or_else(|| eyre!("No types found for {id}"))?;
         let types = if let ParamType::Tuple(types) = tuple { types } else { unreachable!() };
-        self.expand_internal_struct(struct_name, sol_struct, types)
+        self.expand_internal_struct(struct_name, sol_struct, types, renames)
     }
 
     /// Returns the `TokenStream` with all the internal structs extracted form the JSON ABI
@@ -84,6 +141,7 @@ impl Context {
         name: &str,
         sol_struct: &SolStruct,
         types: &[ParamType],
+        renames: &HashMap<String, String>,
     ) -> Result<TokenStream> {
         let mut fields = Vec::with_capacity(sol_struct.fields().len());
 
@@ -93,7 +151,7 @@ impl Context {
         for field in sol_struct.fields() {
             let ty = match field.r#type() {
                 FieldType::Elementary(ty) => types::expand(ty)?,
-                FieldType::Struct(struct_ty) => types::expand_struct_type(struct_ty),
+                FieldType::Struct(struct_ty) => types::expand_struct_type(struct_ty, renames),
                 FieldType::Mapping(_) => {
                     eyre::bail!("Mapping types in struct `{name}` are not supported")
                 }
@@ -128,6 +186,17 @@ impl Context {
     }
 
     fn generate_human_readable_struct(&self, name: &str) -> Result<TokenStream> {
+        self.generate_human_readable_struct_as(name, name, &HashMap::new())
+    }
+
+    /// Generates the type definition for the struct with the given name, named `rust_name`, with
+    /// the struct fields renamed according to `renames`
+    fn generate_human_readable_struct_as(
+        &self,
+        name: &str,
+        rust_name: &str,
+        renames: &HashMap<String, String>,
+    ) -> Result<TokenStream> {
         let sol_struct =
             self.abi_parser.structs.get(name).ok_or_else(|| eyre!("Struct `{name}` not found"))?;
         let mut fields = Vec::with_capacity(sol_struct.fields().len());
@@ -141,7 +210,7 @@ impl Context {
                     fields.push(quote! { pub #field_name: #ty });
                 }
                 FieldType::Struct(struct_ty) => {
-                    let ty = types::expand_struct_type(struct_ty);
+                    let ty = types::expand_struct_type(struct_ty, renames);
                     fields.push(quote! { pub #field_name: #ty });
 
                     let name = struct_ty.name();
@@ -163,7 +232,7 @@ impl Context {
 
         let abi_signature = util::abi_signature(name, &param_types);
 
-        let name = util::ident(name);
+        let name = util::ident(rust_name);
 
         let mut derives = self.expand_extra_derives();
         util::derive_builtin_traits(&param_types, &mut derives, true, true);
@@ -9,6 +9,7 @@ use eyre::{bail, Result};
 use inflector::Inflector;
 use proc_macro2::{Literal, TokenStream};
 use quote::{quote, ToTokens};
+use std::collections::HashMap;
 
 /// Expands a ParamType Solidity type to its Rust equivalent.
 pub(crate) fn expand(kind: &ParamType) -> Result<TokenStream> {
@@ -160,14 +161,22 @@ fn expand_resolved<'a, 'b, F: Fn(&'a Param) -> Option<&'b str>>(
 }
 
 /// Expands to the Rust struct type.
-pub(crate) fn expand_struct_type(struct_ty: &StructFieldType) -> TokenStream {
+///
+/// Structs whose Rust name is a key of `renames` are referred to by the value instead.
+pub(crate) fn expand_struct_type(
+    struct_ty: &StructFieldType,
+    renames: &HashMap<String, String>,
+) -> TokenStream {
     match struct_ty {
         StructFieldType::Type(ty) => {
-            let ty = util::ident(&ty.name().to_pascal_case());
+            let name = ty.name().to_pascal_case();
+            let ty = util::ident(renames.get(&name).unwrap_or(&name));
             quote!(#ty)
         }
-        StructFieldType::Array(ty) => array(expand_struct_type(ty), None),
-        StructFieldType::FixedArray(ty, size) => array(expand_struct_type(ty), Some(*size)),
+        StructFieldType::Array(ty) => array(expand_struct_type(ty, renames), None),
+        StructFieldType::FixedArray(ty, size) => {
+            array(expand_struct_type(ty, renames), Some(*size))
+        }
     }
 }
 
@@ -91,6 +91,12 @@ pub struct Abigen {
 
     /// Manually specified `derive` macros added to all structs and enums.
     derives: Vec<syn::Path>,
+
+    /// Whether to generate builders for the call structs.
+    call_builders: bool,
+
+    /// Whether to accept `impl Into<T>` for address and integer function parameters.
+    into_args: bool,
 }
 
 impl Default for Abigen {
@@ -104,6 +110,8 @@ impl Default for Abigen {
             derives: Vec::new(),
             event_aliases: HashMap::new(),
             error_aliases: HashMap::new(),
+            call_builders: false,
+            into_args: false,
         }
     }
 }
@@ -209,6 +217,28 @@ impl Abigen {
         Ok(self)
     }
 
+    /// Specify whether to generate a builder for each call struct, e.g.
+    /// `TransferCall::builder().to(to).amount(amount).build()?`. False by default.
+    ///
+    /// The builders do not replace the call structs, which can still be constructed directly. The
+    /// setter of a field named `build` is called `build_`.
+    pub fn call_builders(mut self, call_builders: bool) -> Self {
+        self.call_builders = call_builders;
+        self
+    }
+
+    /// Specify whether the generated contract methods accept `impl Into<T>` for address and
+    /// integer parameters, e.g. a `u64` for a `uint256` parameter. False by default.
+    ///
+    /// Only lossless conversions are accepted, so values that need to be parsed, e.g. addresses
+    /// given as strings, must still be converted at the call site. Since the parameter types are no
+    /// longer concrete, arguments whose type is inferred from the parameter, e.g. `1.into()`, have
+    /// to be passed without the conversion instead.
+    pub fn into_args(mut self, into_args: bool) -> Self {
+        self.into_args = into_args;
+        self
+    }
+
     #[deprecated = "Use format instead"]
     #[doc(hidden)]
     pub fn rustfmt(mut self, rustfmt: bool) -> Self {
@@ -439,6 +469,39 @@ mod tests {
         assert!(out.contains("pub struct ConstructorParams"));
     }
 
+    #[test]
+    fn rejects_selector_collisions() {
+        // both functions have the selector 0x42966c68
+        let abi = "[
+            function burn(uint256)
+            function collate_propagate_storage(bytes16)
+        ]";
+        let err = Abigen::new("Collision", abi).unwrap().generate().unwrap_err();
+        assert!(format!("{err:?}").contains("have the same selector 0x42966c68"), "{err:?}");
+    }
+
+    #[test]
+    fn can_generate_deploy() {
+        let greeter = include_str!("../../tests/solidity-contracts/greeter.json");
+        let gen = Abigen::new("Greeter", greeter).unwrap().generate().unwrap();
+        let out = gen.tokens.to_string();
+        assert!(out.contains("pub static GREETER_BYTECODE"));
+        assert!(out.contains("pub fn deploy"));
+    }
+
+    #[test]
+    fn skips_deploy_for_unlinked_bytecode() {
+        let artifact = r#"{
+            "abi": [],
+            "bytecode": { "object": "0x6080__$30bbc0abd4d6364515865950d3e0d10953$__6040" }
+        }"#;
+        let gen = Abigen::new("Linked", artifact).unwrap().generate().unwrap();
+        let out = gen.tokens.to_string();
+        assert!(out.contains("pub struct Linked"));
+        assert!(!out.contains("LINKED_BYTECODE"));
+        assert!(!out.contains("pub fn deploy"));
+    }
+
     // <https://github.com/foundry-rs/foundry/issues/6010>
     #[test]
     fn parse_empty_abigen() {
@@ -2,7 +2,7 @@
 use crate::{util, Abigen, Context, ContractBindings, ContractFilter, ExpandedContract};
 use eyre::Result;
 use inflector::Inflector;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use std::{
     collections::{BTreeMap, BTreeSet},
@@ -187,34 +187,97 @@ impl MultiExpansion {
         // this keeps track of those contracts that need to be updated after a struct was
         // extracted from the contract's module and moved to the shared module
         let mut dirty_contracts = BTreeSet::new();
+        // the shared types that were renamed, by the contracts using them under their own name
+        let mut renamed_types: BTreeMap<usize, Vec<(Ident, Ident)>> = BTreeMap::new();
 
         // merge all types if more than 1 contract
         if expansions.len() > 1 {
-            // check for type conflicts across all contracts
-            let mut conflicts: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+            // check for type conflicts across all contracts, with the Rust name of the type in
+            // each contract
+            let mut conflicts: BTreeMap<String, Vec<(usize, String)>> = BTreeMap::new();
             for (idx, (_, ctx)) in expansions.iter().enumerate() {
-                for type_identifier in ctx.internal_structs().rust_type_names().keys() {
+                for (type_identifier, rust_name) in ctx.internal_structs().rust_type_names() {
                     conflicts
                         .entry(type_identifier.clone())
                         .or_insert_with(|| Vec::with_capacity(1))
-                        .push(idx);
+                        .push((idx, rust_name.clone()));
                 }
             }
 
+            // the shared types by their identifier, their shared name and the contracts using them,
+            // the first of which provides the definition
+            let mut shared: Vec<(&str, String, Vec<usize>)> = Vec::new();
+
             // resolve type conflicts
             for (id, contracts) in conflicts.iter().filter(|(_, c)| c.len() > 1) {
-                // extract the shared type once
+                // types with the same name can still have different definitions, which are
+                // grouped by their signature in the order of the contracts. Types without a
+                // signature are never merged and are kept in their contract's module, where they
+                // shadow the shared types
+                let mut definitions: Vec<(String, Vec<(usize, &str)>)> = Vec::new();
+                for (contract, rust_name) in contracts {
+                    let Some(signature) = expansions[*contract].1.struct_signature(id) else {
+                        continue
+                    };
+                    let contract = (*contract, rust_name.as_str());
+                    match definitions.iter_mut().find(|(s, _)| *s == signature) {
+                        Some((_, contracts)) => contracts.push(contract),
+                        None => definitions.push((signature, vec![contract])),
+                    }
+                }
+                // the most common definition keeps the name, ties go to the first contract
+                definitions.sort_by_key(|(_, contracts)| std::cmp::Reverse(contracts.len()));
+
+                let rust_name = &contracts[0].1;
+                let mut suffix = 0;
+                for (idx, (_, contracts)) in definitions.iter().enumerate() {
+                    // the other definitions are shared as well, with a numeric suffix
+                    let name = if idx == 0 {
+                        rust_name.clone()
+                    } else {
+                        loop {
+                            suffix += 1;
+                            let name = format!("{rust_name}{suffix}");
+                            if !conflicts.contains_key(&name) {
+                                break name
+                            }
+                        }
+                    };
+
+                    for (contract, original) in contracts.iter().copied() {
+                        dirty_contracts.insert(contract);
+                        if name != original {
+                            renamed_types
+                                .entry(contract)
+                                .or_default()
+                                .push((util::ident(&name), util::ident(original)));
+                        }
+                    }
+                    shared.push((id, name, contracts.iter().map(|(c, _)| *c).collect()));
+                }
+            }
+
+            // extract the shared types once all of them are named, so that the struct fields of a
+            // shared type refer to the shared names of the nested types
+            for (id, name, contracts) in &shared {
+                let renames = renamed_types
+                    .get(&contracts[0])
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, original)| (original.to_string(), name.to_string()))
+                    .collect();
                 shared_types.push(
                     expansions[contracts[0]]
                         .1
-                        .struct_definition(id)
+                        .renamed_struct_definition(id, name, &renames)
                         .expect("struct def succeeded previously"),
                 );
+            }
 
-                // remove the shared type from the contract's bindings
-                for contract in contracts.iter().copied() {
+            // remove the shared types from the contracts' bindings
+            for (id, _, contracts) in shared {
+                for contract in contracts {
                     expansions[contract].1.remove_struct(id);
-                    dirty_contracts.insert(contract);
                 }
             }
 
@@ -225,7 +288,13 @@ impl MultiExpansion {
             }
         }
 
-        MultiExpansionResult { root: None, contracts: expansions, dirty_contracts, shared_types }
+        MultiExpansionResult {
+            root: None,
+            contracts: expansions,
+            dirty_contracts,
+            renamed_types,
+            shared_types,
+        }
     }
 }
 
@@ -239,6 +308,9 @@ pub struct MultiExpansionResult {
     contracts: Vec<(ExpandedContract, Context)>,
     /// contains the indices of contracts with structs that need to be updated
     dirty_contracts: BTreeSet<usize>,
+    /// the shared types that were given a suffixed name, as pairs of the shared and the original
+    /// name, by the index of the contract that uses them
+    renamed_types: BTreeMap<usize, Vec<(Ident, Ident)>>,
     /// all type definitions of types that are shared by multiple contracts
     shared_types: Vec<TokenStream>,
 }
@@ -250,9 +322,7 @@ impl MultiExpansionResult {
 
         let shared_types_module = quote! {__shared_types};
         // the import path to the shared types
-        let shared_path = quote!(
-            pub use super::#shared_types_module::*;
-        );
+        let shared_path = quote!(super::#shared_types_module);
         self.add_shared_import_path(shared_path);
 
         let Self { contracts, shared_types, .. } = self;
@@ -283,22 +353,26 @@ impl MultiExpansionResult {
     /// If `single_file` then it's expected that types will be written to `shared_types.rs`
     fn set_shared_import_path(&mut self, single_file: bool) {
         let shared_path = if single_file {
-            quote!(
-                pub use super::__shared_types::*;
-            )
+            quote!(super::__shared_types)
         } else {
-            quote!(
-                pub use super::super::shared_types::*;
-            )
+            quote!(super::super::shared_types)
         };
         self.add_shared_import_path(shared_path);
     }
 
-    /// adds the `shared` import path to every `dirty` contract
+    /// adds the import of the `shared` module to every `dirty` contract, and imports the renamed
+    /// shared types under their original name
     fn add_shared_import_path(&mut self, shared: TokenStream) {
         for contract in self.dirty_contracts.iter().copied() {
             let (expanded, ..) = &mut self.contracts[contract];
-            expanded.imports.extend(shared.clone());
+            expanded.imports.extend(quote!(
+                pub use #shared::*;
+            ));
+            for (name, original) in self.renamed_types.get(&contract).into_iter().flatten() {
+                expanded.imports.extend(quote!(
+                    pub use #shared::#name as #original;
+                ));
+            }
         }
     }
 
@@ -1254,6 +1328,70 @@ mod tests {
         assert!(content.contains("pub struct Stuff"));
     }
 
+    #[test]
+    fn renames_conflicting_types() {
+        let order = "struct Order { address maker; uint256 amount; }";
+        let abigens = [
+            ("Book", "[struct Order { uint256 id; }\nfunction cancel(Order order)]".to_string()),
+            ("Exchange", format!("[{order}\nfunction fill(Order order)]")),
+            ("Vault", format!("[{order}\nfunction deposit(Order order)]")),
+            (
+                "Auction",
+                "[struct Order { uint256 id; bool open; }\nfunction bid(Order order)]".to_string(),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, abi)| Abigen::new(name, abi).unwrap());
+
+        let mut result = MultiExpansion::from_abigen(abigens).unwrap().expand();
+        result.add_shared_import_path(quote!(super::__shared_types));
+        let MultiExpansionResult { contracts, shared_types, dirty_contracts, .. } = result;
+        assert_eq!(dirty_contracts.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+        // the most common definition of `Exchange` and `Vault` keeps the name, the others are
+        // suffixed in the order of the contracts
+        let shared = shared_types.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(shared.len(), 3);
+        assert!(shared[0].contains("pub struct Order {") && shared[0].contains("maker"));
+        assert!(shared[1].contains("pub struct Order1 {") && !shared[1].contains("open"));
+        assert!(shared[2].contains("pub struct Order2 {") && shared[2].contains("open"));
+
+        // the contracts with a renamed definition import it under its original name
+        let imports = |idx: usize| contracts[idx].0.imports.to_string();
+        assert!(imports(0).contains("pub use super :: __shared_types :: Order1 as Order ;"));
+        assert!(imports(3).contains("pub use super :: __shared_types :: Order2 as Order ;"));
+        assert!(!imports(1).contains(" as Order"));
+        assert!(contracts.iter().all(|(exp, _)| !exp.abi_structs.to_string().contains("Order")));
+    }
+
+    #[test]
+    fn renames_nested_conflicting_types() {
+        let structs = "struct Inner { bool flag; }\nstruct Order { Inner inner; }";
+        let abigens = [
+            (
+                "Book",
+                "[struct Inner { uint256 id; }\nstruct Order { Inner inner; }\nfunction cancel(Order order)]"
+                    .to_string(),
+            ),
+            ("Exchange", format!("[{structs}\nfunction fill(Order order)]")),
+            ("Vault", format!("[{structs}\nfunction deposit(Order order)]")),
+        ]
+        .into_iter()
+        .map(|(name, abi)| Abigen::new(name, abi).unwrap());
+
+        let MultiExpansionResult { shared_types, renamed_types, .. } =
+            MultiExpansion::from_abigen(abigens).unwrap().expand();
+        assert_eq!(renamed_types[&0].len(), 2);
+
+        // the renamed `Order` of `Book` refers to the renamed `Inner` of `Book`
+        let shared = shared_types.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(shared.len(), 4);
+        let order = shared.iter().find(|s| s.contains("pub struct Order {")).unwrap();
+        assert!(order.contains("pub inner : Inner }"), "{order}");
+        let order1 = shared.iter().find(|s| s.contains("pub struct Order1 {")).unwrap();
+        assert!(order1.contains("pub inner : Inner1 }"), "{order1}");
+    }
+
     #[test]
     fn can_sanitize_reserved_words() {
         let root = tempfile::tempdir().unwrap();
@@ -285,6 +285,23 @@ fn extend_derives(stream: &mut TokenStream, def: bool, others: bool) {
     }
 }
 
+/// Whether the given derive is one of the builtin traits which are always derived (`Clone`) or
+/// derived whenever all the fields support it (see [`derive_builtin_traits`]).
+///
+/// These are filtered out of the user supplied derives, so for example `derives(Default)` does
+/// not result in conflicting implementations, and is skipped for types that can't implement it.
+pub(crate) fn is_builtin_derive(path: &syn::Path) -> bool {
+    let mut segments = path.segments.iter().map(|segment| segment.ident.to_string());
+    let Some(name) = segments.next_back() else { return false };
+    let prefix = segments.collect::<Vec<_>>();
+    let is_std = match prefix.as_slice() {
+        [] => true,
+        [krate, _] => matches!(krate.as_str(), "std" | "core" | "alloc"),
+        _ => false,
+    };
+    is_std && matches!(name.as_str(), "Clone" | "Default" | "Debug" | "PartialEq" | "Eq" | "Hash")
+}
+
 const MAX_SUPPORTED_ARRAY_LEN: usize = 32;
 const MAX_SUPPORTED_TUPLE_LEN: usize = 12;
 
@@ -367,6 +384,16 @@ mod tests {
         assert!(!can_derive_builtin_traits(&param));
     }
 
+    #[test]
+    fn can_detect_builtin_derives() {
+        for derive in ["Default", "Debug", "std::default::Default", "core::hash::Hash", "Clone"] {
+            assert!(is_builtin_derive(&syn::parse_str(derive).unwrap()), "{derive}");
+        }
+        for derive in ["serde::Serialize", "my::Default", "std::Default", "Ord"] {
+            assert!(!is_builtin_derive(&syn::parse_str(derive).unwrap()), "{derive}");
+        }
+    }
+
     #[test]
     fn can_resolve_path() {
         let raw = "./$ENV_VAR";
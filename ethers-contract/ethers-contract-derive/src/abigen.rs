@@ -67,6 +67,8 @@ impl ContractArgs {
                     .method_aliases_mut()
                     .extend(methods.into_iter().map(|m| (m.signature, m.alias.to_string()))),
                 Parameter::Derives(derives) => builder.derives_mut().extend(derives),
+                Parameter::CallBuilders => builder = builder.call_builders(true),
+                Parameter::IntoArgs => builder = builder.into_args(true),
             }
         }
 
@@ -87,7 +89,11 @@ impl Parse for ContractArgs {
         //   therefore, the path will always be rooted on the cargo manifest
         //   directory. Eventually we can use the `Span::source_file` API to
         //   have a better experience.
-        let abi = input.parse::<LitStr>()?;
+        let abi = if input.peek(syn::token::Bracket) {
+            parse_human_readable_array(input)?
+        } else {
+            input.parse::<LitStr>()?
+        };
 
         // optional parameters
         let mut parameters = Punctuated::default();
@@ -107,11 +113,44 @@ impl Parse for ContractArgs {
     }
 }
 
+/// Parses an inline array of human readable signatures, like
+/// `["function foo()", "event Bar(uint256)"]`, into the equivalent human readable ABI string
+/// `"[\nfunction foo()\nevent Bar(uint256)\n]"`.
+fn parse_human_readable_array(input: ParseStream) -> Result<LitStr> {
+    let content;
+    let bracket = syn::bracketed!(content in input);
+    let signatures = content.parse_terminated(<LitStr as Parse>::parse, Token![,])?;
+    if signatures.is_empty() {
+        return Err(Error::new(
+            bracket.span.join(),
+            "expected at least one human readable signature",
+        ))
+    }
+
+    let mut abi = String::from("[\n");
+    for signature in signatures {
+        let value = signature.value();
+        if value.lines().count() > 1 {
+            return Err(Error::new(
+                signature.span(),
+                "human readable signatures must be on a single line",
+            ))
+        }
+        abi.push_str(value.trim());
+        abi.push('\n');
+    }
+    abi.push(']');
+
+    Ok(LitStr::new(&abi, bracket.span.join()))
+}
+
 /// A single procedural macro parameter.
 #[derive(Clone, Debug, PartialEq, Eq)]
 enum Parameter {
     Methods(Vec<Method>),
     Derives(Punctuated<Path, Token![,]>),
+    CallBuilders,
+    IntoArgs,
 }
 
 impl Parse for Parameter {
@@ -143,6 +182,8 @@ impl Parse for Parameter {
                 let derives = content.parse_terminated(Path::parse, Token![,])?;
                 Ok(Parameter::Derives(derives))
             }
+            "call_builders" => Ok(Parameter::CallBuilders),
+            "into_args" => Ok(Parameter::IntoArgs),
             _ => Err(Error::new(name.span(), "unexpected named parameter")),
         }
     }
@@ -394,6 +435,33 @@ mod tests {
         assert_eq!(*args.first().unwrap(), arg("TestContract", "[{}]", [], false));
     }
 
+    #[test]
+    fn parse_contract_args_human_readable_array() {
+        let args = contract_args!(
+            TestContract,
+            [
+                "function transfer(address to, uint256 amount) returns (bool)",
+                "event Transfer(address indexed from, address indexed to, uint256 value)",
+            ],
+            derives(serde::Deserialize)
+        );
+        assert_eq!(
+            *args.first().unwrap(),
+            arg(
+                "TestContract",
+                "[\nfunction transfer(address to, uint256 amount) returns (bool)\nevent Transfer(address indexed from, address indexed to, uint256 value)\n]",
+                [derives(["serde::Deserialize"], false)],
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn human_readable_array_errors() {
+        contract_args_err!(TestContract, []);
+        contract_args_err!(TestContract, ["function foo()\nfunction bar()"]);
+    }
+
     #[test]
     fn parse_contract_args_with_parameters() {
         let args = contract_args!(
@@ -422,6 +490,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_contract_args_with_call_builders() {
+        let args = contract_args!(TestContract, "abi.json", call_builders);
+        assert_eq!(
+            *args.first().unwrap(),
+            arg("TestContract", "abi.json", [Parameter::CallBuilders], false)
+        );
+    }
+
+    #[test]
+    fn parse_contract_args_with_into_args() {
+        let args = contract_args!(TestContract, "abi.json", call_builders, into_args);
+        assert_eq!(
+            *args.first().unwrap(),
+            arg("TestContract", "abi.json", [Parameter::CallBuilders, Parameter::IntoArgs], false)
+        );
+    }
+
     #[test]
     fn duplicate_method_rename_error() {
         contract_args_err!(
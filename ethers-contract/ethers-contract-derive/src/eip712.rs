@@ -8,11 +8,15 @@ use ethers_core::{
 use inflector::Inflector;
 use proc_macro2::{Literal, TokenStream};
 use quote::quote;
-use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, LitInt, LitStr, Result, Token};
+use syn::{
+    spanned::Spanned, Data, DeriveInput, Error, Fields, Ident, LitInt, LitStr, Meta, Result, Token,
+    Type,
+};
 
 pub(crate) fn impl_derive_eip712(input: &DeriveInput) -> Result<TokenStream> {
     // Primary type should match the type in the ethereum verifying contract;
     let primary_type = &input.ident;
+    let primary_type_name = primary_type.to_string();
 
     // Instantiate domain from parsed attributes
     let domain = parse_attributes(input)?;
@@ -24,19 +28,92 @@ pub(crate) fn impl_derive_eip712(input: &DeriveInput) -> Result<TokenStream> {
     // Must parse the AST at compile time.
     let parsed_fields = parse_fields(input)?;
 
-    // Compute the type hash for the derived struct using the parsed fields from above.
-    let type_hash = into_tokens(make_type_hash(primary_type.to_string(), &parsed_fields));
-
     // Use reference to ethers_core instead of directly using the crate itself.
     let ethers_core = ethers_core_crate();
+    let eip712 = quote!(#ethers_core::types::transaction::eip712);
+
+    let nested_types: Vec<_> = parsed_fields
+        .iter()
+        .filter_map(|field| match &field.kind {
+            FieldKind::Nested { ty, .. } => Some(ty),
+            FieldKind::Atomic(_) => None,
+        })
+        .collect();
+
+    // The type hash of structs without nested structs is computed at compile time, otherwise it
+    // depends on the types of the nested structs.
+    let type_hash = if nested_types.is_empty() {
+        let fields = parsed_fields
+            .iter()
+            .map(|field| match &field.kind {
+                FieldKind::Atomic(ty) => (field.name.clone(), ty.clone()),
+                FieldKind::Nested { .. } => unreachable!("no nested fields"),
+            })
+            .collect::<Vec<_>>();
+        let type_hash = into_tokens(make_type_hash(primary_type_name.clone(), &fields));
+        quote!(Ok([#(#type_hash),*]))
+    } else {
+        quote!(#eip712::hash_type(#primary_type_name, &<Self as #eip712::Eip712>::types()?))
+    };
+
+    let members = parsed_fields.iter().map(|field| {
+        let name = &field.name;
+        let ty = match &field.kind {
+            FieldKind::Atomic(ty) => ty.to_string(),
+            FieldKind::Nested { type_name, .. } => type_name.clone(),
+        };
+        quote! {
+            #eip712::Eip712DomainType {
+                name: ::std::string::String::from(#name),
+                r#type: ::std::string::String::from(#ty),
+            }
+        }
+    });
+
+    let encode_fields = parsed_fields.iter().map(|field| {
+        let ident = &field.ident;
+        match &field.kind {
+            FieldKind::Atomic(_) => quote! {
+                match #ethers_core::abi::Tokenizable::into_token(
+                    ::core::clone::Clone::clone(&self.#ident),
+                ) {
+                    #ethers_core::abi::Token::Tuple(_) => {
+                        // structs must be annotated with `#[eip712]` to be encoded as nested struct
+                        return Err(Self::Error::NestedEip712StructNotImplemented)
+                    }
+                    token => items.push(#eip712::encode_eip712_type(token)),
+                }
+            },
+            FieldKind::Nested { ty, .. } => quote! {
+                let struct_hash = <#ty as #eip712::Eip712>::struct_hash(&self.#ident)
+                    .map_err(|e| Self::Error::Message(e.to_string()))?;
+                items.push(#ethers_core::abi::Token::Uint(
+                    #ethers_core::types::U256::from(&struct_hash[..]),
+                ));
+            },
+        }
+    });
+    let num_items = parsed_fields.len() + 1;
 
     let tokens = quote! {
-        impl #ethers_core::types::transaction::eip712::Eip712 for #primary_type {
-            type Error = #ethers_core::types::transaction::eip712::Eip712Error;
+        impl #eip712::Eip712 for #primary_type {
+            type Error = #eip712::Eip712Error;
 
             #[inline]
             fn type_hash() -> ::core::result::Result<[u8; 32], Self::Error> {
-                Ok([#(#type_hash),*])
+                #type_hash
+            }
+
+            fn types() -> ::core::result::Result<#eip712::Types, Self::Error> {
+                let mut types = #eip712::Types::new();
+                #(
+                    types.extend(
+                        <#nested_types as #eip712::Eip712>::types()
+                            .map_err(|e| Self::Error::Message(e.to_string()))?,
+                    );
+                )*
+                types.insert(::std::string::String::from(#primary_type_name), vec![#(#members),*]);
+                Ok(types)
             }
 
             #[inline]
@@ -44,32 +121,16 @@ pub(crate) fn impl_derive_eip712(input: &DeriveInput) -> Result<TokenStream> {
                 Ok([#(#domain_separator),*])
             }
 
-            fn domain(&self) -> ::core::result::Result<#ethers_core::types::transaction::eip712::EIP712Domain, Self::Error> {
+            fn domain(&self) -> ::core::result::Result<#eip712::EIP712Domain, Self::Error> {
                 #ethers_core::utils::__serde_json::from_str(#domain_str).map_err(::core::convert::Into::into)
             }
 
             fn struct_hash(&self) -> ::core::result::Result<[u8; 32], Self::Error> {
-                let mut items = vec![#ethers_core::abi::Token::Uint(
+                let mut items = ::std::vec::Vec::with_capacity(#num_items);
+                items.push(#ethers_core::abi::Token::Uint(
                     #ethers_core::types::U256::from(&Self::type_hash()?[..]),
-                )];
-
-                if let #ethers_core::abi::Token::Tuple(tokens) =
-                    #ethers_core::abi::Tokenizable::into_token(::core::clone::Clone::clone(self))
-                {
-                    items.reserve(tokens.len());
-                    for token in tokens {
-                        match &token {
-                            #ethers_core::abi::Token::Tuple(t) => {
-                                // TODO: check for nested Eip712 Type;
-                                // Challenge is determining the type hash
-                                return Err(Self::Error::NestedEip712StructNotImplemented);
-                            },
-                            _ => {
-                                items.push(#ethers_core::types::transaction::eip712::encode_eip712_type(token));
-                            }
-                        }
-                    }
-                }
+                ));
+                #(#encode_fields)*
 
                 let struct_hash = #ethers_core::utils::keccak256(#ethers_core::abi::encode(
                     &items,
@@ -83,6 +144,21 @@ pub(crate) fn impl_derive_eip712(input: &DeriveInput) -> Result<TokenStream> {
     Ok(tokens)
 }
 
+/// A member of the derived struct
+struct Eip712Field {
+    ident: Ident,
+    /// The name of the member in the EIP-712 type
+    name: String,
+    kind: FieldKind,
+}
+
+enum FieldKind {
+    /// An atomic or dynamic type, or an array of them
+    Atomic(ParamType),
+    /// A nested struct that implements `Eip712`, annotated with `#[eip712]`
+    Nested { ty: Box<Type>, type_name: String },
+}
+
 fn parse_attributes(input: &DeriveInput) -> Result<EIP712Domain> {
     let mut domain = EIP712Domain::default();
     utils::parse_attributes!(input.attrs.iter(), "eip712", meta,
@@ -127,8 +203,8 @@ fn parse_attributes(input: &DeriveInput) -> Result<EIP712Domain> {
     Ok(domain)
 }
 
-/// Returns a Vec of `(name, param_type)`
-fn parse_fields(input: &DeriveInput) -> Result<Vec<(String, ParamType)>> {
+/// Returns the members of the struct
+fn parse_fields(input: &DeriveInput) -> Result<Vec<Eip712Field>> {
     let data = match &input.data {
         Data::Struct(s) => s,
         Data::Enum(e) => {
@@ -146,28 +222,47 @@ fn parse_fields(input: &DeriveInput) -> Result<Vec<(String, ParamType)>> {
 
     let mut fields = Vec::with_capacity(named_fields.named.len());
     for f in named_fields.named.iter() {
+        let ident = f.ident.clone().unwrap();
         // strip the raw identifier prefix
-        let name = f.ident.as_ref().unwrap().to_string();
+        let name = ident.to_string();
         let s = name.strip_prefix("r#").unwrap_or(&name);
         let name = s.to_camel_case();
 
-        let ty =
-            match f.attrs.iter().find(|a| a.path().segments.iter().any(|s| s.ident == "eip712")) {
-                // Found nested Eip712 Struct
-                // TODO: Implement custom
-                Some(a) => {
-                    return Err(Error::new(a.span(), "nested Eip712 struct are not yet supported"))
+        let kind = match f.attrs.iter().find(|a| a.path().is_ident("eip712")) {
+            // Found nested Eip712 Struct
+            Some(a) => {
+                if !matches!(a.meta, Meta::Path(_)) {
+                    return Err(Error::new(a.span(), "expected `#[eip712]`"))
                 }
-                // Not a nested eip712 struct, return the field param type;
-                None => crate::utils::find_parameter_type(&f.ty)?,
-            };
+                FieldKind::Nested {
+                    type_name: nested_type_name(&f.ty)?,
+                    ty: Box::new(f.ty.clone()),
+                }
+            }
+            // Not a nested eip712 struct, return the field param type;
+            None => FieldKind::Atomic(crate::utils::find_parameter_type(&f.ty)?),
+        };
 
-        fields.push((name, ty));
+        fields.push(Eip712Field { ident, name, kind });
     }
 
     Ok(fields)
 }
 
+/// Returns the name of the type of a nested struct, which is the name of its EIP-712 type
+fn nested_type_name(ty: &Type) -> Result<String> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last().unwrap();
+            if segment.arguments.is_empty() {
+                return Ok(segment.ident.to_string())
+            }
+            Err(Error::new(ty.span(), "generic nested Eip712 structs are not supported"))
+        }
+        _ => Err(Error::new(ty.span(), "nested Eip712 structs must be referenced by their name")),
+    }
+}
+
 /// Convert hash map of field names and types into a type hash corresponding to enc types;
 fn make_type_hash(primary_type: String, fields: &[(String, ParamType)]) -> [u8; 32] {
     let mut sig = String::with_capacity(256);
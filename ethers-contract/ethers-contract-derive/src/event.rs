@@ -66,7 +66,8 @@ pub(crate) fn derive_eth_event_impl(input: DeriveInput) -> Result<TokenStream> {
         utils::signature(event_sig.as_bytes())
     };
 
-    let anon = attributes.anonymous.map(|(b, _)| b).unwrap_or_default();
+    // either set by the attribute or parsed from the `abi` attribute
+    let anon = event.anonymous;
     let event_name = &event.name;
 
     let ethers_core = ethers_core_crate();
@@ -196,13 +197,15 @@ fn derive_decode_from_log_impl(input: &DeriveInput, event: &Event) -> Result<Tok
 
     // Event with no fields, can skip decoding
     if fields.is_empty() {
+        // only the signature topic, which anonymous events don't have
+        let topics_len = usize::from(!event.anonymous);
         return Ok(quote! {
 
             let #ethers_core::abi::RawLog {topics, data} = log;
 
             #signature_check
 
-            if topics.len() != 1usize || !data.is_empty() {
+            if topics.len() != #topics_len || !data.is_empty() {
                 return Err(#ethers_core::abi::Error::InvalidData);
             }
 
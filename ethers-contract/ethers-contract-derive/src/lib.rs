@@ -44,7 +44,17 @@ pub(crate) mod utils;
 ///   * [Default]
 ///   * [Hash]
 ///
+///   Listing one of these in `derives` is allowed, but has no additional effect: it is still only
+///   derived for the types where all fields implement it.
+/// - `call_builders`: Generates a builder for each call struct with named fields, e.g.
+///   `TransferCall::builder().to(to).amount(amount).build()`. `build` returns an
+///   [`UninitializedFieldError`] if a field was not set. The setter of a field named `build` is
+///   called `build_`.
+/// - `into_args`: Generates contract methods accepting `impl Into<T>` for address and integer
+///   parameters, e.g. a `u64` for a `uint256` parameter. Only lossless conversions are accepted.
+///
 /// [Source]: ethers_contract_abigen::Source
+/// [`UninitializedFieldError`]: https://docs.rs/ethers/latest/ethers/contract/struct.UninitializedFieldError.html
 /// [tuple_derive_ref]: https://doc.rust-lang.org/stable/std/primitive.tuple.html#trait-implementations-1
 ///
 /// # Examples
@@ -72,6 +82,13 @@ pub(crate) mod utils;
 ///     function getValue() external view returns (string)
 ///     event ValueChanged(address indexed author, string oldValue, string newValue)
 /// ]");
+///
+/// // Human readable ABI, as an array of signatures
+/// abigen!(MyContract, [
+///     "function setValue(string)",
+///     "function getValue() external view returns (string)",
+///     "event ValueChanged(address indexed author, string oldValue, string newValue)",
+/// ]);
 /// ```
 ///
 /// Specify additional parameters:
@@ -85,6 +102,8 @@ pub(crate) mod utils;
 ///         myMethod(uint256,bool) as my_renamed_method;
 ///     },
 ///     derives(serde::Deserialize, serde::Serialize),
+///     call_builders,
+///     into_args,
 /// );
 /// ```
 ///
@@ -94,7 +113,9 @@ pub(crate) mod utils;
 /// `abigen!` supports multiple abigen definitions separated by a semicolon `;`
 /// This is useful if the contracts use ABIEncoderV2 structs. In which case
 /// `abigen!` bundles all type duplicates so that all rust contracts also use
-/// the same rust types.
+/// the same rust types. Types that share a name but not their fields are shared with a numeric
+/// suffix, like `Order1`, except for the most common one, and are imported under their original
+/// name in the modules of their contracts.
 ///
 /// ```ignore
 /// abigen!(
@@ -451,12 +472,40 @@ pub fn derive_abi_error(input: TokenStream) -> TokenStream {
 /// let hash = puzzle.encode_eip712().unwrap();
 /// ```
 ///
-/// # Limitations
+/// # Nested structs
+///
+/// Fields whose type is another struct deriving `Eip712` must be annotated with `#[eip712]`.
+/// They are encoded recursively, and their types are appended to the encoded type of the struct
+/// as specified by EIP-712. The name of the field's type is used as the name of the nested struct
+/// type.
+///
+/// ```
+/// use ethers_contract_derive::{EthAbiType, Eip712};
+/// use ethers_core::types::{transaction::eip712::Eip712, Address};
+///
+/// #[derive(Clone, Default, EthAbiType, Eip712)]
+/// #[eip712(name = "Ether Mail", version = "1", chain_id = 1, verifying_contract = "0x0000000000000000000000000000000000000000")]
+/// pub struct Person {
+///     pub name: String,
+///     pub wallet: Address,
+/// }
+///
+/// #[derive(Clone, Default, EthAbiType, Eip712)]
+/// #[eip712(name = "Ether Mail", version = "1", chain_id = 1, verifying_contract = "0x0000000000000000000000000000000000000000")]
+/// pub struct Mail {
+///     #[eip712]
+///     pub from: Person,
+///     #[eip712]
+///     pub to: Person,
+///     pub contents: String,
+/// }
+///
+/// let hash = Mail::default().encode_eip712().unwrap();
+/// ```
 ///
-/// At the moment, the derive macro does not recursively encode nested Eip712 structs.
+/// # Limitations
 ///
-/// There is an Inner helper attribute `#[eip712]` for fields that will eventually be used to
-/// determine if there is a nested eip712 struct. However, this work is not yet complete.
+/// Arrays of nested structs and generic nested structs are not supported.
 #[proc_macro_derive(Eip712, attributes(eip712))]
 pub fn derive_eip712(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
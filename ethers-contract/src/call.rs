@@ -1,16 +1,17 @@
 #![allow(clippy::return_self_not_must_use)]
 
-use crate::{error::ContractRevert, EthError};
+use crate::{error::ContractRevert, CallCache, CallCacheKey, EthError};
 
 use super::base::{decode_function_data, AbiError};
 use ethers_core::{
     abi::{Detokenize, Function, InvalidOutputType},
     types::{
-        transaction::eip2718::TypedTransaction, Address, BlockId, Bytes, TransactionRequest, U256,
+        transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber, Bytes,
+        NameOrAddress, TransactionRequest, U256,
     },
 };
 use ethers_providers::{
-    call_raw::{CallBuilder, RawCall},
+    call_raw::{CallBuilder, RawCall, RawCallBlockOverrides},
     JsonRpcError, Middleware, MiddlewareError, PendingTransaction, ProviderError,
 };
 
@@ -20,6 +21,7 @@ use std::{
     future::{Future, IntoFuture},
     marker::PhantomData,
     pin::Pin,
+    sync::Arc,
 };
 
 use thiserror::Error as ThisError;
@@ -66,6 +68,11 @@ pub enum ContractError<M: Middleware> {
     /// receipt
     #[error("Contract was not deployed")]
     ContractNotDeployed,
+
+    /// Thrown during deployment if the bytecode contains placeholders of libraries that were not
+    /// linked
+    #[error("bytecode contains unlinked library placeholders, link the libraries before deploying: {}", .0.join(", "))]
+    UnlinkedLibraries(Vec<String>),
 }
 
 impl<M: Middleware> ContractError<M> {
@@ -171,6 +178,12 @@ pub struct FunctionCall<B, M, D> {
     pub function: Function,
     /// Optional block number to be used when calculating the transaction's gas and nonce
     pub block: Option<BlockId>,
+    /// Percentage the gas estimate is scaled by, see [`FunctionCall::gas_estimate_multiplier`]
+    pub(crate) gas_estimate_percent: Option<u64>,
+    /// Lower bound of the gas estimate, see [`FunctionCall::gas_estimate_floor`]
+    pub(crate) gas_estimate_floor: Option<U256>,
+    /// Store of the call results, see [`FunctionCall::cached`]
+    pub(crate) cache: Option<Arc<dyn CallCache>>,
     pub(crate) client: B,
     pub(crate) datatype: PhantomData<D>,
     pub(crate) _m: PhantomData<M>,
@@ -185,6 +198,9 @@ where
             tx: self.tx.clone(),
             function: self.function.clone(),
             block: self.block,
+            gas_estimate_percent: self.gas_estimate_percent,
+            gas_estimate_floor: self.gas_estimate_floor,
+            cache: self.cache.clone(),
             client: self.client.clone(),
             datatype: self.datatype,
             _m: self._m,
@@ -246,6 +262,84 @@ where
         self.tx.set_nonce(nonce);
         self
     }
+
+    /// Scales the gas estimate of the transaction by the provided multiplier, e.g. `1.2` adds a
+    /// 20% buffer to the estimate.
+    ///
+    /// The multiplier is rounded to a whole percentage and applied as `estimate * percent / 100`,
+    /// so that the estimate itself does not lose precision. This has no effect if the `gas`
+    /// field is set.
+    ///
+    /// # Panics
+    ///
+    /// If the multiplier is negative or not finite.
+    pub fn gas_estimate_multiplier(mut self, multiplier: f64) -> Self {
+        assert!(
+            multiplier.is_finite() && multiplier >= 0.0,
+            "invalid gas estimate multiplier: {multiplier}"
+        );
+        self.gas_estimate_percent = Some((multiplier * 100.0).round() as u64);
+        self
+    }
+
+    /// Sets a lower bound for the gas estimate of the transaction, which is applied after the
+    /// [multiplier](FunctionCall::gas_estimate_multiplier). This has no effect if the `gas` field
+    /// is set.
+    pub fn gas_estimate_floor<T: Into<U256>>(mut self, floor: T) -> Self {
+        self.gas_estimate_floor = Some(floor.into());
+        self
+    }
+
+    /// Memoizes the results of [`call`](FunctionCall::call) in the provided `cache`, keyed by the
+    /// contract address, the calldata and the block number.
+    ///
+    /// Only calls pinned to a concrete [block number](FunctionCall::block) are cached, since
+    /// their result never changes, calls at a tag like `latest` or at a block hash always query
+    /// the node.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ethers_contract::{Contract, MemoryCallCache};
+    /// # use ethers_core::{abi::parse_abi, types::{Address, U256}};
+    /// # use ethers_providers::{Provider, Http};
+    /// # use std::{convert::TryFrom, sync::Arc};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Arc::new(Provider::<Http>::try_from("http://localhost:8545")?);
+    /// # let abi = parse_abi(&["function totalSupply() external view returns (uint256)"])?;
+    /// let contract = Contract::new(Address::zero(), abi, client);
+    /// let cache = Arc::new(MemoryCallCache::new());
+    ///
+    /// let call = contract.method::<_, U256>("totalSupply", ())?.block(17_000_000u64).cached(cache);
+    /// // only the first call is sent to the node
+    /// let supply = call.call().await?;
+    /// assert_eq!(supply, call.call().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cached(mut self, cache: Arc<dyn CallCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Returns the cache key of the call if its result can be cached
+    fn cache_key(&self) -> Option<CallCacheKey> {
+        let Some(BlockId::Number(BlockNumber::Number(block))) = self.block else { return None };
+        let Some(NameOrAddress::Address(to)) = self.tx.to() else { return None };
+        let calldata = self.tx.data().cloned().unwrap_or_default();
+        Some(CallCacheKey { to: *to, calldata, block: block.as_u64() })
+    }
+
+    /// Applies the configured multiplier and floor to the gas estimate
+    fn adjust_gas_estimate(&self, mut estimate: U256) -> U256 {
+        if let Some(percent) = self.gas_estimate_percent {
+            estimate = estimate.saturating_mul(percent.into()) / 100;
+        }
+        if let Some(floor) = self.gas_estimate_floor {
+            estimate = estimate.max(floor);
+        }
+        estimate
+    }
 }
 
 impl<B, M, D> FunctionCall<B, M, D>
@@ -259,13 +353,51 @@ where
         self.tx.data().cloned()
     }
 
+    /// Returns the ABI encoded input of the call without sending it, e.g. to propose the call
+    /// through a multisig.
+    ///
+    /// Returns an error if the transaction's data is missing or does not start with the selector
+    /// of the call's function, e.g. because it was replaced.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ethers_contract::Contract;
+    /// use ethers_core::{abi::parse_abi, types::Address};
+    /// use ethers_providers::{Http, Provider};
+    /// use std::{convert::TryFrom, sync::Arc};
+    ///
+    /// let client = Arc::new(Provider::<Http>::try_from("http://localhost:8545")?);
+    /// let abi = parse_abi(&["function transfer(address to, uint256 amount) returns (bool)"])?;
+    /// let token = Contract::new(Address::zero(), abi, client);
+    ///
+    /// let call = token.method::<_, bool>("transfer", (Address::repeat_byte(1), 100u64))?;
+    /// let to = call.tx.to_addr().copied();
+    /// let data = call.encode_input()?;
+    /// // hand `to` and `data` to the multisig
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn encode_input(&self) -> Result<Bytes, AbiError> {
+        match self.tx.data() {
+            Some(data) if data.starts_with(&self.function.short_signature()) => Ok(data.clone()),
+            _ => Err(AbiError::WrongSelector),
+        }
+    }
+
     /// Returns the estimated gas cost for the underlying transaction to be executed
+    ///
+    /// The [multiplier](FunctionCall::gas_estimate_multiplier) and
+    /// [floor](FunctionCall::gas_estimate_floor), if any, are applied to the node's estimate.
     pub async fn estimate_gas(&self) -> Result<U256, ContractError<M>> {
-        self.client
+        let estimate = self
+            .client
             .borrow()
             .estimate_gas(&self.tx, self.block)
             .await
-            .map_err(ContractError::from_middleware_error)
+            .map_err(ContractError::from_middleware_error)?;
+        Ok(self.adjust_gas_estimate(estimate))
     }
 
     /// Queries the blockchain via an `eth_call` for the provided transaction.
@@ -277,13 +409,26 @@ where
     /// and return the return type of the transaction without mutating the state
     ///
     /// Note: this function _does not_ send a transaction from your account
+    ///
+    /// If the call is [cached](FunctionCall::cached), a cached result is returned without querying
+    /// the node.
     pub async fn call(&self) -> Result<D, ContractError<M>> {
-        let bytes = self
-            .client
-            .borrow()
-            .call(&self.tx, self.block)
-            .await
-            .map_err(ContractError::from_middleware_error)?;
+        let cached = self.cache.as_ref().zip(self.cache_key());
+        let bytes = match cached.as_ref().and_then(|(cache, key)| cache.get(key)) {
+            Some(bytes) => bytes,
+            None => {
+                let bytes = self
+                    .client
+                    .borrow()
+                    .call(&self.tx, self.block)
+                    .await
+                    .map_err(ContractError::from_middleware_error)?;
+                if let Some((cache, key)) = cached {
+                    cache.put(key, bytes.clone());
+                }
+                bytes
+            }
+        };
 
         // decode output
         let data = decode_function_data(&self.function, &bytes, false)?;
@@ -291,15 +436,15 @@ where
         Ok(data)
     }
 
-    /// Returns an implementer of [`RawCall`] which can be `.await`d to query the blockchain via
-    /// `eth_call`, returning the deoded return data.
+    /// Returns an implementer of [`RawCall`] and [`RawCallBlockOverrides`] which can be `.await`d
+    /// to query the blockchain via `eth_call`, returning the deoded return data.
     ///
     /// The returned call can also be used to override the input parameters to `eth_call`.
     ///
     /// Note: this function _does not_ send a transaction from your account
     pub fn call_raw(
         &self,
-    ) -> impl RawCall<'_> + Future<Output = Result<D, ContractError<M>>> + Debug {
+    ) -> impl RawCallBlockOverrides<'_> + Future<Output = Result<D, ContractError<M>>> + Debug {
         let call = self.call_raw_bytes();
         call.map(move |res: Result<Bytes, ProviderError>| {
             let bytes = res?;
@@ -324,9 +469,16 @@ where
 
     /// Signs and broadcasts the provided transaction
     pub async fn send(&self) -> Result<PendingTransaction<'_, M::Provider>, ContractError<M>> {
+        let mut tx = self.tx.clone();
+        let adjust_estimate =
+            self.gas_estimate_percent.is_some() || self.gas_estimate_floor.is_some();
+        if tx.gas().is_none() && adjust_estimate {
+            tx.set_gas(self.estimate_gas().await?);
+        }
+
         self.client
             .borrow()
-            .send_transaction(self.tx.clone(), self.block)
+            .send_transaction(tx, self.block)
             .await
             .map_err(ContractError::from_middleware_error)
     }
@@ -0,0 +1,177 @@
+use crate::{ContractCall, ContractError};
+use ethers_core::{
+    abi::{Detokenize, Function, Token, Tokenizable},
+    types::{transaction::eip2718::TypedTransaction, BlockId, BlockNumber, Bytes},
+    utils,
+};
+use ethers_providers::{JsonRpcClient, JsonRpcError, Middleware, RpcError};
+use std::{fmt, sync::Arc};
+
+/// Executes multiple contract calls in a single JSON-RPC batch of `eth_call`s.
+///
+/// Unlike the [`Multicall`](crate::Multicall), this does not require an aggregator contract to be
+/// deployed, which makes it a fallback for chains without a Multicall3 deployment. The calls are
+/// sent in a single round trip if the transport supports
+/// [JSON-RPC batches](JsonRpcClient::request_batch), i.e. `Http`, also when wrapped in a
+/// `RetryClient` or `RwClient`. Other transports, e.g. `Ws`, `Ipc` and `QuorumProvider`, send the
+/// calls one by one.
+///
+/// The requests are sent by the [`Provider`](ethers_providers::Provider) at the bottom of the
+/// middleware stack, so middlewares do not see the calls. Each call is executed at its own block
+/// if it set one, and at the block of the batch otherwise.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// use ethers_contract::{CallBatch, Contract};
+/// use ethers_core::{abi::parse_abi, types::{Address, U256}};
+/// use ethers_providers::{Http, Provider};
+/// use std::{convert::TryFrom, sync::Arc};
+///
+/// let client = Arc::new(Provider::<Http>::try_from("http://localhost:8545")?);
+/// let abi = parse_abi(&[
+///     "function totalSupply() external view returns (uint256)",
+///     "function symbol() external view returns (string)",
+/// ])?;
+/// let token = Contract::new(Address::zero(), abi, client.clone());
+///
+/// let mut batch = CallBatch::new(client);
+/// batch
+///     .add_call(token.method::<_, U256>("totalSupply", ())?)
+///     .add_call(token.method::<_, String>("symbol", ())?);
+/// let (supply, symbol): (U256, String) = batch.call().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct CallBatch<M> {
+    client: Arc<M>,
+    block: Option<BlockId>,
+    calls: Vec<(TypedTransaction, Option<BlockId>, Function)>,
+}
+
+impl<M> Clone for CallBatch<M> {
+    fn clone(&self) -> Self {
+        Self { client: self.client.clone(), block: self.block, calls: self.calls.clone() }
+    }
+}
+
+impl<M> fmt::Debug for CallBatch<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallBatch").field("block", &self.block).field("calls", &self.calls).finish()
+    }
+}
+
+impl<M: Middleware> CallBatch<M> {
+    /// Creates an empty batch sending its calls with the provider of the `client`
+    pub fn new(client: impl Into<Arc<M>>) -> Self {
+        Self { client: client.into(), block: None, calls: Vec::new() }
+    }
+
+    /// Sets the block at which the calls that do not set a block are executed, defaults to the
+    /// latest block
+    #[must_use]
+    pub fn block(mut self, block: impl Into<BlockId>) -> Self {
+        self.block = Some(block.into());
+        self
+    }
+
+    /// Appends a `call` to the batch
+    pub fn add_call<D: Detokenize>(&mut self, call: ContractCall<M, D>) -> &mut Self {
+        self.calls.push((call.tx, call.block, call.function));
+        self
+    }
+
+    /// Appends multiple `calls` to the batch
+    pub fn add_calls<D: Detokenize>(
+        &mut self,
+        calls: impl IntoIterator<Item = ContractCall<M, D>>,
+    ) -> &mut Self {
+        for call in calls {
+            self.add_call(call);
+        }
+        self
+    }
+
+    /// Removes all calls from the batch
+    pub fn clear_calls(&mut self) -> &mut Self {
+        self.calls.clear();
+        self
+    }
+
+    /// Returns the number of calls in the batch
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns true if the batch has no calls
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Executes the calls, decoding their results as the tuple `T`, see
+    /// [`Multicall::call`](crate::Multicall::call).
+    ///
+    /// Returns an error if any of the calls failed.
+    pub async fn call<T: Tokenizable>(&self) -> Result<T, ContractError<M>> {
+        let tokens = self.call_raw().await?.into_iter().collect::<Result<_, _>>()?;
+        T::from_token(Token::Tuple(tokens)).map_err(Into::into)
+    }
+
+    /// Executes the calls, assuming that every call returns the same type.
+    ///
+    /// Returns an error if any of the calls failed.
+    pub async fn call_array<T: Tokenizable>(&self) -> Result<Vec<T>, ContractError<M>> {
+        self.call_raw()
+            .await?
+            .into_iter()
+            .map(|res| res.and_then(|token| T::from_token(token).map_err(Into::into)))
+            .collect()
+    }
+
+    /// Executes the calls, returning the decoded result of each call in the order of the calls.
+    ///
+    /// The outer error is returned if the batch failed as a whole, the error of each call, e.g.
+    /// a [`ContractError::Revert`], is returned in its place.
+    pub async fn call_raw(&self) -> Result<Vec<Result<Token, ContractError<M>>>, ContractError<M>> {
+        let requests = self
+            .calls
+            .iter()
+            .map(|(tx, block, _)| {
+                let block = block.or(self.block).unwrap_or_else(|| BlockNumber::Latest.into());
+                let params = serde_json::Value::Array(vec![
+                    utils::serialize(tx),
+                    utils::serialize(&block),
+                ]);
+                ("eth_call".to_string(), params)
+            })
+            .collect();
+
+        let client = self.client.provider().as_ref();
+        let results = client
+            .request_batch(requests)
+            .await
+            .map_err(|e| ContractError::ProviderError { e: e.into() })?;
+
+        Ok(self
+            .calls
+            .iter()
+            .zip(results)
+            .map(|((_, _, function), result)| {
+                let bytes: Bytes = match result {
+                    Ok(value) => serde_json::from_value(value)
+                        .map_err(|e| ContractError::ProviderError { e: e.into() })?,
+                    Err(e) => {
+                        let revert = e.as_error_response().and_then(JsonRpcError::as_revert_data);
+                        return Err(match revert {
+                            Some(data) => ContractError::Revert(data),
+                            None => ContractError::ProviderError { e: e.into() },
+                        })
+                    }
+                };
+                let mut tokens = function.decode_output(bytes.as_ref())?;
+                Ok(if tokens.len() == 1 { tokens.pop().unwrap() } else { Token::Tuple(tokens) })
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,72 @@
+use ethers_core::types::{Address, Bytes};
+use std::{collections::HashMap, fmt::Debug, sync::Mutex};
+
+/// The key of a cached contract call, see [`FunctionCall::cached`](crate::FunctionCall::cached).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CallCacheKey {
+    /// The called contract
+    pub to: Address,
+    /// The ABI encoded calldata
+    pub calldata: Bytes,
+    /// The block number the call is executed at
+    pub block: u64,
+}
+
+impl CallCacheKey {
+    /// Returns the key as bytes, for stores that are keyed by bytes.
+    ///
+    /// The bytes are the address, followed by the big endian block number and the calldata.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(20 + 8 + self.calldata.len());
+        bytes.extend_from_slice(self.to.as_bytes());
+        bytes.extend_from_slice(&self.block.to_be_bytes());
+        bytes.extend_from_slice(&self.calldata);
+        bytes
+    }
+}
+
+/// A store of the results of contract calls, see
+/// [`FunctionCall::cached`](crate::FunctionCall::cached).
+///
+/// Only calls at a concrete block number are cached, so the results never become stale and the
+/// store does not need to handle invalidation.
+pub trait CallCache: Debug + Send + Sync {
+    /// Returns the raw result of the call, if it is cached
+    fn get(&self, key: &CallCacheKey) -> Option<Bytes>;
+
+    /// Stores the raw result of the call
+    fn put(&self, key: CallCacheKey, value: Bytes);
+}
+
+/// An in-memory [`CallCache`] without eviction
+#[derive(Debug, Default)]
+pub struct MemoryCallCache {
+    entries: Mutex<HashMap<CallCacheKey, Bytes>>,
+}
+
+impl MemoryCallCache {
+    /// Creates an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached results
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns true if no results are cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl CallCache for MemoryCallCache {
+    fn get(&self, key: &CallCacheKey) -> Option<Bytes> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    fn put(&self, key: CallCacheKey, value: Bytes) {
+        self.entries.lock().unwrap().insert(key, value);
+    }
+}
@@ -4,6 +4,7 @@ use ethers_core::{
     utils::id,
 };
 use std::borrow::Cow;
+use thiserror::Error;
 
 /// A helper trait for types that represent all call input parameters of a specific function
 pub trait EthCall: Tokenizable + AbiDecode + AbiEncode + Send + Sync {
@@ -18,3 +19,9 @@ pub trait EthCall: Tokenizable + AbiDecode + AbiEncode + Send + Sync {
         id(Self::abi_signature())
     }
 }
+
+/// Thrown when building a call struct with the builder generated by `abigen!`'s `call_builders`
+/// option, without setting all of its fields
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[error("field `{0}` was not set")]
+pub struct UninitializedFieldError(pub &'static str);
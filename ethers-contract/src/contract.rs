@@ -312,7 +312,7 @@ where
 
     /// Returns an [`Event`] builder for the provided event.
     pub fn event<D: EthEvent>(&self) -> Event<B, M, D> {
-        D::new(Filter::new(), self.client.clone())
+        D::new(Filter::new(), self.client.clone()).address(ValueOrArray::Value(self.address))
     }
 
     /// Returns an [`Event`] builder with the provided name.
@@ -348,6 +348,9 @@ where
             tx,
             client: self.client.clone(),
             block: None,
+            gas_estimate_percent: None,
+            gas_estimate_floor: None,
+            cache: None,
             function: function.to_owned(),
             datatype: PhantomData,
             _m: self._m,
@@ -61,9 +61,15 @@ pub trait EthError: Tokenizable + AbiDecode + AbiEncode + Send + Sync {
     }
 
     /// Decode the error from EVM revert data including an Error selector
+    ///
+    /// Returns `None` if the selector does not match, so that errors with the
+    /// same parameters are never confused with one another.
     fn decode_with_selector(data: &[u8]) -> Option<Self> {
-        // This will return none if selector mismatch.
-        <Self as AbiDecode>::decode(data.strip_prefix(&Self::selector())?).ok()
+        if !data.starts_with(&Self::selector()) {
+            return None
+        }
+        // derived error types expect the selector as part of their encoding
+        <Self as AbiDecode>::decode(data).ok()
     }
 
     /// The name of the error
@@ -90,6 +96,11 @@ impl EthError for String {
     fn selector() -> Selector {
         [0x08, 0xc3, 0x79, 0xa0]
     }
+
+    fn decode_with_selector(data: &[u8]) -> Option<Self> {
+        // the `AbiDecode` impl of `String` does not include the selector
+        <Self as AbiDecode>::decode(data.strip_prefix(&Self::selector())?).ok()
+    }
 }
 
 #[cfg(all(test, feature = "abigen"))]
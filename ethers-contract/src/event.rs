@@ -183,6 +183,10 @@ where
 {
     /// Returns a subscription for the event
     ///
+    /// On a chain reorganization, the events of the logs that were removed from the chain are
+    /// yielded again, use [`Self::subscribe_with_meta`] to tell them apart by
+    /// [`LogMeta::removed`].
+    ///
     /// See also [Self::stream()].
     pub async fn subscribe(
         &self,
@@ -200,7 +204,8 @@ where
         Ok(EventStream::new(filter.id, filter, Box::new(move |log| Ok(parse_log(log)?))))
     }
 
-    /// As [`Self::subscribe`], but includes event metadata
+    /// As [`Self::subscribe`], but includes event metadata, which flags events that were removed
+    /// due to a chain reorganization
     pub async fn subscribe_with_meta(
         &self,
     ) -> Result<
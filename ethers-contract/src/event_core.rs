@@ -53,7 +53,9 @@ pub trait EthEvent: Detokenize + Send + Sync {
         B: Borrow<M>,
         M: Middleware,
     {
-        let filter = filter.event(&Self::abi_signature());
+        // anonymous events don't emit their signature as `topic0`
+        let filter =
+            if Self::is_anonymous() { filter } else { filter.event(&Self::abi_signature()) };
         Event { filter, provider, datatype: PhantomData, _m: PhantomData }
     }
 }
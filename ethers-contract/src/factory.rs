@@ -4,8 +4,9 @@ use ethers_core::{
     abi::{Abi, Token, Tokenize},
     types::{
         transaction::eip2718::TypedTransaction, Address, BlockNumber, Bytes, NameOrAddress,
-        TransactionReceipt, TransactionRequest, U256, U64,
+        TransactionReceipt, TransactionRequest, H256, U256, U64,
     },
+    utils::{get_create2_address, hex, keccak256},
 };
 use ethers_providers::{
     call_raw::{CallBuilder, RawCall},
@@ -15,7 +16,10 @@ use ethers_providers::{
 #[cfg(not(feature = "legacy"))]
 use ethers_core::types::Eip1559TransactionRequest;
 
-use std::{borrow::Borrow, marker::PhantomData, sync::Arc};
+use std::{borrow::Borrow, iter, marker::PhantomData, sync::Arc};
+
+/// The length of a library placeholder in the hex encoded bytecode, i.e. the length of an address
+const LIBRARY_PLACEHOLDER_LEN: usize = 40;
 
 /// `ContractDeployer` is a [`ContractDeploymentTx`] object with an
 /// [`Arc`] middleware. This type alias exists to preserve backwards
@@ -193,6 +197,8 @@ pub struct Deployer<B, M> {
     client: B,
     confs: usize,
     block: BlockNumber,
+    /// The precomputed address of a deployment through a CREATE2 factory
+    create2_address: Option<Address>,
     _m: PhantomData<M>,
 }
 
@@ -207,6 +213,7 @@ where
             client: self.client.clone(),
             confs: self.confs,
             block: self.block,
+            create2_address: self.create2_address,
             _m: PhantomData,
         }
     }
@@ -293,12 +300,23 @@ where
             .ok()
             .flatten()
             .ok_or(ContractError::ContractNotDeployed)?;
-        let address = receipt.contract_address.ok_or(ContractError::ContractNotDeployed)?;
+        let address = match self.create2_address {
+            // the factory reverts if the contract could not be created
+            Some(address) if receipt.status != Some(0.into()) => address,
+            Some(_) => return Err(ContractError::ContractNotDeployed),
+            None => receipt.contract_address.ok_or(ContractError::ContractNotDeployed)?,
+        };
 
         let contract = ContractInstance::new(address, self.abi, self.client);
         Ok((contract, receipt))
     }
 
+    /// Returns the address the contract will be deployed at, if it is deployed through a CREATE2
+    /// factory
+    pub fn create2_address(&self) -> Option<Address> {
+        self.create2_address
+    }
+
     /// Returns a reference to the deployer's ABI
     pub fn abi(&self) -> &Abi {
         &self.abi
@@ -351,6 +369,8 @@ pub struct DeploymentTxFactory<B, M> {
     client: B,
     abi: Abi,
     bytecode: Bytes,
+    /// The hex encoded bytecode while it contains placeholders of libraries that are not linked
+    unlinked: Option<String>,
     _m: PhantomData<M>,
 }
 
@@ -363,6 +383,7 @@ where
             client: self.client.clone(),
             abi: self.abi.clone(),
             bytecode: self.bytecode.clone(),
+            unlinked: self.unlinked.clone(),
             _m: PhantomData,
         }
     }
@@ -377,41 +398,155 @@ where
     /// constructor defined in the abi. The client will be used to send any deployment
     /// transaction.
     pub fn new(abi: Abi, bytecode: Bytes, client: B) -> Self {
-        Self { client, abi, bytecode, _m: PhantomData }
+        Self { client, abi, bytecode, unlinked: None, _m: PhantomData }
     }
 
-    /// Create a deployment tx using the provided tokens as constructor
-    /// arguments
-    pub fn deploy_tokens(self, params: Vec<Token>) -> Result<Deployer<B, M>, ContractError<M>>
+    /// Creates a factory for a contract whose hex encoded `bytecode` contains placeholders of
+    /// libraries, like `__$<hash>$__`, which have to be replaced with the addresses of the deployed
+    /// libraries with [`link_library`](Self::link_library) before the contract can be deployed.
+    pub fn new_unlinked(abi: Abi, bytecode: impl Into<String>, client: B) -> Self {
+        let bytecode = bytecode.into();
+        let unlinked = bytecode.strip_prefix("0x").unwrap_or(&bytecode).to_string();
+        let mut factory = Self::new(abi, Bytes::new(), client);
+        factory.unlinked = Some(unlinked);
+        factory.try_link();
+        factory
+    }
+
+    /// Replaces the placeholders of the library `name` with its `address`.
+    ///
+    /// The `name` is either the fully qualified name of the library, like `src/Math.sol:Math`,
+    /// whose keccak256 based placeholder or deprecated fully qualified placeholder is replaced, or
+    /// the 34 hex characters of the keccak256 based placeholder itself.
+    ///
+    /// Returns true if all libraries are linked afterwards.
+    pub fn link_library(&mut self, name: &str, address: Address) -> bool {
+        if let Some(unlinked) = self.unlinked.as_mut() {
+            let address = hex::encode(address);
+            let hash = hex::encode(&keccak256(name)[..17]);
+            let fully_qualified: String = name.chars().chain(iter::repeat('_')).take(36).collect();
+            *unlinked = unlinked
+                .replace(&format!("__${hash}$__"), &address)
+                .replace(&format!("__${name}$__"), &address)
+                .replace(&format!("__{fully_qualified}__"), &address);
+        }
+        self.try_link()
+    }
+
+    /// Links all `libraries`, given as pairs of their name and address, see
+    /// [`link_library`](Self::link_library).
+    ///
+    /// Returns true if all libraries are linked afterwards.
+    pub fn link_all<I, S>(&mut self, libraries: I) -> bool
     where
-        B: Clone,
+        I: IntoIterator<Item = (S, Address)>,
+        S: AsRef<str>,
     {
-        // Encode the constructor args & concatenate with the bytecode if necessary
-        let data: Bytes = match (self.abi.constructor(), params.is_empty()) {
+        for (name, address) in libraries {
+            self.link_library(name.as_ref(), address);
+        }
+        self.try_link()
+    }
+
+    /// Returns the placeholders of the libraries that are not linked yet
+    pub fn unlinked_placeholders(&self) -> Vec<String> {
+        let mut placeholders: Vec<String> = Vec::new();
+        let mut rest = self.unlinked.as_deref().unwrap_or_default();
+        while let Some(start) = rest.find("__") {
+            let end = start + LIBRARY_PLACEHOLDER_LEN;
+            let placeholder = rest.get(start..end).unwrap_or(&rest[start..]);
+            if !placeholders.iter().any(|p| p == placeholder) {
+                placeholders.push(placeholder.to_string());
+            }
+            rest = rest.get(end..).unwrap_or_default();
+        }
+        placeholders
+    }
+
+    /// Decodes the bytecode once all libraries are linked, returns true if it is linked
+    fn try_link(&mut self) -> bool {
+        let Some(unlinked) = self.unlinked.as_deref() else { return true };
+        match hex::decode(unlinked) {
+            Ok(bytecode) => {
+                self.bytecode = bytecode.into();
+                self.unlinked = None;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Encodes the constructor args & concatenates them with the bytecode if necessary
+    fn init_code(&self, params: &[Token]) -> Result<Bytes, ContractError<M>> {
+        if self.unlinked.is_some() {
+            let placeholders = self.unlinked_placeholders();
+            if placeholders.is_empty() {
+                // the bytecode is not valid hex regardless of the placeholders
+                return Err(ethers_core::abi::Error::InvalidData.into())
+            }
+            return Err(ContractError::UnlinkedLibraries(placeholders))
+        }
+        Ok(match (self.abi.constructor(), params.is_empty()) {
             (None, false) => return Err(ContractError::ConstructorError),
             (None, true) => self.bytecode.clone(),
             (Some(constructor), _) => {
-                constructor.encode_input(self.bytecode.to_vec(), &params)?.into()
+                constructor.encode_input(self.bytecode.to_vec(), params)?.into()
             }
-        };
+        })
+    }
 
-        // create the tx object. Since we're deploying a contract, `to` is `None`
+    /// Creates the deployment tx, which is sent to `to` with the given `data`
+    fn deployer(
+        self,
+        to: Option<Address>,
+        data: Bytes,
+        create2_address: Option<Address>,
+    ) -> Deployer<B, M> {
+        let to = to.map(Into::into);
         // We default to EIP-1559 transactions, but the sender can convert it back
         // to a legacy one
         #[cfg(feature = "legacy")]
-        let tx = TransactionRequest { to: None, data: Some(data), ..Default::default() };
+        let tx = TransactionRequest { to, data: Some(data), ..Default::default() };
         #[cfg(not(feature = "legacy"))]
-        let tx = Eip1559TransactionRequest { to: None, data: Some(data), ..Default::default() };
-        let tx = tx.into();
+        let tx = Eip1559TransactionRequest { to, data: Some(data), ..Default::default() };
 
-        Ok(Deployer {
-            client: self.client.clone(),
+        Deployer {
+            client: self.client,
             abi: self.abi,
-            tx,
+            tx: tx.into(),
             confs: 1,
             block: BlockNumber::Latest,
+            create2_address,
             _m: PhantomData,
-        })
+        }
+    }
+
+    /// Create a deployment tx using the provided tokens as constructor
+    /// arguments
+    pub fn deploy_tokens(self, params: Vec<Token>) -> Result<Deployer<B, M>, ContractError<M>>
+    where
+        B: Clone,
+    {
+        let data = self.init_code(&params)?;
+        // Since we're deploying a contract, `to` is `None`
+        Ok(self.deployer(None, data, None))
+    }
+
+    /// Create a deployment tx through the CREATE2 factory at `deployer`, using the provided tokens
+    /// as constructor arguments.
+    ///
+    /// See [`deploy_create2`](Self::deploy_create2) for more details.
+    pub fn deploy_create2_tokens(
+        self,
+        params: Vec<Token>,
+        salt: H256,
+        deployer: Address,
+    ) -> Result<Deployer<B, M>, ContractError<M>> {
+        let init_code = self.init_code(&params)?;
+        let address = get_create2_address(deployer, salt, &init_code);
+        // the factory expects the salt followed by the init code as calldata
+        let data = [salt.as_bytes(), init_code.as_ref()].concat().into();
+        Ok(self.deployer(Some(deployer), data, Some(address)))
     }
 
     /// Constructs the deployment transaction based on the provided constructor
@@ -428,4 +563,36 @@ where
     ) -> Result<Deployer<B, M>, ContractError<M>> {
         self.deploy_tokens(constructor_args.into_tokens())
     }
+
+    /// Constructs a transaction that deploys the contract through the CREATE2 factory at
+    /// `deployer`, e.g. the [deterministic deployment proxy](https://github.com/Arachnid/deterministic-deployment-proxy),
+    /// and returns a `Deployer` instance. You must call `send()` in order to actually deploy the
+    /// contract.
+    ///
+    /// The factory is called with the `salt` followed by the init code, i.e. the bytecode and the
+    /// encoded constructor arguments. Since the address of the contract does not depend on the
+    /// sender's nonce, it is the same on every chain the factory is deployed to, and it is known
+    /// before the deployment, see [`compute_create2_address`](Self::compute_create2_address).
+    pub fn deploy_create2<T: Tokenize>(
+        self,
+        constructor_args: T,
+        salt: H256,
+        deployer: Address,
+    ) -> Result<Deployer<B, M>, ContractError<M>> {
+        self.deploy_create2_tokens(constructor_args.into_tokens(), salt, deployer)
+    }
+
+    /// Returns the address the contract is deployed at with
+    /// [`deploy_create2`](Self::deploy_create2), without sending any request:
+    ///
+    /// `keccak256(0xff ++ deployer ++ salt ++ keccak256(init_code))[12..]`
+    pub fn compute_create2_address<T: Tokenize>(
+        &self,
+        constructor_args: T,
+        salt: H256,
+        deployer: Address,
+    ) -> Result<Address, ContractError<M>> {
+        let init_code = self.init_code(&constructor_args.into_tokens())?;
+        Ok(get_create2_address(deployer, salt, init_code))
+    }
 }
@@ -15,7 +15,7 @@ mod base;
 pub use base::{decode_function_data, encode_function_data, AbiError, BaseContract};
 
 mod call_core;
-pub use call_core::EthCall;
+pub use call_core::{EthCall, UninitializedFieldError};
 
 mod error;
 pub use error::{ContractRevert, EthError};
@@ -84,6 +84,12 @@ if_providers! {
     mod call;
     pub use call::{ContractCall, ContractError, FunctionCall};
 
+    mod call_batch;
+    pub use call_batch::CallBatch;
+
+    mod call_cache;
+    pub use call_cache::{CallCache, CallCacheKey, MemoryCallCache};
+
     mod factory;
     pub use factory::{ContractDeployer, ContractDeploymentTx, ContractFactory, DeploymentTxFactory};
 
@@ -38,6 +38,11 @@ pub struct LogMeta {
 
     /// Log index position in the block
     pub log_index: U256,
+
+    /// Whether the log was removed due to a chain reorganization, which is only reported for
+    /// logs of subscriptions and filters
+    #[serde(default)]
+    pub removed: bool,
 }
 
 impl From<&Log> for LogMeta {
@@ -49,6 +54,7 @@ impl From<&Log> for LogMeta {
             transaction_hash: src.transaction_hash.expect("should have a tx hash"),
             transaction_index: src.transaction_index.expect("should have a tx index"),
             log_index: src.log_index.expect("should have a log index"),
+            removed: src.removed.unwrap_or_default(),
         }
     }
 }
@@ -5,7 +5,7 @@ use ethers_core::{
 
 use ethers_providers::{Middleware, ProviderError};
 
-use crate::{ContractError, EthError};
+use crate::{ContractError, ContractRevert, EthError};
 
 /// Errors using the [`crate::Multicall`] system
 #[derive(Debug, thiserror::Error)]
@@ -95,4 +95,13 @@ impl<M: Middleware> MulticallError<M> {
     pub fn decode_revert<Err: EthError>(&self) -> Option<Err> {
         self.as_revert().and_then(|data| Err::decode_with_selector(data))
     }
+
+    /// Decode revert data into a [`ContractRevert`] type. Returns `None` if
+    /// decoding fails, or if this is not a revert
+    ///
+    /// This is intended to be used with error enum outputs from `abigen!`
+    /// contracts
+    pub fn decode_contract_revert<Err: ContractRevert>(&self) -> Option<Err> {
+        self.as_revert().and_then(|data| Err::decode_with_selector(data))
+    }
 }
@@ -10,11 +10,13 @@ use crate::call::{ContractCall, ContractError};
 use ethers_core::{
     abi::{Detokenize, Function, Token, Tokenizable},
     types::{
-        transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber, Bytes,
-        NameOrAddress, U256,
+        transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber, BlockOverrides,
+        Bytes, NameOrAddress, U256,
     },
 };
-use ethers_providers::{spoof::State, Middleware, PendingTransaction, RawCall};
+use ethers_providers::{
+    spoof::State, Middleware, PendingTransaction, RawCall, RawCallBlockOverrides,
+};
 use std::{fmt, result::Result as StdResult, sync::Arc};
 
 pub use super::contract::Multicall3 as MulticallContract;
@@ -148,6 +150,14 @@ pub struct Multicall<M> {
     /// The state overrides of the Multicall aggregate
     pub state: Option<State>,
 
+    /// The block overrides of the Multicall aggregate
+    pub block_overrides: Option<BlockOverrides>,
+
+    /// The maximum estimated gas of a single aggregate call, see [`chunk_gas_limit`].
+    ///
+    /// [`chunk_gas_limit`]: #method.chunk_gas_limit
+    pub chunk_gas_limit: Option<U256>,
+
     /// The internal call vector.
     calls: Vec<Call>,
 }
@@ -162,6 +172,8 @@ impl<M> Clone for Multicall<M> {
             block: self.block,
             calls: self.calls.clone(),
             state: self.state.clone(),
+            block_overrides: self.block_overrides.clone(),
+            chunk_gas_limit: self.chunk_gas_limit,
         }
     }
 }
@@ -174,6 +186,8 @@ impl<M> fmt::Debug for Multicall<M> {
             .field("legacy", &self.legacy)
             .field("block", &self.block)
             .field("state", &self.state)
+            .field("block_overrides", &self.block_overrides)
+            .field("chunk_gas_limit", &self.chunk_gas_limit)
             .field("calls", &self.calls)
             .finish()
     }
@@ -222,6 +236,8 @@ impl<M: Middleware> Multicall<M> {
             legacy: false,
             block: None,
             state: None,
+            block_overrides: None,
+            chunk_gas_limit: None,
             calls: vec![],
             contract,
         })
@@ -272,6 +288,8 @@ impl<M: Middleware> Multicall<M> {
             legacy: false,
             block: None,
             state: None,
+            block_overrides: None,
+            chunk_gas_limit: None,
             calls: vec![],
             contract,
         })
@@ -318,6 +336,29 @@ impl<M: Middleware> Multicall<M> {
         self
     }
 
+    /// Sets the block overrides of the Multicall aggregate call, e.g. to execute the calls as if
+    /// the block had a later timestamp.
+    pub fn block_overrides(mut self, overrides: BlockOverrides) -> Self {
+        self.block_overrides = Some(overrides);
+        self
+    }
+
+    /// Splits the calls into multiple aggregate calls when querying them with [`call`],
+    /// [`call_array`] or [`call_raw`] so that the estimated gas of each aggregate call does not
+    /// exceed `gas_limit`, e.g. the block gas limit. The results are returned in the order the
+    /// calls were added, as if they were made in a single aggregate call.
+    ///
+    /// Batches exceeding the limit are split in halves until they fit, a single call that exceeds
+    /// the limit on its own is still made. The batches are estimated without the state overrides.
+    ///
+    /// [`call`]: #method.call
+    /// [`call_array`]: #method.call_array
+    /// [`call_raw`]: #method.call_raw
+    pub fn chunk_gas_limit(mut self, gas_limit: impl Into<U256>) -> Self {
+        self.chunk_gas_limit = Some(gas_limit.into());
+        self
+    }
+
     /// Appends a `call` to the list of calls of the Multicall instance.
     ///
     /// Version specific details:
@@ -337,6 +378,8 @@ impl<M: Middleware> Multicall<M> {
             TypedTransaction::Legacy(tx) => (tx.to, tx.data, tx.value),
             TypedTransaction::Eip2930(tx) => (tx.tx.to, tx.tx.data, tx.tx.value),
             TypedTransaction::Eip1559(tx) => (tx.to, tx.data, tx.value),
+            TypedTransaction::Eip4844(tx) => (tx.tx.to, tx.tx.data, tx.tx.value),
+            TypedTransaction::Eip7702(tx) => (tx.tx.to, tx.tx.data, tx.tx.value),
             #[cfg(feature = "optimism")]
             TypedTransaction::DepositTransaction(tx) => (tx.tx.to, tx.tx.data, tx.tx.value),
         };
@@ -603,8 +646,9 @@ impl<M: Middleware> Multicall<M> {
     /// Queries the Ethereum blockchain using `eth_call`, but via the Multicall contract.
     ///
     /// Returns a vector of `Result<Token, Bytes>` for each call added to the Multicall:
-    /// `Err(Bytes)` if the individual call failed while allowed or the return data was empty,
-    /// `Ok(Token)` otherwise.
+    /// `Err(Bytes)` if the individual call failed while allowed, the return data was empty, or the
+    /// return data of a call that is allowed to fail could not be decoded with the call's output
+    /// types, `Ok(Token)` otherwise.
     ///
     /// If the Multicall version is 1, this will always be a vector of `Ok`.
     ///
@@ -630,16 +674,54 @@ impl<M: Middleware> Multicall<M> {
     /// # }
     /// ```
     pub async fn call_raw(&self) -> Result<Vec<StdResult<Token, Bytes>>, M> {
+        let Some(gas_limit) = self.chunk_gas_limit else { return self.call_raw_batch().await };
+
+        let mut results = Vec::with_capacity(self.calls.len());
+        for chunk in self.split_by_gas(gas_limit).await? {
+            results.extend(chunk.call_raw_batch().await?);
+        }
+        Ok(results)
+    }
+
+    /// Splits the calls into consecutive batches whose aggregate call's estimated gas does not
+    /// exceed `gas_limit`, or that consist of a single call.
+    async fn split_by_gas(&self, gas_limit: U256) -> Result<Vec<Self>, M> {
+        let mut chunks = Vec::new();
+        // the ranges are popped in order, since the second half is pushed first
+        let mut pending: Vec<_> = std::iter::once(0..self.calls.len()).collect();
+        while let Some(range) = pending.pop() {
+            let chunk = Self { calls: self.calls[range.clone()].to_vec(), ..self.clone() };
+            if range.len() <= 1 || chunk.estimate_aggregate_gas().await? <= gas_limit {
+                chunks.push(chunk);
+                continue
+            }
+            let mid = range.start + range.len() / 2;
+            pending.push(mid..range.end);
+            pending.push(range.start..mid);
+        }
+        Ok(chunks)
+    }
+
+    /// Estimates the gas of the aggregate call used by [`call_raw`].
+    ///
+    /// [`call_raw`]: #method.call_raw
+    async fn estimate_aggregate_gas(&self) -> Result<U256, M> {
+        let estimate = match self.version {
+            MulticallVersion::Multicall => self.as_aggregate().estimate_gas().await?,
+            MulticallVersion::Multicall2 => self.as_try_aggregate().estimate_gas().await?,
+            MulticallVersion::Multicall3 => self.as_aggregate_3().estimate_gas().await?,
+        };
+        Ok(estimate)
+    }
+
+    /// Makes all the calls in a single aggregate call.
+    async fn call_raw_batch(&self) -> Result<Vec<StdResult<Token, Bytes>>, M> {
         // Different call result types based on version
         match self.version {
             // Wrap the return data with `success: true` since version 1 reverts if any call failed
             MulticallVersion::Multicall => {
                 let call = self.as_aggregate();
-                let (_, bytes) = if let Some(state) = &self.state {
-                    ContractCall::call_raw(&call).state(state).await?
-                } else {
-                    ContractCall::call(&call).await?
-                };
+                let (_, bytes) = self.call_with_overrides(&call).await?;
                 self.parse_call_result(
                     bytes
                         .into_iter()
@@ -653,16 +735,27 @@ impl<M: Middleware> Multicall<M> {
                 } else {
                     self.as_aggregate_3()
                 };
-                let results = if let Some(state) = &self.state {
-                    ContractCall::call_raw(&call).state(state).await?
-                } else {
-                    ContractCall::call(&call).await?
-                };
+                let results = self.call_with_overrides(&call).await?;
                 self.parse_call_result(results.into_iter())
             }
         }
     }
 
+    /// Executes the aggregate call with the state and block overrides
+    async fn call_with_overrides<D: Detokenize>(&self, call: &ContractCall<M, D>) -> Result<D, M> {
+        if self.state.is_none() && self.block_overrides.is_none() {
+            return Ok(call.call().await?)
+        }
+        let mut raw = call.call_raw();
+        if let Some(state) = &self.state {
+            raw = raw.state(state);
+        }
+        if let Some(overrides) = &self.block_overrides {
+            raw = raw.block_overrides(overrides);
+        }
+        Ok(raw.await?)
+    }
+
     /// For each call and its `return_data`: if `success` is true, parses `return_data` with the
     /// call's function outputs, otherwise returns the bytes in `Err`.
     fn parse_call_result(
@@ -684,12 +777,17 @@ impl<M: Middleware> Multicall<M> {
 
                 Err(return_data)
             } else {
-                let mut res_tokens = call.function.decode_output(return_data.as_ref())?;
-                Ok(if res_tokens.len() == 1 {
-                    res_tokens.pop().unwrap()
-                } else {
-                    Token::Tuple(res_tokens)
-                })
+                match call.function.decode_output(return_data.as_ref()) {
+                    Ok(mut res_tokens) => Ok(if res_tokens.len() == 1 {
+                        res_tokens.pop().unwrap()
+                    } else {
+                        Token::Tuple(res_tokens)
+                    }),
+                    // the call may have succeeded because the target is not the expected
+                    // contract, e.g. an EOA or a contract with a fallback function
+                    Err(_) if call.allow_failure => Err(return_data),
+                    Err(err) => return Err(err.into()),
+                }
             };
             results.push(result);
         }
@@ -849,3 +947,9 @@ impl<'a, M: Middleware> RawCall<'a> for Multicall<M> {
         self.state(state.clone())
     }
 }
+
+impl<'a, M: Middleware> RawCallBlockOverrides<'a> for Multicall<M> {
+    fn block_overrides(self, overrides: &'a BlockOverrides) -> Self {
+        self.block_overrides(overrides.clone())
+    }
+}
@@ -216,3 +216,31 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::H256;
+    use futures_util::stream;
+
+    #[tokio::test]
+    async fn yields_removed_logs_with_meta() {
+        let log = |removed| Log {
+            block_number: Some(1.into()),
+            block_hash: Some(H256::repeat_byte(1)),
+            transaction_hash: Some(H256::repeat_byte(2)),
+            transaction_index: Some(0.into()),
+            log_index: Some(0.into()),
+            removed: Some(removed),
+            ..Default::default()
+        };
+        let logs = stream::iter([log(false), log(true)]);
+        let events =
+            EventStream::new(U256::one(), logs, Box::new(|log: Log| Ok::<_, ()>(log.data)))
+                .with_meta()
+                .map(|event| event.unwrap().1.removed)
+                .collect::<Vec<_>>()
+                .await;
+        assert_eq!(events, [false, true]);
+    }
+}
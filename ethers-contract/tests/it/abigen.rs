@@ -43,6 +43,22 @@ fn can_generate_human_readable() {
     assert_eq!("ValueChanged(address,string,string)", ValueChangedFilter::abi_signature());
 }
 
+#[test]
+fn can_generate_with_default_derive() {
+    abigen!(
+        SimpleContract,
+        [
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function setValues(uint256[33] values)",
+        ],
+        derives(Default)
+    );
+    // `Default` is derived where all the fields implement it, and skipped otherwise
+    let call = TransferCall::default();
+    assert_eq!(call, TransferCall { to: Address::zero(), amount: U256::zero() });
+    let _ = SetValuesCall { values: [U256::zero(); 33] };
+}
+
 #[test]
 fn can_generate_not_human_readable() {
     abigen!(VerifierAbiHardhatContract, "./tests/solidity-contracts/verifier_abi_hardhat.json");
@@ -69,6 +85,45 @@ fn can_generate_human_readable_multiple() {
     assert_eq!("ValueChanged2(address,string,string)", ValueChanged2Filter::abi_signature());
 }
 
+#[test]
+fn can_generate_conflicting_shared_structs() {
+    mod contracts {
+        use super::*;
+        abigen!(
+            Exchange,
+            ["struct Order { address maker; uint256 amount; }", "function fill(Order order)"];
+            Vault,
+            ["struct Order { address maker; uint256 amount; }", "function deposit(Order order)"];
+            Book,
+            ["struct Order { uint256 id; }", "function cancel(Order order)"];
+        );
+    }
+    use contracts::*;
+
+    // the shared definition can be passed between the interfaces
+    let order = exchange::Order { maker: Address::zero(), amount: 1.into() };
+    let _ = vault::DepositCall { order: order.clone() };
+    let _: __shared_types::Order = exchange::FillCall { order }.order;
+    // the conflicting definition is shared with a suffix, and used under its name by its contract
+    let _: __shared_types::Order1 = book::CancelCall { order: book::Order { id: 1.into() } }.order;
+}
+
+#[test]
+fn can_generate_human_readable_array() {
+    abigen!(
+        SimpleContract,
+        [
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function transfer(address to, uint256 amount, bytes data) returns (bool)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Ping() anonymous",
+        ],
+    );
+    assert_eq!("Transfer(address,address,uint256)", TransferFilter::abi_signature());
+    assert_eq!("Ping() anonymous", PingFilter::abi_signature());
+    assert_eq!(SIMPLECONTRACT_ABI.functions_by_name("transfer").unwrap().len(), 2);
+}
+
 #[test]
 fn can_generate_structs_readable() {
     abigen!(
@@ -581,6 +636,53 @@ fn can_handle_overloaded_events() {
     let _ev2 = ActionPaused2Filter { action: "action".to_string(), pause_state: false };
 }
 
+#[test]
+#[cfg(feature = "providers")]
+fn can_filter_events_by_indexed_params() {
+    use ethers_core::types::H256;
+
+    abigen!(
+        SimpleContract,
+        [
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Named(string indexed name, uint256 indexed id, bool flag) anonymous",
+            "event Moved(address indexed address, uint256 indexed fromBlock, bytes32 indexed topic0)",
+        ]
+    );
+
+    let (provider, _) = Provider::mocked();
+    let contract = SimpleContract::new(Address::zero(), Arc::new(provider));
+
+    let from = Address::random();
+    let to = Address::random();
+    let filter = contract.transfer_filter().with_from(from).with_to(to).filter;
+    assert_eq!(filter.topics[0], Some(TransferFilter::signature().into()));
+    assert_eq!(filter.topics[1], Some(H256::from(from).into()));
+    assert_eq!(filter.topics[2], Some(H256::from(to).into()));
+    assert_eq!(filter.topics[3], None);
+
+    // anonymous events have no signature topic, and indexed strings are filtered by their hash
+    let name = ethers_core::utils::keccak256("name").into();
+    let filter = contract.named_filter().with_name(name).with_id(42u64.into()).filter;
+    assert_eq!(filter.topics[0], Some(name.into()));
+    assert_eq!(filter.topics[1], Some(H256::from_low_u64_be(42).into()));
+    assert_eq!(filter.topics[2], None);
+
+    // parameters named like the inherent methods of `Event` don't clash with them
+    let topic = H256::random();
+    let filter = contract
+        .moved_filter()
+        .with_address(from)
+        .with_from_block(7u64.into())
+        .with_topic_0(topic.into())
+        .from_block(7)
+        .filter;
+    assert_eq!(filter.topics[1], Some(H256::from(from).into()));
+    assert_eq!(filter.topics[2], Some(H256::from_low_u64_be(7).into()));
+    assert_eq!(filter.topics[3], Some(topic.into()));
+    assert_eq!(filter.get_from_block(), Some(7u64.into()));
+}
+
 #[tokio::test]
 #[cfg(not(feature = "celo"))]
 #[cfg(feature = "providers")]
@@ -637,6 +739,127 @@ fn can_generate_seaport_1_0() {
     });
 }
 
+#[tokio::test]
+#[cfg(feature = "providers")]
+async fn decodes_custom_error_from_reverted_call() {
+    use ethers_contract::ContractRevert;
+    use ethers_providers::{JsonRpcError, MockResponse};
+
+    abigen!(
+        Vault,
+        r#"[
+            function withdraw(uint256 amount) external
+            error InsufficientBalance(uint256 available, uint256 required)
+            error InsufficientAllowance(uint256 available, uint256 required)
+        ]"#
+    );
+
+    // both errors have the same shape, only the selector tells them apart
+    let err = InsufficientAllowance { available: 1u64.into(), required: 2u64.into() };
+    let revert_data: Bytes = err.clone().encode().into();
+    assert!(VaultErrors::valid_selector(InsufficientAllowance::selector()));
+    assert_eq!(
+        VaultErrors::decode_with_selector(&revert_data),
+        Some(VaultErrors::InsufficientAllowance(err.clone()))
+    );
+
+    let (provider, mock) = Provider::mocked();
+    mock.push_response(MockResponse::Error(JsonRpcError {
+        code: 3,
+        message: "execution reverted".to_string(),
+        data: Some(serde_json::json!(revert_data)),
+    }));
+    let contract = Vault::new(Address::zero(), Arc::new(provider));
+    let call_err = contract.withdraw(2u64.into()).call().await.unwrap_err();
+
+    assert_eq!(call_err.as_revert(), Some(&revert_data));
+    assert_eq!(
+        call_err.decode_contract_revert::<VaultErrors>(),
+        Some(VaultErrors::InsufficientAllowance(err.clone()))
+    );
+    assert_eq!(call_err.decode_revert::<InsufficientAllowance>(), Some(err));
+    assert_eq!(call_err.decode_revert::<InsufficientBalance>(), None);
+    assert_eq!(call_err.decode_revert::<String>(), None);
+}
+
+#[test]
+#[cfg(feature = "providers")]
+fn event_queries_filter_on_signatures() {
+    use ethers_core::types::{ValueOrArray, H256};
+
+    abigen!(
+        Token,
+        r#"[
+            event Transfer(address indexed from, address indexed to, uint256 value)
+            event Approval(address indexed owner, address indexed spender, uint256 value)
+        ]"#
+    );
+    abigen!(
+        Logger,
+        r#"[
+            event Logged(uint256 value) anonymous
+            event Named(uint256 value)
+        ]"#
+    );
+
+    let (provider, _) = Provider::mocked();
+    let client = Arc::new(provider);
+    let address = Address::repeat_byte(1);
+    let token = Token::new(address, client.clone());
+
+    let filter = token.transfer_filter().filter;
+    assert_eq!(filter.address, Some(ValueOrArray::Value(address)));
+    assert_eq!(filter.topics[0], Some(ValueOrArray::Value(Some(TransferFilter::signature()))));
+
+    let filter = token.events().filter;
+    assert_eq!(filter.address, Some(ValueOrArray::Value(address)));
+    let mut topic0 = match filter.topics[0].clone() {
+        Some(ValueOrArray::Array(topic0)) => topic0,
+        topic0 => panic!("unexpected topic0: {topic0:?}"),
+    };
+    topic0.sort();
+    let mut expected: Vec<Option<H256>> =
+        vec![Some(TransferFilter::signature()), Some(ApprovalFilter::signature())];
+    expected.sort();
+    assert_eq!(topic0, expected);
+
+    // anonymous events don't have their signature as topic0
+    let logger = Logger::new(address, client);
+    assert_eq!(logger.logged_filter().filter.topics[0], None);
+    assert_eq!(logger.events().filter.topics[0], None);
+}
+
+#[test]
+fn decodes_transaction_input_by_selector() {
+    use ethers_core::types::Transaction;
+
+    abigen!(
+        Erc20,
+        r#"[
+            function transfer(address to, uint256 amount) external returns (bool)
+            function approve(address spender, uint256 amount) external returns (bool)
+            function totalSupply() external view returns (uint256)
+        ]"#
+    );
+
+    // `transfer` and `approve` share the same argument types
+    let call = ApproveCall { spender: Address::repeat_byte(1), amount: 42u64.into() };
+    let tx = Transaction { input: call.clone().encode().into(), ..Default::default() };
+    assert_eq!(Erc20Calls::decode(&tx.input).unwrap(), Erc20Calls::Approve(call));
+
+    let tx = Transaction { input: TotalSupplyCall.encode().into(), ..Default::default() };
+    assert_eq!(Erc20Calls::decode(&tx.input).unwrap(), Erc20Calls::TotalSupply(TotalSupplyCall));
+
+    // unknown selector
+    assert!(Erc20Calls::decode([0xde, 0xad, 0xbe, 0xef]).is_err());
+    // too short for a selector
+    assert!(Erc20Calls::decode([0xa9, 0x05]).is_err());
+    // known selector with truncated arguments
+    let mut input = TransferCall { to: Address::zero(), amount: 1u64.into() }.encode();
+    input.truncate(20);
+    assert!(Erc20Calls::decode(input).is_err());
+}
+
 #[test]
 fn can_generate_seaport_gt1_0() {
     mod v1_1 {
@@ -879,3 +1102,110 @@ fn abigen_overloaded_methods2() {
     assert_eq!(e3.3, vec![b1, b2]);
     assert_eq!(e1_encoded.to_string(), "0x24856bc3000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000002abcd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000002abcd00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000011200000000000000000000000000000000000000000000000000000000000000");
 }
+
+#[test]
+fn can_generate_call_builders() {
+    abigen!(
+        BuilderContract,
+        r#"[
+            struct Order { address maker; uint256 amount; }
+            function place(Order order, address taker, uint256 expiry) external
+            function cancel(uint256) external
+            function release(uint256 build, bool force) external
+        ]"#,
+        call_builders,
+    );
+
+    let order = Order { maker: Address::repeat_byte(1), amount: 5.into() };
+    let call = PlaceCall::builder()
+        .order(order.clone())
+        .taker(Address::repeat_byte(2))
+        .expiry(10.into())
+        .build()
+        .unwrap();
+    assert_eq!(
+        call,
+        PlaceCall { order: order.clone(), taker: Address::repeat_byte(2), expiry: 10.into() }
+    );
+
+    let err = PlaceCall::builder().order(order).expiry(10.into()).build().unwrap_err();
+    assert_eq!(err, ethers_contract::UninitializedFieldError("taker"));
+    assert_eq!(err.to_string(), "field `taker` was not set");
+
+    // the setter of a `build` field doesn't collide with the builder's `build` method
+    let call = ReleaseCall::builder().build_(3.into()).force(true).build().unwrap();
+    assert_eq!(call, ReleaseCall { build: 3.into(), force: true });
+
+    // tuple call structs are still built positionally
+    assert_eq!(CancelCall(1.into()).0, U256::from(1));
+}
+
+#[test]
+#[cfg(feature = "providers")]
+fn can_generate_into_args() {
+    abigen!(
+        IntoArgsContract,
+        r#"[
+            function transfer(address to, uint256 amount, int64 delta, bool flag) external
+            function batch(address[] to) external
+        ]"#,
+        into_args,
+    );
+
+    let (provider, _) = Provider::mocked();
+    let contract = IntoArgsContract::new(Address::zero(), Arc::new(provider));
+
+    let call = contract.transfer([1; 20], 5u64, 3i8, true);
+    let expected =
+        TransferCall { to: Address::repeat_byte(1), amount: 5.into(), delta: 3, flag: true }
+            .encode();
+    assert_eq!(call.calldata().unwrap(), expected);
+
+    // the parameters can still be passed with their exact types
+    let call = contract.transfer(Address::repeat_byte(1), U256::from(5), 3, true);
+    assert_eq!(call.calldata().unwrap(), expected);
+
+    // arrays keep their exact types
+    let call = contract.batch(vec![Address::repeat_byte(1)]);
+    assert_eq!(call.calldata().unwrap(), BatchCall { to: vec![Address::repeat_byte(1)] }.encode());
+}
+
+#[test]
+fn decodes_indexed_dynamic_and_anonymous_events() {
+    use ethers_contract::EthLogDecode;
+    use ethers_core::{abi::RawLog, types::H256, utils::keccak256};
+
+    abigen!(
+        IndexedEvents,
+        r#"[
+            event Foo(string indexed name, uint256 value)
+            event Bar(bytes indexed data, address indexed owner, uint256 value) anonymous
+            event Baz() anonymous
+        ]"#,
+    );
+
+    // indexed dynamic values are only available as the hash of the value
+    let log = RawLog {
+        topics: vec![FooFilter::signature(), H256(keccak256("alice"))],
+        data: U256::from(7).encode(),
+    };
+    let foo = <FooFilter as EthEvent>::decode_log(&log).unwrap();
+    assert_eq!(foo, FooFilter { name: H256(keccak256("alice")), value: 7.into() });
+    assert_eq!(IndexedEventsEvents::decode_log(&log).unwrap(), IndexedEventsEvents::FooFilter(foo));
+
+    // anonymous events have no signature topic
+    assert!(BarFilter::is_anonymous());
+    let owner = Address::repeat_byte(1);
+    let log = RawLog {
+        topics: vec![H256(keccak256([1, 2, 3])), H256::from(owner)],
+        data: U256::from(8).encode(),
+    };
+    assert_eq!(
+        <BarFilter as EthEvent>::decode_log(&log).unwrap(),
+        BarFilter { data: H256(keccak256([1, 2, 3])), owner, value: 8.into() }
+    );
+    assert_eq!(
+        <BazFilter as EthEvent>::decode_log(&RawLog { topics: vec![], data: vec![] }).unwrap(),
+        BazFilter
+    );
+}
@@ -1,15 +1,18 @@
 use crate::common::*;
 use ethers_contract::{
-    abigen, ContractFactory, ContractInstance, EthEvent, LogMeta, Multicall, MulticallError,
-    MulticallVersion,
+    abigen, ContractError, ContractFactory, ContractInstance, EthEvent, LogMeta, Multicall,
+    MulticallError, MulticallVersion,
 };
 use ethers_core::{
     abi::{encode, AbiEncode, Token, Tokenizable},
-    types::{Address, BlockId, Bytes, Filter, ValueOrArray, H160, H256, U256},
-    utils::{keccak256, Anvil},
+    types::{
+        Address, BlockId, Bytes, Filter, Transaction, TransactionReceipt, ValueOrArray, H160, H256,
+        U256,
+    },
+    utils::{get_create2_address, hex, keccak256, Anvil},
 };
 use ethers_providers::{spoof, Http, Middleware, MiddlewareError, Provider, StreamExt, Ws};
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 #[derive(Debug)]
 pub struct NonClone<M> {
@@ -93,6 +96,87 @@ fn _it_compiles() {
     // let _ = c.method::<(), ()>("notARealMethod", ());
 }
 
+#[test]
+fn link_libraries() {
+    let (provider, _) = Provider::mocked();
+    let client = Arc::new(provider);
+    let abi = ethers_core::abi::parse_abi(&["constructor()"]).unwrap();
+    let math: Address = "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse().unwrap();
+    let strings: Address = "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512".parse().unwrap();
+    let math_hash = hex::encode(&keccak256("src/Math.sol:Math")[..17]);
+    // the deprecated placeholder is the fully qualified name padded to 36 characters
+    let strings_placeholder = format!("__{:_<36}__", "src/Strings.sol:Strings");
+    let bytecode = format!("0x6080__${math_hash}$__6040{strings_placeholder}6060__${math_hash}$__");
+
+    let mut factory = ContractFactory::new_unlinked(abi, bytecode, client);
+    assert_eq!(factory.unlinked_placeholders().len(), 2);
+    assert!(!factory.link_library("src/Math.sol:Math", math));
+    match factory.clone().deploy(()).unwrap_err() {
+        ContractError::UnlinkedLibraries(placeholders) => {
+            assert_eq!(placeholders, vec![strings_placeholder])
+        }
+        err => panic!("unexpected error {err:?}"),
+    }
+
+    assert!(factory.link_all(HashMap::from([("src/Strings.sol:Strings".to_string(), strings)])));
+    assert!(factory.unlinked_placeholders().is_empty());
+    let deployer = factory.deploy(()).unwrap();
+    let expected = [
+        &[0x60, 0x80],
+        math.as_bytes(),
+        &[0x60, 0x40],
+        strings.as_bytes(),
+        &[0x60, 0x60],
+        math.as_bytes(),
+    ]
+    .concat();
+    assert_eq!(deployer.tx.data().unwrap().to_vec(), expected);
+}
+
+#[tokio::test]
+async fn deploy_through_create2_factory() {
+    let (provider, mock) = Provider::mocked();
+    let client = Arc::new(provider.interval(Duration::from_millis(1)));
+    let abi = ethers_core::abi::parse_abi(&["constructor(uint256 value)"]).unwrap();
+    let bytecode = Bytes::from(vec![0x60, 0x80, 0x60, 0x40]);
+    let factory = ContractFactory::new(abi, bytecode.clone(), client);
+
+    let salt = H256::repeat_byte(0x11);
+    let create2_factory: Address = "0x4e59b44847b379578588920cA78FbF26c0B4956C".parse().unwrap();
+    let init_code = [bytecode.as_ref(), &encode(&[Token::Uint(42.into())])].concat();
+    let expected = get_create2_address(create2_factory, salt, &init_code);
+    assert_eq!(
+        factory.compute_create2_address(U256::from(42), salt, create2_factory).unwrap(),
+        expected
+    );
+
+    let deployer = factory
+        .deploy_create2(U256::from(42), salt, create2_factory)
+        .unwrap()
+        .legacy()
+        .confirmations(1usize);
+    assert_eq!(deployer.create2_address(), Some(expected));
+    assert_eq!(deployer.tx.to_addr(), Some(&create2_factory));
+    assert_eq!(deployer.tx.data().unwrap().to_vec(), [salt.as_bytes(), &init_code].concat());
+
+    let mut deployer = deployer;
+    deployer.tx.set_gas(1_000_000).set_gas_price(1);
+    let tx_hash = H256::repeat_byte(0x22);
+    mock.push(TransactionReceipt {
+        transaction_hash: tx_hash,
+        block_number: Some(1.into()),
+        status: Some(1.into()),
+        ..Default::default()
+    })
+    .unwrap();
+    mock.push(Transaction { hash: tx_hash, block_number: Some(1.into()), ..Default::default() })
+        .unwrap();
+    mock.push(tx_hash).unwrap();
+
+    let contract = deployer.send().await.unwrap();
+    assert_eq!(contract.address(), expected);
+}
+
 #[tokio::test]
 async fn deploy_and_call_contract() {
     let (abi, bytecode) = get_contract("SimpleStorage.json");
@@ -18,3 +18,240 @@ fn _contract_call_into_future_is_send() {
 
     drop(is_send(contract.cache().into_future()));
 }
+
+#[tokio::test]
+async fn contract_call_adjusts_gas_estimate() {
+    use ethers_core::types::U256;
+
+    abigen!(DsProxyFactory, "./../ethers-middleware/contracts/DSProxyFactory.json");
+    let (provider, mock) = Provider::mocked();
+    let client = Arc::new(provider);
+    let contract = DsProxyFactory::new(Address::zero(), client);
+
+    mock.push(U256::from(100_000u64)).unwrap();
+    let call = contract.build().gas_estimate_multiplier(1.25);
+    assert_eq!(call.estimate_gas().await.unwrap(), U256::from(125_000u64));
+
+    // the floor is applied after the multiplier
+    mock.push(U256::from(100_000u64)).unwrap();
+    let call = call.gas_estimate_floor(200_000u64);
+    assert_eq!(call.estimate_gas().await.unwrap(), U256::from(200_000u64));
+
+    mock.push(U256::from(300_000u64)).unwrap();
+    assert_eq!(call.estimate_gas().await.unwrap(), U256::from(375_000u64));
+}
+
+#[test]
+fn contract_call_encodes_input() {
+    use ethers_contract::{AbiError, Contract};
+    use ethers_core::{
+        abi::{parse_abi, Tokenize},
+        types::U256,
+    };
+
+    let (provider, _) = Provider::mocked();
+    let abi = parse_abi(&["function transfer(address to, uint256 amount) returns (bool)"]).unwrap();
+    let token = Contract::new(Address::zero(), abi, Arc::new(provider));
+
+    let args = (Address::repeat_byte(1), U256::from(100));
+    let mut call = token.method::<_, bool>("transfer", args).unwrap();
+    let data = call.encode_input().unwrap();
+    assert_eq!(Some(data.clone()), call.calldata());
+    assert_eq!(data[..4], call.function.short_signature());
+    assert_eq!(data[4..], ethers_core::abi::encode(&args.into_tokens()));
+
+    // the data has to match the function
+    call.tx.set_data(vec![0; 4].into());
+    assert!(matches!(call.encode_input(), Err(AbiError::WrongSelector)));
+}
+
+#[tokio::test]
+async fn contract_call_caches_results_at_block_numbers() {
+    use ethers_contract::{CallCache, CallCacheKey, Contract, MemoryCallCache};
+    use ethers_core::{
+        abi::{self, parse_abi, Token},
+        types::{BlockNumber, Bytes, U256},
+    };
+
+    let (provider, mock) = Provider::mocked();
+    let client = Arc::new(provider);
+    let abi = parse_abi(&["function totalSupply() external view returns (uint256)"]).unwrap();
+    let token = Contract::new(Address::zero(), abi, client);
+    let cache = Arc::new(MemoryCallCache::new());
+    let call = token.method::<_, U256>("totalSupply", ()).unwrap().cached(cache.clone());
+
+    // calls at a block number are only sent once
+    let at_block = call.clone().block(100u64);
+    mock.push::<Bytes, Bytes>(abi::encode(&[Token::Uint(42.into())]).into()).unwrap();
+    assert_eq!(at_block.call().await.unwrap(), U256::from(42));
+    assert_eq!(at_block.call().await.unwrap(), U256::from(42));
+    assert_eq!(cache.len(), 1);
+
+    // the block is part of the key
+    mock.push::<Bytes, Bytes>(abi::encode(&[Token::Uint(7.into())]).into()).unwrap();
+    assert_eq!(call.clone().block(101u64).call().await.unwrap(), U256::from(7));
+    assert_eq!(cache.len(), 2);
+
+    // calls at a tag are not cached
+    for block in [None, Some(BlockNumber::Latest)] {
+        let mut call = call.clone();
+        call.block = block.map(Into::into);
+        mock.push::<Bytes, Bytes>(abi::encode(&[Token::Uint(1.into())]).into()).unwrap();
+        assert_eq!(call.call().await.unwrap(), U256::from(1));
+    }
+    assert_eq!(cache.len(), 2);
+    let key = CallCacheKey { to: Address::zero(), calldata: call.calldata().unwrap(), block: 100 };
+    assert_eq!(cache.get(&key), Some(abi::encode(&[Token::Uint(42.into())]).into()));
+}
+
+#[tokio::test]
+async fn multicall_returns_undecodable_results_of_allowed_failures() {
+    use ethers_contract::{Contract, Multicall};
+    use ethers_core::{
+        abi::{self, parse_abi, Token},
+        types::{Bytes, U256},
+    };
+
+    let (provider, mock) = Provider::mocked();
+    let client = Arc::new(provider);
+    let abi = parse_abi(&["function balanceOf(address) external view returns (uint256)"]).unwrap();
+    let token = Contract::new(Address::zero(), abi, client.clone());
+    let call = token.method::<_, U256>("balanceOf", Address::zero()).unwrap();
+
+    let mut multicall = Multicall::new_with_chain_id(client, None, Some(1u64)).unwrap();
+    multicall.add_call(call.clone(), true).add_call(call.clone(), true).add_call(call, true);
+
+    let garbage = Bytes::from(vec![1, 2, 3]);
+    let revert = Bytes::from(vec![0xde, 0xad]);
+    let results = Token::Array(vec![
+        Token::Tuple(vec![Token::Bool(true), Token::Bytes(abi::encode(&[Token::Uint(42.into())]))]),
+        // e.g. a contract with a fallback function
+        Token::Tuple(vec![Token::Bool(true), Token::Bytes(garbage.to_vec())]),
+        Token::Tuple(vec![Token::Bool(false), Token::Bytes(revert.to_vec())]),
+    ]);
+    mock.push::<Bytes, Bytes>(abi::encode(&[results]).into()).unwrap();
+
+    let results = multicall.call_raw().await.unwrap();
+    assert_eq!(results, vec![Ok(Token::Uint(42.into())), Err(garbage), Err(revert)]);
+}
+
+#[tokio::test]
+async fn multicall_splits_calls_by_gas() {
+    use ethers_contract::{Contract, Multicall};
+    use ethers_core::{
+        abi::{self, parse_abi, Token},
+        types::{Bytes, U256},
+    };
+
+    let (provider, mock) = Provider::mocked();
+    let client = Arc::new(provider);
+    let abi = parse_abi(&["function balanceOf(address) external view returns (uint256)"]).unwrap();
+    let token = Contract::new(Address::zero(), abi, client.clone());
+    let call = token.method::<_, U256>("balanceOf", Address::zero()).unwrap();
+
+    let mut multicall =
+        Multicall::new_with_chain_id(client, None, Some(1u64)).unwrap().chunk_gas_limit(100_000u64);
+    multicall.add_call(call.clone(), false).add_call(call.clone(), false).add_call(call, false);
+
+    let results = |balances: &[u64]| -> Bytes {
+        let results = balances
+            .iter()
+            .map(|balance| {
+                Token::Tuple(vec![
+                    Token::Bool(true),
+                    Token::Bytes(abi::encode(&[Token::Uint((*balance).into())])),
+                ])
+            })
+            .collect();
+        abi::encode(&[Token::Array(results)]).into()
+    };
+    // the batch is split into the first call, which is not estimated, and the remaining two
+    mock.push::<Bytes, Bytes>(results(&[2, 3])).unwrap();
+    mock.push::<Bytes, Bytes>(results(&[1])).unwrap();
+    mock.push(U256::from(80_000u64)).unwrap();
+    mock.push(U256::from(150_000u64)).unwrap();
+
+    let balances: Vec<U256> = multicall.call_array().await.unwrap();
+    assert_eq!(balances, vec![1.into(), 2.into(), 3.into()]);
+}
+
+#[tokio::test]
+async fn call_batch_decodes_each_result() {
+    use ethers_contract::{CallBatch, Contract, ContractError};
+    use ethers_core::{
+        abi::{self, parse_abi, Token},
+        types::{BlockNumber, Bytes, U256},
+    };
+    use ethers_providers::{JsonRpcError, MockResponse};
+
+    let (provider, mock) = Provider::mocked();
+    let client = Arc::new(provider);
+    let abi = parse_abi(&[
+        "function totalSupply() external view returns (uint256)",
+        "function symbol() external view returns (string)",
+    ])
+    .unwrap();
+    let token = Contract::new(Address::zero(), abi, client.clone());
+    let supply = token.method::<_, U256>("totalSupply", ()).unwrap();
+    let symbol = token.method::<_, String>("symbol", ()).unwrap();
+
+    let mut batch = CallBatch::new(client).block(100u64);
+    batch.add_call(supply.clone()).add_call(symbol).add_call(supply.clone().block(5u64));
+    assert_eq!(batch.len(), 3);
+
+    // the mock sends the requests one by one, popping the responses in reverse
+    mock.push_response(MockResponse::Error(JsonRpcError {
+        code: 3,
+        message: "execution reverted".to_string(),
+        data: Some("0xdead".into()),
+    }));
+    mock.push::<Bytes, Bytes>(abi::encode(&[Token::String("ETH".to_string())]).into()).unwrap();
+    mock.push::<Bytes, Bytes>(abi::encode(&[Token::Uint(42.into())]).into()).unwrap();
+    let results = batch.call_raw().await.unwrap();
+    assert_eq!(results[0].as_ref().unwrap(), &Token::Uint(42.into()));
+    assert_eq!(results[1].as_ref().unwrap(), &Token::String("ETH".to_string()));
+    assert!(
+        matches!(&results[2], Err(ContractError::Revert(data)) if data.as_ref() == [0xde, 0xad])
+    );
+
+    // each call is executed at its own block, or at the block of the batch
+    mock.assert_request("eth_call", (&supply.tx, BlockNumber::Number(100.into()))).unwrap();
+    mock.assert_request(
+        "eth_call",
+        (token.method::<_, String>("symbol", ()).unwrap().tx, BlockNumber::Number(100.into())),
+    )
+    .unwrap();
+    mock.assert_request("eth_call", (&supply.tx, BlockNumber::Number(5.into()))).unwrap();
+
+    batch.clear_calls().add_call(supply.clone()).add_call(supply);
+    mock.push::<Bytes, Bytes>(abi::encode(&[Token::Uint(2.into())]).into()).unwrap();
+    mock.push::<Bytes, Bytes>(abi::encode(&[Token::Uint(1.into())]).into()).unwrap();
+    let (a, b): (U256, U256) = batch.call().await.unwrap();
+    assert_eq!((a, b), (1.into(), 2.into()));
+}
+
+#[tokio::test]
+async fn contract_call_with_block_overrides() {
+    use ethers_contract::Contract;
+    use ethers_core::{
+        abi::{self, parse_abi, Token},
+        types::{BlockOverrides, Bytes, U256},
+    };
+    use ethers_providers::RawCallBlockOverrides;
+
+    let (provider, mock) = Provider::mocked();
+    let client = Arc::new(provider);
+    let abi = parse_abi(&["function isExpired() external view returns (bool)"]).unwrap();
+    let contract = Contract::new(Address::zero(), abi, client);
+    let call = contract.method::<_, bool>("isExpired", ()).unwrap();
+
+    // preview the call after a deadline
+    let overrides = BlockOverrides { time: Some(2_000_000_000u64.into()), ..Default::default() };
+    mock.push::<Bytes, Bytes>(abi::encode(&[Token::Bool(true)]).into()).unwrap();
+    assert!(call.call_raw().block_overrides(&overrides).await.unwrap());
+    mock.assert_request(
+        "eth_call",
+        serde_json::json!([call.tx, "latest", {}, { "time": U256::from(2_000_000_000u64) }]),
+    )
+    .unwrap();
+}
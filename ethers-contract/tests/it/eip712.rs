@@ -93,8 +93,8 @@ fn derive_eip712_nested() {
         foo: String,
         bar: U256,
         addr: Address,
-        /* #[eip712] // Todo: Support nested Eip712 structs
-         * nested: MyNestedStruct, */
+        #[eip712]
+        nested: MyNestedStruct,
     }
 
     #[derive(Debug, Clone, Eip712, EthAbiType)]
@@ -114,16 +114,107 @@ fn derive_eip712_nested() {
         foo: "foo".to_string(),
         bar: U256::from(1),
         addr: Address::from(&[0; 20]),
-        /* nested: MyNestedStruct {
-         *     foo: "foo".to_string(),
-         *     bar: U256::from(1),
-         *     addr: Address::from(&[0; 20]),
-         * }, */
+        nested: MyNestedStruct {
+            foo: "foo".to_string(),
+            bar: U256::from(1),
+            addr: Address::from(&[0; 20]),
+        },
     };
 
     let hash = my_struct.struct_hash().expect("failed to hash struct");
 
-    assert_eq!(hash.len(), 32)
+    assert_eq!(hash.len(), 32);
+    assert_eq!(
+        MyStruct::type_hash().unwrap(),
+        keccak256(
+            "MyStruct(string foo,uint256 bar,address addr,MyNestedStruct nested)\
+             MyNestedStruct(string foo,uint256 bar,address addr)"
+        )
+    );
+}
+
+#[test]
+fn derive_eip712_nested_matches_typed_data() {
+    use ethers_core::types::transaction::eip712::TypedData;
+
+    // the example of the EIP-712 specification
+    #[derive(Debug, Clone, Eip712, EthAbiType)]
+    #[eip712(
+        name = "Ether Mail",
+        version = "1",
+        chain_id = 1,
+        verifying_contract = "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+    )]
+    pub struct Person {
+        name: String,
+        wallet: Address,
+    }
+
+    #[derive(Debug, Clone, Eip712, EthAbiType)]
+    #[eip712(
+        name = "Ether Mail",
+        version = "1",
+        chain_id = 1,
+        verifying_contract = "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+    )]
+    pub struct Mail {
+        #[eip712]
+        from: Person,
+        #[eip712]
+        to: Person,
+        contents: String,
+    }
+
+    let mail = Mail {
+        from: Person {
+            name: "Cow".to_string(),
+            wallet: "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826".parse().unwrap(),
+        },
+        to: Person {
+            name: "Bob".to_string(),
+            wallet: "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB".parse().unwrap(),
+        },
+        contents: "Hello, Bob!".to_string(),
+    };
+
+    let json = serde_json::json!({
+      "types": {
+        "EIP712Domain": [
+          { "name": "name", "type": "string" },
+          { "name": "version", "type": "string" },
+          { "name": "chainId", "type": "uint256" },
+          { "name": "verifyingContract", "type": "address" }
+        ],
+        "Person": [
+          { "name": "name", "type": "string" },
+          { "name": "wallet", "type": "address" }
+        ],
+        "Mail": [
+          { "name": "from", "type": "Person" },
+          { "name": "to", "type": "Person" },
+          { "name": "contents", "type": "string" }
+        ]
+      },
+      "primaryType": "Mail",
+      "domain": {
+        "name": "Ether Mail",
+        "version": "1",
+        "chainId": 1,
+        "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+      },
+      "message": {
+        "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+        "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+        "contents": "Hello, Bob!"
+      }
+    });
+    let typed_data: TypedData = serde_json::from_value(json).unwrap();
+
+    assert_eq!(
+        hex::encode(mail.struct_hash().unwrap()),
+        "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+    );
+    assert_eq!(mail.encode_eip712().unwrap(), typed_data.encode_eip712().unwrap());
 }
 
 #[test]
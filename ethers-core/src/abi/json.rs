@@ -0,0 +1,116 @@
+use super::AbiError;
+use crate::types::{Bytes, I256};
+use ethabi::{ParamType, Token};
+use serde_json::{Map, Value};
+
+/// Decodes ABI encoded `data` of the given `types` into a JSON array with one value per type.
+///
+/// This is useful when the types are only known at runtime, e.g. from a fetched ABI. The tokens
+/// are converted as follows:
+/// - addresses, `bytes` and `bytesN` become `0x` prefixed hex strings;
+/// - `uintN` and `intN` become decimal strings, so that large numbers do not lose precision;
+/// - `bool` and `string` become JSON booleans and strings;
+/// - arrays become JSON arrays, and tuples become JSON objects keyed by the position of their
+///   components, since [`ParamType`] does not carry their names.
+///
+/// # Examples
+///
+/// ```
+/// # use ethers_core::abi::{self, ParamType, Token};
+/// # use ethers_core::types::{Address, U256};
+/// let types = [ParamType::Address, ParamType::Tuple(vec![ParamType::Uint(256), ParamType::Bool])];
+/// let data = abi::encode(&[
+///     Token::Address(Address::repeat_byte(0x11)),
+///     Token::Tuple(vec![Token::Uint(U256::MAX), Token::Bool(true)]),
+/// ]);
+///
+/// let value = abi::decode_to_json(&types, &data)?;
+/// assert_eq!(
+///     value,
+///     serde_json::json!([
+///         "0x1111111111111111111111111111111111111111",
+///         {
+///             "0": "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+///             "1": true
+///         }
+///     ])
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn decode_to_json(types: &[ParamType], data: &[u8]) -> Result<Value, AbiError> {
+    let tokens = ethabi::decode(types, data)?;
+    Ok(Value::Array(tokens.into_iter().map(token_to_json).collect()))
+}
+
+fn token_to_json(token: Token) -> Value {
+    match token {
+        Token::Address(address) => Value::String(format!("{address:?}")),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => {
+            Value::String(Bytes::from(bytes).to_string())
+        }
+        Token::Int(int) => Value::String(I256::from_raw(int).to_string()),
+        Token::Uint(uint) => Value::String(uint.to_string()),
+        Token::Bool(b) => Value::Bool(b),
+        Token::String(s) => Value::String(s),
+        Token::FixedArray(tokens) | Token::Array(tokens) => {
+            Value::Array(tokens.into_iter().map(token_to_json).collect())
+        }
+        Token::Tuple(tokens) => Value::Object(
+            tokens
+                .into_iter()
+                .enumerate()
+                .map(|(i, token)| (i.to_string(), token_to_json(token)))
+                .collect::<Map<_, _>>(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Address, U256};
+    use serde_json::json;
+
+    #[test]
+    fn decodes_to_json() {
+        let types = [
+            ParamType::Address,
+            ParamType::Int(256),
+            ParamType::Bytes,
+            ParamType::FixedBytes(4),
+            ParamType::String,
+            ParamType::Array(Box::new(ParamType::Tuple(vec![
+                ParamType::Uint(8),
+                ParamType::FixedArray(Box::new(ParamType::Bool), 2),
+            ]))),
+        ];
+        let data = ethabi::encode(&[
+            Token::Address(Address::repeat_byte(0xab)),
+            Token::Int(I256::from(-42).into_raw()),
+            Token::Bytes(vec![0xde, 0xad]),
+            Token::FixedBytes(vec![1, 2, 3, 4]),
+            Token::String("gm".to_string()),
+            Token::Array(vec![Token::Tuple(vec![
+                Token::Uint(U256::from(7)),
+                Token::FixedArray(vec![Token::Bool(true), Token::Bool(false)]),
+            ])]),
+        ]);
+
+        assert_eq!(
+            decode_to_json(&types, &data).unwrap(),
+            json!([
+                "0xabababababababababababababababababababab",
+                "-42",
+                "0xdead",
+                "0x01020304",
+                "gm",
+                [{ "0": "7", "1": [true, false] }]
+            ])
+        );
+    }
+
+    #[test]
+    fn fails_on_invalid_data() {
+        assert!(decode_to_json(&[ParamType::Uint(256)], &[0; 16]).is_err());
+    }
+}
@@ -29,7 +29,10 @@ mod raw;
 pub use raw::{AbiObject, Component, Item, JsonAbi, RawAbi};
 
 mod packed;
-pub use packed::{encode_packed, EncodePackedError};
+pub use packed::{encode_packed, encode_packed_with_types, EncodePackedError};
+
+mod json;
+pub use json::decode_to_json;
 
 mod sealed {
     use ethabi::{Event, Function};
@@ -315,6 +318,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_logs_of_indexed_dynamic_and_anonymous_events() {
+        use crate::{types::H256, utils::keccak256};
+
+        let abi = parse_abi(&[
+            "event Foo(string indexed name, uint256 value)",
+            "event Bar(bytes indexed data, uint256 value) anonymous",
+        ])
+        .unwrap();
+        let name = H256(keccak256("alice"));
+        let data = encode(&[Token::Uint(7.into())]);
+
+        // indexed dynamic values are only available as the hash of the value
+        let foo = abi.event("Foo").unwrap();
+        let log = foo
+            .parse_log(RawLog { topics: vec![foo.signature(), name], data: data.clone() })
+            .unwrap();
+        assert_eq!(log.params[0].value, Token::FixedBytes(name.0.to_vec()));
+        assert_eq!(log.params[1].value, Token::Uint(7.into()));
+
+        // anonymous events have no signature topic
+        let log = abi.event("Bar").unwrap().parse_log(RawLog { topics: vec![name], data }).unwrap();
+        assert_eq!(log.params[0].value, Token::FixedBytes(name.0.to_vec()));
+    }
+
     #[test]
     fn abi_type_works() {
         assert_eq!(ParamType::Bytes, types::Bytes::param_type());
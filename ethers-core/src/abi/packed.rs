@@ -1,4 +1,4 @@
-use ethabi::Token;
+use ethabi::{ParamType, Token};
 use thiserror::Error;
 use Token::*;
 
@@ -10,6 +10,12 @@ pub enum EncodePackedError {
 
     #[error("FixedBytes token length > 32")]
     InvalidBytesLength,
+
+    #[error("Expected {expected} tokens, got {got}")]
+    InvalidTokenCount { expected: usize, got: usize },
+
+    #[error("This token does not match the type {1}: {0:?}")]
+    TypeMismatch(Token, ParamType),
 }
 
 /// Encodes the given tokens into an ABI compliant vector of bytes.
@@ -67,6 +73,82 @@ pub fn encode_packed(tokens: &[Token]) -> Result<Vec<u8>, EncodePackedError> {
     Ok(bytes)
 }
 
+/// Encodes the given tokens of the given types into a vector of bytes, using
+/// [non-standard packed mode][ref] like [`encode_packed`].
+///
+/// Unlike [`encode_packed`], integers and fixed bytes outside of arrays are encoded with the size
+/// of their type, which is what Solidity does, e.g. `uint256(1)` is encoded as 32 bytes and
+/// `int8(-1)` as `0xff`. Integers that don't fit into their type are rejected.
+///
+/// [ref]: https://docs.soliditylang.org/en/latest/abi-spec.html#non-standard-packed-mode
+///
+/// # Examples
+///
+/// ```
+/// # use ethers_core::abi::{self, ParamType, Token};
+/// # use ethers_core::types::I256;
+/// // abi.encodePacked(uint256(1), int16(-2), bytes4(0x12345678), "a")
+/// let encoded = abi::encode_packed_with_types(
+///     &[ParamType::Uint(256), ParamType::Int(16), ParamType::FixedBytes(4), ParamType::String],
+///     &[
+///         Token::Uint(1.into()),
+///         Token::Int(I256::from(-2).into_raw()),
+///         Token::FixedBytes(vec![0x12, 0x34, 0x56, 0x78]),
+///         Token::String("a".to_string()),
+///     ],
+/// )?;
+///
+/// let mut expected = [0u8; 32].to_vec();
+/// expected[31] = 1;
+/// expected.extend_from_slice(&[0xff, 0xfe, 0x12, 0x34, 0x56, 0x78, b'a']);
+/// assert_eq!(encoded, expected);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn encode_packed_with_types(
+    types: &[ParamType],
+    tokens: &[Token],
+) -> Result<Vec<u8>, EncodePackedError> {
+    if types.len() != tokens.len() {
+        return Err(EncodePackedError::InvalidTokenCount {
+            expected: types.len(),
+            got: tokens.len(),
+        })
+    }
+
+    let mut bytes = Vec::new();
+    for (kind, token) in types.iter().zip(tokens) {
+        let mismatch = || EncodePackedError::TypeMismatch(token.clone(), kind.clone());
+        if !token.type_check(kind) {
+            return Err(mismatch())
+        }
+        check(token)?;
+
+        match (kind, token) {
+            (ParamType::Int(bits), Int(n)) | (ParamType::Uint(bits), Uint(n)) => {
+                if *bits == 0 || *bits > 256 || bits % 8 != 0 {
+                    return Err(mismatch())
+                }
+                let mut buf = [0; 32];
+                n.to_big_endian(&mut buf);
+                let (high, low) = buf.split_at(32 - bits / 8);
+                // the truncated bytes have to be the sign extension of the value
+                let is_negative = matches!(kind, ParamType::Int(_)) && low[0] & 0x80 != 0;
+                let extension = if is_negative { 0xff } else { 0 };
+                if high.iter().any(|byte| *byte != extension) {
+                    return Err(mismatch())
+                }
+                bytes.extend_from_slice(low);
+            }
+            (ParamType::FixedBytes(size), FixedBytes(value)) => {
+                bytes.extend_from_slice(value);
+                bytes.resize(bytes.len() + size - value.len(), 0);
+            }
+            _ => encode_token(token, &mut bytes, false),
+        }
+    }
+    Ok(bytes)
+}
+
 /// The maximum byte length of the token encoded using packed mode.
 fn max_encoded_length(token: &Token) -> usize {
     match token {
@@ -82,7 +164,7 @@ fn max_encoded_length(token: &Token) -> usize {
     }
 }
 
-/// Tuples and nested arrays are invalid in packed encoding.
+/// Tuples, nested arrays and arrays of dynamic types are invalid in packed encoding.
 fn check(token: &Token) -> Result<(), EncodePackedError> {
     match token {
         FixedBytes(vec) if vec.len() > 32 => Err(EncodePackedError::InvalidBytesLength),
@@ -90,7 +172,7 @@ fn check(token: &Token) -> Result<(), EncodePackedError> {
         Tuple(_) => Err(EncodePackedError::InvalidToken(token.clone())),
         Array(vec) | FixedArray(vec) => {
             for t in vec.iter() {
-                if t.is_dynamic() || matches!(t, Array(_)) {
+                if t.is_dynamic() || matches!(t, Array(_) | FixedArray(_)) {
                     return Err(EncodePackedError::InvalidToken(token.clone()))
                 }
                 check(t)?;
@@ -149,6 +231,7 @@ fn encode_token(token: &Token, out: &mut Vec<u8>, in_array: bool) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::U256;
     use hex_literal::hex;
 
     fn encode(tokens: &[Token]) -> Vec<u8> {
@@ -365,6 +448,77 @@ mod tests {
         assert_eq!(encoded, expected);
     }
 
+    #[test]
+    fn rejects_nested_arrays() {
+        let uint = Token::Uint(1.into());
+        let nested = Token::FixedArray(vec![uint.clone(), uint.clone()]);
+        for array in [
+            Token::FixedArray(vec![nested.clone()]),
+            Token::Array(vec![nested]),
+            Token::Array(vec![Token::Array(vec![uint])]),
+            Token::Array(vec![string("a")]),
+            Token::FixedArray(vec![bytes(b"a")]),
+        ] {
+            assert!(matches!(
+                encode_packed(&[array]).unwrap_err(),
+                EncodePackedError::InvalidToken(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn encode_with_types() {
+        let encode = |types: &[ParamType], tokens: &[Token]| {
+            encode_packed_with_types(types, tokens).unwrap()
+        };
+        let minus_one = U256::MAX;
+
+        assert_eq!(encode(&[ParamType::Uint(8)], &[Token::Uint(4.into())]), hex!("04"));
+        assert_eq!(
+            encode(&[ParamType::Uint(256)], &[Token::Uint(4.into())]),
+            hex!("0000000000000000000000000000000000000000000000000000000000000004")
+        );
+        assert_eq!(encode(&[ParamType::Int(8)], &[Token::Int(minus_one)]), hex!("ff"));
+        assert_eq!(encode(&[ParamType::Int(24)], &[Token::Int(0x7fff.into())]), hex!("007fff"));
+        // bytesN is right padded to its size, while bytes isn't padded
+        assert_eq!(
+            encode(
+                &[ParamType::FixedBytes(4), ParamType::Bytes],
+                &[Token::FixedBytes(vec![0x12]), bytes(&[0x34])]
+            ),
+            hex!("1200000034")
+        );
+        // array elements are always padded
+        assert_eq!(
+            encode(
+                &[ParamType::Array(Box::new(ParamType::Uint(8)))],
+                &[Token::Array(vec![Token::Uint(1.into())])]
+            ),
+            hex!("0000000000000000000000000000000000000000000000000000000000000001")
+        );
+
+        let err = |types: &[ParamType], tokens: &[Token]| {
+            encode_packed_with_types(types, tokens).unwrap_err()
+        };
+        // values that don't fit into their type
+        assert!(matches!(
+            err(&[ParamType::Uint(8)], &[Token::Uint(256.into())]),
+            EncodePackedError::TypeMismatch(..)
+        ));
+        assert!(matches!(
+            err(&[ParamType::Int(8)], &[Token::Int(0x80.into())]),
+            EncodePackedError::TypeMismatch(..)
+        ));
+        assert!(matches!(
+            err(&[ParamType::Address], &[Token::Uint(1.into())]),
+            EncodePackedError::TypeMismatch(..)
+        ));
+        assert!(matches!(
+            err(&[ParamType::Bool, ParamType::Bool], &[Token::Bool(true)]),
+            EncodePackedError::InvalidTokenCount { expected: 2, got: 1 }
+        ));
+    }
+
     #[test]
     fn comprehensive_test() {
         let bytes = hex!(
@@ -65,7 +65,11 @@ pub struct Block<TX> {
     #[serde(rename = "totalDifficulty")]
     pub total_difficulty: Option<U256>,
     /// Seal fields
-    #[serde(default, rename = "sealFields", deserialize_with = "deserialize_null_default")]
+    #[serde(
+        default,
+        rename = "sealFields",
+        deserialize_with = "crate::types::serde_helpers::deserialize_null_default"
+    )]
     pub seal_fields: Vec<Bytes>,
     /// Uncles' hashes
     #[cfg(not(feature = "celo"))]
@@ -124,15 +128,6 @@ pub struct Block<TX> {
     pub other: crate::types::OtherFields,
 }
 
-fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
-where
-    T: Default + Deserialize<'de>,
-    D: Deserializer<'de>,
-{
-    let opt = Option::deserialize(deserializer)?;
-    Ok(opt.unwrap_or_default())
-}
-
 /// Error returned by [`Block::time`].
 #[derive(Clone, Copy, Debug, Error)]
 pub enum TimeError {
@@ -466,7 +461,9 @@ pub struct EpochSnarkData {
 pub enum BlockId {
     // TODO: May want to expand this to include the requireCanonical field
     // <https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1898.md>
-    /// A block hash
+    /// A block hash, which pins a query to that exact block as specified in
+    /// [EIP-1898](https://eips.ethereum.org/EIPS/eip-1898), so that consecutive queries are not
+    /// affected by a reorg in between
     Hash(H256),
     /// A block number
     Number(BlockNumber),
@@ -860,6 +857,136 @@ mod tests {
         assert_eq!(block.base_fee_per_gas, Some(U256::from(7)));
     }
 
+    #[test]
+    fn block_with_mixed_tx_types() {
+        let json = r#"
+        {
+            "baseFeePerGas": "0x7",
+            "blobGasUsed": "0x20000",
+            "excessBlobGas": "0x0",
+            "miner": "0x0000000000000000000000000000000000000001",
+            "number": "0x1b4",
+            "hash": "0x0e670ec64341771606e55d6b4ca35a1a6b75ee3d5145a99d05921026d1527331",
+            "parentHash": "0x9646252be9520f6e71339a8df9c55e4d7619deeb018d2a3f2d21fc165dde5eb5",
+            "mixHash": "0x1010101010101010101010101010101010101010101010101010101010101010",
+            "nonce": "0x0000000000000000",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "stateRoot": "0xd5855eb08b3387c0af375e9cdb6acfc05eb8f519e419b874b6ff2ffda7ed1dff",
+            "difficulty": "0x0",
+            "totalDifficulty": "0x27f07",
+            "extraData": "0x",
+            "size": "0x27f07",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x2a5b8",
+            "timestamp": "0x65f0b4c0",
+            "transactions": [
+                {
+                    "type": "0x0",
+                    "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                    "nonce": "0x1",
+                    "blockHash": "0x0e670ec64341771606e55d6b4ca35a1a6b75ee3d5145a99d05921026d1527331",
+                    "blockNumber": "0x1b4",
+                    "transactionIndex": "0x0",
+                    "from": "0x1000000000000000000000000000000000000001",
+                    "to": "0x2000000000000000000000000000000000000002",
+                    "value": "0x1",
+                    "gasPrice": "0x3b9aca00",
+                    "gas": "0x5208",
+                    "input": "0x",
+                    "v": "0x25",
+                    "r": "0x1",
+                    "s": "0x2"
+                },
+                {
+                    "type": "0x2",
+                    "hash": "0x2222222222222222222222222222222222222222222222222222222222222222",
+                    "nonce": "0x2",
+                    "blockHash": "0x0e670ec64341771606e55d6b4ca35a1a6b75ee3d5145a99d05921026d1527331",
+                    "blockNumber": "0x1b4",
+                    "transactionIndex": "0x1",
+                    "from": "0x1000000000000000000000000000000000000001",
+                    "to": "0x2000000000000000000000000000000000000002",
+                    "value": "0x0",
+                    "gasPrice": "0x3b9aca07",
+                    "maxPriorityFeePerGas": "0x3b9aca00",
+                    "maxFeePerGas": "0x77359400",
+                    "gas": "0x7530",
+                    "input": "0xd0e30db0",
+                    "accessList": [
+                        {
+                            "address": "0x2000000000000000000000000000000000000002",
+                            "storageKeys": [
+                                "0x0000000000000000000000000000000000000000000000000000000000000001"
+                            ]
+                        }
+                    ],
+                    "chainId": "0x1",
+                    "v": "0x1",
+                    "r": "0x3",
+                    "s": "0x4"
+                },
+                {
+                    "type": "0x3",
+                    "hash": "0x3333333333333333333333333333333333333333333333333333333333333333",
+                    "nonce": "0x3",
+                    "blockHash": "0x0e670ec64341771606e55d6b4ca35a1a6b75ee3d5145a99d05921026d1527331",
+                    "blockNumber": "0x1b4",
+                    "transactionIndex": "0x2",
+                    "from": "0x1000000000000000000000000000000000000001",
+                    "to": "0x2000000000000000000000000000000000000002",
+                    "value": "0x0",
+                    "gasPrice": "0x3b9aca07",
+                    "maxPriorityFeePerGas": "0x3b9aca00",
+                    "maxFeePerGas": "0x77359400",
+                    "maxFeePerBlobGas": "0x1",
+                    "blobVersionedHashes": [
+                        "0x01b0a4cdd5f55589f5c5b4d46c76704bb6ce95c0a8c09f77f197a57808dded28"
+                    ],
+                    "gas": "0x5208",
+                    "input": "0x",
+                    "accessList": [],
+                    "chainId": "0x1",
+                    "yParity": "0x0",
+                    "v": "0x0",
+                    "r": "0x5",
+                    "s": "0x6"
+                }
+            ],
+            "uncles": []
+          }
+        "#;
+
+        let block: Block<Transaction> = serde_json::from_str(json).unwrap();
+        assert_eq!(block.blob_gas_used, Some(0x20000u64.into()));
+
+        let legacy = &block.transactions[0];
+        assert_eq!(legacy.transaction_type, Some(0u64.into()));
+        assert_eq!(legacy.gas_price, Some(1_000_000_000u64.into()));
+        assert_eq!(legacy.max_fee_per_gas, None);
+        assert_eq!(legacy.access_list, None);
+
+        let eip1559 = &block.transactions[1];
+        assert_eq!(eip1559.transaction_type, Some(2u64.into()));
+        assert_eq!(eip1559.max_priority_fee_per_gas, Some(1_000_000_000u64.into()));
+        assert_eq!(eip1559.max_fee_per_gas, Some(2_000_000_000u64.into()));
+        assert_eq!(eip1559.access_list.as_ref().map(|list| list.0.len()), Some(1));
+        assert_eq!(eip1559.chain_id, Some(1u64.into()));
+
+        let eip4844 = &block.transactions[2];
+        assert_eq!(eip4844.transaction_type, Some(3u64.into()));
+        assert_eq!(eip4844.max_fee_per_gas, Some(2_000_000_000u64.into()));
+        assert_eq!(eip4844.max_fee_per_blob_gas, Some(1u64.into()));
+        assert_eq!(
+            eip4844.blob_versioned_hashes,
+            Some(vec!["0x01b0a4cdd5f55589f5c5b4d46c76704bb6ce95c0a8c09f77f197a57808dded28"
+                .parse()
+                .unwrap()])
+        );
+    }
+
     #[test]
     fn test_next_block_base_fee() {
         // <https://etherscan.io/block/14402566>
@@ -0,0 +1,54 @@
+//! Types for executing calls on top of each other with `eth_callMany`
+use crate::types::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// The result of a call executed by `eth_callMany`, as returned by Erigon and Reth
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallManyResponse {
+    /// The data returned by the call, or the revert data if it reverted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Bytes>,
+    /// The reason the call failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CallManyResponse {
+    /// Returns `true` if the call succeeded
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the data returned by the call, or the reason it failed
+    pub fn into_result(self) -> Result<Bytes, String> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.value.unwrap_or_default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_call_many_responses() {
+        let s = r#"[
+            { "value": "0x0000000000000000000000000000000000000000000000000000000000000001" },
+            { "error": "execution reverted: STF", "value": "0x08c379a0" },
+            { "error": "insufficient funds for gas * price + value" }
+        ]"#;
+        let responses: Vec<CallManyResponse> = serde_json::from_str(s).unwrap();
+        let [approve, swap, transfer] = &responses[..] else { panic!("expected three results") };
+
+        assert!(approve.is_success());
+        assert_eq!(approve.clone().into_result().unwrap().len(), 32);
+        assert!(!swap.is_success());
+        assert_eq!(swap.value.as_ref().unwrap().to_vec(), vec![0x08, 0xc3, 0x79, 0xa0]);
+        assert_eq!(
+            transfer.clone().into_result().unwrap_err(),
+            "insufficient funds for gas * price + value"
+        );
+    }
+}
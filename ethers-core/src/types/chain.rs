@@ -402,6 +402,24 @@ impl Chain {
         }
     }
 
+    /// Returns whether the chain supports EIP-1559 transactions, i.e. whether transactions should
+    /// be priced with a max fee and priority fee rather than a gas price.
+    ///
+    /// This is the inverse of [`is_legacy`](Self::is_legacy), so chains whose support is unknown
+    /// are assumed to support EIP-1559.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ethers_core::types::Chain;
+    ///
+    /// assert!(Chain::Mainnet.supports_eip1559());
+    /// assert!(!Chain::BinanceSmartChain.supports_eip1559());
+    /// ```
+    pub const fn supports_eip1559(&self) -> bool {
+        !self.is_legacy()
+    }
+
     /// Returns whether the chain supports the `PUSH0` opcode or not.
     ///
     /// For more information, see EIP-3855:
@@ -1,10 +1,17 @@
-use crate::types::{serde_helpers::deserialize_stringified_numeric, U256};
+use crate::types::{
+    serde_helpers::{deserialize_null_default, deserialize_stringified_numeric},
+    U256,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FeeHistory {
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "Vec::is_empty"
+    )]
     pub base_fee_per_gas: Vec<U256>,
     pub gas_used_ratio: Vec<f64>,
     #[serde(deserialize_with = "deserialize_stringified_numeric")]
@@ -15,6 +22,44 @@ pub struct FeeHistory {
     pub oldest_block: U256,
     /// An (optional) array of effective priority fee per gas data points from a single block. All
     /// zeroes are returned if the block is empty.
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ///
+    /// This is empty if no reward percentiles were requested, or if the node omitted the field.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "Vec::is_empty"
+    )]
     pub reward: Vec<Vec<U256>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_fee_history() {
+        let s = r#"{
+            "oldestBlock": "0x10",
+            "baseFeePerGas": ["0x3b9aca00", "0x3a6d5d3b"],
+            "gasUsedRatio": [0.25],
+            "reward": [["0x59682f00", "0x77359400"]]
+        }"#;
+        let history: FeeHistory = serde_json::from_str(s).unwrap();
+        assert_eq!(history.oldest_block, 16u64.into());
+        assert_eq!(history.base_fee_per_gas.len(), 2);
+        assert_eq!(history.gas_used_ratio, vec![0.25]);
+        assert_eq!(history.reward, vec![vec![1_500_000_000u64.into(), 2_000_000_000u64.into()]]);
+    }
+
+    #[test]
+    fn deserialize_fee_history_without_reward() {
+        let s = r#"{"oldestBlock": 16, "baseFeePerGas": ["0x3b9aca00"], "gasUsedRatio": [0.5]}"#;
+        let history: FeeHistory = serde_json::from_str(s).unwrap();
+        assert!(history.reward.is_empty());
+
+        let s = r#"{"oldestBlock": "0x10", "baseFeePerGas": null, "gasUsedRatio": [0.5], "reward": null}"#;
+        let history: FeeHistory = serde_json::from_str(s).unwrap();
+        assert!(history.base_fee_per_gas.is_empty());
+        assert!(history.reward.is_empty());
+    }
+}
@@ -9,12 +9,18 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 use std::ops::{Range, RangeFrom, RangeTo};
+use thiserror::Error;
 
 pub type BloomFilter = Vec<Option<Bloom>>;
 
 /// A single topic
 pub type Topic = ValueOrArray<Option<H256>>;
 
+/// The error type returned when a filter is restricted to a block hash and a range of blocks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+#[error("blockHash is mutually exclusive with fromBlock and toBlock")]
+pub struct BlockHashRangeError;
+
 /// Represents the target range of blocks for the filter
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum FilterBlockOption {
@@ -112,6 +118,15 @@ impl FilterBlockOption {
     pub fn set_hash(&self, hash: H256) -> Self {
         FilterBlockOption::AtBlockHash(hash)
     }
+
+    /// Restricts the option to the block `hash`, failing if a `from_block` or `to_block` is set
+    pub fn try_set_hash(&self, hash: H256) -> Result<Self, BlockHashRangeError> {
+        match self {
+            FilterBlockOption::Range { from_block: None, to_block: None } |
+            FilterBlockOption::AtBlockHash(_) => Ok(self.set_hash(hash)),
+            FilterBlockOption::Range { .. } => Err(BlockHashRangeError),
+        }
+    }
 }
 
 /// Filter for
@@ -217,12 +232,37 @@ impl Filter {
         self
     }
 
+    /// Restricts the filter to the block with the `hash`, replacing the range of blocks, see
+    /// [EIP-234](https://eips.ethereum.org/EIPS/eip-234)
+    ///
+    /// Setting a `from_block` or `to_block` afterwards replaces the block hash, use
+    /// [`Filter::try_at_block_hash`] to catch filters that set both.
     #[allow(clippy::wrong_self_convention)]
     #[must_use]
     pub fn at_block_hash<T: Into<H256>>(mut self, hash: T) -> Self {
         self.block_option = self.block_option.set_hash(hash.into());
         self
     }
+
+    /// Restricts the filter to the block with the `hash`, failing if the filter already sets a
+    /// `from_block` or `to_block`, since the node rejects filters with both
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ethers_core::types::{Filter, H256};
+    /// let filter = Filter::new().try_at_block_hash(H256::zero()).unwrap();
+    /// assert_eq!(filter.get_block_hash(), Some(H256::zero()));
+    ///
+    /// assert!(Filter::new().from_block(1337u64).try_at_block_hash(H256::zero()).is_err());
+    /// ```
+    pub fn try_at_block_hash<T: Into<H256>>(
+        mut self,
+        hash: T,
+    ) -> Result<Self, BlockHashRangeError> {
+        self.block_option = self.block_option.try_set_hash(hash.into())?;
+        Ok(self)
+    }
     /// Sets the inner filter object
     ///
     /// *NOTE:* ranges are always inclusive
@@ -546,6 +586,18 @@ impl From<Vec<H160>> for ValueOrArray<H160> {
     }
 }
 
+impl From<&[H160]> for ValueOrArray<H160> {
+    fn from(src: &[H160]) -> Self {
+        ValueOrArray::Array(src.to_vec())
+    }
+}
+
+impl<const N: usize> From<[H160; N]> for ValueOrArray<H160> {
+    fn from(src: [H160; N]) -> Self {
+        ValueOrArray::Array(src.to_vec())
+    }
+}
+
 impl From<H256> for Topic {
     fn from(src: H256) -> Self {
         ValueOrArray::Value(Some(src))
@@ -962,6 +1014,31 @@ mod tests {
         assert_eq!(ser, json!({ "address" : addr, "topics": [t0, t1_padded, t2, t3_padded]}));
     }
 
+    #[test]
+    fn filter_addresses_and_block_hash() {
+        let addr1 = Address::repeat_byte(1);
+        let addr2 = Address::repeat_byte(2);
+        let hash = H256::repeat_byte(3);
+
+        let filter = Filter::new().address([addr1, addr2]).try_at_block_hash(hash).unwrap();
+        let ser = serialize(&filter);
+        assert_eq!(ser, json!({ "blockHash": hash, "address": [addr1, addr2], "topics": [] }));
+        assert_eq!(serde_json::from_value::<Filter>(ser).unwrap(), filter);
+
+        // a single address in a list is still serialized as an array
+        let ser = serialize(&Filter::new().address(&[addr1][..]));
+        assert_eq!(ser, json!({ "address": [addr1], "topics": [] }));
+
+        assert_eq!(
+            Filter::new().to_block(5u64).try_at_block_hash(hash).unwrap_err(),
+            BlockHashRangeError
+        );
+        assert!(serde_json::from_value::<Filter>(
+            json!({ "fromBlock": "0x1", "blockHash": hash, "topics": [] })
+        )
+        .is_err());
+    }
+
     fn build_bloom(address: Address, topic1: H256, topic2: H256) -> Bloom {
         let mut block_bloom = Bloom::default();
         block_bloom.accrue(BloomInput::Raw(&address[..]));
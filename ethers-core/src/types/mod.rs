@@ -15,8 +15,10 @@ pub mod transaction;
 pub use transaction::{
     eip1559::Eip1559TransactionRequest,
     eip2930::Eip2930TransactionRequest,
+    eip4844::Eip4844TransactionRequest,
+    eip7702::{Eip7702TransactionRequest, SignedAuthorization},
     request::TransactionRequest,
-    response::{Transaction, TransactionReceipt},
+    response::{FeeBreakdown, Transaction, TransactionReceipt},
 };
 
 mod address_or_bytes;
@@ -71,6 +73,12 @@ pub use proof::*;
 mod fee;
 pub use fee::*;
 
+mod simulate;
+pub use simulate::*;
+
+mod call_many;
+pub use call_many::*;
+
 mod other;
 pub use other::OtherFields;
 
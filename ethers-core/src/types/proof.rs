@@ -1,8 +1,17 @@
-use crate::types::{Address, Bytes, H256, U256, U64};
-use serde::{Deserialize, Serialize};
+use crate::{
+    types::{serde_helpers::deserialize_stringified_numeric, Address, Bytes, H256, U256, U64},
+    utils::keccak256,
+};
+use rlp::{DecoderError, Rlp};
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct StorageProof {
+    /// The requested storage slot.
+    ///
+    /// Some clients return the slot as a quantity, e.g. `0x1`, which is left padded to 32 bytes.
+    #[serde(deserialize_with = "deserialize_storage_key")]
     pub key: H256,
     pub proof: Vec<Bytes>,
     pub value: U256,
@@ -20,6 +29,208 @@ pub struct EIP1186ProofResponse {
     pub storage_proof: Vec<StorageProof>,
 }
 
+impl EIP1186ProofResponse {
+    /// Returns the proof for the given storage slot, if it was requested.
+    pub fn get_storage_proof(&self, key: H256) -> Option<&StorageProof> {
+        self.storage_proof.iter().find(|proof| proof.key == key)
+    }
+
+    /// Verifies the account of the proof against the `state_root` of a block, and all storage
+    /// proofs against the verified storage hash of the account.
+    ///
+    /// See [`verify_account_proof`] and [`verify_storage_proof`].
+    pub fn verify(&self, state_root: H256) -> Result<(), ProofError> {
+        verify_account_proof(self, state_root, self.address)?;
+        self.storage_proof
+            .iter()
+            .try_for_each(|proof| verify_storage_proof(proof, self.storage_hash))
+    }
+}
+
+/// Error thrown when verifying an [`EIP1186ProofResponse`]
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ProofError {
+    /// Thrown when the hash of a node does not match the hash referenced by its parent, or the
+    /// root hash for the first node
+    #[error("hash of proof node is {found:?}, expected {expected:?}")]
+    HashMismatch {
+        /// The hash referenced by the parent node
+        expected: H256,
+        /// The hash of the node in the proof
+        found: H256,
+    },
+    /// Thrown when the proof ends before it reaches the value or the absence of the key
+    #[error("proof is missing a node")]
+    MissingNode,
+    /// Thrown when a node is not a valid branch, extension or leaf node
+    #[error("invalid proof node")]
+    InvalidNode,
+    /// Thrown when a node or the value can not be RLP decoded
+    #[error(transparent)]
+    Rlp(#[from] DecoderError),
+    /// Thrown when the value in the trie differs from the value of the response
+    #[error("value in the trie does not match the value of the proof")]
+    ValueMismatch,
+}
+
+/// Verifies that the `proof` of the `address` proves its balance, nonce, code hash and storage
+/// hash under the `state_root` of a block, e.g. from a header of a trusted source.
+///
+/// An account that does not exist is proven by a proof of the absence of the address, in which
+/// case the response must claim an empty account. No requests are made, so this can be used to
+/// verify the response of an untrusted node.
+pub fn verify_account_proof(
+    proof: &EIP1186ProofResponse,
+    state_root: H256,
+    address: Address,
+) -> Result<(), ProofError> {
+    let account = match verify_trie_proof(state_root, &keccak256(address), &proof.account_proof)? {
+        Some(account) => {
+            let account = Rlp::new(&account);
+            if account.item_count()? != 4 {
+                return Err(ProofError::InvalidNode)
+            }
+            (account.val_at(0)?, account.val_at(1)?, account.val_at(2)?, account.val_at(3)?)
+        }
+        // some nodes return zero hashes for accounts that do not exist
+        None if proof.storage_hash.is_zero() && proof.code_hash.is_zero() => {
+            (U64::zero(), U256::zero(), H256::zero(), H256::zero())
+        }
+        None => (U64::zero(), U256::zero(), H256(keccak256(rlp::NULL_RLP)), H256(keccak256([]))),
+    };
+    if account != (proof.nonce, proof.balance, proof.storage_hash, proof.code_hash) {
+        return Err(ProofError::ValueMismatch)
+    }
+    Ok(())
+}
+
+/// Verifies that the storage `proof` proves the value of its slot under the `storage_root` of the
+/// account, i.e. the [`storage_hash`](EIP1186ProofResponse::storage_hash) of a response that was
+/// verified with [`verify_account_proof`].
+///
+/// A slot with a zero value is proven by a proof of the absence of the slot.
+pub fn verify_storage_proof(proof: &StorageProof, storage_root: H256) -> Result<(), ProofError> {
+    let value = match verify_trie_proof(storage_root, &keccak256(proof.key), &proof.proof)? {
+        Some(value) => rlp::decode(&value)?,
+        None => U256::zero(),
+    };
+    if value != proof.value {
+        return Err(ProofError::ValueMismatch)
+    }
+    Ok(())
+}
+
+/// A reference to a child node, nodes shorter than 32 bytes are inlined in their parent
+enum NodeRef {
+    Hash(H256),
+    Inline(Vec<u8>),
+}
+
+/// Walks the nodes of the Merkle-Patricia trie `proof` from the `root` along the path of the
+/// `key`, returning the value at the key, or `None` if the proof shows that it is not in the trie
+fn verify_trie_proof(
+    root: H256,
+    key: &[u8],
+    proof: &[Bytes],
+) -> Result<Option<Vec<u8>>, ProofError> {
+    // the empty trie does not have any nodes
+    if root == H256(keccak256(rlp::NULL_RLP)) {
+        return Ok(None)
+    }
+
+    let nibbles: Vec<u8> = key.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect();
+    let mut path = nibbles.as_slice();
+    let mut nodes = proof.iter();
+    let mut next = NodeRef::Hash(root);
+    loop {
+        let node = match next {
+            NodeRef::Hash(expected) => {
+                let node = nodes.next().ok_or(ProofError::MissingNode)?;
+                let found = H256(keccak256(node));
+                if found != expected {
+                    return Err(ProofError::HashMismatch { expected, found })
+                }
+                node.to_vec()
+            }
+            NodeRef::Inline(node) => node,
+        };
+        let node = Rlp::new(&node);
+        match node.item_count()? {
+            // branch node
+            17 => {
+                let Some((&nibble, rest)) = path.split_first() else {
+                    let value = node.at(16)?.data()?;
+                    return Ok((!value.is_empty()).then(|| value.to_vec()))
+                };
+                path = rest;
+                match child_ref(&node.at(nibble as usize)?)? {
+                    Some(child) => next = child,
+                    None => return Ok(None),
+                }
+            }
+            // leaf or extension node
+            2 => {
+                let (is_leaf, node_path) = decode_path(node.at(0)?.data()?)?;
+                if is_leaf {
+                    if path != node_path.as_slice() {
+                        return Ok(None)
+                    }
+                    return Ok(Some(node.at(1)?.data()?.to_vec()))
+                }
+                let Some(rest) = path.strip_prefix(node_path.as_slice()) else { return Ok(None) };
+                path = rest;
+                next = child_ref(&node.at(1)?)?.ok_or(ProofError::InvalidNode)?;
+            }
+            _ => return Err(ProofError::InvalidNode),
+        }
+    }
+}
+
+/// Returns the reference to the child of a branch or extension node, `None` if the branch has no
+/// child at the position
+fn child_ref(child: &Rlp<'_>) -> Result<Option<NodeRef>, ProofError> {
+    if child.is_list() {
+        return Ok(Some(NodeRef::Inline(child.as_raw().to_vec())))
+    }
+    match child.data()? {
+        [] => Ok(None),
+        hash if hash.len() == 32 => Ok(Some(NodeRef::Hash(H256::from_slice(hash)))),
+        _ => Err(ProofError::InvalidNode),
+    }
+}
+
+/// Decodes the hex prefix encoded path of a leaf or extension node, returning whether the node is
+/// a leaf and the nibbles of the path.
+///
+/// The high nibble of the first byte is a flag, whose second bit is set for leaf nodes and whose
+/// first bit is set if the path has an odd number of nibbles. Odd paths start with the low nibble
+/// of the first byte, which is zero for even paths.
+fn decode_path(encoded: &[u8]) -> Result<(bool, Vec<u8>), ProofError> {
+    let (&first, rest) = encoded.split_first().ok_or(ProofError::InvalidNode)?;
+    let (flag, first_nibble) = (first >> 4, first & 0x0f);
+    let is_odd = match flag {
+        0 | 2 if first_nibble == 0 => false,
+        1 | 3 => true,
+        _ => return Err(ProofError::InvalidNode),
+    };
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if is_odd {
+        nibbles.push(first_nibble);
+    }
+    nibbles.extend(rest.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+    Ok((flag & 2 == 2, nibbles))
+}
+
+fn deserialize_storage_key<'de, D>(deserializer: D) -> Result<H256, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let key = deserialize_stringified_numeric(deserializer)?;
+    let mut bytes = [0u8; 32];
+    key.to_big_endian(&mut bytes);
+    Ok(H256(bytes))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,4 +240,111 @@ mod tests {
         serde_json::from_str::<EIP1186ProofResponse>(include_str!("../../testdata/proof.json"))
             .unwrap();
     }
+
+    #[test]
+    fn can_verify_proof() {
+        let proof: EIP1186ProofResponse =
+            serde_json::from_str(include_str!("../../testdata/proof.json")).unwrap();
+        let state_root = H256(keccak256(&proof.account_proof[0]));
+        proof.verify(state_root).unwrap();
+
+        // the storage slot is not set, which is proven by its absence
+        let storage = &proof.storage_proof[0];
+        assert!(storage.value.is_zero());
+        verify_storage_proof(storage, proof.storage_hash).unwrap();
+        let mut tampered = storage.clone();
+        tampered.value = 1.into();
+        assert_eq!(
+            verify_storage_proof(&tampered, proof.storage_hash),
+            Err(ProofError::ValueMismatch)
+        );
+
+        let mut tampered = proof.clone();
+        tampered.balance = 1.into();
+        assert_eq!(tampered.verify(state_root), Err(ProofError::ValueMismatch));
+
+        // the proof does not cover the path of another address
+        assert!(verify_account_proof(&proof, state_root, Address::zero()).is_err());
+
+        let mut truncated = proof.clone();
+        truncated.account_proof.pop();
+        assert_eq!(truncated.verify(state_root), Err(ProofError::MissingNode));
+
+        let wrong_root = H256::repeat_byte(1);
+        assert_eq!(
+            proof.verify(wrong_root),
+            Err(ProofError::HashMismatch {
+                expected: wrong_root,
+                found: H256(keccak256(&proof.account_proof[0]))
+            })
+        );
+    }
+
+    #[test]
+    fn can_verify_leaf_paths() {
+        let key = H256::from_low_u64_be(7);
+        let hashed = keccak256(key);
+        let value = rlp::encode(&U256::from(42u64)).to_vec();
+
+        // a trie with a single leaf holding the whole path, which has an even length
+        let mut leaf = rlp::RlpStream::new_list(2);
+        leaf.append(&[&[0x20][..], &hashed[..]].concat()).append(&value);
+        let leaf = leaf.out().to_vec();
+        let proof = StorageProof { key, proof: vec![leaf.clone().into()], value: 42.into() };
+        verify_storage_proof(&proof, H256(keccak256(&leaf))).unwrap();
+
+        // a branch with the leaf of the odd rest of the path inlined at the first nibble
+        let mut short = rlp::RlpStream::new_list(2);
+        short.append(&vec![0x30 | (hashed[0] & 0x0f)]).append(&vec![0x01]);
+        let short = short.out();
+        let mut branch = rlp::RlpStream::new_list(17);
+        for nibble in 0..16 {
+            if nibble == hashed[0] >> 4 {
+                branch.append_raw(&short, 1);
+            } else {
+                branch.append_empty_data();
+            }
+        }
+        branch.append_empty_data();
+        let branch = branch.out().to_vec();
+        let proof = StorageProof { key, proof: vec![branch.into()], value: 1.into() };
+        // the inlined leaf only holds one nibble of the path, so the key is not in the trie
+        let root = H256(keccak256(&proof.proof[0]));
+        assert_eq!(verify_storage_proof(&proof, root), Err(ProofError::ValueMismatch));
+        let absent = StorageProof { value: 0.into(), ..proof };
+        verify_storage_proof(&absent, root).unwrap();
+    }
+
+    #[test]
+    fn can_deserialize_storage_proof_keys() {
+        let proof = serde_json::json!({
+            "address": "0x7ae1d57b58fa6411f32948314badd83583ee0e8c",
+            "accountProof": [],
+            "balance": "0x0",
+            "codeHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            "nonce": "0x0",
+            "storageHash": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "storageProof": [
+                {
+                    "key": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                    "proof": [],
+                    "value": "0x0"
+                },
+                { "key": "0x1", "proof": ["0x80"], "value": "0x2a" }
+            ]
+        });
+        let proof: EIP1186ProofResponse = serde_json::from_value(proof).unwrap();
+
+        let slot = H256::from_low_u64_be(1);
+        let storage = proof.get_storage_proof(slot).unwrap();
+        assert_eq!(storage.key, slot);
+        assert_eq!(storage.value, 42.into());
+        assert_eq!(storage.proof, vec![Bytes::from(vec![0x80])]);
+        assert!(proof.get_storage_proof(H256::from_low_u64_be(2)).is_some());
+        assert!(proof.get_storage_proof(H256::from_low_u64_be(3)).is_none());
+
+        // keys are serialized as full 32 byte words
+        let ser = serde_json::to_value(storage).unwrap();
+        assert_eq!(ser["key"], serde_json::json!(slot));
+    }
 }
@@ -80,6 +80,16 @@ impl TryFrom<StringifiedNumeric> for U64 {
     }
 }
 
+/// Deserializes a `null` value as the type's default value
+pub fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Default + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let opt = Option::deserialize(deserializer)?;
+    Ok(opt.unwrap_or_default())
+}
+
 /// Supports parsing numbers as strings
 ///
 /// See <https://github.com/gakonst/ethers-rs/issues/1507>
@@ -86,8 +86,13 @@ impl Signature {
 
     /// Recovers the Ethereum address which was used to sign the given message.
     ///
-    /// Recovery signature data uses 'Electrum' notation, this means the `v`
-    /// value is expected to be either `27` or `28`.
+    /// Messages given as [`RecoveryMessage::Data`], e.g. strings or bytes, are hashed with the
+    /// EIP-191 prefix first, see [`hash_message`], while [`RecoveryMessage::Hash`]es are
+    /// recovered as is.
+    ///
+    /// The `v` value may be given in any of the common conventions, i.e. the raw recovery id
+    /// (`0` or `1`), 'Electrum' notation (`27` or `28`) or EIP-155 notation
+    /// (`chain_id * 2 + 35` or `chain_id * 2 + 36`), see also [`Self::chain_id`].
     pub fn recover<M>(&self, message: M) -> Result<Address, SignatureError>
     where
         M: Into<RecoveryMessage>,
@@ -115,8 +120,7 @@ impl Signature {
     /// Recovers the ethereum address which was used to sign a given EIP712
     /// typed data payload.
     ///
-    /// Recovery signature data uses 'Electrum' notation, this means the `v`
-    /// value is expected to be either `27` or `28`.
+    /// See [`Self::recover`] for the accepted `v` values.
     pub fn recover_typed_data<T>(&self, payload: &T) -> Result<Address, SignatureError>
     where
         T: super::transaction::eip712::Eip712,
@@ -149,6 +153,12 @@ impl Signature {
         Ok((signature, recovery_id))
     }
 
+    /// Returns the chain id of a signature with an EIP-155 encoded `v` value, i.e.
+    /// `v = chain_id * 2 + 35 + y_parity`
+    pub fn chain_id(&self) -> Option<u64> {
+        (self.v >= 35).then(|| (self.v - 35) / 2)
+    }
+
     /// Retrieve the recovery ID.
     pub fn recovery_id(&self) -> Result<RecoveryId, SignatureError> {
         let standard_v = normalize_recovery_id(self.v);
@@ -355,6 +365,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn recovers_all_v_conventions() {
+        let signature = Signature::from_str(
+            "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c"
+        ).unwrap();
+        let expected = Address::from_str("2c7536E3605D9C16a7a3D7b1898e529396a65c23").unwrap();
+        let hash = hash_message("Some data");
+
+        // y parity of 1, as raw, Electrum and EIP-155 value of mainnet and a large chain id
+        for (v, chain_id) in [(1, None), (28, None), (38, Some(1)), (2 * 42161 + 36, Some(42161))] {
+            let signature = Signature { v, ..signature };
+            assert_eq!(signature.chain_id(), chain_id);
+            assert_eq!(signature.recover("Some data").unwrap(), expected, "v = {v}");
+            assert_eq!(signature.recover(hash).unwrap(), expected, "v = {v}");
+        }
+    }
+
     #[test]
     fn signature_from_str() {
         let s1 = Signature::from_str(
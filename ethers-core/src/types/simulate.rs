@@ -0,0 +1,291 @@
+//! Types for simulating calls across blocks with `eth_simulateV1`
+use crate::types::{
+    spoof, transaction::eip2718::TypedTransaction, Address, Block, Bytes, Log, TxHash, H256, U256,
+    U64,
+};
+use serde::{Deserialize, Serialize};
+
+/// The payload of an `eth_simulateV1` request: a sequence of blocks whose calls are simulated on
+/// top of each other.
+///
+/// See <https://github.com/ethereum/execution-apis/pull/484>
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate, in order
+    pub block_state_calls: Vec<SimulateBlock>,
+    /// Whether ETH transfers should be reported as logs of the `0xeeee...eeee` address
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub trace_transfers: bool,
+    /// Whether the calls are validated like transactions, e.g. checking nonces and balances
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub validation: bool,
+    /// Whether the simulated blocks contain the full transactions instead of their hashes
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub return_full_transactions: bool,
+}
+
+impl SimulatePayload {
+    /// Creates an empty payload
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a block to simulate
+    #[must_use]
+    pub fn block(mut self, block: SimulateBlock) -> Self {
+        self.block_state_calls.push(block);
+        self
+    }
+
+    /// Reports ETH transfers as logs
+    #[must_use]
+    pub fn trace_transfers(mut self) -> Self {
+        self.trace_transfers = true;
+        self
+    }
+
+    /// Validates the calls like transactions
+    #[must_use]
+    pub fn validation(mut self) -> Self {
+        self.validation = true;
+        self
+    }
+}
+
+/// A block of calls to simulate, together with the state and block overrides applied before
+/// executing the calls
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+    /// The overrides of the simulated block's header fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<SimulateBlockOverrides>,
+    /// The overrides of the state the calls are executed on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<spoof::State>,
+    /// The calls to execute, in order
+    #[serde(default)]
+    pub calls: Vec<TypedTransaction>,
+}
+
+impl SimulateBlock {
+    /// Creates a block with the given calls
+    pub fn new(calls: impl IntoIterator<Item = TypedTransaction>) -> Self {
+        Self { calls: calls.into_iter().collect(), ..Default::default() }
+    }
+
+    /// Appends a call to the block
+    #[must_use]
+    pub fn call<T: Into<TypedTransaction>>(mut self, call: T) -> Self {
+        self.calls.push(call.into());
+        self
+    }
+
+    /// Sets the overrides of the state the calls are executed on
+    #[must_use]
+    pub fn state_overrides(mut self, state: spoof::State) -> Self {
+        self.state_overrides = Some(state);
+        self
+    }
+
+    /// Sets the overrides of the block's header fields
+    #[must_use]
+    pub fn block_overrides(mut self, overrides: SimulateBlockOverrides) -> Self {
+        self.block_overrides = Some(overrides);
+        self
+    }
+}
+
+/// The header fields of a simulated block that can be overridden.
+///
+/// Unlike the [`BlockOverrides`](crate::types::BlockOverrides) of `debug_traceCall`, these use the
+/// field names of the header.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlockOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_randao: Option<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_base_fee: Option<U256>,
+}
+
+/// A block returned by `eth_simulateV1`, with the results of its calls
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedBlock {
+    /// The simulated block
+    #[serde(flatten)]
+    pub inner: Block<TxHash>,
+    /// The result of each call of the block, in order
+    pub calls: Vec<SimulatedCall>,
+}
+
+/// The result of a single simulated call
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    /// The data returned by the call, or the revert data if it failed
+    pub return_data: Bytes,
+    /// The logs emitted by the call
+    #[serde(default)]
+    pub logs: Vec<Log>,
+    /// The gas used by the call
+    pub gas_used: U64,
+    /// Either 1 (success) or 0 (failure)
+    pub status: U64,
+    /// The reason the call failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+impl SimulatedCall {
+    /// Returns `true` if the call succeeded
+    pub fn is_success(&self) -> bool {
+        self.status == U64::one()
+    }
+
+    /// Returns the logs emitted by the call whose first topic, i.e. the event signature of a
+    /// non-anonymous event, is `topic0`
+    pub fn logs_with_topic0(&self, topic0: H256) -> impl Iterator<Item = &Log> + '_ {
+        self.logs.iter().filter(move |log| log.topics.first() == Some(&topic0))
+    }
+}
+
+/// The error of a failed simulated call
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateError {
+    /// The error code, `3` if the call reverted
+    pub code: i64,
+    /// The error message
+    pub message: String,
+    /// The revert data, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TransactionRequest;
+
+    #[test]
+    fn serialize_payload() {
+        let mut state = spoof::state();
+        state.account(Address::repeat_byte(1)).balance(U256::exp10(18));
+        let payload = SimulatePayload::new()
+            .block(
+                SimulateBlock::default()
+                    .call(TransactionRequest::new().from(Address::repeat_byte(1)).value(1))
+                    .state_overrides(state)
+                    .block_overrides(SimulateBlockOverrides {
+                        time: Some(1_700_000_000u64.into()),
+                        ..Default::default()
+                    }),
+            )
+            .trace_transfers();
+
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value["traceTransfers"], true);
+        assert!(value.get("validation").is_none());
+        let block = &value["blockStateCalls"][0];
+        assert_eq!(block["blockOverrides"], serde_json::json!({ "time": "0x6553f100" }));
+        assert_eq!(
+            block["stateOverrides"]["0x0101010101010101010101010101010101010101"]["balance"],
+            "0xde0b6b3a7640000"
+        );
+        assert_eq!(block["calls"][0]["value"], "0x1");
+    }
+
+    #[test]
+    fn deserialize_simulated_blocks() {
+        let s = r#"[{
+            "baseFeePerGas": "0x0",
+            "blobGasUsed": "0x0",
+            "calls": [
+                {
+                    "returnData": "0x",
+                    "logs": [{
+                        "address": "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee",
+                        "topics": [
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x000000000000000000000000c000000000000000000000000000000000000000",
+                            "0x000000000000000000000000c100000000000000000000000000000000000000"
+                        ],
+                        "data": "0x00000000000000000000000000000000000000000000000000000000000003e8",
+                        "blockNumber": "0x1",
+                        "transactionHash": "0x4a6a7421d9d5e35e0d3717e6a3e8a4ad11818569b8e930d1324b7bd947c9d7c4",
+                        "transactionIndex": "0x0",
+                        "blockHash": "0x9d6caa5ba23a68a4b4e982f2aa6e9fa33e9fbeb6ff74ec1931f12fa3c06dbd96",
+                        "logIndex": "0x0",
+                        "removed": false
+                    }],
+                    "gasUsed": "0x5208",
+                    "status": "0x1"
+                },
+                {
+                    "returnData": "0x08c379a0",
+                    "logs": [],
+                    "gasUsed": "0x5fb2",
+                    "status": "0x0",
+                    "error": { "code": 3, "message": "execution reverted", "data": "0x08c379a0" }
+                }
+            ],
+            "difficulty": "0x0",
+            "excessBlobGas": "0x0",
+            "extraData": "0x",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0xb1ba",
+            "hash": "0x9d6caa5ba23a68a4b4e982f2aa6e9fa33e9fbeb6ff74ec1931f12fa3c06dbd96",
+            "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "nonce": "0x0000000000000000",
+            "number": "0x1",
+            "parentBeaconBlockRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "parentHash": "0xe5d4f7ab0b8bc6d6ac159f2ad3a7c5e8d9b4e8f1c2a3b4d5e6f708192a3b4c5d",
+            "receiptsRoot": "0x75308898d571eafb5cd8cde8278bf5b3d13c5f6ec074926de3bb895b519264e1",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "size": "0x29f",
+            "stateRoot": "0xbb1c4b8fd1ca6bb7a4bafe8a9b28b2558b5f0e1d2f76a8f4d0e9a2c5e7231a3f",
+            "timestamp": "0x6553f100",
+            "transactions": [
+                "0x4a6a7421d9d5e35e0d3717e6a3e8a4ad11818569b8e930d1324b7bd947c9d7c4",
+                "0x9c5d2e0e4e0ec4d4b1fd1e6b0a3ec2ad7c6e4d37a1b8c8b4b1f1a80f12e0a3b2"
+            ],
+            "transactionsRoot": "0x4cf1f4f9d6b4bd2e1e3a1d594bbd9261e2ba4cc5bcf0f30e5fe6bf4e827e4e2b",
+            "uncles": [],
+            "withdrawals": [],
+            "withdrawalsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        }]"#;
+        let blocks: Vec<SimulatedBlock> = serde_json::from_str(s).unwrap();
+        assert_eq!(blocks.len(), 1);
+        let block = &blocks[0];
+        assert_eq!(block.inner.number, Some(1u64.into()));
+        assert_eq!(block.inner.transactions.len(), 2);
+        #[cfg(not(feature = "celo"))]
+        assert!(block.inner.other.get("calls").is_none());
+
+        let [transfer, revert] = &block.calls[..] else { panic!("expected two calls") };
+        assert!(transfer.is_success());
+        assert_eq!(transfer.gas_used, 21_000u64.into());
+        let topic0: H256 =
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".parse().unwrap();
+        assert_eq!(transfer.logs_with_topic0(topic0).count(), 1);
+        assert_eq!(transfer.logs_with_topic0(H256::zero()).count(), 0);
+
+        assert!(!revert.is_success());
+        let error = revert.error.as_ref().unwrap();
+        assert_eq!(error.code, 3);
+        assert_eq!(error.data.as_ref().unwrap().to_vec(), vec![0x08, 0xc3, 0x79, 0xa0]);
+    }
+}
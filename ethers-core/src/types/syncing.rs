@@ -12,6 +12,21 @@ pub enum SyncingStatus {
     IsSyncing(Box<SyncProgress>),
 }
 
+impl SyncingStatus {
+    /// Returns true if the node is still syncing
+    pub fn is_syncing(&self) -> bool {
+        matches!(self, SyncingStatus::IsSyncing(_))
+    }
+
+    /// Returns the sync progress if the node is still syncing
+    pub fn progress(&self) -> Option<&SyncProgress> {
+        match self {
+            SyncingStatus::IsFalse => None,
+            SyncingStatus::IsSyncing(progress) => Some(progress),
+        }
+    }
+}
+
 impl Serialize for SyncingStatus {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -161,4 +176,21 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn sync_progress_accessors() {
+        let synced: SyncingStatus = serde_json::from_str("false").unwrap();
+        assert!(!synced.is_syncing());
+        assert!(synced.progress().is_none());
+
+        let s = r#"{"currentBlock":"0x2","highestBlock":"0x3","startingBlock":"0x1"}"#;
+        let syncing: SyncingStatus = serde_json::from_str(s).unwrap();
+        assert!(syncing.is_syncing());
+        let progress = syncing.progress().unwrap();
+        assert_eq!(progress.starting_block, 1u64.into());
+        assert_eq!(progress.current_block, 2u64.into());
+        assert_eq!(progress.highest_block, 3u64.into());
+
+        assert!(serde_json::from_str::<SyncingStatus>("true").is_err());
+    }
 }
@@ -80,7 +80,9 @@ impl TraceFilter {
 
 // `LocalizedTrace` in Parity
 /// Trace-Filtering API trace type
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+///
+/// The variant of the [`Action`] is determined by the `type` of the trace when deserializing.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 pub struct Trace {
     /// Action
     pub action: Action,
@@ -111,6 +113,52 @@ pub struct Trace {
     pub error: Option<String>,
 }
 
+impl<'de> Deserialize<'de> for Trace {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct TraceRepr {
+            action: serde_json::Value,
+            result: Option<Res>,
+            trace_address: Vec<usize>,
+            subtraces: usize,
+            transaction_position: Option<usize>,
+            transaction_hash: Option<H256>,
+            block_number: u64,
+            block_hash: H256,
+            #[serde(rename = "type")]
+            action_type: ActionType,
+            #[serde(default)]
+            error: Option<String>,
+        }
+
+        let trace = TraceRepr::deserialize(deserializer)?;
+        let action = match trace.action_type {
+            ActionType::Call => serde_json::from_value(trace.action).map(Action::Call),
+            ActionType::Create => serde_json::from_value(trace.action).map(Action::Create),
+            ActionType::Suicide => serde_json::from_value(trace.action).map(Action::Suicide),
+            ActionType::Reward => serde_json::from_value(trace.action).map(Action::Reward),
+        }
+        .map_err(serde::de::Error::custom)?;
+
+        Ok(Trace {
+            action,
+            result: trace.result,
+            trace_address: trace.trace_address,
+            subtraces: trace.subtraces,
+            transaction_position: trace.transaction_position,
+            transaction_hash: trace.transaction_hash,
+            block_number: trace.block_number,
+            block_hash: trace.block_hash,
+            action_type: trace.action_type,
+            error: trace.error,
+        })
+    }
+}
+
 /// Response
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -476,4 +524,26 @@ mod tests {
         let _trace: Trace = serde_json::from_str(EXAMPLE_TRACE_SUICIDE).unwrap();
         let _trace: Trace = serde_json::from_str(EXAMPLE_TRACE_REWARD).unwrap();
     }
+
+    #[test]
+    fn test_deserialize_trace_action_by_type() {
+        let trace: Trace = serde_json::from_str(EXAMPLE_TRACE_CALL).unwrap();
+        assert!(matches!(trace.action, Action::Call(Call { call_type: CallType::Call, .. })));
+        let trace: Trace = serde_json::from_str(EXAMPLE_TRACE_SUICIDE).unwrap();
+        assert!(matches!(trace.action, Action::Suicide(_)));
+        let trace: Trace = serde_json::from_str(EXAMPLE_TRACE_REWARD).unwrap();
+        assert!(matches!(
+            trace.action,
+            Action::Reward(Reward { reward_type: RewardType::Block, .. })
+        ));
+
+        // the action must match the type of the trace
+        let mismatched = EXAMPLE_TRACE_CALL.replace(r#""type": "call""#, r#""type": "reward""#);
+        assert!(serde_json::from_str::<Trace>(&mismatched).is_err());
+
+        let trace: Trace = serde_json::from_str(EXAMPLE_TRACE_CREATE).unwrap();
+        let roundtrip: Trace =
+            serde_json::from_str(&serde_json::to_string(&trace).unwrap()).unwrap();
+        assert_eq!(trace, roundtrip);
+    }
 }
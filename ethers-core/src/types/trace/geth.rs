@@ -123,6 +123,39 @@ impl From<GethTraceResult> for GethTrace {
     }
 }
 
+impl GethTrace {
+    /// Deserializes the `value` returned by a trace request made with the given `tracer` into the
+    /// frame of that tracer, e.g. [`CallFrame`] for the `callTracer`.
+    ///
+    /// Without a tracer, the default struct logger's [`DefaultFrame`] is expected. Traces of JS
+    /// tracers and values that don't match the requested tracer are returned as
+    /// [`GethTrace::Unknown`].
+    pub fn from_value_with_tracer(value: Value, tracer: Option<&GethDebugTracerType>) -> Self {
+        let frame = match tracer {
+            None => DefaultFrame::deserialize(&value).map(GethTraceFrame::Default),
+            Some(GethDebugTracerType::BuiltInTracer(tracer)) => match tracer {
+                GethDebugBuiltInTracerType::FourByteTracer => {
+                    FourByteFrame::deserialize(&value).map(GethTraceFrame::FourByteTracer)
+                }
+                GethDebugBuiltInTracerType::CallTracer => {
+                    CallFrame::deserialize(&value).map(GethTraceFrame::CallTracer)
+                }
+                GethDebugBuiltInTracerType::PreStateTracer => {
+                    PreStateFrame::deserialize(&value).map(GethTraceFrame::PreStateTracer)
+                }
+                GethDebugBuiltInTracerType::NoopTracer => {
+                    NoopFrame::deserialize(&value).map(GethTraceFrame::NoopTracer)
+                }
+            },
+            Some(GethDebugTracerType::JsTracer(_)) => return GethTrace::Unknown(value),
+        };
+        match frame {
+            Ok(frame) => GethTrace::Known(frame),
+            Err(_) => GethTrace::Unknown(value),
+        }
+    }
+}
+
 impl From<GethTraceFrame> for GethTrace {
     fn from(value: GethTraceFrame) -> Self {
         GethTrace::Known(value)
@@ -284,6 +317,20 @@ pub mod spoof {
             self.storage.get_or_insert_with(Default::default).insert(key, val);
             self
         }
+        /// Replace the entire account storage, so that every slot that is not set via `store` or
+        /// `replace_storage` is treated as empty.
+        ///
+        /// Slots that were previously overridden as a diff become part of the replaced storage.
+        pub fn replace_storage(&mut self, key: H256, val: H256) -> &mut Self {
+            let storage = match self.storage.take() {
+                Some(Storage::Diff(map)) | Some(Storage::Replace(map)) => map,
+                None => Default::default(),
+            };
+            let mut storage = Storage::Replace(storage);
+            storage.insert(key, val);
+            self.storage = Some(storage);
+            self
+        }
     }
 
     /// Wraps a map from storage slot to the overriden value.
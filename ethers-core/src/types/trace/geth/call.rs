@@ -27,6 +27,8 @@ pub struct CallFrame {
     pub output: Option<Bytes>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(default, rename = "revertReason", skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub calls: Option<Vec<CallFrame>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -88,4 +90,34 @@ mod tests {
         let trace: CallFrame = serde_json::from_str(WITH_LOG).unwrap();
         let _logs = trace.logs.unwrap();
     }
+
+    #[test]
+    fn test_deserialize_reverted_call_trace_with_tracer() {
+        let s = r#"{
+            "type": "CALL",
+            "from": "0x0000000000000000000000000000000000000001",
+            "to": "0x0000000000000000000000000000000000000002",
+            "value": "0x0",
+            "gas": "0x7148",
+            "gasUsed": "0x5bfc",
+            "input": "0x",
+            "output": "0x08c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000a6e6f7420656e6f75676800000000000000000000000000000000000000000000",
+            "error": "execution reverted",
+            "revertReason": "not enough"
+        }"#;
+        let value: serde_json::Value = serde_json::from_str(s).unwrap();
+        let tracer = GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer);
+
+        let trace = GethTrace::from_value_with_tracer(value.clone(), Some(&tracer));
+        let frame = match trace {
+            GethTrace::Known(GethTraceFrame::CallTracer(frame)) => frame,
+            _ => unreachable!(),
+        };
+        assert_eq!(frame.error.as_deref(), Some("execution reverted"));
+        assert_eq!(frame.revert_reason.as_deref(), Some("not enough"));
+
+        // a call frame is not a valid trace of the default struct logger
+        let trace = GethTrace::from_value_with_tracer(value.clone(), None);
+        assert_eq!(trace, GethTrace::Unknown(value));
+    }
 }
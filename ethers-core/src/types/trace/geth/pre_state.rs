@@ -86,4 +86,15 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_deserialize_empty_pre_state_trace_with_tracer() {
+        // an empty object is a valid trace for several tracers
+        let tracer = GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::PreStateTracer);
+        let trace = GethTrace::from_value_with_tracer(serde_json::json!({}), Some(&tracer));
+        match trace {
+            GethTrace::Known(GethTraceFrame::PreStateTracer(PreStateFrame::Default(_))) => {}
+            _ => unreachable!(),
+        }
+    }
 }
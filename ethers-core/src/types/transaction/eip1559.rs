@@ -267,6 +267,27 @@ impl From<Eip1559TransactionRequest> for super::request::TransactionRequest {
     }
 }
 
+/// The gas price of the legacy request is used as both the max fee and the max priority fee, which
+/// is what a legacy transaction pays on a chain with a base fee.
+///
+/// The celo fee fields of the legacy request have no equivalent and are dropped.
+impl From<super::request::TransactionRequest> for Eip1559TransactionRequest {
+    fn from(tx: super::request::TransactionRequest) -> Self {
+        Self {
+            from: tx.from,
+            to: tx.to,
+            gas: tx.gas,
+            value: tx.value,
+            data: tx.data,
+            nonce: tx.nonce,
+            access_list: AccessList::default(),
+            max_priority_fee_per_gas: tx.gas_price,
+            max_fee_per_gas: tx.gas_price,
+            chain_id: tx.chain_id,
+        }
+    }
+}
+
 impl From<&Transaction> for Eip1559TransactionRequest {
     fn from(tx: &Transaction) -> Eip1559TransactionRequest {
         Eip1559TransactionRequest {
@@ -286,7 +307,39 @@ impl From<&Transaction> for Eip1559TransactionRequest {
 
 #[cfg(test)]
 mod tests {
-    use crate::types::Eip1559TransactionRequest;
+    use crate::types::{Address, Eip1559TransactionRequest, TransactionRequest};
+
+    #[test]
+    fn from_legacy_request() {
+        let legacy = TransactionRequest::new()
+            .to(Address::zero())
+            .value(1u64)
+            .gas(21_000u64)
+            .gas_price(7u64)
+            .nonce(2u64)
+            .chain_id(1u64);
+        let tx: Eip1559TransactionRequest = legacy.into();
+        assert_eq!(tx.to, Some(Address::zero().into()));
+        assert_eq!(tx.value, Some(1u64.into()));
+        assert_eq!(tx.gas, Some(21_000u64.into()));
+        assert_eq!(tx.nonce, Some(2u64.into()));
+        assert_eq!(tx.chain_id, Some(1u64.into()));
+        assert_eq!(tx.max_fee_per_gas, Some(7u64.into()));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(7u64.into()));
+    }
+
+    #[test]
+    #[cfg(feature = "celo")]
+    fn from_legacy_request_drops_celo_fields() {
+        let legacy = TransactionRequest::new().to(Address::zero()).gas_price(7u64);
+        let with_celo_fields = legacy
+            .clone()
+            .fee_currency(Address::repeat_byte(0xce))
+            .gateway_fee_recipient(Address::repeat_byte(0x9a))
+            .gateway_fee(1u64);
+        let tx: Eip1559TransactionRequest = with_celo_fields.into();
+        assert_eq!(tx, legacy.into());
+    }
 
     #[test]
     fn test_tx_with_input_or_data() {
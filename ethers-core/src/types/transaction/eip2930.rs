@@ -105,6 +105,13 @@ impl Eip2930TransactionRequest {
         Self { tx, access_list }
     }
 
+    /// Sets the `access_list` field in the transaction to the provided value
+    #[must_use]
+    pub fn access_list<T: Into<AccessList>>(mut self, access_list: T) -> Self {
+        self.access_list = access_list.into();
+        self
+    }
+
     pub fn rlp(&self) -> Bytes {
         let mut rlp = RlpStream::new();
         rlp.begin_list(NUM_EIP2930_FIELDS);
@@ -0,0 +1,470 @@
+use super::{
+    eip1559::Eip1559TransactionRequest, eip2718::TypedTransaction, eip2930::AccessList,
+    normalize_v, rlp_opt,
+};
+use crate::types::{
+    Address, Bytes, NameOrAddress, Signature, SignatureError, Transaction, H256, U256, U64,
+};
+use rlp::{Decodable, DecoderError, RlpStream};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// EIP-4844 transactions have 11 fields
+const NUM_TX_FIELDS: usize = 11;
+
+/// The size of a single blob in bytes
+pub const BYTES_PER_BLOB: usize = 131_072;
+
+/// The amount of blob gas consumed by a single blob
+pub const DATA_GAS_PER_BLOB: u64 = 131_072;
+
+/// The version byte of a KZG commitment's versioned hash
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// An error involving an EIP4844 transaction request.
+#[derive(Debug, Error)]
+pub enum Eip4844RequestError {
+    /// When decoding a transaction request from RLP
+    #[error(transparent)]
+    DecodingError(#[from] rlp::DecoderError),
+    /// When recovering the address from a signature
+    #[error(transparent)]
+    RecoveryError(#[from] SignatureError),
+    /// When a blob does not have the expected size of [`BYTES_PER_BLOB`] bytes
+    #[error("invalid blob size: expected {BYTES_PER_BLOB} bytes, got {0}")]
+    InvalidBlobSize(usize),
+    /// When computing a KZG commitment or proof fails
+    #[cfg(feature = "kzg")]
+    #[error(transparent)]
+    KzgError(#[from] c_kzg::Error),
+}
+
+/// The blobs of an EIP-4844 transaction, together with their KZG commitments and proofs.
+///
+/// The sidecar is not part of the signed transaction, but has to be sent alongside it in the
+/// network form of the transaction, e.g. for `eth_sendRawTransaction`.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct BlobTransactionSidecar {
+    /// The blobs of the transaction, each exactly [`BYTES_PER_BLOB`] bytes long
+    pub blobs: Vec<Bytes>,
+    /// The KZG commitment of each blob
+    pub commitments: Vec<Bytes>,
+    /// The KZG proof of each blob
+    pub proofs: Vec<Bytes>,
+}
+
+impl BlobTransactionSidecar {
+    /// Computes the KZG commitments and proofs of the given blobs, using the Ethereum mainnet
+    /// trusted setup. The trusted setup is only loaded once and cached for subsequent calls.
+    #[cfg(feature = "kzg")]
+    pub fn from_blobs(blobs: Vec<Bytes>) -> Result<Self, Eip4844RequestError> {
+        use c_kzg::{Blob, KzgCommitment, KzgProof};
+
+        let settings = c_kzg::ethereum_kzg_settings();
+        let mut commitments = Vec::with_capacity(blobs.len());
+        let mut proofs = Vec::with_capacity(blobs.len());
+        for blob in &blobs {
+            if blob.len() != BYTES_PER_BLOB {
+                return Err(Eip4844RequestError::InvalidBlobSize(blob.len()))
+            }
+            let blob = Blob::from_bytes(blob)?;
+            let commitment = KzgCommitment::blob_to_kzg_commitment(&blob, settings)?.to_bytes();
+            let proof = KzgProof::compute_blob_kzg_proof(&blob, &commitment, settings)?;
+            commitments.push(Bytes::from(commitment.into_inner().to_vec()));
+            proofs.push(Bytes::from(proof.to_bytes().into_inner().to_vec()));
+        }
+        Ok(Self { blobs, commitments, proofs })
+    }
+
+    /// Returns the versioned hash of each commitment, i.e. the `sha256` hash of the commitment
+    /// with its first byte replaced by [`VERSIONED_HASH_VERSION_KZG`]
+    #[cfg(feature = "kzg")]
+    pub fn versioned_hashes(&self) -> Vec<H256> {
+        use sha2::Digest;
+
+        self.commitments
+            .iter()
+            .map(|commitment| {
+                let mut hash: [u8; 32] = sha2::Sha256::digest(commitment).into();
+                hash[0] = VERSIONED_HASH_VERSION_KZG;
+                H256(hash)
+            })
+            .collect()
+    }
+
+    fn rlp_append(&self, rlp: &mut RlpStream) {
+        rlp.begin_list(self.blobs.len());
+        for blob in &self.blobs {
+            rlp.append(&blob.as_ref());
+        }
+        rlp.begin_list(self.commitments.len());
+        for commitment in &self.commitments {
+            rlp.append(&commitment.as_ref());
+        }
+        rlp.begin_list(self.proofs.len());
+        for proof in &self.proofs {
+            rlp.append(&proof.as_ref());
+        }
+    }
+
+    fn decode_rlp(rlp: &rlp::Rlp, offset: usize) -> Result<Self, DecoderError> {
+        let decode_list = |index: usize| -> Result<Vec<Bytes>, DecoderError> {
+            rlp.at(index)?.iter().map(|item| Ok(Bytes::from(item.data()?.to_vec()))).collect()
+        };
+        Ok(Self {
+            blobs: decode_list(offset)?,
+            commitments: decode_list(offset + 1)?,
+            proofs: decode_list(offset + 2)?,
+        })
+    }
+}
+
+/// Parameters for sending a blob-carrying transaction
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct Eip4844TransactionRequest {
+    #[serde(flatten)]
+    pub tx: Eip1559TransactionRequest,
+
+    /// The maximum fee per unit of blob gas the sender is willing to pay
+    #[serde(rename = "maxFeePerBlobGas", default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+
+    /// The versioned hashes of the blobs carried by the transaction
+    #[serde(rename = "blobVersionedHashes", default)]
+    pub blob_versioned_hashes: Vec<H256>,
+
+    /// The blobs, commitments and proofs sent alongside the transaction
+    #[serde(skip)]
+    pub sidecar: Option<BlobTransactionSidecar>,
+}
+
+impl Eip4844TransactionRequest {
+    /// Creates an empty transaction request with all fields left empty
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Builder pattern helpers
+
+    /// Sets the `from` field in the transaction to the provided value
+    #[must_use]
+    pub fn from<T: Into<Address>>(mut self, from: T) -> Self {
+        self.tx.from = Some(from.into());
+        self
+    }
+
+    /// Sets the `to` field in the transaction to the provided value
+    #[must_use]
+    pub fn to<T: Into<NameOrAddress>>(mut self, to: T) -> Self {
+        self.tx.to = Some(to.into());
+        self
+    }
+
+    /// Sets the `gas` field in the transaction to the provided value
+    #[must_use]
+    pub fn gas<T: Into<U256>>(mut self, gas: T) -> Self {
+        self.tx.gas = Some(gas.into());
+        self
+    }
+
+    /// Sets the `max_priority_fee_per_gas` field in the transaction to the provided value
+    #[must_use]
+    pub fn max_priority_fee_per_gas<T: Into<U256>>(mut self, max_priority_fee_per_gas: T) -> Self {
+        self.tx.max_priority_fee_per_gas = Some(max_priority_fee_per_gas.into());
+        self
+    }
+
+    /// Sets the `max_fee_per_gas` field in the transaction to the provided value
+    #[must_use]
+    pub fn max_fee_per_gas<T: Into<U256>>(mut self, max_fee_per_gas: T) -> Self {
+        self.tx.max_fee_per_gas = Some(max_fee_per_gas.into());
+        self
+    }
+
+    /// Sets the `max_fee_per_blob_gas` field in the transaction to the provided value
+    #[must_use]
+    pub fn max_fee_per_blob_gas<T: Into<U256>>(mut self, max_fee_per_blob_gas: T) -> Self {
+        self.max_fee_per_blob_gas = Some(max_fee_per_blob_gas.into());
+        self
+    }
+
+    /// Sets the `value` field in the transaction to the provided value
+    #[must_use]
+    pub fn value<T: Into<U256>>(mut self, value: T) -> Self {
+        self.tx.value = Some(value.into());
+        self
+    }
+
+    /// Sets the `data` field in the transaction to the provided value
+    #[must_use]
+    pub fn data<T: Into<Bytes>>(mut self, data: T) -> Self {
+        self.tx.data = Some(data.into());
+        self
+    }
+
+    /// Sets the `access_list` field in the transaction to the provided value
+    #[must_use]
+    pub fn access_list<T: Into<AccessList>>(mut self, access_list: T) -> Self {
+        self.tx.access_list = access_list.into();
+        self
+    }
+
+    /// Sets the `nonce` field in the transaction to the provided value
+    #[must_use]
+    pub fn nonce<T: Into<U256>>(mut self, nonce: T) -> Self {
+        self.tx.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Sets the `chain_id` field in the transaction to the provided value
+    #[must_use]
+    pub fn chain_id<T: Into<U64>>(mut self, chain_id: T) -> Self {
+        self.tx.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Sets the `blob_versioned_hashes` field in the transaction to the provided value
+    #[must_use]
+    pub fn blob_versioned_hashes(mut self, blob_versioned_hashes: Vec<H256>) -> Self {
+        self.blob_versioned_hashes = blob_versioned_hashes;
+        self
+    }
+
+    /// Sets the `sidecar` field in the transaction to the provided value
+    #[must_use]
+    pub fn sidecar(mut self, sidecar: BlobTransactionSidecar) -> Self {
+        self.sidecar = Some(sidecar);
+        self
+    }
+
+    /// Attaches the given blobs to the transaction, computing their KZG commitments and proofs
+    /// and setting the `blob_versioned_hashes` accordingly
+    #[cfg(feature = "kzg")]
+    pub fn blobs(mut self, blobs: Vec<Bytes>) -> Result<Self, Eip4844RequestError> {
+        let sidecar = BlobTransactionSidecar::from_blobs(blobs)?;
+        self.blob_versioned_hashes = sidecar.versioned_hashes();
+        self.sidecar = Some(sidecar);
+        Ok(self)
+    }
+
+    /// The total amount of blob gas consumed by the transaction's blobs
+    pub fn blob_gas(&self) -> U256 {
+        U256::from(DATA_GAS_PER_BLOB) * self.blob_versioned_hashes.len()
+    }
+
+    /// Gets the unsigned transaction's RLP encoding
+    pub fn rlp(&self) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(NUM_TX_FIELDS);
+        self.rlp_base(&mut rlp);
+        rlp.out().freeze().into()
+    }
+
+    /// Produces the RLP encoding of the transaction with the provided signature.
+    ///
+    /// This does not include the sidecar, use [`Self::rlp_signed_network`] to get the form of
+    /// the transaction that is broadcast to the network.
+    pub fn rlp_signed(&self, signature: &Signature) -> Bytes {
+        let mut rlp = RlpStream::new();
+        self.rlp_append_signed(&mut rlp, signature);
+        rlp.out().freeze().into()
+    }
+
+    /// Produces the network form of the signed transaction, i.e. the signed transaction wrapped
+    /// together with its blobs, commitments and proofs. Returns the same encoding as
+    /// [`Self::rlp_signed`] if the transaction has no sidecar.
+    pub fn rlp_signed_network(&self, signature: &Signature) -> Bytes {
+        let Some(ref sidecar) = self.sidecar else { return self.rlp_signed(signature) };
+
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(4);
+        self.rlp_append_signed(&mut rlp, signature);
+        sidecar.rlp_append(&mut rlp);
+        rlp.out().freeze().into()
+    }
+
+    fn rlp_append_signed(&self, rlp: &mut RlpStream, signature: &Signature) {
+        rlp.begin_unbounded_list();
+        self.rlp_base(rlp);
+
+        // if the chain_id is none we assume mainnet and choose one
+        let chain_id = self.tx.chain_id.unwrap_or_else(U64::one);
+
+        // append the signature
+        let v = normalize_v(signature.v, chain_id);
+        rlp.append(&v);
+        rlp.append(&signature.r);
+        rlp.append(&signature.s);
+        rlp.finalize_unbounded_list();
+    }
+
+    pub(crate) fn rlp_base(&self, rlp: &mut RlpStream) {
+        self.tx.rlp_base(rlp);
+        rlp_opt(rlp, &self.max_fee_per_blob_gas);
+        rlp.append_list(&self.blob_versioned_hashes);
+    }
+
+    /// Decodes fields of the request starting at the RLP offset passed. Increments the offset for
+    /// each element parsed.
+    #[inline]
+    pub fn decode_base_rlp(rlp: &rlp::Rlp, offset: &mut usize) -> Result<Self, DecoderError> {
+        let tx = Eip1559TransactionRequest::decode_base_rlp(rlp, offset)?;
+        let max_fee_per_blob_gas = Some(rlp.val_at(*offset)?);
+        *offset += 1;
+        let blob_versioned_hashes = rlp.list_at(*offset)?;
+        *offset += 1;
+        Ok(Self { tx, max_fee_per_blob_gas, blob_versioned_hashes, sidecar: None })
+    }
+
+    /// Decodes the given RLP into a transaction, attempting to decode its signature as well.
+    ///
+    /// Both the plain signed transaction and its network form, which includes the sidecar, are
+    /// accepted.
+    pub fn decode_signed_rlp(rlp: &rlp::Rlp) -> Result<(Self, Signature), Eip4844RequestError> {
+        // the network form is a list of the signed transaction, blobs, commitments and proofs
+        if rlp.at(0)?.is_list() {
+            let (mut txn, sig) = Self::decode_signed_rlp(&rlp.at(0)?)?;
+            txn.sidecar = Some(BlobTransactionSidecar::decode_rlp(rlp, 1)?);
+            return Ok((txn, sig))
+        }
+
+        let mut offset = 0;
+        let mut txn = Self::decode_base_rlp(rlp, &mut offset)?;
+
+        let v = rlp.val_at(offset)?;
+        offset += 1;
+        let r = rlp.val_at(offset)?;
+        offset += 1;
+        let s = rlp.val_at(offset)?;
+
+        let sig = Signature { r, s, v };
+        txn.tx.from = Some(sig.recover(TypedTransaction::Eip4844(txn.clone()).sighash())?);
+
+        Ok((txn, sig))
+    }
+}
+
+impl Decodable for Eip4844TransactionRequest {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        Self::decode_base_rlp(rlp, &mut 0)
+    }
+}
+
+impl From<Eip1559TransactionRequest> for Eip4844TransactionRequest {
+    fn from(tx: Eip1559TransactionRequest) -> Self {
+        Self { tx, ..Default::default() }
+    }
+}
+
+impl From<&Transaction> for Eip4844TransactionRequest {
+    fn from(tx: &Transaction) -> Eip4844TransactionRequest {
+        Eip4844TransactionRequest {
+            tx: tx.into(),
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas,
+            blob_versioned_hashes: tx.blob_versioned_hashes.clone().unwrap_or_default(),
+            sidecar: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::transaction::eip2718::TypedTransaction;
+    use std::str::FromStr;
+
+    fn blob_tx() -> Eip4844TransactionRequest {
+        Eip4844TransactionRequest::new()
+            .to(Address::from_str("0x2000000000000000000000000000000000000002").unwrap())
+            .nonce(3)
+            .gas(21_000)
+            .max_priority_fee_per_gas(1_000_000_000u64)
+            .max_fee_per_gas(2_000_000_000u64)
+            .max_fee_per_blob_gas(1)
+            .chain_id(1)
+            .blob_versioned_hashes(vec![H256::from_str(
+                "0x01b0a4cdd5f55589f5c5b4d46c76704bb6ce95c0a8c09f77f197a57808dded28",
+            )
+            .unwrap()])
+    }
+
+    #[test]
+    fn serde_eip4844_tx() {
+        let tx: TypedTransaction = blob_tx().into();
+        let serialized = serde_json::to_value(&tx).unwrap();
+        assert_eq!(serialized["type"], "0x03");
+        assert_eq!(serialized["maxFeePerBlobGas"], "0x1");
+
+        let de: TypedTransaction = serde_json::from_value(serialized).unwrap();
+        assert_eq!(
+            de,
+            TypedTransaction::Eip4844(Eip4844TransactionRequest {
+                tx: Eip1559TransactionRequest { chain_id: None, ..blob_tx().tx },
+                ..blob_tx()
+            })
+        );
+    }
+
+    #[test]
+    fn decode_network_form() {
+        let signature = Signature { r: 1u64.into(), s: 2u64.into(), v: 0 };
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![Bytes::from(vec![1u8; 4])],
+            commitments: vec![Bytes::from(vec![2u8; 48])],
+            proofs: vec![Bytes::from(vec![3u8; 48])],
+        };
+        let tx = blob_tx().sidecar(sidecar.clone());
+        let typed: TypedTransaction = tx.clone().into();
+
+        // the sidecar is only part of the network form, not of the hashed transaction
+        let signed = typed.rlp_signed(&signature);
+        assert_eq!(signed[0], 0x03);
+        assert_eq!(&signed[1..], tx.rlp_signed_network(&signature).as_ref());
+        assert_eq!(
+            typed.hash(&signature),
+            H256(crate::utils::keccak256(
+                [&[0x03][..], tx.rlp_signed(&signature).as_ref()].concat()
+            ))
+        );
+
+        let decoded =
+            Eip4844TransactionRequest::decode_signed_rlp(&rlp::Rlp::new(&signed[1..])).unwrap();
+        assert_eq!(decoded.0.sidecar, Some(sidecar));
+        assert_eq!(decoded.0.blob_versioned_hashes, tx.blob_versioned_hashes);
+        assert_eq!(decoded.0.max_fee_per_blob_gas, Some(1u64.into()));
+        assert_eq!(decoded.1, signature);
+
+        let plain = Eip4844TransactionRequest::decode_signed_rlp(&rlp::Rlp::new(
+            &tx.rlp_signed(&signature),
+        ))
+        .unwrap();
+        assert_eq!(plain.0.sidecar, None);
+        assert_eq!(plain.0.tx.from, decoded.0.tx.from);
+    }
+
+    #[test]
+    #[cfg(feature = "kzg")]
+    fn computes_sidecar_from_blobs() {
+        let tx = blob_tx().blobs(vec![Bytes::from(vec![0u8; BYTES_PER_BLOB])]).unwrap();
+        let sidecar = tx.sidecar.as_ref().unwrap();
+
+        // the commitment to the zero blob is the point at infinity
+        let mut infinity = vec![0u8; 48];
+        infinity[0] = 0xc0;
+        assert_eq!(sidecar.commitments, vec![Bytes::from(infinity.clone())]);
+        assert_eq!(sidecar.proofs, vec![Bytes::from(infinity)]);
+        assert_eq!(
+            tx.blob_versioned_hashes,
+            vec![H256::from_str(
+                "0x010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+            )
+            .unwrap()]
+        );
+        assert_eq!(tx.blob_gas(), DATA_GAS_PER_BLOB.into());
+
+        assert!(matches!(
+            BlobTransactionSidecar::from_blobs(vec![Bytes::from(vec![0u8; 32])]),
+            Err(Eip4844RequestError::InvalidBlobSize(32))
+        ));
+    }
+}
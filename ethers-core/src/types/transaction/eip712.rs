@@ -81,6 +81,15 @@ pub trait Eip712 {
     /// ethereum types or custom types defined in the contract.
     fn type_hash() -> Result<[u8; 32], Self::Error>;
 
+    /// Returns the definitions of the struct's type and of all the struct types it references.
+    ///
+    /// These are needed to compute the type hash of structs containing this struct. The default
+    /// implementation returns no definitions, so a manually implemented struct can only be
+    /// referenced by other structs if it overrides this method.
+    fn types() -> Result<Types, Self::Error> {
+        Ok(Types::new())
+    }
+
     /// Hash of the struct, according to EIP-712 definition of `hashStruct`
     fn struct_hash(&self) -> Result<[u8; 32], Self::Error>;
 
@@ -193,6 +202,21 @@ impl EIP712Domain {
 
         keccak256(encode(&tokens))
     }
+
+    /// Returns the fields of the `EIP712Domain` type made of the fields that are set in the domain
+    pub fn field_types(&self) -> Vec<Eip712DomainType> {
+        [
+            ("name", "string", self.name.is_some()),
+            ("version", "string", self.version.is_some()),
+            ("chainId", "uint256", self.chain_id.is_some()),
+            ("verifyingContract", "address", self.verifying_contract.is_some()),
+            ("salt", "bytes32", self.salt.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, _, is_set)| *is_set)
+        .map(|(name, ty, _)| Eip712DomainType { name: name.to_string(), r#type: ty.to_string() })
+        .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -229,6 +253,10 @@ impl<T: Eip712 + Clone> Eip712 for EIP712WithDomain<T> {
         Ok(type_hash)
     }
 
+    fn types() -> Result<Types, Self::Error> {
+        T::types().map_err(|e| Self::Error::Message(e.to_string()))
+    }
+
     fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
         let struct_hash =
             self.inner.clone().struct_hash().map_err(|e| Self::Error::Message(e.to_string()))?;
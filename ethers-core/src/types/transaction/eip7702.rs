@@ -0,0 +1,391 @@
+use super::{
+    eip1559::Eip1559TransactionRequest, eip2718::TypedTransaction, eip2930::AccessList, normalize_v,
+};
+use crate::{
+    types::{
+        Address, Bytes, NameOrAddress, Signature, SignatureError, Transaction, H256, U256, U64,
+    },
+    utils::keccak256,
+};
+use rlp::{Decodable, DecoderError, Encodable, RlpStream};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// EIP-7702 transactions have 10 fields
+const NUM_TX_FIELDS: usize = 10;
+
+/// The prefix of the message that is signed to authorize the delegation of an account
+pub const AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// An error involving an EIP7702 transaction request.
+#[derive(Debug, Error)]
+pub enum Eip7702RequestError {
+    /// When decoding a transaction request from RLP
+    #[error(transparent)]
+    DecodingError(#[from] rlp::DecoderError),
+    /// When recovering the address from a signature
+    #[error(transparent)]
+    RecoveryError(#[from] SignatureError),
+}
+
+/// An unsigned [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorization, which delegates
+/// the code of the signing account to the code at `address`.
+#[derive(Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Authorization {
+    /// The chain the authorization is valid on, or `0` if it is valid on all chains
+    pub chain_id: U256,
+    /// The address of the code the account delegates to
+    pub address: Address,
+    /// The nonce of the signing account at the time the authorization is processed
+    pub nonce: U64,
+}
+
+impl Authorization {
+    /// Creates a new authorization to delegate to `address` on the given chain
+    pub fn new(chain_id: impl Into<U256>, address: Address, nonce: impl Into<U64>) -> Self {
+        Self { chain_id: chain_id.into(), address, nonce: nonce.into() }
+    }
+
+    /// Returns the hash that is signed by the authorizing account:
+    ///
+    /// `keccak256(0x05 ++ rlp([chain_id, address, nonce]))`
+    pub fn signature_hash(&self) -> H256 {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(3);
+        self.rlp_base(&mut rlp);
+        keccak256([&[AUTHORIZATION_MAGIC][..], rlp.as_raw()].concat()).into()
+    }
+
+    /// Attaches the signature of the authorizing account
+    pub fn into_signed(self, signature: Signature) -> Result<SignedAuthorization, SignatureError> {
+        let y_parity = signature.recovery_id()?.is_y_odd() as u64;
+        Ok(SignedAuthorization {
+            inner: self,
+            y_parity: y_parity.into(),
+            r: signature.r,
+            s: signature.s,
+        })
+    }
+
+    fn rlp_base(&self, rlp: &mut RlpStream) {
+        rlp.append(&self.chain_id);
+        rlp.append(&self.address);
+        rlp.append(&self.nonce);
+    }
+}
+
+/// A signed [`Authorization`], as included in the `authorization_list` of an
+/// [`Eip7702TransactionRequest`]
+#[derive(Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedAuthorization {
+    /// The signed authorization
+    #[serde(flatten)]
+    pub inner: Authorization,
+    /// The parity of the `y` coordinate of the signature's curve point
+    pub y_parity: U64,
+    /// The `r` value of the signature
+    pub r: U256,
+    /// The `s` value of the signature
+    pub s: U256,
+}
+
+impl SignedAuthorization {
+    /// Returns the signature of the authorization
+    pub fn signature(&self) -> Signature {
+        Signature { r: self.r, s: self.s, v: self.y_parity.as_u64() }
+    }
+
+    /// Recovers the address of the account that signed the authorization
+    pub fn recover_authority(&self) -> Result<Address, SignatureError> {
+        self.signature().recover(self.inner.signature_hash())
+    }
+}
+
+impl Encodable for SignedAuthorization {
+    fn rlp_append(&self, rlp: &mut RlpStream) {
+        rlp.begin_list(6);
+        self.inner.rlp_base(rlp);
+        rlp.append(&self.y_parity);
+        rlp.append(&self.r);
+        rlp.append(&self.s);
+    }
+}
+
+impl Decodable for SignedAuthorization {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, DecoderError> {
+        Ok(Self {
+            inner: Authorization {
+                chain_id: rlp.val_at(0)?,
+                address: rlp.val_at(1)?,
+                nonce: rlp.val_at(2)?,
+            },
+            y_parity: rlp.val_at(3)?,
+            r: rlp.val_at(4)?,
+            s: rlp.val_at(5)?,
+        })
+    }
+}
+
+/// Parameters for sending a transaction that sets the code of EOAs
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct Eip7702TransactionRequest {
+    #[serde(flatten)]
+    pub tx: Eip1559TransactionRequest,
+
+    /// The authorizations of the accounts whose code is set by the transaction
+    #[serde(rename = "authorizationList", default)]
+    pub authorization_list: Vec<SignedAuthorization>,
+}
+
+impl Eip7702TransactionRequest {
+    /// Creates an empty transaction request with all fields left empty
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Builder pattern helpers
+
+    /// Sets the `from` field in the transaction to the provided value
+    #[must_use]
+    pub fn from<T: Into<Address>>(mut self, from: T) -> Self {
+        self.tx.from = Some(from.into());
+        self
+    }
+
+    /// Sets the `to` field in the transaction to the provided value
+    #[must_use]
+    pub fn to<T: Into<NameOrAddress>>(mut self, to: T) -> Self {
+        self.tx.to = Some(to.into());
+        self
+    }
+
+    /// Sets the `gas` field in the transaction to the provided value
+    #[must_use]
+    pub fn gas<T: Into<U256>>(mut self, gas: T) -> Self {
+        self.tx.gas = Some(gas.into());
+        self
+    }
+
+    /// Sets the `max_priority_fee_per_gas` field in the transaction to the provided value
+    #[must_use]
+    pub fn max_priority_fee_per_gas<T: Into<U256>>(mut self, max_priority_fee_per_gas: T) -> Self {
+        self.tx.max_priority_fee_per_gas = Some(max_priority_fee_per_gas.into());
+        self
+    }
+
+    /// Sets the `max_fee_per_gas` field in the transaction to the provided value
+    #[must_use]
+    pub fn max_fee_per_gas<T: Into<U256>>(mut self, max_fee_per_gas: T) -> Self {
+        self.tx.max_fee_per_gas = Some(max_fee_per_gas.into());
+        self
+    }
+
+    /// Sets the `value` field in the transaction to the provided value
+    #[must_use]
+    pub fn value<T: Into<U256>>(mut self, value: T) -> Self {
+        self.tx.value = Some(value.into());
+        self
+    }
+
+    /// Sets the `data` field in the transaction to the provided value
+    #[must_use]
+    pub fn data<T: Into<Bytes>>(mut self, data: T) -> Self {
+        self.tx.data = Some(data.into());
+        self
+    }
+
+    /// Sets the `access_list` field in the transaction to the provided value
+    #[must_use]
+    pub fn access_list<T: Into<AccessList>>(mut self, access_list: T) -> Self {
+        self.tx.access_list = access_list.into();
+        self
+    }
+
+    /// Sets the `nonce` field in the transaction to the provided value
+    #[must_use]
+    pub fn nonce<T: Into<U256>>(mut self, nonce: T) -> Self {
+        self.tx.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Sets the `chain_id` field in the transaction to the provided value
+    #[must_use]
+    pub fn chain_id<T: Into<U64>>(mut self, chain_id: T) -> Self {
+        self.tx.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Sets the `authorization_list` field in the transaction to the provided value
+    #[must_use]
+    pub fn authorization_list(mut self, authorization_list: Vec<SignedAuthorization>) -> Self {
+        self.authorization_list = authorization_list;
+        self
+    }
+
+    /// Appends a signed authorization to the `authorization_list` of the transaction
+    #[must_use]
+    pub fn authorization(mut self, authorization: SignedAuthorization) -> Self {
+        self.authorization_list.push(authorization);
+        self
+    }
+
+    /// Gets the unsigned transaction's RLP encoding
+    pub fn rlp(&self) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(NUM_TX_FIELDS);
+        self.rlp_base(&mut rlp);
+        rlp.out().freeze().into()
+    }
+
+    /// Produces the RLP encoding of the transaction with the provided signature
+    pub fn rlp_signed(&self, signature: &Signature) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_unbounded_list();
+        self.rlp_base(&mut rlp);
+
+        // if the chain_id is none we assume mainnet and choose one
+        let chain_id = self.tx.chain_id.unwrap_or_else(U64::one);
+
+        // append the signature
+        let v = normalize_v(signature.v, chain_id);
+        rlp.append(&v);
+        rlp.append(&signature.r);
+        rlp.append(&signature.s);
+        rlp.finalize_unbounded_list();
+        rlp.out().freeze().into()
+    }
+
+    pub(crate) fn rlp_base(&self, rlp: &mut RlpStream) {
+        self.tx.rlp_base(rlp);
+        rlp.append_list(&self.authorization_list);
+    }
+
+    /// Decodes fields of the request starting at the RLP offset passed. Increments the offset for
+    /// each element parsed.
+    #[inline]
+    pub fn decode_base_rlp(rlp: &rlp::Rlp, offset: &mut usize) -> Result<Self, DecoderError> {
+        let tx = Eip1559TransactionRequest::decode_base_rlp(rlp, offset)?;
+        let authorization_list = rlp.list_at(*offset)?;
+        *offset += 1;
+        Ok(Self { tx, authorization_list })
+    }
+
+    /// Decodes the given RLP into a transaction, attempting to decode its signature as well.
+    pub fn decode_signed_rlp(rlp: &rlp::Rlp) -> Result<(Self, Signature), Eip7702RequestError> {
+        let mut offset = 0;
+        let mut txn = Self::decode_base_rlp(rlp, &mut offset)?;
+
+        let v = rlp.val_at(offset)?;
+        offset += 1;
+        let r = rlp.val_at(offset)?;
+        offset += 1;
+        let s = rlp.val_at(offset)?;
+
+        let sig = Signature { r, s, v };
+        txn.tx.from = Some(sig.recover(TypedTransaction::Eip7702(txn.clone()).sighash())?);
+
+        Ok((txn, sig))
+    }
+}
+
+impl Decodable for Eip7702TransactionRequest {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        Self::decode_base_rlp(rlp, &mut 0)
+    }
+}
+
+impl From<Eip1559TransactionRequest> for Eip7702TransactionRequest {
+    fn from(tx: Eip1559TransactionRequest) -> Self {
+        Self { tx, ..Default::default() }
+    }
+}
+
+impl From<&Transaction> for Eip7702TransactionRequest {
+    fn from(tx: &Transaction) -> Eip7702TransactionRequest {
+        Eip7702TransactionRequest {
+            tx: tx.into(),
+            authorization_list: tx.authorization_list.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn authorization() -> SignedAuthorization {
+        Authorization::new(1, Address::repeat_byte(0x42), 7u64)
+            .into_signed(Signature { r: 1u64.into(), s: 2u64.into(), v: 28 })
+            .unwrap()
+    }
+
+    fn set_code_tx() -> Eip7702TransactionRequest {
+        Eip7702TransactionRequest::new()
+            .to(Address::from_str("0x2000000000000000000000000000000000000002").unwrap())
+            .nonce(3)
+            .gas(60_000)
+            .value(0)
+            .max_priority_fee_per_gas(1_000_000_000u64)
+            .max_fee_per_gas(2_000_000_000u64)
+            .chain_id(1)
+            .authorization(authorization())
+    }
+
+    #[test]
+    fn authorization_signature_hash() {
+        let auth = Authorization::new(1, Address::repeat_byte(0x42), 7u64);
+        let mut rlp = RlpStream::new_list(3);
+        rlp.append(&1u64).append(&Address::repeat_byte(0x42)).append(&7u64);
+        let expected = keccak256([&[0x05][..], &rlp.out()].concat());
+        assert_eq!(auth.signature_hash(), H256(expected));
+        assert_eq!(authorization().y_parity, U64::one());
+    }
+
+    #[test]
+    fn serde_eip7702_tx() {
+        let tx: TypedTransaction = set_code_tx().into();
+        let serialized = serde_json::to_value(&tx).unwrap();
+        assert_eq!(serialized["type"], "0x04");
+        assert_eq!(
+            serialized["authorizationList"][0],
+            serde_json::json!({
+                "chainId": "0x1",
+                "address": "0x4242424242424242424242424242424242424242",
+                "nonce": "0x7",
+                "yParity": "0x1",
+                "r": "0x1",
+                "s": "0x2",
+            })
+        );
+
+        let de: TypedTransaction = serde_json::from_value(serialized).unwrap();
+        assert_eq!(
+            de,
+            TypedTransaction::Eip7702(Eip7702TransactionRequest {
+                tx: Eip1559TransactionRequest { chain_id: None, ..set_code_tx().tx },
+                ..set_code_tx()
+            })
+        );
+    }
+
+    #[test]
+    fn rlp_roundtrip() {
+        let signature = Signature { r: 1u64.into(), s: 2u64.into(), v: 0 };
+        let typed: TypedTransaction = set_code_tx().into();
+
+        let signed = typed.rlp_signed(&signature);
+        assert_eq!(signed[0], 0x04);
+        let (decoded, sig) = TypedTransaction::decode_signed(&rlp::Rlp::new(&signed)).unwrap();
+        assert_eq!(sig, signature);
+        let decoded = decoded.as_eip7702_ref().unwrap();
+        assert_eq!(decoded.authorization_list, vec![authorization()]);
+        assert_eq!(decoded.tx.nonce, Some(3u64.into()));
+
+        let unsigned = typed.rlp();
+        let decoded: TypedTransaction = rlp::decode(&unsigned).unwrap();
+        assert_eq!(decoded, typed);
+    }
+}
@@ -211,6 +211,9 @@ mod test {
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
             chain_id: None,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             other: crate::types::OtherFields::default(),
         };
 
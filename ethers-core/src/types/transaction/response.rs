@@ -1,7 +1,7 @@
 //! Transaction types
 use super::{
-    decode_signature, decode_to, eip2718::TypedTransaction, eip2930::AccessList, normalize_v,
-    rlp_opt, rlp_opt_list,
+    decode_signature, decode_to, eip2718::TypedTransaction, eip2930::AccessList,
+    eip7702::SignedAuthorization, normalize_v, rlp_opt, rlp_opt_list,
 };
 use crate::{
     types::{
@@ -101,7 +101,7 @@ pub struct Transaction {
     pub gateway_fee: Option<U256>,
 
     // EIP2718
-    /// Transaction type, Some(2) for EIP-1559 transaction,
+    /// Transaction type, Some(3) for EIP-4844 transaction, Some(2) for EIP-1559 transaction,
     /// Some(1) for AccessList transaction, None for Legacy
     #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
     pub transaction_type: Option<U64>,
@@ -131,6 +131,20 @@ pub struct Transaction {
     #[serde(rename = "chainId", default, skip_serializing_if = "Option::is_none")]
     pub chain_id: Option<U256>,
 
+    // EIP4844
+    /// The maximum fee per unit of blob gas the sender is willing to pay
+    #[serde(rename = "maxFeePerBlobGas", default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+
+    /// The versioned hashes of the blobs carried by the transaction
+    #[serde(rename = "blobVersionedHashes", default, skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+
+    // EIP7702
+    /// The authorizations of the accounts whose code is set by the transaction
+    #[serde(rename = "authorizationList", default, skip_serializing_if = "Option::is_none")]
+    pub authorization_list: Option<Vec<SignedAuthorization>>,
+
     /// Captures unknown fields such as additional fields used by L2s
     #[cfg(not(any(feature = "celo")))]
     #[serde(flatten)]
@@ -194,6 +208,43 @@ impl Transaction {
                 rlp.append(&self.r);
                 rlp.append(&self.s);
             }
+            // EIP-4844 (0x03)
+            Some(x) if x == U64::from(0x3) => {
+                rlp_opt(&mut rlp, &self.chain_id);
+                rlp.append(&self.nonce);
+                rlp_opt(&mut rlp, &self.max_priority_fee_per_gas);
+                rlp_opt(&mut rlp, &self.max_fee_per_gas);
+                rlp.append(&self.gas);
+                rlp_opt(&mut rlp, &self.to);
+                rlp.append(&self.value);
+                rlp.append(&self.input.as_ref());
+                rlp_opt_list(&mut rlp, &self.access_list);
+                rlp_opt(&mut rlp, &self.max_fee_per_blob_gas);
+                rlp.append_list(self.blob_versioned_hashes.as_deref().unwrap_or_default());
+                if let Some(chain_id) = self.chain_id {
+                    rlp.append(&normalize_v(self.v.as_u64(), U64::from(chain_id.as_u64())));
+                }
+                rlp.append(&self.r);
+                rlp.append(&self.s);
+            }
+            // EIP-7702 (0x04)
+            Some(x) if x == U64::from(0x4) => {
+                rlp_opt(&mut rlp, &self.chain_id);
+                rlp.append(&self.nonce);
+                rlp_opt(&mut rlp, &self.max_priority_fee_per_gas);
+                rlp_opt(&mut rlp, &self.max_fee_per_gas);
+                rlp.append(&self.gas);
+                rlp_opt(&mut rlp, &self.to);
+                rlp.append(&self.value);
+                rlp.append(&self.input.as_ref());
+                rlp_opt_list(&mut rlp, &self.access_list);
+                rlp.append_list(self.authorization_list.as_deref().unwrap_or_default());
+                if let Some(chain_id) = self.chain_id {
+                    rlp.append(&normalize_v(self.v.as_u64(), U64::from(chain_id.as_u64())));
+                }
+                rlp.append(&self.r);
+                rlp.append(&self.s);
+            }
             // Optimism Deposited Transaction
             #[cfg(feature = "optimism")]
             Some(x) if x == U64::from(0x7E) => {
@@ -239,6 +290,16 @@ impl Transaction {
                 encoded.extend_from_slice(rlp_bytes.as_ref());
                 encoded.into()
             }
+            Some(x) if x == U64::from(0x3) => {
+                encoded.extend_from_slice(&[0x3]);
+                encoded.extend_from_slice(rlp_bytes.as_ref());
+                encoded.into()
+            }
+            Some(x) if x == U64::from(0x4) => {
+                encoded.extend_from_slice(&[0x4]);
+                encoded.extend_from_slice(rlp_bytes.as_ref());
+                encoded.into()
+            }
             #[cfg(feature = "optimism")]
             Some(x) if x == U64::from(0x7E) => {
                 encoded.extend_from_slice(&[0x7E]);
@@ -296,6 +357,36 @@ impl Transaction {
         Ok(())
     }
 
+    /// Decodes fields of the type 3 transaction response starting at the RLP offset passed.
+    /// Increments the offset for each element parsed.
+    #[inline]
+    fn decode_base_eip4844(
+        &mut self,
+        rlp: &rlp::Rlp,
+        offset: &mut usize,
+    ) -> Result<(), DecoderError> {
+        self.decode_base_eip1559(rlp, offset)?;
+        self.max_fee_per_blob_gas = Some(rlp.val_at(*offset)?);
+        *offset += 1;
+        self.blob_versioned_hashes = Some(rlp.list_at(*offset)?);
+        *offset += 1;
+        Ok(())
+    }
+
+    /// Decodes fields of the type 4 transaction response starting at the RLP offset passed.
+    /// Increments the offset for each element parsed.
+    #[inline]
+    fn decode_base_eip7702(
+        &mut self,
+        rlp: &rlp::Rlp,
+        offset: &mut usize,
+    ) -> Result<(), DecoderError> {
+        self.decode_base_eip1559(rlp, offset)?;
+        self.authorization_list = Some(rlp.list_at(*offset)?);
+        *offset += 1;
+        Ok(())
+    }
+
     /// Decodes fields of the type 1 transaction response based on the RLP offset passed.
     /// Increments the offset for each element parsed.
     fn decode_base_eip2930(
@@ -383,6 +474,28 @@ impl Transaction {
         Ok(())
     }
 
+    /// Decodes a raw signed transaction, as broadcast with `eth_sendRawTransaction`, and recovers
+    /// its sender.
+    ///
+    /// Typed transactions are prefixed with their type byte, while legacy transactions are plain
+    /// RLP lists. Blob transactions may also be wrapped with their blobs, commitments and
+    /// proofs, as found in the mempool. [`Transaction::rlp`] re-encodes the transaction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_core::{types::Transaction, utils::hex};
+    ///
+    /// let raw = hex::decode("02f874018201bb8405f5e10085096a1d45b782520894d696a5c568160bbbf5a1356f8ac56ee81a190588871550f7dca7000080c080a07df2299b0181d6d5b817795a7d2eff5897d0d3914ff5f602e17d5b75d32ec25fa051833973e8a8c222e682d2dcea02ad7bf3ec5bc3a86bfbcdbbaa3b853e52ad08").unwrap();
+    /// let tx = Transaction::decode_rlp(&raw).unwrap();
+    /// assert_eq!(tx.rlp().as_ref(), raw.as_slice());
+    /// ```
+    pub fn decode_rlp(bytes: &[u8]) -> Result<Self, DecoderError> {
+        let mut tx: Self = rlp::decode(bytes)?;
+        tx.recover_from_mut().map_err(|_| DecoderError::Custom("invalid signature"))?;
+        Ok(tx)
+    }
+
     /// Recover the sender of the tx from signature
     pub fn recover_from(&self) -> Result<Address, SignatureError> {
         let signature = Signature { r: self.r, s: self.s, v: self.v.as_u64() };
@@ -427,6 +540,8 @@ impl Decodable for Transaction {
                 (first, data)
             };
 
+            // the hash of typed transactions excludes the envelope
+            txn.hash = H256(keccak256(data));
             let bytes = data.get(1..).ok_or(DecoderError::Custom("no tx body"))?;
             let rest = rlp::Rlp::new(bytes);
             match first {
@@ -448,6 +563,31 @@ impl Decodable for Transaction {
                     txn.r = rest.val_at(offset + 1)?;
                     txn.s = rest.val_at(offset + 2)?;
                 }
+                0x03 => {
+                    // the network form wraps the transaction with its blobs, commitments and
+                    // proofs, which are not part of the signed transaction
+                    let is_wrapped = rest.at(0)?.is_list();
+                    let rest = if is_wrapped { rest.at(0)? } else { rest };
+                    txn.decode_base_eip4844(&rest, &mut offset)?;
+                    txn.transaction_type = Some(3u64.into());
+
+                    let odd_y_parity: bool = rest.val_at(offset)?;
+                    txn.v = (odd_y_parity as u8).into();
+                    txn.r = rest.val_at(offset + 1)?;
+                    txn.s = rest.val_at(offset + 2)?;
+                    if is_wrapped {
+                        txn.hash = txn.hash();
+                    }
+                }
+                0x04 => {
+                    txn.decode_base_eip7702(&rest, &mut offset)?;
+                    txn.transaction_type = Some(4u64.into());
+
+                    let odd_y_parity: bool = rest.val_at(offset)?;
+                    txn.v = (odd_y_parity as u8).into();
+                    txn.r = rest.val_at(offset + 1)?;
+                    txn.s = rest.val_at(offset + 2)?;
+                }
                 #[cfg(feature = "optimism")]
                 0x7E => {
                     txn.decode_base_deposit(&rest, &mut offset)?;
@@ -511,22 +651,31 @@ pub struct TransactionReceipt {
     pub effective_gas_price: Option<U256>,
     /// Deposit nonce for Optimism deposited transactions
     #[cfg(feature = "optimism")]
+    #[serde(
+        rename = "depositNonce",
+        default,
+        deserialize_with = "crate::types::serde_helpers::deserialize_stringified_u64_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub deposit_nonce: Option<u64>,
     /// L1 fee for the transaction
     #[cfg(feature = "optimism")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "l1Fee", default, skip_serializing_if = "Option::is_none")]
     pub l1_fee: Option<U256>,
     /// L1 fee scalar for the transaction
+    ///
+    /// Nodes return the `l1FeeScalar` as a decimal string, e.g. `"0.684"`, so it is not parsed
+    /// into this field but kept in [`TransactionReceipt::other`].
     #[cfg(feature = "optimism")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub l1_fee_scalar: Option<U256>,
     /// L1 gas price for the transaction
     #[cfg(feature = "optimism")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "l1GasPrice", default, skip_serializing_if = "Option::is_none")]
     pub l1_gas_price: Option<U256>,
     /// L1 gas used for the transaction
     #[cfg(feature = "optimism")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "l1GasUsed", default, skip_serializing_if = "Option::is_none")]
     pub l1_gas_used: Option<U256>,
     /// Captures unknown fields such as additional fields used by L2s
     #[cfg(not(feature = "celo"))]
@@ -534,6 +683,81 @@ pub struct TransactionReceipt {
     pub other: crate::types::OtherFields,
 }
 
+#[cfg(not(feature = "celo"))]
+impl TransactionReceipt {
+    /// Returns the fee paid for posting the transaction data to L1 on OP stack chains, i.e. the
+    /// `l1Fee` field.
+    ///
+    /// The total cost of an L2 transaction is the L2 execution cost, i.e. `gas_used *
+    /// effective_gas_price`, plus this fee.
+    pub fn l1_data_fee(&self) -> Option<U256> {
+        #[cfg(feature = "optimism")]
+        if self.l1_fee.is_some() {
+            return self.l1_fee
+        }
+        self.other.get_deserialized("l1Fee")?.ok()
+    }
+
+    /// Returns the part of the gas used that pays for posting the transaction to L1 on Arbitrum,
+    /// i.e. the `gasUsedForL1` field.
+    ///
+    /// Unlike on OP stack chains, this gas is already included in the `gas_used` of the receipt.
+    pub fn gas_used_for_l1(&self) -> Option<U256> {
+        self.other.get_deserialized("gasUsedForL1")?.ok()
+    }
+
+    /// Returns the number of the L1 block the transaction was included in on Arbitrum, i.e. the
+    /// `l1BlockNumber` field
+    pub fn l1_block_number(&self) -> Option<U64> {
+        self.other.get_deserialized("l1BlockNumber")?.ok()
+    }
+}
+
+impl TransactionReceipt {
+    /// Returns the cost of executing the transaction, i.e. `gas_used * effective_gas_price`.
+    ///
+    /// Returns `None` if the receipt lacks either field, e.g. receipts of nodes from before
+    /// London, or if the cost overflows. This does not include the
+    /// [L1 data fee](Self::l1_data_fee) of OP stack chains.
+    pub fn total_cost(&self) -> Option<U256> {
+        self.gas_used?.checked_mul(self.effective_gas_price?)
+    }
+
+    /// Splits the [cost](Self::total_cost) of the transaction into the burned base fee and the
+    /// priority fee paid to the block producer, given the `base_fee_per_gas` of the block the
+    /// transaction was included in.
+    ///
+    /// Returns `None` if the receipt lacks a field, or if the fees overflow.
+    pub fn fee_breakdown(&self, base_fee_per_gas: U256) -> Option<FeeBreakdown> {
+        let gas_used = self.gas_used?;
+        let priority_fee_per_gas = self.effective_gas_price?.saturating_sub(base_fee_per_gas);
+        let fees = FeeBreakdown {
+            base_fee_burned: gas_used.checked_mul(base_fee_per_gas)?,
+            priority_fee_paid: gas_used.checked_mul(priority_fee_per_gas)?,
+        };
+        // makes sure that `FeeBreakdown::total` can't overflow
+        fees.base_fee_burned.checked_add(fees.priority_fee_paid)?;
+        Some(fees)
+    }
+}
+
+/// The cost of an EIP-1559 transaction split into the burned base fee and the priority fee, see
+/// [`TransactionReceipt::fee_breakdown`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FeeBreakdown {
+    /// The base fee burned by the transaction, `gas_used * base_fee_per_gas`
+    pub base_fee_burned: U256,
+    /// The fee paid to the block producer, `gas_used * (effective_gas_price - base_fee_per_gas)`
+    pub priority_fee_paid: U256,
+}
+
+impl FeeBreakdown {
+    /// Returns the total cost of the transaction
+    pub fn total(&self) -> U256 {
+        self.base_fee_burned + self.priority_fee_paid
+    }
+}
+
 impl rlp::Encodable for TransactionReceipt {
     fn rlp_append(&self, s: &mut RlpStream) {
         #[cfg(feature = "optimism")]
@@ -622,6 +846,26 @@ mod tests {
         assert_eq!(expected.rlp_bytes().to_vec(), data);
     }
 
+    #[test]
+    fn decode_op_receipt_fields() {
+        let mut value = serde_json::to_value(TransactionReceipt::default()).unwrap();
+        let fields = value.as_object_mut().unwrap();
+        fields.insert("depositNonce".to_string(), "0x3d3bbf".into());
+        fields.insert("l1Fee".to_string(), "0x1ed7ba97dc".into());
+        fields.insert("l1FeeScalar".to_string(), "0.684".into());
+        fields.insert("l1GasPrice".to_string(), "0x7a5d5f3b1".into());
+        fields.insert("l1GasUsed".to_string(), "0x640".into());
+
+        let receipt: TransactionReceipt = serde_json::from_value(value).unwrap();
+        assert_eq!(receipt.deposit_nonce, Some(4012991));
+        assert_eq!(receipt.l1_fee, Some(U256::from(0x1ed7ba97dcu64)));
+        assert_eq!(receipt.l1_data_fee(), receipt.l1_fee);
+        assert_eq!(receipt.l1_gas_price, Some(U256::from(0x7a5d5f3b1u64)));
+        assert_eq!(receipt.l1_gas_used, Some(U256::from(0x640)));
+        assert_eq!(receipt.l1_fee_scalar, None);
+        assert_eq!(receipt.other.get("l1FeeScalar"), Some(&"0.684".into()));
+    }
+
     #[test]
     fn test_encode_and_decode_deposit_tx() {
         let deposited_tx = Transaction {
@@ -649,6 +893,9 @@ mod tests {
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
             chain_id: None,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             #[cfg(not(feature = "celo"))]
             other: crate::types::OtherFields::default(),
         };
@@ -731,6 +978,54 @@ mod tests {
         assert_eq!(receipt.effective_gas_price.unwrap().as_u64(), 0x3b9aca07);
     }
 
+    #[test]
+    fn receipt_cost_breakdown() {
+        let receipt = TransactionReceipt {
+            gas_used: Some(21_000.into()),
+            effective_gas_price: Some(30_000_000_000u64.into()),
+            ..Default::default()
+        };
+        assert_eq!(receipt.total_cost(), Some(U256::from(630_000_000_000_000u64)));
+
+        let fees = receipt.fee_breakdown(28_000_000_000u64.into()).unwrap();
+        assert_eq!(fees.base_fee_burned, U256::from(588_000_000_000_000u64));
+        assert_eq!(fees.priority_fee_paid, U256::from(42_000_000_000_000u64));
+        assert_eq!(Some(fees.total()), receipt.total_cost());
+
+        let receipt = TransactionReceipt { effective_gas_price: None, ..receipt };
+        assert_eq!(receipt.total_cost(), None);
+        assert_eq!(receipt.fee_breakdown(1.into()), None);
+
+        // bogus values of a misbehaving node don't panic
+        let receipt = TransactionReceipt {
+            gas_used: Some(U256::MAX),
+            effective_gas_price: Some(2.into()),
+            ..Default::default()
+        };
+        assert_eq!(receipt.total_cost(), None);
+        assert_eq!(receipt.fee_breakdown(1.into()), None);
+        assert_eq!(receipt.fee_breakdown(U256::MAX), None);
+    }
+
+    #[test]
+    fn decode_l2_receipt_fields() {
+        let mut value = serde_json::to_value(TransactionReceipt::default()).unwrap();
+        let fields = value.as_object_mut().unwrap();
+        fields.insert("l1Fee".to_string(), "0x1ed7ba97dc".into());
+        fields.insert("l1FeeScalar".to_string(), "0.684".into());
+        fields.insert("gasUsedForL1".to_string(), "0x4f86".into());
+        fields.insert("l1BlockNumber".to_string(), "0x117a9b1".into());
+
+        let receipt: TransactionReceipt = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(receipt.l1_data_fee(), Some(U256::from(0x1ed7ba97dcu64)));
+        assert_eq!(receipt.gas_used_for_l1(), Some(U256::from(0x4f86)));
+        assert_eq!(receipt.l1_block_number(), Some(U64::from(0x117a9b1)));
+        assert_eq!(TransactionReceipt::default().l1_data_fee(), None);
+
+        // unknown fields are preserved
+        assert_eq!(serde_json::to_value(&receipt).unwrap(), value);
+    }
+
     #[test]
     fn decode_london_tx() {
         let tx: Transaction = serde_json::from_value(serde_json::json!({"accessList":[{"address":"0x8ba1f109551bd432803012645ac136ddd64dba72","storageKeys":["0x0000000000000000000000000000000000000000000000000000000000000000","0x0000000000000000000000000000000000000000000000000000000000000042"]}],"blockHash":"0x55ae43d3511e327dc532855510d110676d340aa1bbba369b4b98896d86559586","blockNumber":"0xa3d322","chainId":"0x3","from":"0x541d6a0e9ca9e7a083e41e2e178eef9f22d7492e","gas":"0x6a40","gasPrice":"0x3b9aca07","hash":"0x824384376c5972498c6fcafe71fd8cad1689f64e7d5e270d025a898638c0c34d","input":"0x","maxFeePerGas":"0x3b9aca0e","maxPriorityFeePerGas":"0x3b9aca00","nonce":"0x2","r":"0xf13b5088108f783f4b6048d4be456971118aabfb88be96bb541d734b6c2b20dc","s":"0x13fb7eb25a7d5df42a176cd4c6a086e19163ed7cd8ffba015f939d24f66bc17a","to":"0x8210357f377e901f18e45294e86a2a32215cc3c9","transactionIndex":"0xd","type":"0x2","v":"0x1","value":"0x7b"})).unwrap();
@@ -783,6 +1078,9 @@ mod tests {
             transaction_type: Some(U64::from(0x2)),
             access_list: Some(AccessList::from(vec![])),
             chain_id: Some(U256::from(1)),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             v: U64::from(0x1),
             r: U256::from_str_radix(
                 "0x5616cdaec839ca14d209b59eafb706e623169dc9d0fa58fbf13931cef5b5e3b0",
@@ -827,6 +1125,9 @@ mod tests {
             transaction_type: Some(U64::from(0x2)),
             access_list: None,
             chain_id: Some(U256::from(1)),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             v: U64::from(0x1),
             r: U256::from_str_radix(
                 "0x5616cdaec839ca14d209b59eafb706e623169dc9d0fa58fbf13931cef5b5e3b0",
@@ -870,6 +1171,9 @@ mod tests {
             r: U256::from_str_radix("c81e70f9e49e0d3b854720143e86d172fecc9e76ef8a8666f2fdc017017c5141", 16).unwrap(),
             s: U256::from_str_radix("1dd3410180f6a6ca3e25ad3058789cd0df3321ed76b5b4dbe0a2bb2dc28ae274", 16).unwrap(),
             chain_id: Some(U256::from(1)),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             access_list: None,
             max_fee_per_gas: None,
             max_priority_fee_per_gas: None,
@@ -919,6 +1223,9 @@ mod tests {
             max_priority_fee_per_gas: Some(1500000000.into()),
             max_fee_per_gas: Some(1500000009.into()),
             chain_id: Some(5.into()),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             other: Default::default(),
         };
         assert_eq!(
@@ -966,6 +1273,9 @@ mod tests {
             max_priority_fee_per_gas: Some(1500000000.into()),
             max_fee_per_gas: Some(1500000009.into()),
             chain_id: Some(5.into()),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             other: Default::default(),
         };
 
@@ -1026,6 +1336,9 @@ mod tests {
             r: U256::from_str_radix("c81e70f9e49e0d3b854720143e86d172fecc9e76ef8a8666f2fdc017017c5141", 16).unwrap(),
             s: U256::from_str_radix("1dd3410180f6a6ca3e25ad3058789cd0df3321ed76b5b4dbe0a2bb2dc28ae274", 16).unwrap(),
             chain_id: Some(U256::from(1)),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             access_list: None,
             max_fee_per_gas: None,
             max_priority_fee_per_gas: None,
@@ -1061,6 +1374,9 @@ mod tests {
             r: U256::from_str_radix("c81e70f9e49e0d3b854720143e86d172fecc9e76ef8a8666f2fdc017017c5141", 16).unwrap(),
             s: U256::from_str_radix("1dd3410180f6a6ca3e25ad3058789cd0df3321ed76b5b4dbe0a2bb2dc28ae274", 16).unwrap(),
             chain_id: Some(U256::from(1)),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             access_list: None,
             max_fee_per_gas: None,
             max_priority_fee_per_gas: None,
@@ -1130,6 +1446,9 @@ mod tests {
             max_priority_fee_per_gas: Some(1500000000.into()),
             max_fee_per_gas: Some(1500000009.into()),
             chain_id: Some(5.into()),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
             other: Default::default(),
         };
 
@@ -1308,6 +1627,170 @@ mod tests {
         Transaction::decode(&r).unwrap();
     }
 
+    #[test]
+    fn rlp_eip4844_roundtrip() {
+        let tx = Transaction {
+            from: Address::from_str("c26ad91f4e7a0cad84c4b9315f420ca9217e315d").unwrap(),
+            to: Some(Address::from_str("fdae129ecc2c27d166a3131098bc05d143fa258e").unwrap()),
+            nonce: U256::from(7),
+            gas: U256::from(21000),
+            max_priority_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+            max_fee_per_gas: Some(U256::from(2_000_000_000u64)),
+            max_fee_per_blob_gas: Some(U256::from(1)),
+            blob_versioned_hashes: Some(vec![H256::from_str(
+                "01b0a4cdd5f55589f5c5b4d46c76704bb6ce95c0a8c09f77f197a57808dded28",
+            )
+            .unwrap()]),
+            access_list: Some(AccessList::from(vec![])),
+            chain_id: Some(U256::from(1)),
+            transaction_type: Some(U64::from(3)),
+            v: U64::from(1),
+            r: U256::from(5),
+            s: U256::from(6),
+            ..Default::default()
+        };
+
+        let rlp = tx.rlp();
+        assert_eq!(rlp[0], 0x03);
+
+        let decoded = Transaction::decode(&Rlp::new(&rlp)).unwrap();
+        assert_eq!(decoded.transaction_type, Some(U64::from(3)));
+        assert_eq!(decoded.hash, tx.hash());
+        assert_eq!(decoded.max_fee_per_blob_gas, tx.max_fee_per_blob_gas);
+        assert_eq!(decoded.blob_versioned_hashes, tx.blob_versioned_hashes);
+        assert_eq!(decoded.max_fee_per_gas, tx.max_fee_per_gas);
+        assert_eq!(decoded.to, tx.to);
+        assert_eq!((decoded.v, decoded.r, decoded.s), (tx.v, tx.r, tx.s));
+    }
+
+    #[test]
+    fn rlp_eip7702_roundtrip() {
+        let authorization = SignedAuthorization {
+            inner: super::super::eip7702::Authorization::new(
+                1,
+                Address::from_str("fdae129ecc2c27d166a3131098bc05d143fa258e").unwrap(),
+                3u64,
+            ),
+            y_parity: U64::from(1),
+            r: U256::from(2),
+            s: U256::from(3),
+        };
+        let tx = Transaction {
+            from: Address::from_str("c26ad91f4e7a0cad84c4b9315f420ca9217e315d").unwrap(),
+            to: Some(Address::from_str("c26ad91f4e7a0cad84c4b9315f420ca9217e315d").unwrap()),
+            nonce: U256::from(7),
+            gas: U256::from(60000),
+            max_priority_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+            max_fee_per_gas: Some(U256::from(2_000_000_000u64)),
+            authorization_list: Some(vec![authorization]),
+            access_list: Some(AccessList::from(vec![])),
+            chain_id: Some(U256::from(1)),
+            transaction_type: Some(U64::from(4)),
+            v: U64::from(1),
+            r: U256::from(5),
+            s: U256::from(6),
+            ..Default::default()
+        };
+
+        let rlp = tx.rlp();
+        assert_eq!(rlp[0], 0x04);
+
+        let decoded = Transaction::decode(&Rlp::new(&rlp)).unwrap();
+        assert_eq!(decoded.transaction_type, Some(U64::from(4)));
+        assert_eq!(decoded.hash, tx.hash());
+        assert_eq!(decoded.authorization_list, tx.authorization_list);
+        assert_eq!((decoded.v, decoded.r, decoded.s), (tx.v, tx.r, tx.s));
+
+        let request: TypedTransaction = (&tx).into();
+        assert_eq!(request.as_eip7702_ref().unwrap().authorization_list, vec![authorization]);
+    }
+
+    #[test]
+    fn decode_rlp_roundtrips_signed_transactions() {
+        use k256::ecdsa::SigningKey;
+
+        let key = SigningKey::from_slice(&[1u8; 32]).unwrap();
+        let sender = crate::utils::secret_key_to_address(&key);
+        let base = Transaction {
+            to: Some(Address::from_str("fdae129ecc2c27d166a3131098bc05d143fa258e").unwrap()),
+            nonce: U256::from(7),
+            gas: U256::from(21000),
+            value: U256::from(100),
+            input: Bytes::from(vec![1, 2, 3]),
+            chain_id: Some(U256::from(1)),
+            ..Default::default()
+        };
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::repeat_byte(1),
+            storage_keys: vec![H256::repeat_byte(2)],
+        }]);
+        let txs = [
+            Transaction {
+                transaction_type: Some(U64::zero()),
+                gas_price: Some(U256::from(1_000_000_000u64)),
+                ..base.clone()
+            },
+            Transaction {
+                transaction_type: Some(U64::from(1)),
+                gas_price: Some(U256::from(1_000_000_000u64)),
+                access_list: Some(access_list.clone()),
+                ..base.clone()
+            },
+            Transaction {
+                transaction_type: Some(U64::from(2)),
+                max_priority_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+                max_fee_per_gas: Some(U256::from(2_000_000_000u64)),
+                access_list: Some(access_list.clone()),
+                ..base.clone()
+            },
+            Transaction {
+                transaction_type: Some(U64::from(3)),
+                max_priority_fee_per_gas: Some(U256::from(1_000_000_000u64)),
+                max_fee_per_gas: Some(U256::from(2_000_000_000u64)),
+                max_fee_per_blob_gas: Some(U256::from(1)),
+                blob_versioned_hashes: Some(vec![H256::repeat_byte(1)]),
+                access_list: Some(access_list),
+                ..base
+            },
+        ];
+
+        for mut tx in txs {
+            let request: TypedTransaction = (&tx).into();
+            let sighash = request.sighash();
+            let (signature, recovery_id) = key.sign_prehash_recoverable(sighash.as_ref()).unwrap();
+            let v = recovery_id.to_byte() as u64;
+            // EIP-155: v = {0, 1} + CHAIN_ID * 2 + 35
+            tx.v = if tx.transaction_type == Some(U64::zero()) { v + 37 } else { v }.into();
+            tx.r = U256::from_big_endian(&signature.r().to_bytes());
+            tx.s = U256::from_big_endian(&signature.s().to_bytes());
+
+            let raw = tx.rlp();
+            let decoded = Transaction::decode_rlp(&raw).unwrap();
+            assert_eq!(decoded.rlp(), raw, "{:?}", tx.transaction_type);
+            assert_eq!(decoded.hash, tx.hash());
+            assert_eq!(decoded.from, sender);
+            // legacy transactions are decoded without a type
+            assert_eq!(decoded.transaction_type.unwrap_or_default(), tx.transaction_type.unwrap());
+
+            if tx.transaction_type == Some(U64::from(3)) {
+                // blob transaction with blobs, commitments and proofs
+                let mut wrapped = RlpStream::new_list(4);
+                wrapped.append_raw(&raw[1..], 1);
+                wrapped.begin_list(0);
+                wrapped.begin_list(0);
+                wrapped.begin_list(0);
+                let wrapped = [&[0x03], wrapped.as_raw()].concat();
+                let decoded = Transaction::decode_rlp(&wrapped).unwrap();
+                assert_eq!(decoded.rlp(), raw);
+                assert_eq!(decoded.hash, tx.hash());
+                assert_eq!(decoded.from, sender);
+            }
+        }
+
+        // unsigned transactions are rejected
+        assert!(Transaction::decode_rlp(&Transaction::default().rlp()).is_err());
+    }
+
     #[test]
     fn test_rlp_decoding_create_roundtrip() {
         let tx = Transaction {
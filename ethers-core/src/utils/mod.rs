@@ -36,7 +36,7 @@ pub use rlp;
 /// Re-export hex
 pub use hex;
 
-use crate::types::{Address, Bytes, ParseI256Error, H256, I256, U256};
+use crate::types::{Address, Bytes, FeeHistory, ParseI256Error, H256, I256, U256};
 use elliptic_curve::sec1::ToEncodedPoint;
 use ethabi::ethereum_types::FromDecStrErr;
 use k256::{
@@ -79,6 +79,10 @@ pub enum ConversionError {
     InvalidAddressChecksum,
     #[error(transparent)]
     FromHexError(<Address as std::str::FromStr>::Err),
+    #[error("Invalid amount: {0:?}")]
+    InvalidAmount(String),
+    #[error("Too many decimals, expected at most {0}")]
+    TooManyDecimals(u8),
 }
 
 /// 1 Ether = 1e18 Wei == 0x0de0b6b3a7640000 Wei
@@ -97,6 +101,11 @@ pub const EIP1559_FEE_ESTIMATION_PRIORITY_FEE_TRIGGER: u64 = 100_000_000_000;
 /// under it.
 pub const EIP1559_FEE_ESTIMATION_THRESHOLD_MAX_CHANGE: i64 = 200;
 
+/// The minimum price of a unit of blob gas, see [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844).
+pub const EIP4844_MIN_BLOB_GASPRICE: u64 = 1;
+/// Controls the maximum rate of change of the blob gas price.
+pub const EIP4844_BLOB_GASPRICE_UPDATE_FRACTION: u64 = 3_338_477;
+
 /// This enum holds the numeric types that a possible to be returned by `parse_units` and
 /// that are taken by `format_units`.
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
@@ -295,6 +304,78 @@ where
     }
 }
 
+/// Multiplies the provided decimal amount with 10^{decimals}, e.g. to convert a token amount to
+/// its base unit.
+///
+/// Unlike [`parse_units`], this rejects negative amounts and amounts with more fractional digits
+/// than `decimals` instead of truncating them.
+///
+/// ```
+/// use ethers_core::{types::U256, utils::parse_decimal_units};
+///
+/// assert_eq!(parse_decimal_units("1.5", 6).unwrap(), U256::from(1_500_000));
+/// assert_eq!(parse_decimal_units("0.000001", 6).unwrap(), U256::one());
+/// assert!(parse_decimal_units("0.0000001", 6).is_err());
+/// assert!(parse_decimal_units("-1", 6).is_err());
+/// ```
+pub fn parse_decimal_units(amount: &str, decimals: u8) -> Result<U256, ConversionError> {
+    let amount = amount.replace('_', "");
+    let (integer, fraction) = amount.split_once('.').unwrap_or((&amount, ""));
+    if (integer.is_empty() && fraction.is_empty()) ||
+        !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(ConversionError::InvalidAmount(amount))
+    }
+    if fraction.len() > decimals as usize {
+        return Err(ConversionError::TooManyDecimals(decimals))
+    }
+
+    // the strings only contain digits, so parsing them can only fail if they overflow
+    let parse = |digits: &str| {
+        if digits.is_empty() {
+            Ok(U256::zero())
+        } else {
+            U256::from_dec_str(digits).map_err(|_| ConversionError::ParseOverflow)
+        }
+    };
+    // multiplies `value` with 10^exp, zero never overflows even if 10^exp does
+    let scale = |value: U256, exp: usize| {
+        if value.is_zero() {
+            return Ok(value)
+        }
+        U256::from(10)
+            .checked_pow(U256::from(exp))
+            .and_then(|factor| value.checked_mul(factor))
+            .ok_or(ConversionError::ParseOverflow)
+    };
+    let fraction = scale(parse(fraction)?, decimals as usize - fraction.len())?;
+    scale(parse(integer)?, decimals as usize)?
+        .checked_add(fraction)
+        .ok_or(ConversionError::ParseOverflow)
+}
+
+/// Divides the provided amount with 10^{decimals} and formats it as a decimal number without
+/// trailing zeros, e.g. to display a token amount given in its base unit.
+///
+/// ```
+/// use ethers_core::{types::U256, utils::format_decimal_units};
+///
+/// assert_eq!(format_decimal_units(U256::from(1_500_000), 6), "1.5");
+/// assert_eq!(format_decimal_units(U256::from(1), 6), "0.000001");
+/// assert_eq!(format_decimal_units(U256::from(2_000_000), 6), "2");
+/// ```
+pub fn format_decimal_units(amount: U256, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = format!("{:0>width$}", amount.to_string(), width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{integer}.{fraction}")
+    }
+}
+
 /// The address for an Ethereum contract is deterministically computed from the
 /// address of its creator (sender) and how many transactions the creator has
 /// sent (nonce). The sender and nonce are RLP encoded and then hashed with Keccak-256.
@@ -436,6 +517,11 @@ pub fn secret_key_to_address(secret_key: &SigningKey) -> Address {
 /// [EIP-155 chain ID]: https://eips.ethereum.org/EIPS/eip-155
 /// [EIP-1191]: https://eips.ethereum.org/EIPS/eip-1191
 pub fn to_checksum(addr: &Address, chain_id: Option<u8>) -> String {
+    to_checksum_u64(addr, chain_id.map(Into::into))
+}
+
+/// Like [`to_checksum`], but accepts chain IDs that don't fit into a `u8`.
+pub fn to_checksum_u64(addr: &Address, chain_id: Option<u64>) -> String {
     let prefixed_addr = match chain_id {
         Some(chain_id) => format!("{chain_id}0x{addr:x}"),
         None => format!("{addr:x}"),
@@ -461,9 +547,17 @@ pub fn to_checksum(addr: &Address, chain_id: Option<u8>) -> String {
 /// Returns `Ok(address)` if the checksummed address is valid, `Err()` otherwise.
 /// If `chain_id` is `None`, falls back to [EIP-55](https://eips.ethereum.org/EIPS/eip-55) address checksum method
 pub fn parse_checksummed(addr: &str, chain_id: Option<u8>) -> Result<Address, ConversionError> {
+    parse_checksummed_u64(addr, chain_id.map(Into::into))
+}
+
+/// Like [`parse_checksummed`], but accepts chain IDs that don't fit into a `u8`.
+pub fn parse_checksummed_u64(
+    addr: &str,
+    chain_id: Option<u64>,
+) -> Result<Address, ConversionError> {
     let addr = addr.strip_prefix("0x").unwrap_or(addr);
     let address: Address = addr.parse().map_err(ConversionError::FromHexError)?;
-    let checksum_addr = to_checksum(&address, chain_id);
+    let checksum_addr = to_checksum_u64(&address, chain_id);
 
     if checksum_addr.strip_prefix("0x").unwrap_or(&checksum_addr) == addr {
         Ok(address)
@@ -472,6 +566,45 @@ pub fn parse_checksummed(addr: &str, chain_id: Option<u8>) -> Result<Address, Co
     }
 }
 
+/// Checksum encoding and parsing of addresses as methods, see [`to_checksum_u64`] and
+/// [`parse_checksummed_u64`].
+///
+/// # Example
+///
+/// ```
+/// use ethers_core::{types::Address, utils::ChecksumAddress};
+///
+/// let addr: Address = "0x27b1fdb04752bbc536007a920d24acb045561c26".parse().unwrap();
+/// // EIP-1191 checksum on RSK mainnet
+/// let checksummed = addr.to_checksum(Some(30));
+/// assert_eq!(checksummed, "0x27b1FdB04752BBc536007A920D24ACB045561c26");
+/// assert_eq!(Address::from_checksummed(&checksummed, Some(30)).unwrap(), addr);
+/// // the EIP-55 checksum differs
+/// assert!(Address::from_checksummed(&checksummed, None).is_err());
+/// ```
+pub trait ChecksumAddress: Sized {
+    /// Encodes the address to its [EIP-55] checksum, or to its [EIP-1191] checksum if a
+    /// `chain_id` is given.
+    ///
+    /// [EIP-55]: https://eips.ethereum.org/EIPS/eip-55
+    /// [EIP-1191]: https://eips.ethereum.org/EIPS/eip-1191
+    fn to_checksum(&self, chain_id: Option<u64>) -> String;
+
+    /// Parses a checksummed address, returning
+    /// [`ConversionError::InvalidAddressChecksum`] if the checksum does not match.
+    fn from_checksummed(addr: &str, chain_id: Option<u64>) -> Result<Self, ConversionError>;
+}
+
+impl ChecksumAddress for Address {
+    fn to_checksum(&self, chain_id: Option<u64>) -> String {
+        to_checksum_u64(self, chain_id)
+    }
+
+    fn from_checksummed(addr: &str, chain_id: Option<u64>) -> Result<Self, ConversionError> {
+        parse_checksummed_u64(addr, chain_id)
+    }
+}
+
 /// Returns a bytes32 string representation of text. If the length of text exceeds 32 bytes,
 /// an error is returned.
 pub fn format_bytes32_string(text: &str) -> Result<[u8; 32], ConversionError> {
@@ -516,6 +649,40 @@ pub fn eip1559_default_estimator(base_fee_per_gas: U256, rewards: Vec<Vec<U256>>
     (max_fee_per_gas, max_priority_fee_per_gas)
 }
 
+/// The [`eip1559_default_estimator`] as a strategy of estimating the fees from the [`FeeHistory`],
+/// using the base fee of the pending block, i.e. the last base fee of the history.
+pub fn eip1559_fee_history_estimator(fee_history: &FeeHistory) -> (U256, U256) {
+    let base_fee_per_gas = fee_history.base_fee_per_gas.last().copied().unwrap_or_default();
+    eip1559_default_estimator(base_fee_per_gas, fee_history.reward.clone())
+}
+
+/// Calculates the price of a unit of blob gas from a block's `excess_blob_gas`, as specified by
+/// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844#gas-accounting).
+///
+/// Returns `None` if the price overflows.
+pub fn calc_blob_gasprice(excess_blob_gas: U256) -> Option<U256> {
+    fake_exponential(
+        U256::from(EIP4844_MIN_BLOB_GASPRICE),
+        excess_blob_gas,
+        U256::from(EIP4844_BLOB_GASPRICE_UPDATE_FRACTION),
+    )
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` using a Taylor expansion.
+///
+/// Returns `None` on overflow.
+fn fake_exponential(factor: U256, numerator: U256, denominator: U256) -> Option<U256> {
+    let mut i = U256::one();
+    let mut output = U256::zero();
+    let mut numerator_accum = factor.checked_mul(denominator)?;
+    while !numerator_accum.is_zero() {
+        output = output.checked_add(numerator_accum)?;
+        numerator_accum = numerator_accum.checked_mul(numerator)? / denominator.checked_mul(i)?;
+        i += U256::one();
+    }
+    Some(output / denominator)
+}
+
 /// Converts a Bytes value into a H256, accepting inputs that are less than 32 bytes long. These
 /// inputs will be left padded with zeros.
 pub fn from_bytes_to_h256<'de, D>(bytes: Bytes) -> Result<H256, D::Error>
@@ -559,7 +726,7 @@ where
 
 fn estimate_priority_fee(rewards: Vec<Vec<U256>>) -> U256 {
     let mut rewards: Vec<U256> =
-        rewards.iter().map(|r| r[0]).filter(|r| *r > U256::zero()).collect();
+        rewards.iter().filter_map(|r| r.first().copied()).filter(|r| *r > U256::zero()).collect();
     if rewards.is_empty() {
         return U256::zero()
     }
@@ -803,6 +970,57 @@ mod tests {
         assert_eq!(n.to_string(), "10550000000000000000000000000");
     }
 
+    #[test]
+    fn test_parse_decimal_units() {
+        assert_eq!(parse_decimal_units("1", 6).unwrap(), U256::from(1_000_000));
+        assert_eq!(parse_decimal_units("1.", 6).unwrap(), U256::from(1_000_000));
+        assert_eq!(parse_decimal_units(".25", 2).unwrap(), U256::from(25));
+        assert_eq!(parse_decimal_units("1_000.5", 1).unwrap(), U256::from(10_005));
+        assert_eq!(parse_decimal_units("330", 0).unwrap(), U256::from(330));
+        assert_eq!(parse_decimal_units("1.5", 18).unwrap(), parse_ether("1.5").unwrap());
+        assert_eq!(parse_decimal_units(&U256::MAX.to_string(), 0).unwrap(), U256::MAX, "max value");
+
+        assert!(matches!(
+            parse_decimal_units("1.1234567", 6),
+            Err(ConversionError::TooManyDecimals(6))
+        ));
+        assert!(matches!(parse_decimal_units("1.5", 0), Err(ConversionError::TooManyDecimals(0))));
+        for invalid in ["", ".", "-1", "+1", "1e6", "1.2.3", " 1", "0x10"] {
+            assert!(
+                matches!(parse_decimal_units(invalid, 6), Err(ConversionError::InvalidAmount(_))),
+                "{invalid}"
+            );
+        }
+        assert!(matches!(
+            parse_decimal_units(&U256::MAX.to_string(), 1),
+            Err(ConversionError::ParseOverflow)
+        ));
+        assert!(matches!(parse_decimal_units("1", 78), Err(ConversionError::ParseOverflow)));
+        assert_eq!(parse_decimal_units("0", 78).unwrap(), U256::zero());
+        assert_eq!(parse_decimal_units("0.00", 255).unwrap(), U256::zero());
+        assert_eq!(
+            parse_decimal_units("0.1", 78).unwrap(),
+            U256::exp10(77),
+            "largest power of ten"
+        );
+    }
+
+    #[test]
+    fn test_format_decimal_units() {
+        assert_eq!(format_decimal_units(U256::zero(), 6), "0");
+        assert_eq!(format_decimal_units(U256::from(1), 6), "0.000001");
+        assert_eq!(format_decimal_units(U256::from(1_234_500), 6), "1.2345");
+        assert_eq!(format_decimal_units(U256::from(10_000_000), 6), "10");
+        assert_eq!(format_decimal_units(U256::from(330), 0), "330");
+        assert_eq!(format_decimal_units(WEI_IN_ETHER, 18), "1");
+        assert_eq!(format_decimal_units(U256::MAX, 78), format!("0.{}", U256::MAX));
+
+        for amount in ["0.000001", "1.2345", "10", "123456789.987654"] {
+            let parsed = parse_decimal_units(amount, 6).unwrap();
+            assert_eq!(format_decimal_units(parsed, 6), amount);
+        }
+    }
+
     #[test]
     fn test_parse_units() {
         let gwei: U256 = parse_units(1.5, 9).unwrap().into();
@@ -1042,6 +1260,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn checksum_address_methods() {
+        let addr: Address = "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap();
+        assert_eq!(
+            ChecksumAddress::to_checksum(&addr, None),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+        // rsk testnet
+        let checksummed = ChecksumAddress::to_checksum(&addr, Some(31));
+        assert_eq!(checksummed, "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd");
+        assert_eq!(Address::from_checksummed(&checksummed, Some(31)).unwrap(), addr);
+        assert!(matches!(
+            Address::from_checksummed(&checksummed, Some(30)),
+            Err(ConversionError::InvalidAddressChecksum)
+        ));
+
+        // chain ids beyond u8 are supported
+        let checksummed = ChecksumAddress::to_checksum(&addr, Some(1337));
+        assert_eq!(checksummed, to_checksum_u64(&addr, Some(1337)));
+        assert_eq!(Address::from_checksummed(&checksummed, Some(1337)).unwrap(), addr);
+        assert_eq!(parse_checksummed_u64(&checksummed, Some(1337)).unwrap(), addr);
+        assert_eq!(to_checksum_u64(&addr, Some(31)), to_checksum(&addr, Some(31)));
+    }
+
     #[test]
     fn contract_address() {
         // http://ethereum.stackexchange.com/questions/760/how-is-the-address-of-an-ethereum-contract-computed
@@ -1198,6 +1440,26 @@ mod tests {
         let overflow = U256::from(u32::MAX) + 1;
         let rewards_overflow: Vec<Vec<U256>> = vec![vec![overflow], vec![overflow]];
         assert_eq!(estimate_priority_fee(rewards_overflow), overflow);
+
+        // Blocks without rewards are skipped.
+        let rewards: Vec<Vec<U256>> = vec![vec![], vec![5u64.into()], vec![]];
+        assert_eq!(estimate_priority_fee(rewards), 5u64.into());
+    }
+
+    #[test]
+    fn test_eip1559_fee_history_estimator() {
+        let base_fee_per_gas = U256::from(EIP1559_FEE_ESTIMATION_PRIORITY_FEE_TRIGGER) + 1;
+        let fee_history = FeeHistory {
+            // the base fee of the pending block is used
+            base_fee_per_gas: vec![1u64.into(), base_fee_per_gas],
+            gas_used_ratio: vec![0.5],
+            oldest_block: 1u64.into(),
+            reward: vec![vec![4_000_000_000u64.into()]],
+        };
+        assert_eq!(
+            eip1559_fee_history_estimator(&fee_history),
+            (base_fee_surged(base_fee_per_gas), 4_000_000_000u64.into())
+        );
     }
 
     #[test]
@@ -1229,6 +1491,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn blob_gasprice() {
+        for (excess_blob_gas, blob_gasprice) in
+            [(0u64, 1u64), (2_314_057, 1), (2_314_058, 2), (10 * 1024 * 1024, 23)]
+        {
+            assert_eq!(calc_blob_gasprice(excess_blob_gas.into()), Some(blob_gasprice.into()));
+        }
+        assert_eq!(calc_blob_gasprice(U256::from(u64::MAX)), None);
+        assert_eq!(calc_blob_gasprice(U256::MAX), None);
+    }
+
     // Only tests for correctness, no edge cases. Uses examples from https://docs.ethers.org/v5/api/utils/address/#utils-computeAddress
     #[test]
     fn test_public_key_to_address() {
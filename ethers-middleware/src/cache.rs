@@ -0,0 +1,315 @@
+use async_trait::async_trait;
+use ethers_core::types::{
+    Address, BlockId, BlockNumber, Bytes, NameOrAddress, TransactionReceipt, TxHash, U256,
+};
+use ethers_providers::{Middleware, MiddlewareError};
+use instant::{Duration, Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::Mutex,
+};
+use thiserror::Error;
+
+/// The default maximum number of entries of each cache
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// The default number of blocks a receipt must be buried under before it is cached
+const DEFAULT_RECEIPT_CONFIRMATIONS: u64 = 12;
+
+/// The prefix of the code of an account that delegated its code via EIP-7702
+const DELEGATION_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// The requests whose responses can be cached by the [`CacheMiddleware`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CachedMethod {
+    /// `eth_chainId`, cached forever
+    ChainId,
+    /// `eth_getCode` of an address at the latest block, cached by address once the address has
+    /// code that is not an EIP-7702 delegation
+    Code,
+    /// `eth_getTransactionReceipt`, cached by transaction hash once the transaction has enough
+    /// confirmations
+    Receipt,
+}
+
+/// Middleware that caches the responses of requests that do not change, to save requests to the
+/// node.
+///
+/// By default the chain id, the code of contracts and the receipts of transactions with at least
+/// 12 confirmations are cached. Receipts are only cached once their block is unlikely to be
+/// reorged, and the code of an address is only cached once it is not empty, since a contract may
+/// still be deployed to it. The code of accounts that delegated to a contract via EIP-7702 is
+/// never cached, since the delegation can be changed at any time.
+///
+/// The code and receipts are kept in least recently used caches with a maximum number of
+/// entries, and optionally expire after a time to live.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_middleware::cache::{CacheMiddleware, CachedMethod};
+/// use ethers_providers::{Http, Middleware, Provider};
+/// use std::{convert::TryFrom, time::Duration};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let provider = CacheMiddleware::new(provider)
+///     .methods([CachedMethod::ChainId, CachedMethod::Receipt])
+///     .receipt_confirmations(64)
+///     .ttl(Duration::from_secs(3600));
+///
+/// // only the first call is sent to the node
+/// let chain_id = provider.get_chainid().await?;
+/// assert_eq!(chain_id, provider.get_chainid().await?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CacheMiddleware<M> {
+    inner: M,
+    methods: HashSet<CachedMethod>,
+    receipt_confirmations: u64,
+    chain_id: Mutex<Option<U256>>,
+    code: Mutex<LruCache<Address, Bytes>>,
+    receipts: Mutex<LruCache<TxHash, TransactionReceipt>>,
+}
+
+impl<M> CacheMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Instantiates the cache middleware, caching all [`CachedMethod`]s
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            methods: [CachedMethod::ChainId, CachedMethod::Code, CachedMethod::Receipt].into(),
+            receipt_confirmations: DEFAULT_RECEIPT_CONFIRMATIONS,
+            chain_id: Mutex::new(None),
+            code: Mutex::new(LruCache::new(DEFAULT_CAPACITY, None)),
+            receipts: Mutex::new(LruCache::new(DEFAULT_CAPACITY, None)),
+        }
+    }
+
+    /// Sets the requests whose responses are cached
+    #[must_use]
+    pub fn methods(mut self, methods: impl IntoIterator<Item = CachedMethod>) -> Self {
+        self.methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Sets the maximum number of entries of the code and the receipt cache, the least recently
+    /// used entries are evicted first
+    #[must_use]
+    pub fn capacity(self, capacity: usize) -> Self {
+        self.code.lock().unwrap().capacity = capacity;
+        self.receipts.lock().unwrap().capacity = capacity;
+        self
+    }
+
+    /// Sets the time after which cached code and receipts expire
+    #[must_use]
+    pub fn ttl(self, ttl: Duration) -> Self {
+        self.code.lock().unwrap().ttl = Some(ttl);
+        self.receipts.lock().unwrap().ttl = Some(ttl);
+        self
+    }
+
+    /// Sets the number of blocks a receipt's block must be buried under before it is cached
+    #[must_use]
+    pub fn receipt_confirmations(mut self, confirmations: u64) -> Self {
+        self.receipt_confirmations = confirmations;
+        self
+    }
+
+    /// Removes all cached responses
+    pub fn clear_cache(&self) {
+        *self.chain_id.lock().unwrap() = None;
+        self.code.lock().unwrap().clear();
+        self.receipts.lock().unwrap().clear();
+    }
+
+    fn is_cached(&self, method: CachedMethod) -> bool {
+        self.methods.contains(&method)
+    }
+}
+
+#[derive(Error, Debug)]
+/// Thrown when an error happens at the cache middleware
+pub enum CacheMiddlewareError<M: Middleware> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> MiddlewareError for CacheMiddlewareError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        CacheMiddlewareError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            CacheMiddlewareError::MiddlewareError(e) => Some(e),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> Middleware for CacheMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = CacheMiddlewareError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn get_chainid(&self) -> Result<U256, Self::Error> {
+        if !self.is_cached(CachedMethod::ChainId) {
+            return self.inner.get_chainid().await.map_err(MiddlewareError::from_err)
+        }
+        if let Some(chain_id) = *self.chain_id.lock().unwrap() {
+            return Ok(chain_id)
+        }
+        let chain_id = self.inner.get_chainid().await.map_err(MiddlewareError::from_err)?;
+        *self.chain_id.lock().unwrap() = Some(chain_id);
+        Ok(chain_id)
+    }
+
+    async fn get_code<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        at: T,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        let at = at.into();
+        let address = match (&at, block) {
+            (
+                NameOrAddress::Address(address),
+                None | Some(BlockId::Number(BlockNumber::Latest)),
+            ) if self.is_cached(CachedMethod::Code) => *address,
+            _ => return self.inner.get_code(at, block).await.map_err(MiddlewareError::from_err),
+        };
+
+        if let Some(code) = self.code.lock().unwrap().get(&address) {
+            return Ok(code)
+        }
+        let code = self.inner.get_code(at, block).await.map_err(MiddlewareError::from_err)?;
+        if !code.is_empty() && !code.starts_with(&DELEGATION_PREFIX) {
+            self.code.lock().unwrap().insert(address, code.clone());
+        }
+        Ok(code)
+    }
+
+    async fn get_transaction_receipt<T: Send + Sync + Into<TxHash>>(
+        &self,
+        transaction_hash: T,
+    ) -> Result<Option<TransactionReceipt>, Self::Error> {
+        let hash = transaction_hash.into();
+        if !self.is_cached(CachedMethod::Receipt) {
+            return self.inner.get_transaction_receipt(hash).await.map_err(MiddlewareError::from_err)
+        }
+
+        if let Some(receipt) = self.receipts.lock().unwrap().get(&hash) {
+            return Ok(Some(receipt))
+        }
+        let receipt =
+            self.inner.get_transaction_receipt(hash).await.map_err(MiddlewareError::from_err)?;
+        if let Some(block) = receipt.as_ref().and_then(|receipt| receipt.block_number) {
+            // only cache receipts that are unlikely to be reorged
+            let tip = self.inner.get_block_number().await.map_err(MiddlewareError::from_err)?;
+            if tip.saturating_sub(block).as_u64() >= self.receipt_confirmations {
+                self.receipts.lock().unwrap().insert(hash, receipt.clone().unwrap());
+            }
+        }
+        Ok(receipt)
+    }
+}
+
+/// A cache evicting its least recently used entry when it is full
+#[derive(Debug)]
+struct LruCache<K, V> {
+    capacity: usize,
+    ttl: Option<Duration>,
+    /// Incremented on every access, to order the entries by their last use
+    tick: u64,
+    entries: HashMap<K, LruEntry<V>>,
+}
+
+#[derive(Debug)]
+struct LruEntry<V> {
+    value: V,
+    inserted: Instant,
+    last_used: u64,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> LruCache<K, V> {
+    fn new(capacity: usize, ttl: Option<Duration>) -> Self {
+        Self { capacity, ttl, tick: 0, entries: HashMap::new() }
+    }
+
+    /// Returns the value of `key` if it is cached and not expired
+    fn get(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.get_mut(key)?;
+        if self.ttl.map_or(false, |ttl| entry.inserted.elapsed() >= ttl) {
+            self.entries.remove(key);
+            return None
+        }
+        self.tick += 1;
+        entry.last_used = self.tick;
+        Some(entry.value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return
+        }
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let lru = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(lru) = lru {
+                self.entries.remove(&lru);
+            }
+        }
+        self.tick += 1;
+        self.entries
+            .insert(key, LruEntry { value, inserted: Instant::now(), last_used: self.tick });
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(2, None);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        assert_eq!(cache.get(&1), Some("a"));
+        cache.insert(3, "c");
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some("a"));
+        assert_eq!(cache.get(&3), Some("c"));
+    }
+
+    #[test]
+    fn expires_entries() {
+        let mut cache = LruCache::new(2, Some(Duration::ZERO));
+        cache.insert(1, "a");
+        assert_eq!(cache.get(&1), None);
+        assert!(cache.entries.is_empty());
+    }
+}
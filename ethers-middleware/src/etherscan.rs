@@ -0,0 +1,75 @@
+use ethers_contract::Contract;
+use ethers_core::types::{Address, H256};
+use ethers_etherscan::{errors::EtherscanError, Client};
+use ethers_providers::Middleware;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// The storage slot of the implementation address of an [EIP-1967](https://eips.ethereum.org/EIPS/eip-1967)
+/// proxy, `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`
+pub const EIP1967_IMPLEMENTATION_SLOT: H256 = H256([
+    0x36, 0x08, 0x94, 0xa1, 0x3b, 0xa1, 0xa3, 0x21, 0x06, 0x67, 0xc8, 0x28, 0x49, 0x2d, 0xb9, 0x8d,
+    0xca, 0x3e, 0x20, 0x76, 0xcc, 0x37, 0x35, 0xa9, 0x20, 0xa3, 0xca, 0x50, 0x5d, 0x38, 0x2b, 0xbc,
+]);
+
+#[derive(Error, Debug)]
+/// Thrown when a contract could not be loaded from Etherscan
+pub enum EtherscanContractError<M: Middleware> {
+    /// Thrown when the ABI could not be fetched from Etherscan
+    #[error(transparent)]
+    EtherscanError(#[from] EtherscanError),
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+}
+
+/// Returns the implementation address of `proxy` if it is an
+/// [EIP-1967](https://eips.ethereum.org/EIPS/eip-1967) proxy, i.e. if its implementation slot is
+/// set, or `None` otherwise.
+pub async fn eip1967_implementation<M: Middleware>(
+    client: &M,
+    proxy: Address,
+) -> Result<Option<Address>, M::Error> {
+    let slot = client.get_storage_at(proxy, EIP1967_IMPLEMENTATION_SLOT, None).await?;
+    let implementation = Address::from(slot);
+    Ok((!implementation.is_zero()).then_some(implementation))
+}
+
+/// Fetches the verified ABI of the contract at `address` from Etherscan and returns a [`Contract`]
+/// for it.
+///
+/// If `address` is an [EIP-1967](https://eips.ethereum.org/EIPS/eip-1967) proxy, the ABI of its
+/// current implementation is fetched instead, and the returned contract calls the implementation
+/// through the proxy.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_core::types::{Address, Chain, U256};
+/// use ethers_etherscan::Client;
+/// use ethers_middleware::etherscan::etherscan_contract;
+/// use ethers_providers::{Http, Provider};
+/// use std::sync::Arc;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Arc::new(Provider::<Http>::try_from("http://localhost:8545")?);
+/// let etherscan = Client::new_from_env(Chain::Mainnet)?;
+///
+/// let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse()?;
+/// let contract = etherscan_contract(&etherscan, usdc, provider).await?;
+/// let owner: Address = "0x0000000000000000000000000000000000000001".parse()?;
+/// let balance = contract.method::<_, U256>("balanceOf", owner)?.call().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn etherscan_contract<M: Middleware>(
+    etherscan: &Client,
+    address: Address,
+    client: Arc<M>,
+) -> Result<Contract<M>, EtherscanContractError<M>> {
+    let implementation = eip1967_implementation(client.as_ref(), address)
+        .await
+        .map_err(EtherscanContractError::MiddlewareError)?;
+    let abi = etherscan.contract_abi(implementation.unwrap_or(address)).await?;
+    Ok(Contract::new(address, abi, client))
+}
@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+use ethers_core::{
+    types::{transaction::eip2718::TypedTransaction, BlockId, U256},
+    utils::format_ether,
+};
+use ethers_providers::Middleware;
+use std::{error::Error, fmt::Debug};
+use thiserror::Error;
+
+/// A feed of the price of the native token of a chain in a fiat currency, e.g. the price of one
+/// ether in USD.
+///
+/// This keeps the [`FiatGasEstimator`] independent of any price API, implement it for the API of
+/// your choice.
+///
+/// # Example
+///
+/// ```
+/// use async_trait::async_trait;
+/// use ethers_middleware::fiat::PriceOracle;
+/// use std::error::Error;
+///
+/// #[derive(Debug)]
+/// struct FixedPrice(f64);
+///
+/// #[async_trait]
+/// impl PriceOracle for FixedPrice {
+///     async fn native_price(&self) -> Result<f64, Box<dyn Error + Send + Sync>> {
+///         Ok(self.0)
+///     }
+/// }
+/// ```
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[auto_impl(&, Box, Arc)]
+pub trait PriceOracle: Send + Sync + Debug {
+    /// Fetches the price of one unit of the native token, e.g. one ether, in fiat
+    async fn native_price(&self) -> Result<f64, Box<dyn Error + Send + Sync>>;
+}
+
+/// The estimated cost of a transaction, see [`FiatGasEstimator::estimate`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FiatCost {
+    /// The cost in wei of the native token
+    pub native_wei: U256,
+    /// The cost in the fiat currency of the [`PriceOracle`]
+    pub fiat: f64,
+}
+
+/// Error thrown by the [`FiatGasEstimator`]
+#[derive(Debug, Error)]
+pub enum FiatGasEstimatorError<M: Middleware> {
+    /// Thrown when the gas or the gas price could not be estimated
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+    /// Thrown when the price oracle failed
+    #[error("Price oracle error: {0}")]
+    PriceOracleError(Box<dyn Error + Send + Sync>),
+    /// Thrown when the price oracle returned a negative or non finite price
+    #[error("Invalid native token price: {0}")]
+    InvalidPrice(f64),
+}
+
+/// Estimates the cost of transactions in a fiat currency, e.g. to show it to users before they
+/// send a transaction.
+///
+/// The cost is `gas * gas price * token price`, where the gas and the gas price are taken from the
+/// transaction if they are set, and are estimated with [`Middleware::estimate_gas`] and
+/// [`Middleware::get_gas_price`] otherwise. The price of the native token is fetched from the
+/// [`PriceOracle`].
+///
+/// # Example
+///
+/// ```no_run
+/// use async_trait::async_trait;
+/// use ethers_core::types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest};
+/// use ethers_middleware::fiat::{FiatGasEstimator, PriceOracle};
+/// use ethers_providers::{Http, Provider};
+/// use std::{convert::TryFrom, error::Error};
+///
+/// #[derive(Debug)]
+/// struct FixedPrice(f64);
+///
+/// #[async_trait]
+/// impl PriceOracle for FixedPrice {
+///     async fn native_price(&self) -> Result<f64, Box<dyn Error + Send + Sync>> {
+///         Ok(self.0)
+///     }
+/// }
+///
+/// # async fn foo() -> Result<(), Box<dyn Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let estimator = FiatGasEstimator::new(provider, FixedPrice(2000.0));
+///
+/// let tx: TypedTransaction = TransactionRequest::pay(Address::zero(), 100).into();
+/// let cost = estimator.estimate(&tx, None).await?;
+/// println!("sending the transaction costs ${:.2}", cost.fiat);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FiatGasEstimator<M, P> {
+    inner: M,
+    oracle: P,
+}
+
+impl<M, P> FiatGasEstimator<M, P>
+where
+    M: Middleware,
+    P: PriceOracle,
+{
+    /// Creates an estimator using the `inner` middleware to estimate the gas and the `oracle` to
+    /// fetch the price of the native token
+    pub fn new(inner: M, oracle: P) -> Self {
+        Self { inner, oracle }
+    }
+
+    /// Returns the inner middleware
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Returns the price oracle
+    pub fn oracle(&self) -> &P {
+        &self.oracle
+    }
+
+    /// Estimates the cost of the transaction, executed at the given block
+    pub async fn estimate(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<FiatCost, FiatGasEstimatorError<M>> {
+        let gas = match tx.gas() {
+            Some(gas) => *gas,
+            None => self
+                .inner
+                .estimate_gas(tx, block)
+                .await
+                .map_err(FiatGasEstimatorError::MiddlewareError)?,
+        };
+        let gas_price = match tx.gas_price() {
+            Some(gas_price) => gas_price,
+            None => {
+                self.inner.get_gas_price().await.map_err(FiatGasEstimatorError::MiddlewareError)?
+            }
+        };
+        let price =
+            self.oracle.native_price().await.map_err(FiatGasEstimatorError::PriceOracleError)?;
+        self.cost(gas, gas_price, price)
+    }
+
+    /// Returns the cost of `gas` at the `gas_price` in wei, for the `price` of the native token
+    fn cost(
+        &self,
+        gas: U256,
+        gas_price: U256,
+        price: f64,
+    ) -> Result<FiatCost, FiatGasEstimatorError<M>> {
+        if !price.is_finite() || price < 0.0 {
+            return Err(FiatGasEstimatorError::InvalidPrice(price))
+        }
+        let native_wei = gas.saturating_mul(gas_price);
+        // the cost in ether always fits an f64, with a loss of precision that is irrelevant for
+        // displaying it
+        let ether: f64 = format_ether(native_wei).parse().unwrap_or_default();
+        Ok(FiatCost { native_wei, fiat: ether * price })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Address, Eip1559TransactionRequest, TransactionRequest};
+    use ethers_providers::Provider;
+
+    #[derive(Debug)]
+    struct FixedPrice(f64);
+
+    #[async_trait]
+    impl PriceOracle for FixedPrice {
+        async fn native_price(&self) -> Result<f64, Box<dyn Error + Send + Sync>> {
+            Ok(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn estimates_fiat_cost() {
+        let (provider, mock) = Provider::mocked();
+        let estimator = FiatGasEstimator::new(provider, FixedPrice(2000.0));
+
+        // 21000 gas at 50 gwei
+        mock.push(U256::from(50_000_000_000u64)).unwrap();
+        mock.push(U256::from(21_000)).unwrap();
+        let tx: TypedTransaction = TransactionRequest::pay(Address::zero(), 100).into();
+        let cost = estimator.estimate(&tx, None).await.unwrap();
+        assert_eq!(cost.native_wei, U256::from(1_050_000_000_000_000u64));
+        assert!((cost.fiat - 2.1).abs() < 1e-9);
+        mock.assert_request("eth_estimateGas", (&tx,)).unwrap();
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+
+        // the gas and gas price of the transaction are used if set
+        let tx: TypedTransaction =
+            Eip1559TransactionRequest::new().gas(100_000).max_fee_per_gas(10_000_000_000u64).into();
+        let cost = estimator.estimate(&tx, None).await.unwrap();
+        assert_eq!(cost.native_wei, U256::from(1_000_000_000_000_000u64));
+        assert!((cost.fiat - 2.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_prices() {
+        let (provider, _) = Provider::mocked();
+        let estimator = FiatGasEstimator::new(provider, FixedPrice(f64::NAN));
+        let tx: TypedTransaction = TransactionRequest::new().gas(21_000).gas_price(1).into();
+        let err = estimator.estimate(&tx, None).await.unwrap_err();
+        assert!(matches!(err, FiatGasEstimatorError::InvalidPrice(_)));
+    }
+}
@@ -0,0 +1,193 @@
+use async_trait::async_trait;
+use ethers_core::types::{transaction::eip2718::TypedTransaction, BlockId, U256};
+use ethers_providers::{Middleware, MiddlewareError, PendingTransaction};
+use std::sync::atomic::{AtomicU64, Ordering};
+use thiserror::Error;
+
+/// Middleware that refuses to send transactions whose gas price exceeds a ceiling, e.g. to stop a
+/// bot from sending transactions during gas spikes.
+///
+/// The gas price is checked after the transaction was filled by the inner middleware, so it
+/// should wrap the middleware resolving the gas price, e.g. a
+/// [`GasOracleMiddleware`](crate::gas_oracle::GasOracleMiddleware). For EIP-1559 transactions the
+/// `max_fee_per_gas` is checked, since it is the most the transaction can pay.
+///
+/// The ceiling is in wei, and can be adjusted at runtime with
+/// [`GasGuardMiddleware::set_max_gas_price`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_core::types::{Address, TransactionRequest};
+/// use ethers_middleware::{gas_guard::GasGuardMiddlewareError, GasGuardMiddleware};
+/// use ethers_providers::{Http, Middleware, Provider};
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// // refuse to pay more than 100 gwei
+/// let provider = GasGuardMiddleware::new(provider, 100_000_000_000);
+///
+/// let tx = TransactionRequest::pay(Address::zero(), 100);
+/// match provider.send_transaction(tx, None).await {
+///     Err(GasGuardMiddlewareError::GasPriceTooHigh { gas_price, .. }) => {
+///         println!("gas price of {gas_price} is too high, retrying later")
+///     }
+///     res => {
+///         res?;
+///     }
+/// }
+///
+/// // raise the ceiling to 200 gwei
+/// provider.set_max_gas_price(200_000_000_000);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GasGuardMiddleware<M> {
+    inner: M,
+    max_gas_price: AtomicU64,
+}
+
+impl<M> GasGuardMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Wraps the `inner` middleware, refusing transactions with a gas price above `max_gas_price`
+    /// wei
+    pub fn new(inner: M, max_gas_price: u64) -> Self {
+        Self { inner, max_gas_price: AtomicU64::new(max_gas_price) }
+    }
+
+    /// Returns the current ceiling of the gas price in wei
+    pub fn max_gas_price(&self) -> u64 {
+        self.max_gas_price.load(Ordering::SeqCst)
+    }
+
+    /// Sets the ceiling of the gas price in wei, applying to all transactions that are filled
+    /// afterwards
+    pub fn set_max_gas_price(&self, max_gas_price: u64) {
+        self.max_gas_price.store(max_gas_price, Ordering::SeqCst);
+    }
+
+    /// Returns an error if the gas price of the transaction exceeds the ceiling
+    fn check(&self, tx: &TypedTransaction) -> Result<(), GasGuardMiddlewareError<M>> {
+        let max_gas_price = U256::from(self.max_gas_price());
+        match tx.gas_price() {
+            Some(gas_price) if gas_price > max_gas_price => {
+                Err(GasGuardMiddlewareError::GasPriceTooHigh { gas_price, max_gas_price })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Error thrown by the [`GasGuardMiddleware`]
+#[derive(Error, Debug)]
+pub enum GasGuardMiddlewareError<M: Middleware> {
+    /// Thrown when the gas price of a transaction exceeds the ceiling
+    #[error("gas price of {gas_price} wei exceeds the maximum of {max_gas_price} wei")]
+    GasPriceTooHigh {
+        /// The gas price of the transaction, or its max fee per gas
+        gas_price: U256,
+        /// The ceiling at the time the transaction was checked
+        max_gas_price: U256,
+    },
+    /// Thrown when an internal middleware errors
+    #[error(transparent)]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> MiddlewareError for GasGuardMiddlewareError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        GasGuardMiddlewareError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            GasGuardMiddlewareError::MiddlewareError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> Middleware for GasGuardMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = GasGuardMiddlewareError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Fills the transaction with the inner middleware, and fails if its resolved gas price
+    /// exceeds the ceiling
+    async fn fill_transaction(
+        &self,
+        tx: &mut TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<(), Self::Error> {
+        self.inner.fill_transaction(tx, block).await.map_err(MiddlewareError::from_err)?;
+        self.check(tx)
+    }
+
+    /// Fills the transaction and only sends it if its gas price does not exceed the ceiling
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let mut tx = tx.into();
+        self.fill_transaction(&mut tx, block).await?;
+        self.inner.send_transaction(tx, block).await.map_err(MiddlewareError::from_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Address, Eip1559TransactionRequest, TransactionRequest, H256};
+    use ethers_providers::Provider;
+
+    #[tokio::test]
+    async fn rejects_gas_prices_above_ceiling() {
+        let (provider, mock) = Provider::mocked();
+        let provider = GasGuardMiddleware::new(provider, 100);
+
+        let tx = TransactionRequest::pay(Address::zero(), 1).from(Address::zero()).nonce(0).gas(1);
+
+        let err = provider.send_transaction(tx.clone().gas_price(101), None).await.unwrap_err();
+        assert!(matches!(
+            err,
+            GasGuardMiddlewareError::GasPriceTooHigh { gas_price, max_gas_price }
+                if gas_price == 101.into() && max_gas_price == 100.into()
+        ));
+        let mut eip1559: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(Address::zero())
+            .nonce(0)
+            .gas(1)
+            .max_fee_per_gas(101)
+            .max_priority_fee_per_gas(1)
+            .into();
+        assert!(provider.fill_transaction(&mut eip1559, None).await.is_err());
+
+        // the ceiling applies to the gas price resolved by the inner middleware
+        mock.push(U256::from(150)).unwrap();
+        assert!(provider.send_transaction(tx.clone(), None).await.is_err());
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+
+        provider.set_max_gas_price(150);
+        let hash = H256::repeat_byte(1);
+        mock.push(hash).unwrap();
+        mock.push(U256::from(150)).unwrap();
+        let pending = provider.send_transaction(tx, None).await.unwrap();
+        assert_eq!(*pending, hash);
+    }
+}
@@ -5,15 +5,20 @@ use futures_util::future::join_all;
 use std::{fmt::Debug, future::Future};
 use tracing::warn;
 
+/// Computes the median gas price from a selection of oracles.
+///
+/// All oracles are queried concurrently and the (weighted) median of the successful responses is
+/// returned. By default a single successful response is enough, use [`Median::set_quorum`] to
+/// require more.
+///
+/// Don't forget to set a timeout on the source oracles. By default
+/// the reqwest based oracles will never time out.
 #[derive(Default, Debug)]
 pub struct Median {
     oracles: Vec<(f32, Box<dyn GasOracle>)>,
+    quorum: usize,
 }
 
-/// Computes the median gas price from a selection of oracles.
-///
-/// Don't forget to set a timeout on the source oracles. By default
-/// the reqwest based oracles will never time out.
 impl Median {
     pub fn new() -> Self {
         Self::default()
@@ -28,6 +33,15 @@ impl Median {
         self.oracles.push((weight, Box::new(oracle)));
     }
 
+    /// Sets the minimum number of oracles that need to return a value, defaults to 1.
+    ///
+    /// If fewer oracles succeed, including none of them, [`GasOracleError::QuorumNotReached`] is
+    /// returned with the errors of all failed oracles. [`GasOracleError::NoValues`] is only
+    /// returned if no oracles were added.
+    pub fn set_quorum(&mut self, quorum: usize) {
+        self.quorum = quorum;
+    }
+
     pub async fn query_all<'a, Fn, Fut, O>(&'a self, mut f: Fn) -> Result<Vec<(f32, O)>>
     where
         Fn: FnMut(&'a dyn GasOracle) -> Fut,
@@ -38,20 +52,29 @@ impl Median {
         let results = join_all(futures).await;
 
         // Filter out any errors
-        let values =
-            self.oracles.iter().zip(results).filter_map(
-                |((weight, oracle), result)| match result {
-                    Ok(value) => Some((*weight, value)),
-                    Err(err) => {
-                        warn!("Failed to fetch gas price from {:?}: {}", oracle, err);
-                        None
-                    }
-                },
-            );
-        let values = values.collect::<Vec<_>>();
-        if values.is_empty() {
+        let mut values = Vec::with_capacity(results.len());
+        let mut failures = Vec::new();
+        for ((weight, oracle), result) in self.oracles.iter().zip(results) {
+            match result {
+                Ok(value) => values.push((*weight, value)),
+                Err(err) => {
+                    warn!("Failed to fetch gas price from {:?}: {}", oracle, err);
+                    failures.push((format!("{oracle:?}"), err));
+                }
+            }
+        }
+
+        if self.oracles.is_empty() {
             return Err(GasOracleError::NoValues)
         }
+        let quorum = self.quorum.max(1);
+        if values.len() < quorum {
+            return Err(GasOracleError::QuorumNotReached {
+                quorum,
+                successes: values.len(),
+                failures,
+            })
+        }
         Ok(values)
     }
 }
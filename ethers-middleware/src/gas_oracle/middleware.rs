@@ -1,6 +1,12 @@
 use super::{GasOracle, GasOracleError};
 use async_trait::async_trait;
-use ethers_core::types::{transaction::eip2718::TypedTransaction, *};
+use ethers_core::types::{
+    transaction::{
+        eip2718::TypedTransaction, eip4844::Eip4844TransactionRequest,
+        eip7702::Eip7702TransactionRequest,
+    },
+    *,
+};
 use ethers_providers::{Middleware, MiddlewareError as METrait, PendingTransaction};
 use thiserror::Error;
 
@@ -81,7 +87,9 @@ where
                     inner.tx.gas_price = Some(self.get_gas_price().await?);
                 }
             }
-            TypedTransaction::Eip1559(ref mut inner) => {
+            TypedTransaction::Eip1559(ref mut inner) |
+            TypedTransaction::Eip4844(Eip4844TransactionRequest { tx: ref mut inner, .. }) |
+            TypedTransaction::Eip7702(Eip7702TransactionRequest { tx: ref mut inner, .. }) => {
                 if inner.max_priority_fee_per_gas.is_none() || inner.max_fee_per_gas.is_none() {
                     let (max_fee_per_gas, max_priority_fee_per_gas) =
                         self.estimate_eip1559_fees(None).await?;
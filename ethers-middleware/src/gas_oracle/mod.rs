@@ -86,6 +86,12 @@ pub enum GasOracleError {
     #[error("None of the oracles returned a value")]
     NoValues,
 
+    /// Thrown by [`Median`] when fewer oracles than the configured quorum returned a value.
+    ///
+    /// Contains the debug representation of every failed oracle alongside its error.
+    #[error("Only {successes} oracles returned a value, but a quorum of {quorum} is required: {}", format_failures(.failures))]
+    QuorumNotReached { quorum: usize, successes: usize, failures: Vec<(String, GasOracleError)> },
+
     #[error("Chain is not supported by the oracle")]
     UnsupportedChain,
 
@@ -96,6 +102,10 @@ pub enum GasOracleError {
     ConversionError(#[from] ethers_core::utils::ConversionError),
 }
 
+fn format_failures(failures: &[(String, GasOracleError)]) -> String {
+    failures.iter().map(|(oracle, err)| format!("{oracle}: {err}")).collect::<Vec<_>>().join(", ")
+}
+
 /// An Ethereum gas price oracle.
 ///
 /// # Example
@@ -41,6 +41,31 @@ pub use policy::{
 pub mod timelag;
 pub use timelag::TimeLag;
 
+/// The [RetryMiddleware] retries requests that failed with a retryable error, e.g. because the
+/// provider rate limited them, with an exponential backoff.
+pub mod retry;
+pub use retry::RetryMiddleware;
+
+/// The [CacheMiddleware] caches the responses of requests that do not change, e.g. the chain id,
+/// the code of contracts and the receipts of confirmed transactions.
+pub mod cache;
+pub use cache::CacheMiddleware;
+
+/// The [GasGuardMiddleware] refuses to send transactions whose gas price exceeds a ceiling, e.g.
+/// during gas spikes.
+pub mod gas_guard;
+pub use gas_guard::GasGuardMiddleware;
+
+/// The [FiatGasEstimator] estimates the cost of transactions in a fiat currency, using the native
+/// token price of a pluggable [PriceOracle].
+pub mod fiat;
+pub use fiat::{FiatCost, FiatGasEstimator, PriceOracle};
+
+/// Loads [`Contract`](ethers_contract::Contract)s from their ABI verified on Etherscan, resolving
+/// EIP-1967 proxies to their implementation.
+#[cfg(feature = "etherscan")]
+pub mod etherscan;
+
 /// [MiddlewareBuilder] provides a way to compose many [`Middleware`]s in a concise way.
 pub mod builder;
 pub use builder::MiddlewareBuilder;
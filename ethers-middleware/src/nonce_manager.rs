@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use ethers_core::types::{transaction::eip2718::TypedTransaction, *};
 use ethers_providers::{Middleware, MiddlewareError, PendingTransaction};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use thiserror::Error;
 
 #[derive(Debug)]
@@ -13,6 +13,11 @@ pub struct NonceManagerMiddleware<M> {
     initialized: AtomicBool,
     nonce: AtomicU64,
     address: Address,
+    /// Held for reading while a transaction is being sent, and for writing during a resync
+    resync_lock: futures_locks::RwLock<()>,
+    /// Resync after this many consecutive sends failed with a nonce error
+    auto_resync: Option<u32>,
+    nonce_errors: AtomicU32,
 }
 
 impl<M> NonceManagerMiddleware<M>
@@ -28,9 +33,20 @@ where
             initialized: Default::default(),
             nonce: Default::default(),
             address,
+            resync_lock: futures_locks::RwLock::new(()),
+            auto_resync: None,
+            nonce_errors: Default::default(),
         }
     }
 
+    /// Automatically [resync](Self::resync) the nonce after `consecutive_nonce_errors` sends in a
+    /// row failed with a "nonce too low" or "nonce too high" error
+    #[must_use]
+    pub fn auto_resync(mut self, consecutive_nonce_errors: u32) -> Self {
+        self.auto_resync = Some(consecutive_nonce_errors);
+        self
+    }
+
     /// Returns the next nonce to be used
     pub fn next(&self) -> U256 {
         let nonce = self.nonce.fetch_add(1, Ordering::SeqCst);
@@ -65,6 +81,26 @@ where
         Ok(nonce)
     } // guard dropped here
 
+    /// Resets the local nonce to the transaction count of the address, including pending
+    /// transactions, and returns it.
+    ///
+    /// This recovers from transactions that were dropped from the mempool. The resync waits for
+    /// transactions that are currently being sent via [`Middleware::send_transaction`], and
+    /// blocks new ones until it is done, so no nonce is assigned twice.
+    pub async fn resync(&self) -> Result<U256, NonceManagerError<M>> {
+        let _guard = self.resync_lock.write().await;
+
+        let nonce = self
+            .inner
+            .get_transaction_count(self.address, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(MiddlewareError::from_err)?;
+        self.nonce.store(nonce.as_u64(), Ordering::SeqCst);
+        self.initialized.store(true, Ordering::SeqCst);
+        self.nonce_errors.store(0, Ordering::SeqCst);
+        Ok(nonce)
+    }
+
     async fn get_transaction_count_with_manager(
         &self,
         block: Option<BlockId>,
@@ -142,18 +178,20 @@ where
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
         let mut tx = tx.into();
 
+        let guard = self.resync_lock.read().await;
+
         if tx.nonce().is_none() {
             tx.set_nonce(self.get_transaction_count_with_manager(block).await?);
         }
 
-        match self.inner.send_transaction(tx.clone(), block).await {
+        let res = match self.inner.send_transaction(tx.clone(), block).await {
             Ok(tx_hash) => Ok(tx_hash),
             Err(err) => {
                 let nonce = self.get_transaction_count(self.address, block).await?;
                 if nonce != self.nonce.load(Ordering::SeqCst).into() {
                     // try re-submitting the transaction with the correct nonce if there
                     // was a nonce mismatch
-                    self.nonce.store(nonce.as_u64(), Ordering::SeqCst);
+                    self.nonce.store(nonce.as_u64() + 1, Ordering::SeqCst);
                     tx.set_nonce(nonce);
                     self.inner.send_transaction(tx, block).await.map_err(MiddlewareError::from_err)
                 } else {
@@ -161,6 +199,26 @@ where
                     Err(MiddlewareError::from_err(err))
                 }
             }
+        };
+        drop(guard);
+
+        match &res {
+            Ok(_) => self.nonce_errors.store(0, Ordering::SeqCst),
+            Err(err) if is_nonce_error(err) => {
+                let nonce_errors = self.nonce_errors.fetch_add(1, Ordering::SeqCst) + 1;
+                if self.auto_resync.map(|max| nonce_errors >= max).unwrap_or_default() {
+                    self.resync().await?;
+                }
+            }
+            Err(_) => {}
         }
+
+        res
     }
 }
+
+/// Whether the node rejected the transaction because of its nonce
+fn is_nonce_error(err: &impl std::error::Error) -> bool {
+    let err = err.to_string().to_lowercase();
+    err.contains("nonce too low") || err.contains("nonce too high")
+}
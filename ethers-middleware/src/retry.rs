@@ -0,0 +1,321 @@
+use async_trait::async_trait;
+use ethers_core::types::{
+    transaction::eip2718::TypedTransaction, Address, Block, BlockId, BlockNumber, Bytes,
+    FeeHistory, Filter, Log, NameOrAddress, Transaction, TransactionReceipt, TxHash, H256, U256,
+    U64,
+};
+use ethers_providers::{
+    HttpRateLimitRetryPolicy, Middleware, MiddlewareError, ProviderError, RetryPolicy,
+};
+use futures_timer::Delay;
+use rand::Rng;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt::Debug, future::Future, time::Duration};
+use thiserror::Error;
+use tracing::trace;
+
+/// The default maximum delay between two attempts
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Middleware that retries failed requests with an exponential backoff.
+///
+/// Whether an error is retried is decided by the configured [RetryPolicy], based on the
+/// underlying [ProviderError]. Errors that are not retryable, e.g. reverts, are returned
+/// immediately. Before each retry the middleware sleeps for `initial_backoff * 2^attempt`,
+/// capped at `max_backoff`, with a random jitter applied to spread out bursts of requests.
+///
+/// Only requests that do not change state are retried, so that a transaction is never
+/// submitted twice.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_middleware::retry::{DefaultRetryPolicy, RetryMiddleware};
+/// use ethers_providers::{Http, Provider};
+/// use std::{convert::TryFrom, time::Duration};
+///
+/// let provider = Provider::<Http>::try_from("http://localhost:8545").unwrap();
+/// let provider = RetryMiddleware::new(
+///     provider,
+///     Box::<DefaultRetryPolicy>::default(),
+///     5,
+///     Duration::from_millis(500),
+/// )
+/// .max_backoff(Duration::from_secs(10));
+/// ```
+#[derive(Debug)]
+pub struct RetryMiddleware<M> {
+    inner: M,
+    policy: Box<dyn RetryPolicy<ProviderError>>,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl<M> RetryMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Creates a new `RetryMiddleware` that retries requests at most `max_retries` times if the
+    /// `policy` classifies the error as retryable, starting with a delay of `initial_backoff`.
+    pub fn new(
+        inner: M,
+        policy: Box<dyn RetryPolicy<ProviderError>>,
+        max_retries: u32,
+        initial_backoff: Duration,
+    ) -> Self {
+        Self { inner, policy, max_retries, initial_backoff, max_backoff: DEFAULT_MAX_BACKOFF }
+    }
+
+    /// Sets the maximum delay between two attempts, defaults to 30 seconds.
+    #[must_use]
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Returns the delay before the retry with the given number, without jitter
+    fn backoff(&self, retry_number: u32) -> Duration {
+        self.initial_backoff
+            .checked_mul(2u32.saturating_pow(retry_number))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    /// Executes the request created by `f`, retrying it while the policy allows it
+    async fn retry<T, F, Fut>(&self, f: F) -> Result<T, RetryMiddlewareError<M>>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, M::Error>>,
+    {
+        let mut retry_number = 0;
+        loop {
+            let err = match f().await {
+                Ok(res) => return Ok(res),
+                Err(err) => err,
+            };
+
+            let should_retry = retry_number < self.max_retries &&
+                err.as_provider_error().map(|e| self.policy.should_retry(e)).unwrap_or_default();
+            if !should_retry {
+                return Err(RetryMiddlewareError::MiddlewareError(err))
+            }
+
+            // the provider may tell us how long to wait, otherwise wait for the exponential
+            // backoff with "equal jitter", i.e. a random delay between half and the full backoff
+            let backoff = err.as_provider_error().and_then(|e| self.policy.backoff_hint(e));
+            let backoff = backoff.unwrap_or_else(|| {
+                let backoff = self.backoff(retry_number);
+                let half = backoff / 2;
+                half + half.mul_f64(rand::thread_rng().gen::<f64>())
+            });
+
+            retry_number += 1;
+            trace!(?err, ?backoff, retry_number, "retrying request");
+            Delay::new(backoff).await;
+        }
+    }
+}
+
+/// Errors thrown by the [RetryMiddleware]
+#[derive(Error, Debug)]
+pub enum RetryMiddlewareError<M: Middleware> {
+    /// Thrown when the internal middleware errors, including the last error once all retries are
+    /// exhausted
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> MiddlewareError for RetryMiddlewareError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        RetryMiddlewareError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            RetryMiddlewareError::MiddlewareError(e) => Some(e),
+        }
+    }
+}
+
+/// The default [RetryPolicy] of the [RetryMiddleware].
+///
+/// Retries rate limited requests, as classified by [HttpRateLimitRetryPolicy], as well as
+/// requests that failed due to connectivity issues: timeouts, connection errors and responses
+/// with a `5xx` status code.
+#[derive(Debug, Default)]
+pub struct DefaultRetryPolicy;
+
+impl RetryPolicy<ProviderError> for DefaultRetryPolicy {
+    fn should_retry(&self, error: &ProviderError) -> bool {
+        if HttpRateLimitRetryPolicy.should_retry(error) {
+            return true
+        }
+
+        if let ProviderError::HTTPError(err) = error {
+            #[cfg(not(target_arch = "wasm32"))]
+            if err.is_connect() {
+                return true
+            }
+            return err.is_timeout() ||
+                err.status().map(|status| status.is_server_error()).unwrap_or_default()
+        }
+        false
+    }
+
+    fn backoff_hint(&self, error: &ProviderError) -> Option<Duration> {
+        HttpRateLimitRetryPolicy.backoff_hint(error)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> Middleware for RetryMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = RetryMiddlewareError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Retries raw requests, except for the `eth_send*` methods that submit transactions
+    async fn request_raw<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned + Debug + Send,
+    {
+        if method.starts_with("eth_send") {
+            return self.inner.request_raw(method, params).await.map_err(MiddlewareError::from_err)
+        }
+        self.retry(|| self.inner.request_raw(method, &params)).await
+    }
+
+    async fn get_block_number(&self) -> Result<U64, Self::Error> {
+        self.retry(|| self.inner.get_block_number()).await
+    }
+
+    async fn get_block<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<TxHash>>, Self::Error> {
+        let block = block_hash_or_number.into();
+        self.retry(|| self.inner.get_block(block)).await
+    }
+
+    async fn get_block_with_txs<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<Transaction>>, Self::Error> {
+        let block = block_hash_or_number.into();
+        self.retry(|| self.inner.get_block_with_txs(block)).await
+    }
+
+    async fn get_transaction_count<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        let from = from.into();
+        self.retry(|| self.inner.get_transaction_count(from.clone(), block)).await
+    }
+
+    async fn estimate_gas(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        self.retry(|| self.inner.estimate_gas(tx, block)).await
+    }
+
+    async fn call(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        self.retry(|| self.inner.call(tx, block)).await
+    }
+
+    async fn get_chainid(&self) -> Result<U256, Self::Error> {
+        self.retry(|| self.inner.get_chainid()).await
+    }
+
+    async fn get_balance<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        let from = from.into();
+        self.retry(|| self.inner.get_balance(from.clone(), block)).await
+    }
+
+    async fn get_transaction<T: Send + Sync + Into<TxHash>>(
+        &self,
+        transaction_hash: T,
+    ) -> Result<Option<Transaction>, Self::Error> {
+        let hash = transaction_hash.into();
+        self.retry(|| self.inner.get_transaction(hash)).await
+    }
+
+    async fn get_transaction_receipt<T: Send + Sync + Into<TxHash>>(
+        &self,
+        transaction_hash: T,
+    ) -> Result<Option<TransactionReceipt>, Self::Error> {
+        let hash = transaction_hash.into();
+        self.retry(|| self.inner.get_transaction_receipt(hash)).await
+    }
+
+    async fn get_block_receipts<T: Into<BlockNumber> + Send + Sync>(
+        &self,
+        block: T,
+    ) -> Result<Vec<TransactionReceipt>, Self::Error> {
+        let block = block.into();
+        self.retry(|| self.inner.get_block_receipts(block)).await
+    }
+
+    async fn get_gas_price(&self) -> Result<U256, Self::Error> {
+        self.retry(|| self.inner.get_gas_price()).await
+    }
+
+    async fn get_accounts(&self) -> Result<Vec<Address>, Self::Error> {
+        self.retry(|| self.inner.get_accounts()).await
+    }
+
+    async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, Self::Error> {
+        self.retry(|| self.inner.get_logs(filter)).await
+    }
+
+    async fn get_code<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        at: T,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        let at = at.into();
+        self.retry(|| self.inner.get_code(at.clone(), block)).await
+    }
+
+    async fn get_storage_at<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        location: H256,
+        block: Option<BlockId>,
+    ) -> Result<H256, Self::Error> {
+        let from = from.into();
+        self.retry(|| self.inner.get_storage_at(from.clone(), location, block)).await
+    }
+
+    async fn fee_history<T: Into<U256> + serde::Serialize + Send + Sync>(
+        &self,
+        block_count: T,
+        last_block: BlockNumber,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, Self::Error> {
+        let block_count = block_count.into();
+        self.retry(|| self.inner.fee_history(block_count, last_block, reward_percentiles)).await
+    }
+}
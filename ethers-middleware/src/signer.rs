@@ -1,6 +1,16 @@
-use ethers_core::types::{
-    transaction::{eip2718::TypedTransaction, eip2930::AccessListWithGasUsed},
-    Address, BlockId, Bytes, Chain, Signature, TransactionRequest, U256,
+use ethers_core::{
+    abi::{self, Token},
+    types::{
+        transaction::{
+            eip2718::TypedTransaction,
+            eip2930::AccessListWithGasUsed,
+            eip712::{EIP712Domain, Eip712DomainType, TypedData},
+            eip7702::Eip7702TransactionRequest,
+        },
+        Address, BlockId, BlockNumber, Bytes, Chain, Eip1559TransactionRequest, NameOrAddress,
+        Signature, TransactionRequest, TxHash, U256,
+    },
+    utils::id,
 };
 use ethers_providers::{maybe, Middleware, MiddlewareError, PendingTransaction};
 use ethers_signers::Signer;
@@ -65,6 +75,9 @@ pub struct SignerMiddleware<M, S> {
     pub(crate) inner: M,
     pub(crate) signer: S,
     pub(crate) address: Address,
+    pub(crate) forwarder: Option<MetaTxForwarder>,
+    /// Whether the chain supports EIP-1559 transactions, looked up by chain id if `None`
+    pub(crate) eip1559: Option<bool>,
 }
 
 #[derive(Error, Debug)]
@@ -93,8 +106,33 @@ pub enum SignerMiddlewareError<M: Middleware, S: Signer> {
     /// Thrown if the signer's chain_id is different than the chain_id of the transaction
     #[error("specified chain_id is different than the signer's chain_id")]
     DifferentChainID,
+    /// Thrown if a meta transaction is signed without a forwarder
+    #[error("no meta transaction forwarder was specified")]
+    ForwarderMissing,
+    /// Thrown if a meta transaction has no recipient
+    #[error("meta transaction has no recipient")]
+    RecipientMissing,
+    /// Thrown if the forwarder's nonce could not be decoded
+    #[error(transparent)]
+    AbiError(#[from] abi::Error),
+    /// Thrown if a replacement transaction bumps the fees by less than the minimum
+    #[error("fee bump of {0}% is below the minimum of {MIN_FEE_BUMP_PERCENT}%")]
+    FeeBumpTooSmall(u16),
+    /// Thrown if the transaction to replace is not known to the node
+    #[error("transaction {0:?} not found")]
+    TransactionNotFound(TxHash),
+    /// Thrown if the transaction to replace was already mined
+    #[error("transaction {0:?} was already mined")]
+    TransactionMined(TxHash),
+    /// Thrown if the transaction to replace is a blob transaction, whose sidecar is unknown
+    #[error("blob transaction {0:?} can not be replaced")]
+    BlobTransactionReplacement(TxHash),
 }
 
+/// The minimum percentage by which nodes require the fees of a replacement transaction to be
+/// increased, see [`SignerMiddleware::speed_up`]
+pub const MIN_FEE_BUMP_PERCENT: u16 = 10;
+
 impl<M: Middleware, S: Signer> MiddlewareError for SignerMiddlewareError<M, S> {
     type Inner = M::Error;
 
@@ -110,6 +148,114 @@ impl<M: Middleware, S: Signer> MiddlewareError for SignerMiddlewareError<M, S> {
     }
 }
 
+/// The [ERC-2771](https://eips.ethereum.org/EIPS/eip-2771) trusted forwarder that meta
+/// transactions are signed for.
+///
+/// By default, requests are signed as the `ForwardRequest` struct of OpenZeppelin's
+/// `MinimalForwarder`:
+///
+/// ```solidity
+/// struct ForwardRequest {
+///     address from;
+///     address to;
+///     uint256 value;
+///     uint256 gas;
+///     uint256 nonce;
+///     bytes data;
+/// }
+/// ```
+///
+/// Forwarders with a differently named struct, or with its fields in a different order, are
+/// supported via [`MetaTxForwarder::request_type`]. The fields must be named after the fields of
+/// [`ForwardRequest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MetaTxForwarder {
+    /// The EIP-712 domain of the forwarder
+    pub domain: EIP712Domain,
+    /// The name of the EIP-712 request struct
+    pub request_name: String,
+    /// The fields of the EIP-712 request struct
+    pub request_fields: Vec<Eip712DomainType>,
+}
+
+impl MetaTxForwarder {
+    /// Creates a forwarder signing OpenZeppelin `MinimalForwarder` requests for the given domain.
+    ///
+    /// The domain's verifying contract must be the forwarder, since the forwarder's nonce of the
+    /// signer is read from it.
+    pub fn new(domain: EIP712Domain) -> Self {
+        let request_fields = [
+            ("from", "address"),
+            ("to", "address"),
+            ("value", "uint256"),
+            ("gas", "uint256"),
+            ("nonce", "uint256"),
+            ("data", "bytes"),
+        ]
+        .into_iter()
+        .map(|(name, ty)| Eip712DomainType { name: name.to_string(), r#type: ty.to_string() })
+        .collect();
+        Self { domain, request_name: "ForwardRequest".to_string(), request_fields }
+    }
+
+    /// Sets the name and the fields of the EIP-712 request struct
+    #[must_use]
+    pub fn request_type(
+        mut self,
+        name: impl Into<String>,
+        fields: impl IntoIterator<Item = Eip712DomainType>,
+    ) -> Self {
+        self.request_name = name.into();
+        self.request_fields = fields.into_iter().collect();
+        self
+    }
+
+    /// Returns the EIP-712 typed data of the request, as signed by
+    /// [`SignerMiddleware::sign_meta_tx`]
+    pub fn typed_data(&self, request: &ForwardRequest) -> TypedData {
+        let message = match serde_json::to_value(request) {
+            Ok(serde_json::Value::Object(fields)) => fields.into_iter().collect(),
+            _ => Default::default(),
+        };
+        TypedData {
+            domain: self.domain.clone(),
+            types: [
+                ("EIP712Domain".to_string(), self.domain.field_types()),
+                (self.request_name.clone(), self.request_fields.clone()),
+            ]
+            .into(),
+            primary_type: self.request_name.clone(),
+            message,
+        }
+    }
+}
+
+/// A call that is relayed to its recipient by an ERC-2771 forwarder on behalf of `from`
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ForwardRequest {
+    /// The signer of the request
+    pub from: Address,
+    /// The recipient of the call
+    pub to: Address,
+    /// The value sent with the call
+    pub value: U256,
+    /// The gas limit of the call
+    pub gas: U256,
+    /// The forwarder's nonce of the signer
+    pub nonce: U256,
+    /// The calldata
+    pub data: Bytes,
+}
+
+/// A [`ForwardRequest`] signed for a forwarder, to be submitted by a relayer
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SignedMetaTx {
+    /// The signed request
+    pub request: ForwardRequest,
+    /// The EIP-712 signature of the request
+    pub signature: Signature,
+}
+
 // Helper functions for locally signing transactions
 impl<M, S> SignerMiddleware<M, S>
 where
@@ -127,7 +273,7 @@ where
     /// [`Signer`] ethers_signers::Signer
     pub fn new(inner: M, signer: S) -> Self {
         let address = signer.address();
-        SignerMiddleware { inner, signer, address }
+        SignerMiddleware { inner, signer, address, forwarder: None, eip1559: None }
     }
 
     /// Signs and returns the RLP encoding of the signed transaction.
@@ -196,7 +342,151 @@ where
         let chain_id =
             inner.get_chainid().await.map_err(|e| SignerMiddlewareError::MiddlewareError(e))?;
         let signer = signer.with_chain_id(chain_id.as_u64());
-        Ok(SignerMiddleware { inner, signer, address })
+        Ok(SignerMiddleware { inner, signer, address, forwarder: None, eip1559: None })
+    }
+
+    /// Sets the forwarder that meta transactions are signed for with
+    /// [`sign_meta_tx`](Self::sign_meta_tx)
+    #[must_use]
+    pub fn with_forwarder(mut self, forwarder: MetaTxForwarder) -> Self {
+        self.forwarder = Some(forwarder);
+        self
+    }
+
+    /// Sets whether the chain supports EIP-1559 transactions, overriding
+    /// [`Chain::supports_eip1559`] of the transaction's chain, e.g. for private chains.
+    ///
+    /// On chains without EIP-1559 support, EIP-1559 transactions are converted to legacy ones
+    /// before they are signed.
+    #[must_use]
+    pub fn with_eip1559_support(mut self, supported: bool) -> Self {
+        self.eip1559 = Some(supported);
+        self
+    }
+
+    /// Signs the transaction as an [ERC-2771](https://eips.ethereum.org/EIPS/eip-2771) meta
+    /// transaction for the configured [`MetaTxForwarder`], instead of as a raw transaction.
+    ///
+    /// The signed request is meant to be submitted to the forwarder by a relayer paying for the
+    /// gas. If the transaction does not set a nonce or gas limit, the signer's nonce is read from
+    /// the forwarder's `getNonce(address)` and the gas is estimated.
+    pub async fn sign_meta_tx<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+    ) -> Result<SignedMetaTx, SignerMiddlewareError<M, S>> {
+        let forwarder = self.forwarder.as_ref().ok_or(SignerMiddlewareError::ForwarderMissing)?;
+        let mut tx = tx.into();
+        tx.set_from(self.address);
+
+        let to = match tx.to().ok_or(SignerMiddlewareError::RecipientMissing)? {
+            NameOrAddress::Address(address) => *address,
+            NameOrAddress::Name(ens_name) => self
+                .inner
+                .resolve_name(ens_name)
+                .await
+                .map_err(SignerMiddlewareError::MiddlewareError)?,
+        };
+        let gas = match tx.gas() {
+            Some(gas) => *gas,
+            None => self.estimate_gas(&tx, None).await?,
+        };
+        let nonce = match (tx.nonce(), forwarder.domain.verifying_contract) {
+            (Some(nonce), _) => *nonce,
+            (None, Some(contract)) => self.forwarder_nonce(contract).await?,
+            (None, None) => return Err(SignerMiddlewareError::NonceMissing),
+        };
+
+        let request = ForwardRequest {
+            from: self.address,
+            to,
+            value: tx.value().copied().unwrap_or_default(),
+            gas,
+            nonce,
+            data: tx.data().cloned().unwrap_or_default(),
+        };
+        let signature = self
+            .signer
+            .sign_typed_data(&forwarder.typed_data(&request))
+            .await
+            .map_err(SignerMiddlewareError::SignerError)?;
+        Ok(SignedMetaTx { request, signature })
+    }
+
+    /// Replaces a pending transaction by a copy paying fees increased by `bump_percent`, e.g. to
+    /// un-stick a transaction that is underpriced.
+    ///
+    /// The original transaction is fetched by its hash, and is re-signed with the same nonce,
+    /// recipient, value, data and gas limit. The `max_fee_per_gas` and `max_priority_fee_per_gas`
+    /// of EIP-1559 transactions and the `gas_price` of legacy transactions are bumped, rounding
+    /// up. Nodes only accept replacements bumping the fees by at least
+    /// [`MIN_FEE_BUMP_PERCENT`], so smaller bumps are rejected with
+    /// [`SignerMiddlewareError::FeeBumpTooSmall`].
+    ///
+    /// Blob transactions can not be replaced this way, since their sidecar is not returned by
+    /// the node, and are rejected with [`SignerMiddlewareError::BlobTransactionReplacement`].
+    pub async fn speed_up(
+        &self,
+        pending: &PendingTransaction<'_, M::Provider>,
+        bump_percent: u16,
+    ) -> Result<PendingTransaction<'_, M::Provider>, SignerMiddlewareError<M, S>> {
+        if bump_percent < MIN_FEE_BUMP_PERCENT {
+            return Err(SignerMiddlewareError::FeeBumpTooSmall(bump_percent))
+        }
+        let hash = **pending;
+        let tx = self
+            .inner
+            .get_transaction(hash)
+            .await
+            .map_err(SignerMiddlewareError::MiddlewareError)?
+            .ok_or(SignerMiddlewareError::TransactionNotFound(hash))?;
+        if tx.block_hash.is_some() {
+            return Err(SignerMiddlewareError::TransactionMined(hash))
+        }
+        if tx.from != self.address {
+            return Err(SignerMiddlewareError::WrongSigner)
+        }
+
+        let bump = |fee: &mut Option<U256>| {
+            if let Some(fee) = fee {
+                let bumped = fee.saturating_mul(U256::from(100 + bump_percent as u64));
+                *fee = bumped.saturating_add(99.into()) / 100;
+            }
+        };
+        let mut tx: TypedTransaction = (&tx).into();
+        match &mut tx {
+            TypedTransaction::Eip4844(_) => {
+                return Err(SignerMiddlewareError::BlobTransactionReplacement(hash))
+            }
+            TypedTransaction::Eip1559(inner) |
+            TypedTransaction::Eip7702(Eip7702TransactionRequest { tx: inner, .. }) => {
+                bump(&mut inner.max_fee_per_gas);
+                bump(&mut inner.max_priority_fee_per_gas);
+            }
+            TypedTransaction::Legacy(inner) => bump(&mut inner.gas_price),
+            TypedTransaction::Eip2930(inner) => bump(&mut inner.tx.gas_price),
+            #[cfg(feature = "optimism")]
+            TypedTransaction::DepositTransaction(inner) => bump(&mut inner.tx.gas_price),
+        }
+
+        let signed_tx = self.sign_transaction(tx).await?;
+        self.inner
+            .send_raw_transaction(signed_tx)
+            .await
+            .map_err(SignerMiddlewareError::MiddlewareError)
+    }
+
+    /// Returns the signer's nonce at the forwarder, using `getNonce(address)`
+    async fn forwarder_nonce(
+        &self,
+        forwarder: Address,
+    ) -> Result<U256, SignerMiddlewareError<M, S>> {
+        let mut data = id("getNonce(address)").to_vec();
+        data.extend(abi::encode(&[Token::Address(self.address)]));
+        let tx = TransactionRequest::new().to(forwarder).data(data).into();
+        let output =
+            self.inner.call(&tx, None).await.map_err(SignerMiddlewareError::MiddlewareError)?;
+        let nonce = abi::decode(&[abi::ParamType::Uint(256)], &output)?;
+        Ok(nonce[0].clone().into_uint().unwrap_or_default())
     }
 
     fn set_tx_from_if_none(&self, tx: &TypedTransaction) -> TypedTransaction {
@@ -242,18 +532,27 @@ where
     }
 
     /// Helper for filling a transaction's nonce using the wallet
+    ///
+    /// The `from` field is set to the signer's address if it is unset, a `from` field of another
+    /// address is rejected with [`SignerMiddlewareError::WrongSigner`].
+    ///
+    /// Legacy transactions without a gas price are upgraded to EIP-1559 transactions if the latest
+    /// block has a base fee, unless the chain is known to not support EIP-1559.
     async fn fill_transaction(
         &self,
         tx: &mut TypedTransaction,
         block: Option<BlockId>,
     ) -> Result<(), Self::Error> {
-        // get the `from` field's nonce if it's set, else get the signer's nonce
-        let from = if tx.from().is_some() && tx.from() != Some(&self.address()) {
-            *tx.from().unwrap()
-        } else {
-            self.address
-        };
-        tx.set_from(from);
+        // the transaction is signed by the signer, so a `from` field of another account is a
+        // mistake that would otherwise surface as an invalid signature or a wrong nonce
+        let from = self.address;
+        match tx.from() {
+            Some(addr) if *addr != from => return Err(SignerMiddlewareError::WrongSigner),
+            Some(_) => {}
+            None => {
+                tx.set_from(from);
+            }
+        }
 
         // get the signer's chain_id if the transaction does not set it
         let chain_id = self.signer.chain_id();
@@ -263,12 +562,42 @@ where
 
         // If a chain_id is matched to a known chain that doesn't support EIP-1559, automatically
         // change transaction to be Legacy type.
-        if let Some(chain_id) = tx.chain_id() {
-            let chain = Chain::try_from(chain_id.as_u64());
-            if chain.unwrap_or_default().is_legacy() {
-                if let TypedTransaction::Eip1559(inner) = tx {
-                    let tx_req: TransactionRequest = inner.clone().into();
-                    *tx = TypedTransaction::Legacy(tx_req);
+        let is_legacy_chain = match self.eip1559 {
+            Some(supported) => !supported,
+            None => tx
+                .chain_id()
+                .map(|chain_id| {
+                    !Chain::try_from(chain_id.as_u64()).unwrap_or_default().supports_eip1559()
+                })
+                .unwrap_or_default(),
+        };
+        if is_legacy_chain {
+            if let TypedTransaction::Eip1559(inner) = tx {
+                let tx_req: TransactionRequest = inner.clone().into();
+                *tx = TypedTransaction::Legacy(tx_req);
+            }
+        }
+
+        // Otherwise, if the chain has a base fee, upgrade legacy transactions that do not set a
+        // gas price to EIP-1559, so that the fees are estimated from the fee history. The celo fee
+        // fields have no EIP-1559 equivalent, so transactions using them stay legacy
+        if let TypedTransaction::Legacy(inner) = tx {
+            #[cfg(feature = "celo")]
+            let has_celo_fees = inner.fee_currency.is_some() ||
+                inner.gateway_fee_recipient.is_some() ||
+                inner.gateway_fee.is_some();
+            #[cfg(not(feature = "celo"))]
+            let has_celo_fees = false;
+            if !is_legacy_chain && inner.gas_price.is_none() && !has_celo_fees {
+                let base_fee = self
+                    .inner
+                    .get_block(BlockNumber::Latest)
+                    .await
+                    .map_err(SignerMiddlewareError::MiddlewareError)?
+                    .and_then(|block| block.base_fee_per_gas);
+                if base_fee.is_some() {
+                    let tx_req: Eip1559TransactionRequest = inner.clone().into();
+                    *tx = TypedTransaction::Eip1559(tx_req);
                 }
             }
         }
@@ -295,15 +624,6 @@ where
         // fill any missing fields
         self.fill_transaction(&mut tx, block).await?;
 
-        // If the from address is set and is not our signer, delegate to inner
-        if tx.from().is_some() && tx.from() != Some(&self.address()) {
-            return self
-                .inner
-                .send_transaction(tx, block)
-                .await
-                .map_err(SignerMiddlewareError::MiddlewareError)
-        }
-
         // if we have a nonce manager set, we should try handling the result in
         // case there was a nonce mismatch
         let signed_tx = self.sign_transaction(tx).await?;
@@ -360,10 +680,12 @@ where
 mod tests {
     use super::*;
     use ethers_core::{
-        types::{Eip1559TransactionRequest, TransactionRequest},
+        types::{
+            Block, Eip1559TransactionRequest, FeeHistory, Transaction, TransactionRequest, H256,
+        },
         utils::{self, keccak256, Anvil},
     };
-    use ethers_providers::Provider;
+    use ethers_providers::{MockError, Provider};
     use ethers_signers::LocalWallet;
     use std::convert::TryFrom;
 
@@ -522,11 +844,84 @@ mod tests {
         assert_eq!(tx.from, client.address());
 
         // signing a TransactionRequest with a from address that is not the
-        // signer should result in the default anvil account being used
+        // signer should be rejected
         let request_from_other = request.from(acc);
-        let hash = *client.send_transaction(request_from_other, None).await.unwrap();
-        let tx = client.get_transaction(hash).await.unwrap().unwrap();
-        assert_eq!(tx.from, acc);
+        let err = client.send_transaction(request_from_other, None).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::WrongSigner));
+    }
+
+    #[tokio::test]
+    async fn fills_from_with_signer_address() {
+        let (provider, _) = Provider::mocked();
+        let key = LocalWallet::new(&mut rand::thread_rng()).with_chain_id(1u32);
+        let client = SignerMiddleware::new(provider, key);
+
+        let request = TransactionRequest::new().nonce(0).gas(21_000).gas_price(1).chain_id(1);
+        let mut tx = request.clone().into();
+        client.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.from(), Some(&client.address()));
+
+        let mut tx = request.from(Address::repeat_byte(1)).into();
+        let err = client.fill_transaction(&mut tx, None).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::WrongSigner));
+    }
+
+    #[tokio::test]
+    async fn speeds_up_pending_tx() {
+        let (provider, mock) = Provider::mocked();
+        let key = LocalWallet::new(&mut rand::thread_rng()).with_chain_id(1u32);
+        let client = SignerMiddleware::new(provider, key.clone());
+
+        let hash = TxHash::repeat_byte(1);
+        let original = Transaction {
+            hash,
+            from: client.address(),
+            to: Some(Address::repeat_byte(2)),
+            value: 100.into(),
+            gas: 21_000.into(),
+            nonce: 7.into(),
+            transaction_type: Some(2.into()),
+            max_fee_per_gas: Some(105.into()),
+            max_priority_fee_per_gas: Some(10.into()),
+            chain_id: Some(1.into()),
+            ..Default::default()
+        };
+        let pending = PendingTransaction::new(hash, client.provider());
+
+        let err = client.speed_up(&pending, 9).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::FeeBumpTooSmall(9)));
+
+        let replacement = TxHash::repeat_byte(3);
+        mock.push(replacement).unwrap();
+        mock.push(original.clone()).unwrap();
+        let pending = client.speed_up(&pending, 10).await.unwrap();
+        assert_eq!(*pending, replacement);
+
+        // the fees are bumped by 10%, rounding up
+        let expected: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(client.address())
+            .to(Address::repeat_byte(2))
+            .value(100)
+            .gas(21_000)
+            .nonce(7)
+            .data(Bytes::default())
+            .max_fee_per_gas(116)
+            .max_priority_fee_per_gas(11)
+            .chain_id(1)
+            .into();
+        let signature = key.sign_transaction(&expected).await.unwrap();
+        mock.assert_request("eth_getTransactionByHash", [hash]).unwrap();
+        mock.assert_request("eth_sendRawTransaction", [expected.rlp_signed(&signature)]).unwrap();
+
+        // mined transactions can not be replaced
+        mock.push(Transaction { block_hash: Some(H256::zero()), ..original.clone() }).unwrap();
+        let err = client.speed_up(&pending, 10).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::TransactionMined(_)));
+
+        // nor can blob transactions, whose sidecar is unknown
+        mock.push(Transaction { transaction_type: Some(3.into()), ..original }).unwrap();
+        let err = client.speed_up(&pending, 10).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::BlobTransactionReplacement(_)));
     }
 
     #[tokio::test]
@@ -596,4 +991,183 @@ mod tests {
         assert!(tx.as_legacy_ref().is_none());
         assert_eq!(tx, TypedTransaction::Eip1559(tx.as_eip1559_ref().unwrap().clone()));
     }
+
+    #[tokio::test]
+    async fn upgrades_legacy_tx_without_gas_price_on_eip1559_chain() {
+        let (provider, mock) = Provider::mocked();
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap()
+            .with_chain_id(1u64);
+        let client = SignerMiddleware::new(provider, key);
+
+        let block = Block::<TxHash> { base_fee_per_gas: Some(100u64.into()), ..Default::default() };
+        let fee_history = FeeHistory {
+            base_fee_per_gas: vec![],
+            gas_used_ratio: vec![],
+            oldest_block: U256::zero(),
+            reward: vec![vec![3u64.into()]],
+        };
+        // responses are popped in reverse order
+        mock.push(fee_history).unwrap();
+        mock.push(block.clone()).unwrap();
+        mock.push(block).unwrap();
+
+        let mut tx = TransactionRequest::new()
+            .to(Address::zero())
+            .value(1u64)
+            .gas(21_000u64)
+            .nonce(0u64)
+            .into();
+        client.fill_transaction(&mut tx, None).await.unwrap();
+
+        let tx = tx.as_eip1559_ref().unwrap();
+        assert_eq!(tx.to, Some(Address::zero().into()));
+        assert_eq!(tx.value, Some(1u64.into()));
+        assert_eq!(tx.gas, Some(21_000u64.into()));
+        assert!(tx.max_fee_per_gas.is_some());
+        assert!(tx.max_priority_fee_per_gas.is_some());
+    }
+
+    #[tokio::test]
+    async fn converts_tx_to_legacy_with_eip1559_support_override() {
+        let (provider, mock) = Provider::mocked();
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap()
+            .with_chain_id(1u64);
+        let client = SignerMiddleware::new(provider, key).with_eip1559_support(false);
+
+        mock.push(U256::from(7u64)).unwrap();
+        let mut tx =
+            Eip1559TransactionRequest::new().to(Address::zero()).gas(21_000u64).nonce(0u64).into();
+        client.fill_transaction(&mut tx, None).await.unwrap();
+
+        assert_eq!(tx.as_legacy_ref().unwrap().gas_price, Some(7u64.into()));
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn keeps_legacy_tx_with_gas_price() {
+        let (provider, mock) = Provider::mocked();
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap()
+            .with_chain_id(1u64);
+        let client = SignerMiddleware::new(provider, key);
+
+        let mut tx = TransactionRequest::new()
+            .to(Address::zero())
+            .gas(21_000u64)
+            .gas_price(5u64)
+            .nonce(0u64)
+            .into();
+        client.fill_transaction(&mut tx, None).await.unwrap();
+
+        assert_eq!(tx.as_legacy_ref().unwrap().gas_price, Some(5u64.into()));
+        // no requests were needed to fill the transaction
+        assert!(matches!(
+            mock.assert_request("eth_getBlockByNumber", ()),
+            Err(MockError::EmptyRequests)
+        ));
+    }
+
+    fn forwarder() -> MetaTxForwarder {
+        MetaTxForwarder::new(EIP712Domain {
+            name: Some("MinimalForwarder".to_string()),
+            version: Some("0.0.1".to_string()),
+            chain_id: Some(1.into()),
+            verifying_contract: Some(Address::repeat_byte(0xfa)),
+            salt: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn signs_meta_tx() {
+        use ethers_core::types::transaction::eip712::Eip712;
+
+        let (provider, mock) = Provider::mocked();
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let client = SignerMiddleware::new(provider, wallet.clone()).with_forwarder(forwarder());
+
+        // the forwarder's nonce of the signer
+        mock.push::<Bytes, _>(Bytes::from(abi::encode(&[Token::Uint(7.into())]))).unwrap();
+        let tx = TransactionRequest::new()
+            .to(Address::repeat_byte(1))
+            .value(3u64)
+            .gas(100_000u64)
+            .data(vec![0xca, 0xfe]);
+        let signed = client.sign_meta_tx(tx).await.unwrap();
+
+        assert_eq!(
+            signed.request,
+            ForwardRequest {
+                from: wallet.address(),
+                to: Address::repeat_byte(1),
+                value: 3.into(),
+                gas: 100_000.into(),
+                nonce: 7.into(),
+                data: vec![0xca, 0xfe].into(),
+            }
+        );
+
+        let mut data = id("getNonce(address)").to_vec();
+        data.extend(abi::encode(&[Token::Address(wallet.address())]));
+        let call: TypedTransaction =
+            TransactionRequest::new().to(Address::repeat_byte(0xfa)).data(data).into();
+        mock.assert_request("eth_call", (call, "latest")).unwrap();
+
+        // the request is signed as the forwarder's EIP-712 struct
+        let type_hash = keccak256(
+            "ForwardRequest(address from,address to,uint256 value,uint256 gas,uint256 nonce,bytes data)",
+        );
+        let struct_hash = keccak256(abi::encode(&[
+            Token::FixedBytes(type_hash.to_vec()),
+            Token::Address(wallet.address()),
+            Token::Address(Address::repeat_byte(1)),
+            Token::Uint(3.into()),
+            Token::Uint(100_000.into()),
+            Token::Uint(7.into()),
+            Token::FixedBytes(keccak256([0xca, 0xfe]).to_vec()),
+        ]));
+        let typed_data = forwarder().typed_data(&signed.request);
+        assert_eq!(typed_data.struct_hash().unwrap(), struct_hash);
+
+        let digest = typed_data.encode_eip712().unwrap();
+        assert_eq!(signed.signature.recover(digest).unwrap(), wallet.address());
+    }
+
+    #[tokio::test]
+    async fn signs_meta_tx_with_custom_request_type() {
+        let (provider, _) = Provider::mocked();
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let fields = [("from", "address"), ("nonce", "uint256"), ("data", "bytes")]
+            .map(|(name, ty)| Eip712DomainType { name: name.to_string(), r#type: ty.to_string() });
+        let forwarder = forwarder().request_type("Relay", fields);
+        let client = SignerMiddleware::new(provider, wallet.clone()).with_forwarder(forwarder);
+
+        let tx = TransactionRequest::new().to(Address::repeat_byte(1)).gas(1u64).nonce(2u64);
+        let signed = client.sign_meta_tx(tx).await.unwrap();
+        assert_eq!(signed.request.nonce, 2.into());
+
+        let typed_data = client.forwarder.as_ref().unwrap().typed_data(&signed.request);
+        assert_eq!(typed_data.primary_type, "Relay");
+        let digest =
+            ethers_core::types::transaction::eip712::Eip712::encode_eip712(&typed_data).unwrap();
+        assert_eq!(signed.signature.recover(digest).unwrap(), wallet.address());
+    }
+
+    #[tokio::test]
+    async fn sign_meta_tx_requires_forwarder() {
+        let (provider, _) = Provider::mocked();
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let client = SignerMiddleware::new(provider, wallet);
+
+        let tx = TransactionRequest::new().to(Address::repeat_byte(1));
+        let err = client.sign_meta_tx(tx).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::ForwarderMissing));
+    }
 }
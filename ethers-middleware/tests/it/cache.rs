@@ -0,0 +1,118 @@
+use ethers_core::types::{Address, BlockNumber, Bytes, TransactionReceipt, H256, U256, U64};
+use ethers_middleware::cache::{CacheMiddleware, CachedMethod};
+use ethers_providers::{Middleware, Provider};
+
+fn receipt(block_number: u64) -> TransactionReceipt {
+    TransactionReceipt {
+        transaction_hash: H256::repeat_byte(1),
+        block_number: Some(U64::from(block_number)),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn caches_chain_id() {
+    let (provider, mock) = Provider::mocked();
+    let provider = CacheMiddleware::new(provider);
+    mock.push(U256::from(1)).unwrap();
+
+    assert_eq!(provider.get_chainid().await.unwrap(), U256::from(1));
+    assert_eq!(provider.get_chainid().await.unwrap(), U256::from(1));
+    mock.assert_request("eth_chainId", ()).unwrap();
+    assert!(mock.assert_request("eth_chainId", ()).is_err());
+}
+
+#[tokio::test]
+async fn caches_code_of_contracts() {
+    let (provider, mock) = Provider::mocked();
+    let provider = CacheMiddleware::new(provider);
+    let contract = Address::repeat_byte(1);
+    let code = Bytes::from(vec![0x60, 0x80]);
+    mock.push::<Bytes, _>(code.clone()).unwrap();
+
+    assert_eq!(provider.get_code(contract, None).await.unwrap(), code);
+    assert_eq!(provider.get_code(contract, Some(BlockNumber::Latest.into())).await.unwrap(), code);
+    mock.assert_request("eth_getCode", (contract, "latest")).unwrap();
+    assert!(mock.assert_request("eth_getCode", (contract, "latest")).is_err());
+}
+
+#[tokio::test]
+async fn does_not_cache_empty_or_historical_code() {
+    let (provider, mock) = Provider::mocked();
+    let provider = CacheMiddleware::new(provider);
+    let address = Address::repeat_byte(1);
+    let code = Bytes::from(vec![0x60, 0x80]);
+    mock.push::<Bytes, _>(code.clone()).unwrap();
+    mock.push::<Bytes, _>(Bytes::default()).unwrap();
+    mock.push::<Bytes, _>(Bytes::default()).unwrap();
+
+    assert!(provider.get_code(address, None).await.unwrap().is_empty());
+    assert!(provider
+        .get_code(address, Some(BlockNumber::from(1).into()))
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(provider.get_code(address, None).await.unwrap(), code);
+}
+
+#[tokio::test]
+async fn does_not_cache_delegated_code() {
+    let (provider, mock) = Provider::mocked();
+    let provider = CacheMiddleware::new(provider);
+    let account = Address::repeat_byte(1);
+    let delegation = |target: u8| {
+        Bytes::from([&[0xef, 0x01, 0x00][..], Address::repeat_byte(target).as_bytes()].concat())
+    };
+    mock.push::<Bytes, _>(delegation(3)).unwrap();
+    mock.push::<Bytes, _>(delegation(2)).unwrap();
+
+    assert_eq!(provider.get_code(account, None).await.unwrap(), delegation(2));
+    assert_eq!(provider.get_code(account, None).await.unwrap(), delegation(3));
+}
+
+#[tokio::test]
+async fn caches_receipts_once_confirmed() {
+    let (provider, mock) = Provider::mocked();
+    let provider = CacheMiddleware::new(provider).receipt_confirmations(5);
+    let hash = H256::repeat_byte(1);
+
+    // not deep enough yet
+    mock.push(U64::from(14)).unwrap();
+    mock.push(receipt(10)).unwrap();
+    assert_eq!(provider.get_transaction_receipt(hash).await.unwrap(), Some(receipt(10)));
+
+    mock.push(U64::from(15)).unwrap();
+    mock.push(receipt(10)).unwrap();
+    assert_eq!(provider.get_transaction_receipt(hash).await.unwrap(), Some(receipt(10)));
+
+    // served from the cache
+    assert_eq!(provider.get_transaction_receipt(hash).await.unwrap(), Some(receipt(10)));
+    mock.assert_request("eth_getTransactionReceipt", [hash]).unwrap();
+    mock.assert_request("eth_blockNumber", ()).unwrap();
+    mock.assert_request("eth_getTransactionReceipt", [hash]).unwrap();
+    mock.assert_request("eth_blockNumber", ()).unwrap();
+    assert!(mock.assert_request("eth_getTransactionReceipt", [hash]).is_err());
+}
+
+#[tokio::test]
+async fn clears_cache() {
+    let (provider, mock) = Provider::mocked();
+    let provider = CacheMiddleware::new(provider);
+    mock.push(U256::from(2)).unwrap();
+    mock.push(U256::from(1)).unwrap();
+
+    assert_eq!(provider.get_chainid().await.unwrap(), U256::from(1));
+    provider.clear_cache();
+    assert_eq!(provider.get_chainid().await.unwrap(), U256::from(2));
+}
+
+#[tokio::test]
+async fn only_caches_configured_methods() {
+    let (provider, mock) = Provider::mocked();
+    let provider = CacheMiddleware::new(provider).methods([CachedMethod::Code]);
+    mock.push(U256::from(2)).unwrap();
+    mock.push(U256::from(1)).unwrap();
+
+    assert_eq!(provider.get_chainid().await.unwrap(), U256::from(1));
+    assert_eq!(provider.get_chainid().await.unwrap(), U256::from(2));
+}
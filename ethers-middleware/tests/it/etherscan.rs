@@ -0,0 +1,105 @@
+use ethers_core::{
+    abi::{Abi, Function, Param, ParamType, StateMutability},
+    types::{Address, BigEndianHash, H256, U256},
+    utils::keccak256,
+};
+use ethers_etherscan::Client;
+use ethers_middleware::etherscan::{
+    eip1967_implementation, etherscan_contract, EIP1967_IMPLEMENTATION_SLOT,
+};
+use ethers_providers::Provider;
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::Arc,
+    thread::JoinHandle,
+};
+
+/// Serves a single Etherscan API request responding with `abi`, returns the request line
+fn serve_abi(abi: &Abi) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/api", listener.local_addr().unwrap());
+    let body = serde_json::json!({
+        "status": "1",
+        "message": "OK",
+        "result": serde_json::to_string(abi).unwrap(),
+    })
+    .to_string();
+
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let n = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..n]);
+        }
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        String::from_utf8(request).unwrap().lines().next().unwrap().to_string()
+    });
+    (url, handle)
+}
+
+#[allow(deprecated)]
+fn balance_of_abi() -> Abi {
+    let function = Function {
+        name: "balanceOf".to_string(),
+        inputs: vec![Param {
+            name: "owner".to_string(),
+            kind: ParamType::Address,
+            internal_type: None,
+        }],
+        outputs: vec![Param {
+            name: "".to_string(),
+            kind: ParamType::Uint(256),
+            internal_type: None,
+        }],
+        constant: None,
+        state_mutability: StateMutability::View,
+    };
+    let mut abi = Abi::default();
+    abi.functions.insert(function.name.clone(), vec![function]);
+    abi
+}
+
+#[test]
+fn eip1967_implementation_slot() {
+    let slot = U256::from(keccak256("eip1967.proxy.implementation")) - 1;
+    assert_eq!(EIP1967_IMPLEMENTATION_SLOT, H256::from_uint(&slot));
+}
+
+#[tokio::test]
+async fn loads_contract_through_proxy() {
+    let proxy = Address::repeat_byte(1);
+    let implementation = Address::repeat_byte(2);
+    let (provider, mock) = Provider::mocked();
+    mock.push(H256::from(implementation)).unwrap();
+
+    let abi = balance_of_abi();
+    let (url, server) = serve_abi(&abi);
+    let etherscan =
+        Client::builder().with_url(&url).unwrap().with_api_url(&url).unwrap().build().unwrap();
+
+    let contract = etherscan_contract(&etherscan, proxy, Arc::new(provider)).await.unwrap();
+    assert_eq!(contract.address(), proxy);
+    assert_eq!(contract.abi(), &abi);
+    assert!(contract.method::<_, U256>("balanceOf", proxy).is_ok());
+
+    // the ABI of the implementation was requested
+    let request = server.join().unwrap();
+    assert!(request.contains(&format!("address={implementation:?}")), "{request}");
+    let slot = EIP1967_IMPLEMENTATION_SLOT.into_uint();
+    mock.assert_request("eth_getStorageAt", (proxy, slot, "latest")).unwrap();
+}
+
+#[tokio::test]
+async fn detects_non_proxy_contracts() {
+    let (provider, mock) = Provider::mocked();
+    mock.push(H256::zero()).unwrap();
+    assert_eq!(eip1967_implementation(&provider, Address::repeat_byte(1)).await.unwrap(), None);
+}
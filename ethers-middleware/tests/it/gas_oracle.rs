@@ -1,8 +1,8 @@
 use async_trait::async_trait;
 use ethers_core::{types::*, utils::Anvil};
 use ethers_middleware::gas_oracle::{
-    BlockNative, Etherchain, GasNow, GasOracle, GasOracleError, GasOracleMiddleware, Polygon,
-    ProviderOracle, Result,
+    BlockNative, Etherchain, GasNow, GasOracle, GasOracleError, GasOracleMiddleware, Median,
+    Polygon, ProviderOracle, Result,
 };
 use ethers_providers::{Http, Middleware, Provider};
 
@@ -23,6 +23,21 @@ impl GasOracle for FakeGasOracle {
     }
 }
 
+#[derive(Debug)]
+struct FailingGasOracle;
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GasOracle for FailingGasOracle {
+    async fn fetch(&self) -> Result<U256> {
+        Err(GasOracleError::InvalidResponse)
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256)> {
+        Err(GasOracleError::InvalidResponse)
+    }
+}
+
 #[tokio::test]
 async fn provider_using_gas_oracle() {
     let anvil = Anvil::new().spawn();
@@ -123,3 +138,54 @@ async fn polygon() {
     let gas_price = polygon_oracle.fetch().await.unwrap();
     assert!(gas_price > U256::zero());
 }
+
+#[tokio::test]
+async fn median_ignores_failing_oracles() {
+    let mut median = Median::new();
+    median.add(FakeGasOracle { gas_price: 10.into() });
+    median.add(FailingGasOracle);
+    median.add(FakeGasOracle { gas_price: 30.into() });
+    median.add(FakeGasOracle { gas_price: 20.into() });
+    median.set_quorum(3);
+
+    assert_eq!(median.fetch().await.unwrap(), 20.into());
+}
+
+#[tokio::test]
+async fn median_requires_quorum() {
+    let mut median = Median::new();
+    median.add(FakeGasOracle { gas_price: 10.into() });
+    median.add(FailingGasOracle);
+    median.add(FailingGasOracle);
+    median.set_quorum(2);
+
+    match median.fetch().await.unwrap_err() {
+        GasOracleError::QuorumNotReached { quorum, successes, failures } => {
+            assert_eq!(quorum, 2);
+            assert_eq!(successes, 1);
+            assert_eq!(failures.len(), 2);
+            assert!(failures.iter().all(|(oracle, err)| oracle == "FailingGasOracle" &&
+                matches!(err, GasOracleError::InvalidResponse)));
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+}
+
+#[tokio::test]
+async fn median_without_values() {
+    let mut median = Median::new();
+    median.add(FailingGasOracle);
+    median.add(FailingGasOracle);
+
+    match median.fetch().await.unwrap_err() {
+        GasOracleError::QuorumNotReached { quorum, successes, failures } => {
+            assert_eq!(quorum, 1);
+            assert_eq!(successes, 0);
+            assert_eq!(failures.len(), 2);
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+
+    let median = Median::new();
+    assert!(matches!(median.fetch().await.unwrap_err(), GasOracleError::NoValues));
+}
@@ -8,10 +8,17 @@ use std::time::Duration;
 
 mod builder;
 
+mod cache;
+
+#[cfg(feature = "etherscan")]
+mod etherscan;
+
 mod gas_escalator;
 
 mod gas_oracle;
 
+mod retry;
+
 #[cfg(not(feature = "celo"))]
 mod signer;
 
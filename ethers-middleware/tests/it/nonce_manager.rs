@@ -1,7 +1,7 @@
 use crate::spawn_anvil;
 use ethers_core::types::*;
-use ethers_middleware::MiddlewareBuilder;
-use ethers_providers::Middleware;
+use ethers_middleware::{MiddlewareBuilder, NonceManagerMiddleware};
+use ethers_providers::{JsonRpcError, Middleware, MockResponse, Provider};
 
 #[tokio::test]
 async fn nonce_manager() {
@@ -36,3 +36,55 @@ async fn nonce_manager() {
 
     assert_eq!(nonces, (nonce..nonce + num_tx as u64).collect::<Vec<_>>());
 }
+
+#[tokio::test]
+async fn nonce_manager_resync() {
+    let (provider, mock) = Provider::mocked();
+    let address = Address::from_low_u64_be(1);
+    let nonce_manager = NonceManagerMiddleware::new(provider, address);
+
+    mock.push(U256::from(7)).unwrap();
+    assert_eq!(nonce_manager.resync().await.unwrap(), 7.into());
+    mock.assert_request("eth_getTransactionCount", (address, "pending")).unwrap();
+    assert_eq!(nonce_manager.next(), 7.into());
+    assert_eq!(nonce_manager.next(), 8.into());
+
+    // the counter may run ahead of the chain, e.g. if a transaction was dropped
+    mock.push(U256::from(7)).unwrap();
+    assert_eq!(nonce_manager.resync().await.unwrap(), 7.into());
+    assert_eq!(nonce_manager.next(), 7.into());
+}
+
+#[tokio::test]
+async fn nonce_manager_auto_resync() {
+    let (provider, mock) = Provider::mocked();
+    let address = Address::from_low_u64_be(1);
+    let nonce_manager = NonceManagerMiddleware::new(provider, address).auto_resync(2);
+
+    let tx = TransactionRequest::new()
+        .from(address)
+        .to(Address::from_low_u64_be(2))
+        .gas(21_000)
+        .gas_price(1)
+        .nonce(3);
+    let nonce_too_low = || {
+        MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: "nonce too low".to_string(),
+            data: None,
+        })
+    };
+
+    // responses are popped from the back: the failed send, then the transaction count used to
+    // check for a nonce mismatch
+    mock.push(U256::zero()).unwrap();
+    mock.push_response(nonce_too_low());
+    nonce_manager.send_transaction(tx.clone(), None).await.unwrap_err();
+
+    // the second consecutive nonce error resyncs with the pending transaction count
+    mock.push(U256::from(5)).unwrap();
+    mock.push(U256::zero()).unwrap();
+    mock.push_response(nonce_too_low());
+    nonce_manager.send_transaction(tx, None).await.unwrap_err();
+    assert_eq!(nonce_manager.next(), 5.into());
+}
@@ -0,0 +1,103 @@
+use ethers_core::types::U64;
+use ethers_middleware::{
+    retry::{DefaultRetryPolicy, RetryMiddleware},
+    GasGuardMiddleware,
+};
+use ethers_providers::{JsonRpcError, Middleware, MockProvider, MockResponse, Provider};
+use std::time::{Duration, Instant};
+
+fn retry_middleware(max_retries: u32) -> (RetryMiddleware<Provider<MockProvider>>, MockProvider) {
+    let (provider, mock) = Provider::mocked();
+    let provider = RetryMiddleware::new(
+        provider,
+        Box::<DefaultRetryPolicy>::default(),
+        max_retries,
+        Duration::from_millis(20),
+    );
+    (provider, mock)
+}
+
+fn rate_limited() -> MockResponse {
+    MockResponse::Error(JsonRpcError {
+        code: 429,
+        message: "Too many requests".to_string(),
+        data: None,
+    })
+}
+
+#[tokio::test]
+async fn retries_rate_limited_requests() {
+    let (provider, mock) = retry_middleware(5);
+
+    // responses are popped from the back
+    mock.push(U64::from(42)).unwrap();
+    mock.push_response(rate_limited());
+    mock.push_response(rate_limited());
+
+    let start = Instant::now();
+    let block_number = provider.get_block_number().await.unwrap();
+    assert_eq!(block_number, 42.into());
+
+    // backs off for at least half of 20ms and 40ms
+    assert!(start.elapsed() >= Duration::from_millis(30));
+    for _ in 0..3 {
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+    }
+    mock.assert_request("eth_blockNumber", ()).unwrap_err();
+}
+
+#[tokio::test]
+async fn does_not_retry_reverts() {
+    let (provider, mock) = retry_middleware(5);
+
+    mock.push(U64::from(42)).unwrap();
+    mock.push_response(MockResponse::Error(JsonRpcError {
+        code: 3,
+        message: "execution reverted".to_string(),
+        data: None,
+    }));
+
+    let err = provider.get_block_number().await.unwrap_err();
+    assert!(err.to_string().contains("execution reverted"));
+    mock.assert_request("eth_blockNumber", ()).unwrap();
+    mock.assert_request("eth_blockNumber", ()).unwrap_err();
+}
+
+#[tokio::test]
+async fn gives_up_after_max_retries() {
+    let (provider, mock) = retry_middleware(1);
+
+    mock.push(U64::from(42)).unwrap();
+    mock.push_response(rate_limited());
+    mock.push_response(rate_limited());
+
+    let err = provider.get_block_number().await.unwrap_err();
+    assert!(err.to_string().contains("Too many requests"));
+    mock.assert_request("eth_blockNumber", ()).unwrap();
+    mock.assert_request("eth_blockNumber", ()).unwrap();
+    mock.assert_request("eth_blockNumber", ()).unwrap_err();
+}
+
+#[tokio::test]
+async fn retries_raw_requests() {
+    let (provider, mock) = retry_middleware(5);
+    // raw requests are forwarded through middlewares that do not override them
+    let provider = GasGuardMiddleware::new(provider, 100);
+
+    let header = serde_json::json!({ "number": "0x1" });
+    mock.push(header.clone()).unwrap();
+    mock.push_response(rate_limited());
+    let res: serde_json::Value =
+        provider.request_raw("erigon_getHeaderByNumber", [U64::from(1)]).await.unwrap();
+    assert_eq!(res, header);
+    for _ in 0..2 {
+        mock.assert_request("erigon_getHeaderByNumber", [U64::from(1)]).unwrap();
+    }
+
+    // transactions are never submitted twice
+    mock.push_response(rate_limited());
+    let err = provider.request_raw::<_, String>("eth_sendRawTransaction", ["0x00"]).await;
+    assert!(err.is_err());
+    mock.assert_request("eth_sendRawTransaction", ["0x00"]).unwrap();
+    mock.assert_request("eth_sendRawTransaction", ["0x00"]).unwrap_err();
+}
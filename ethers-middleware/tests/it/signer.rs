@@ -1,6 +1,9 @@
 use crate::{get_wallet, spawn_anvil, spawn_anvil_ws};
 use ethers_core::types::*;
-use ethers_middleware::{signer::SignerMiddleware, MiddlewareBuilder};
+use ethers_middleware::{
+    signer::{SignerMiddleware, SignerMiddlewareError},
+    MiddlewareBuilder,
+};
 use ethers_providers::{JsonRpcClient, Middleware};
 use ethers_signers::{LocalWallet, Signer};
 
@@ -86,13 +89,10 @@ async fn send_transaction_handles_tx_from_field() {
     assert_eq!(sent_tx.from, signer.address());
 
     // sending a TransactionRequest with a from address that is not the signer
-    // should result in a transaction from the specified address
+    // should be rejected
     let request_from_other = TransactionRequest::new().from(other.address());
-    let receipt =
-        provider.send_transaction(request_from_other, None).await.unwrap().await.unwrap().unwrap();
-    let sent_tx = provider.get_transaction(receipt.transaction_hash).await.unwrap().unwrap();
-
-    assert_eq!(sent_tx.from, other.address());
+    let err = provider.send_transaction(request_from_other, None).await.unwrap_err();
+    assert!(matches!(err, SignerMiddlewareError::WrongSigner));
 }
 
 async fn check_tx<P: JsonRpcClient + Clone>(
@@ -1,4 +1,5 @@
-use std::{error::Error, fmt::Debug};
+use ethers_core::types::{Bytes, TransactionReceipt};
+use std::{error::Error, fmt, fmt::Debug};
 use thiserror::Error;
 
 use crate::JsonRpcError;
@@ -175,6 +176,45 @@ pub enum ProviderError {
     /// Signer is not available to this provider.
     #[error("Attempted to sign a transaction with no available signer. Hint: did you mean to use a SignerMiddleware?")]
     SignerUnavailable,
+
+    /// A transaction was mined but reverted, see
+    /// [`Middleware::send_transaction_and_confirm`](crate::Middleware::send_transaction_and_confirm)
+    #[error("{0}")]
+    TransactionReverted(Box<RevertedTransaction>),
+}
+
+/// A mined transaction that reverted, along with the revert reason recovered by re-executing it.
+#[derive(Clone, Debug)]
+pub struct RevertedTransaction {
+    /// The receipt of the failed transaction
+    pub receipt: TransactionReceipt,
+    /// The revert data returned when re-executing the transaction via `eth_call`, `None` if the
+    /// re-execution did not revert
+    pub revert_data: Option<Bytes>,
+}
+
+impl RevertedTransaction {
+    /// Decodes the revert data as a revert reason string, i.e. the data of `Error(string)`
+    pub fn reason(&self) -> Option<String> {
+        const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+        let data = self.revert_data.as_ref()?.strip_prefix(&ERROR_SELECTOR)?;
+        match ethers_core::abi::decode(&[ethers_core::abi::ParamType::String], data).ok()?.pop()? {
+            ethers_core::abi::Token::String(reason) => Some(reason),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RevertedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transaction {:?} reverted", self.receipt.transaction_hash)?;
+        match (self.reason(), &self.revert_data) {
+            (Some(reason), _) => write!(f, ": {reason}"),
+            (None, Some(data)) if !data.is_empty() => write!(f, " with data {data}"),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl RpcError for ProviderError {
@@ -216,4 +256,8 @@ impl MiddlewareError for ProviderError {
         // prevents infinite loops
         None
     }
+
+    fn as_provider_error(&self) -> Option<&ProviderError> {
+        Some(self)
+    }
 }
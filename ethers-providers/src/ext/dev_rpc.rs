@@ -35,14 +35,66 @@
 
 use crate::{Middleware, MiddlewareError, ProviderError};
 use async_trait::async_trait;
-use ethers_core::types::U256;
+use ethers_core::types::{serde_helpers::StringifiedNumeric, Address, Bytes, H256, U256, U64};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 /// `DevRpcMiddleware`
+///
+/// Exposes the cheatcodes of development nodes, e.g. manipulating the time, the state of accounts
+/// or impersonating accounts. The node specific `anvil_*` and `hardhat_*` methods are sent with
+/// the prefix of the [`DevNode`], the `evm_*` methods are supported by both.
 #[derive(Clone, Debug)]
-pub struct DevRpcMiddleware<M>(M);
+pub struct DevRpcMiddleware<M> {
+    inner: M,
+    node: DevNode,
+}
+
+/// The development node a [`DevRpcMiddleware`] is connected to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DevNode {
+    /// Anvil, which also supports the `hardhat_*` aliases
+    #[default]
+    Anvil,
+    /// Hardhat Network
+    Hardhat,
+}
+
+impl DevNode {
+    /// Returns the prefix of the node specific methods
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            DevNode::Anvil => "anvil",
+            DevNode::Hardhat => "hardhat",
+        }
+    }
+}
+
+/// The ID of a snapshot of the state of a development node, see [`DevRpcMiddleware::snapshot`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SnapshotId(U256);
+
+impl SnapshotId {
+    /// Returns the ID returned by the node
+    pub fn id(&self) -> U256 {
+        self.0
+    }
+}
+
+impl From<U256> for SnapshotId {
+    fn from(id: U256) -> Self {
+        Self(id)
+    }
+}
+
+impl fmt::Display for SnapshotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
 
 /// DevRpcMiddleware Errors
 #[derive(Error, Debug)]
@@ -67,7 +119,7 @@ impl<M: Middleware> Middleware for DevRpcMiddleware<M> {
     type Inner = M;
 
     fn inner(&self) -> &M {
-        &self.0
+        &self.inner
     }
 }
 
@@ -96,9 +148,30 @@ where
 }
 
 impl<M: Middleware> DevRpcMiddleware<M> {
-    /// Instantiate a new `DevRpcMiddleware`
+    /// Instantiate a new `DevRpcMiddleware` for an Anvil node
     pub fn new(inner: M) -> Self {
-        Self(inner)
+        Self::with_node(inner, DevNode::Anvil)
+    }
+
+    /// Instantiate a new `DevRpcMiddleware` for the given node
+    pub fn with_node(inner: M, node: DevNode) -> Self {
+        Self { inner, node }
+    }
+
+    /// Returns the node the middleware sends the node specific methods to
+    pub fn node(&self) -> DevNode {
+        self.node
+    }
+
+    /// Sends a node specific method, e.g. `setBalance` as `anvil_setBalance`, ignoring its
+    /// result since Anvil returns `null` and Hardhat `true`
+    async fn request_node<T>(&self, method: &str, params: T) -> Result<(), DevRpcMiddlewareError<M>>
+    where
+        T: Debug + Serialize + Send + Sync,
+    {
+        let method = format!("{}_{method}", self.node.prefix());
+        self.provider().request::<T, serde_json::Value>(&method, params).await?;
+        Ok(())
     }
 
     /// Create a new snapshot on the DevRpc node. Return the Snapshot ID
@@ -106,15 +179,17 @@ impl<M: Middleware> DevRpcMiddleware<M> {
     /// ### Note
     ///
     /// Ganache, Hardhat and Anvil increment snapshot ID even if no state has changed
-    pub async fn snapshot(&self) -> Result<U256, DevRpcMiddlewareError<M>> {
-        self.provider().request::<(), U256>("evm_snapshot", ()).await.map_err(From::from)
+    pub async fn snapshot(&self) -> Result<SnapshotId, DevRpcMiddlewareError<M>> {
+        self.provider().request::<(), SnapshotId>("evm_snapshot", ()).await.map_err(From::from)
     }
 
     /// Revert the state of the DevRpc node to the Snapshot, specified by its ID
-    pub async fn revert_to_snapshot(&self, id: U256) -> Result<(), DevRpcMiddlewareError<M>> {
+    ///
+    /// Snapshots can only be reverted to once, and reverting invalidates all later snapshots.
+    pub async fn revert_to_snapshot(&self, id: SnapshotId) -> Result<(), DevRpcMiddlewareError<M>> {
         let ok = self
             .provider()
-            .request::<[U256; 1], bool>("evm_revert", [id])
+            .request::<[SnapshotId; 1], bool>("evm_revert", [id])
             .await
             .map_err(DevRpcMiddlewareError::ProviderError)?;
         if ok {
@@ -123,6 +198,98 @@ impl<M: Middleware> DevRpcMiddleware<M> {
             Err(DevRpcMiddlewareError::NoSnapshot)
         }
     }
+
+    /// Mines a single block, including the pending transactions
+    pub async fn mine_block(&self) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.provider().request::<(), serde_json::Value>("evm_mine", ()).await?;
+        Ok(())
+    }
+
+    /// Mines `blocks` blocks in a single request
+    pub async fn mine(&self, blocks: u64) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.request_node("mine", [U64::from(blocks)]).await
+    }
+
+    /// Enables or disables mining a block for every transaction that is sent
+    pub async fn set_automine(&self, enabled: bool) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.provider().request::<_, serde_json::Value>("evm_setAutomine", [enabled]).await?;
+        Ok(())
+    }
+
+    /// Moves the time of the node forward by `seconds`, taking effect in the next block. Returns
+    /// the total time adjustment in seconds
+    pub async fn increase_time(&self, seconds: u64) -> Result<U256, DevRpcMiddlewareError<M>> {
+        let total =
+            self.provider().request::<_, serde_json::Value>("evm_increaseTime", [seconds]).await?;
+        let total: StringifiedNumeric =
+            serde_json::from_value(total).map_err(ProviderError::SerdeJson)?;
+        U256::try_from(total).map_err(|e| ProviderError::CustomError(e).into())
+    }
+
+    /// Sets the timestamp of the next block
+    pub async fn set_next_block_timestamp(
+        &self,
+        timestamp: u64,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.provider()
+            .request::<_, serde_json::Value>("evm_setNextBlockTimestamp", [timestamp])
+            .await?;
+        Ok(())
+    }
+
+    /// Sets the balance of the `address` in wei
+    pub async fn set_balance(
+        &self,
+        address: Address,
+        balance: U256,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.request_node("setBalance", (address, balance)).await
+    }
+
+    /// Sets the nonce of the `address`
+    pub async fn set_nonce(
+        &self,
+        address: Address,
+        nonce: U256,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.request_node("setNonce", (address, nonce)).await
+    }
+
+    /// Sets the runtime bytecode of the `address`
+    pub async fn set_code(
+        &self,
+        address: Address,
+        code: Bytes,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.request_node("setCode", (address, code)).await
+    }
+
+    /// Sets the value of the storage `slot` of the `address`
+    pub async fn set_storage_at(
+        &self,
+        address: Address,
+        slot: H256,
+        value: H256,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.request_node("setStorageAt", (address, slot, value)).await
+    }
+
+    /// Allows sending transactions from the `address` without its private key, the transactions
+    /// have to be sent with `eth_sendTransaction`
+    pub async fn impersonate_account(
+        &self,
+        address: Address,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.request_node("impersonateAccount", [address]).await
+    }
+
+    /// Stops impersonating the `address`, see [`DevRpcMiddleware::impersonate_account`]
+    pub async fn stop_impersonating_account(
+        &self,
+        address: Address,
+    ) -> Result<(), DevRpcMiddlewareError<M>> {
+        self.request_node("stopImpersonatingAccount", [address]).await
+    }
 }
 
 #[cfg(test)]
@@ -188,4 +355,37 @@ mod tests {
         assert_eq!(block, block0);
         assert_eq!(time, time0);
     }
+
+    #[tokio::test]
+    async fn sends_node_specific_methods() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+
+        let client = DevRpcMiddleware::new(provider.clone());
+        mock.push(()).unwrap();
+        client.set_balance(address, 100.into()).await.unwrap();
+        mock.assert_request("anvil_setBalance", (address, U256::from(100))).unwrap();
+
+        let client = DevRpcMiddleware::with_node(provider, DevNode::Hardhat);
+        mock.push(true).unwrap();
+        client.impersonate_account(address).await.unwrap();
+        mock.assert_request("hardhat_impersonateAccount", [address]).unwrap();
+        mock.push(true).unwrap();
+        client.mine(3).await.unwrap();
+        mock.assert_request("hardhat_mine", [U64::from(3)]).unwrap();
+
+        mock.push(U256::from(5)).unwrap();
+        let id = client.snapshot().await.unwrap();
+        assert_eq!(id.id(), 5.into());
+        mock.push(false).unwrap();
+        let err = client.revert_to_snapshot(id).await.unwrap_err();
+        assert!(matches!(err, DevRpcMiddlewareError::NoSnapshot));
+        mock.assert_request("evm_snapshot", ()).unwrap();
+        mock.assert_request("evm_revert", ["0x5"]).unwrap();
+
+        // hardhat returns the time adjustment as a decimal string
+        mock.push::<&str, _>("3600").unwrap();
+        assert_eq!(client.increase_time(3600).await.unwrap(), 3600.into());
+        mock.assert_request("evm_increaseTime", [3600]).unwrap();
+    }
 }
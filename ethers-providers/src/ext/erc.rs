@@ -102,3 +102,36 @@ pub fn http_link_ipfs(url: Url) -> Result<Url, String> {
         .join(url.to_string().trim_start_matches("ipfs://").trim_start_matches("ipfs/"))
         .map_err(|e| e.to_string())
 }
+
+/// Returns a URL the image at `url` can be loaded from over HTTP(S), or the `data:` URL itself.
+///
+/// IPFS links are translated to an IPFS gateway URL.
+pub fn http_link(url: Url) -> Result<Url, String> {
+    match url.scheme() {
+        "https" | "http" | "data" => Ok(url),
+        "ipfs" => http_link_ipfs(url),
+        scheme => Err(format!("Unsupported scheme: {scheme}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_get_http_link() {
+        for url in ["https://i.imgur.com/YW3Hzph.jpg", "http://example.com/a.png", "data:,Hello"] {
+            let url = Url::parse(url).unwrap();
+            assert_eq!(http_link(url.clone()).unwrap(), url);
+        }
+
+        let url = Url::parse("ipfs://QmYA6ZpEARgHvRHZQdFPynMMX8NtdL2JCadvyuyG2oA88u").unwrap();
+        assert_eq!(
+            http_link(url).unwrap().as_str(),
+            "https://ipfs.io/ipfs/QmYA6ZpEARgHvRHZQdFPynMMX8NtdL2JCadvyuyG2oA88u"
+        );
+
+        let url = Url::parse("ftp://example.com/a.png").unwrap();
+        assert_eq!(http_link(url).unwrap_err(), "Unsupported scheme: ftp");
+    }
+}
@@ -10,4 +10,4 @@ pub mod erc;
 #[cfg(feature = "dev-rpc")]
 pub mod dev_rpc;
 #[cfg(feature = "dev-rpc")]
-pub use dev_rpc::{DevRpcMiddleware, DevRpcMiddlewareError};
+pub use dev_rpc::{DevNode, DevRpcMiddleware, DevRpcMiddlewareError, SnapshotId};
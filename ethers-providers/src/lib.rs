@@ -19,12 +19,15 @@ pub use utils::{interval, maybe, EscalationPolicy};
 
 /// Errors
 mod errors;
-pub use errors::{MiddlewareError, ProviderError, RpcError};
+pub use errors::{MiddlewareError, ProviderError, RevertedTransaction, RpcError};
 
 mod stream;
 pub use futures_util::StreamExt;
 pub use stream::{
-    tx_stream::TransactionStream, FilterWatcher, DEFAULT_LOCAL_POLL_INTERVAL, DEFAULT_POLL_INTERVAL,
+    gaps::{BlockGapStream, BlockUpdate, GapMode},
+    reorg::ReorgSafeLogs,
+    tx_stream::{FullPendingTransactionStream, TransactionStream},
+    FilterWatcher, DEFAULT_LOCAL_POLL_INTERVAL, DEFAULT_POLL_INTERVAL,
 };
 
 mod middleware;
@@ -4,7 +4,7 @@ use ethers_core::types::{
     transaction::{eip2718::TypedTransaction, eip2930::AccessListWithGasUsed},
     *,
 };
-use futures_util::future::join_all;
+use futures_util::{future::join_all, stream, StreamExt, TryStreamExt};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 use url::Url;
@@ -12,9 +12,13 @@ use url::Url;
 use crate::{
     erc, EscalatingPending, EscalationPolicy, FilterKind, FilterWatcher, JsonRpcClient, LogQuery,
     MiddlewareError, NodeInfo, PeerInfo, PendingTransaction, Provider, ProviderError, PubsubClient,
-    SubscriptionStream,
+    RevertedTransaction, SubscriptionStream,
 };
 
+/// The maximum number of concurrent `eth_getStorageAt` requests of
+/// [`Middleware::get_storage_range`].
+const STORAGE_RANGE_CONCURRENCY: usize = 16;
+
 /// A middleware allows customizing requests send and received from an ethereum node.
 ///
 /// Writing a middleware is as simple as:
@@ -113,6 +117,33 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().default_sender()
     }
 
+    /// Sends a raw JSON-RPC request with the given method and params to the
+    /// provider at the bottom of the middleware stack, and deserializes the
+    /// response into `R`.
+    ///
+    /// This allows calling RPC methods that are not modelled by this trait,
+    /// such as client-specific methods.
+    ///
+    /// The request is delegated to the next-lower layer, so it passes through
+    /// every middleware in the stack that overrides this method.
+    ///
+    /// ```no_run
+    /// # use ethers_providers::{Http, Middleware, Provider};
+    /// # use ethers_core::types::U64;
+    /// # async fn foo(provider: Provider<Http>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let header: serde_json::Value =
+    ///     provider.request_raw("erigon_getHeaderByNumber", [U64::from(1)]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn request_raw<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned + Debug + Send,
+    {
+        self.inner().request_raw(method, params).await.map_err(MiddlewareError::from_err)
+    }
+
     /// Returns the current client version using the `web3_clientVersion` RPC.
     async fn client_version(&self) -> Result<String, Self::Error> {
         self.inner().client_version().await.map_err(MiddlewareError::from_err)
@@ -160,6 +191,71 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().send_transaction(tx, block).await.map_err(MiddlewareError::from_err)
     }
 
+    /// Sends the transaction and waits for it to be mined with the given number of
+    /// `confirmations`, returning its receipt, or `None` if it was dropped from the mempool.
+    ///
+    /// If the transaction reverted, it is re-executed via `eth_call` on top of the parent of the
+    /// block it was mined in to recover the revert data, and a
+    /// [`ProviderError::TransactionReverted`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ethers_core::types::{Address, TransactionRequest};
+    /// # use ethers_providers::{Middleware, Provider, Http, ProviderError, RpcError};
+    /// # use std::convert::TryFrom;
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+    /// let tx = TransactionRequest::pay(Address::zero(), 100);
+    /// match provider.send_transaction_and_confirm(tx, None, 1).await {
+    ///     Ok(receipt) => println!("mined: {receipt:?}"),
+    ///     Err(ProviderError::TransactionReverted(reverted)) => {
+    ///         println!("reverted: {:?}", reverted.reason())
+    ///     }
+    ///     Err(err) => return Err(err.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn send_transaction_and_confirm<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+        confirmations: usize,
+    ) -> Result<Option<TransactionReceipt>, Self::Error> {
+        let mut tx = tx.into();
+        // fill the transaction up front so that the exact same call can be re-executed
+        self.fill_transaction(&mut tx, block).await?;
+
+        let Some(receipt) = self
+            .send_transaction(tx.clone(), block)
+            .await?
+            .confirmations(confirmations)
+            .await
+            .map_err(Self::convert_err)?
+        else {
+            return Ok(None)
+        };
+        if receipt.status != Some(U64::zero()) {
+            return Ok(Some(receipt))
+        }
+
+        let revert_data = match receipt.block_number {
+            // replay on the state the transaction was executed on, which the parent block is the
+            // closest approximation of
+            Some(mined) => self
+                .call(&tx, Some(mined.saturating_sub(U64::one()).into()))
+                .await
+                .err()
+                .and_then(|err| err.as_error_response()?.as_revert_data()),
+            None => None,
+        };
+        Err(Self::convert_err(ProviderError::TransactionReverted(Box::new(RevertedTransaction {
+            receipt,
+            revert_data,
+        }))))
+    }
+
     /// Send a transaction with a simple escalation policy.
     ///
     /// `policy` should be a boxed function that maps `original_gas_price`
@@ -237,6 +333,9 @@ pub trait Middleware: Sync + Send + Debug {
     /// Returns the avatar HTTP link of the avatar that the `ens_name` resolves to (or None
     /// if not configured)
     ///
+    /// Supports HTTP(S), IPFS and `data:` avatars, as well as ERC-721 and ERC-1155 NFT avatars
+    /// (`eip155:` links) which must be owned by the address the `ens_name` resolves to.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -281,6 +380,8 @@ pub trait Middleware: Sync + Send + Debug {
 
     /// Fetch a field for the `ens_name` (no None if not configured).
     ///
+    /// This resolves the ENS text record with the key `field`, e.g. `url` or `com.twitter`.
+    ///
     /// # Panics
     ///
     /// If the bytes returned from the ENS registrar/resolver cannot be interpreted as
@@ -308,7 +409,8 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(MiddlewareError::from_err)
     }
 
-    /// Gets the block uncle count at `block_hash_or_number`
+    /// Gets the block uncle count at `block_hash_or_number`, which is zero for blocks that can't
+    /// have uncles, e.g. after the merge. Returns an error if the block is unknown.
     async fn get_uncle_count<T: Into<BlockId> + Send + Sync>(
         &self,
         block_hash_or_number: T,
@@ -316,7 +418,7 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().get_uncle_count(block_hash_or_number).await.map_err(MiddlewareError::from_err)
     }
 
-    /// Gets the block uncle at `block_hash_or_number` and `idx`
+    /// Gets the block uncle at `block_hash_or_number` and `idx`, or `None` if it doesn't exist
     async fn get_uncle<T: Into<BlockId> + Send + Sync>(
         &self,
         block_hash_or_number: T,
@@ -326,6 +428,8 @@ pub trait Middleware: Sync + Send + Debug {
     }
 
     /// Returns the nonce of the address
+    ///
+    /// The query is executed at `block`, or at the latest block if `None`. See [`BlockId`].
     async fn get_transaction_count<T: Into<NameOrAddress> + Send + Sync>(
         &self,
         from: T,
@@ -357,6 +461,42 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().call(tx, block).await.map_err(MiddlewareError::from_err)
     }
 
+    /// Simulates the calls of the payload's blocks on top of each other with `eth_simulateV1`,
+    /// applying the state and block overrides of each block, and returns the simulated blocks
+    /// with the result and logs of each call.
+    ///
+    /// Nothing is broadcast to the network.
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Self::Error> {
+        self.inner().simulate_v1(payload, block).await.map_err(MiddlewareError::from_err)
+    }
+
+    /// Executes the `calls` on top of each other with `eth_callMany`, so that each call sees the
+    /// state changes of the previous calls, e.g. to simulate an approval followed by a swap.
+    ///
+    /// The calls are executed at the end of the block. Unlike [`Middleware::call`], a failing call
+    /// does not fail the request, the result of each call is returned in the order of the calls.
+    ///
+    /// `eth_callMany` only accepts a single state override set, which is applied before the first
+    /// call. The state overrides of all calls are therefore merged and applied up front, so a call
+    /// already sees the overrides of the calls after it. Overriding the same account in more than
+    /// one call is rejected with a [`ProviderError::CustomError`], since the overrides can't be
+    /// applied in order.
+    ///
+    /// `eth_callMany` is supported by Erigon and Reth, [`ProviderError::UnsupportedRPC`] is
+    /// returned by nodes that do not support it, see [`Middleware::simulate_v1`] for an
+    /// alternative.
+    async fn call_many(
+        &self,
+        calls: &[(TypedTransaction, Option<spoof::State>)],
+        block: Option<BlockId>,
+    ) -> Result<Vec<CallManyResponse>, Self::Error> {
+        self.inner().call_many(calls, block).await.map_err(MiddlewareError::from_err)
+    }
+
     /// Return current client syncing status. If IsFalse sync is over.
     async fn syncing(&self) -> Result<SyncingStatus, Self::Error> {
         self.inner().syncing().await.map_err(MiddlewareError::from_err)
@@ -374,6 +514,8 @@ pub trait Middleware: Sync + Send + Debug {
     }
 
     /// Returns the account's balance
+    ///
+    /// The query is executed at `block`, or at the latest block if `None`. See [`BlockId`].
     async fn get_balance<T: Into<NameOrAddress> + Send + Sync>(
         &self,
         from: T,
@@ -416,7 +558,9 @@ pub trait Middleware: Sync + Send + Debug {
     /// Returns all receipts for a block.
     ///
     /// Note that this uses the `eth_getBlockReceipts` RPC, which is
-    /// non-standard and currently supported by Erigon.
+    /// non-standard and currently supported by Erigon. See
+    /// [`Provider::block_receipts_fallback`] for fetching the receipts one by one on nodes that
+    /// do not support it.
     async fn get_block_receipts<T: Into<BlockNumber> + Send + Sync>(
         &self,
         block: T,
@@ -431,6 +575,9 @@ pub trait Middleware: Sync + Send + Debug {
 
     /// Gets a heuristic recommendation of max fee per gas and max priority fee per gas for
     /// EIP-1559 compatible transactions.
+    ///
+    /// If no `estimator` is given and the recent blocks paid no priority fees, the priority fee
+    /// suggested by the node via `eth_maxPriorityFeePerGas` is used.
     async fn estimate_eip1559_fees(
         &self,
         estimator: Option<fn(U256, Vec<Vec<U256>>) -> (U256, U256)>,
@@ -573,6 +720,8 @@ pub trait Middleware: Sync + Send + Debug {
     }
 
     /// Returns the deployed code at a given address
+    ///
+    /// The query is executed at `block`, or at the latest block if `None`. See [`BlockId`].
     async fn get_code<T: Into<NameOrAddress> + Send + Sync>(
         &self,
         at: T,
@@ -591,6 +740,56 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().get_storage_at(from, location, block).await.map_err(MiddlewareError::from_err)
     }
 
+    /// Get the storage of an address for `count` consecutive slots starting at `start_slot`,
+    /// e.g. the elements of a statically sized array.
+    ///
+    /// The slots are read with concurrent `eth_getStorageAt` requests, at most
+    /// 16 at a time. The values are returned in slot order.
+    async fn get_storage_range<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        start_slot: U256,
+        count: u64,
+        block: Option<BlockId>,
+    ) -> Result<Vec<(U256, H256)>, Self::Error> {
+        // resolve the name once instead of once per slot
+        let from = match from.into() {
+            NameOrAddress::Name(ens_name) => self.resolve_name(&ens_name).await?,
+            NameOrAddress::Address(addr) => addr,
+        };
+
+        stream::iter(0..count)
+            .map(|i| {
+                let slot = start_slot.overflowing_add(i.into()).0;
+                async move {
+                    let location = H256::from_uint(&slot);
+                    self.get_storage_at(from, location, block).await.map(|value| (slot, value))
+                }
+            })
+            .buffered(STORAGE_RANGE_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Splits the cost of the transaction of the `receipt` into the burned base fee and the
+    /// priority fee, fetching the base fee of the block the transaction was included in, see
+    /// [`TransactionReceipt::fee_breakdown`].
+    ///
+    /// Returns `None` if the block has no base fee, i.e. is from before London, or if the receipt
+    /// lacks the gas used or the effective gas price.
+    async fn get_fee_breakdown(
+        &self,
+        receipt: &TransactionReceipt,
+    ) -> Result<Option<FeeBreakdown>, Self::Error> {
+        let block = match (receipt.block_hash, receipt.block_number) {
+            (Some(hash), _) => BlockId::from(hash),
+            (None, Some(number)) => BlockId::from(number),
+            (None, None) => return Ok(None),
+        };
+        let base_fee = self.get_block(block).await?.and_then(|block| block.base_fee_per_gas);
+        Ok(base_fee.and_then(|base_fee| receipt.fee_breakdown(base_fee)))
+    }
+
     /// Returns the EIP-1186 proof response
     /// <https://github.com/ethereum/EIPs/issues/1186>
     async fn get_proof<T: Into<NameOrAddress> + Send + Sync>(
@@ -735,6 +934,10 @@ pub trait Middleware: Sync + Send + Debug {
     }
 
     /// Executes the given call and returns a number of possible traces for it
+    ///
+    /// The trace is deserialized into the frame of the tracer selected in `trace_options`, e.g. a
+    /// [`CallFrame`](ethers_core::types::CallFrame) including the revert reason for the
+    /// `callTracer`.
     async fn debug_trace_call<T: Into<TypedTransaction> + Send + Sync>(
         &self,
         req: T,
@@ -905,6 +1108,9 @@ pub trait Middleware: Sync + Send + Debug {
     /// or IPC. For a polling alternative available over HTTP, use
     /// [`Middleware::watch_blocks`]. However, be aware that polling increases
     /// RPC usage drastically.
+    ///
+    /// Nodes may skip heads, use [`SubscriptionStream::fill_gaps`] or
+    /// [`SubscriptionStream::report_gaps`] to not silently miss blocks.
     async fn subscribe_blocks(
         &self,
     ) -> Result<SubscriptionStream<'_, Self::Provider, Block<TxHash>>, Self::Error>
@@ -936,7 +1142,9 @@ pub trait Middleware: Sync + Send + Debug {
     /// [`Middleware::watch_pending_transactions`]. However, be aware that
     /// polling increases RPC usage drastically.
     ///
-    /// Note: This endpoint is compatible only with Geth client version 1.11.0 or later.
+    /// Note: This endpoint is compatible only with Geth client version 1.11.0 or later. See
+    /// [`Provider::subscribe_full_pending_txs_with_fallback`] for a stream that falls back to
+    /// fetching the transactions of the pending hashes.
     async fn subscribe_full_pending_txs(
         &self,
     ) -> Result<SubscriptionStream<'_, Self::Provider, Transaction>, Self::Error>
@@ -952,6 +1160,10 @@ pub trait Middleware: Sync + Send + Debug {
     /// or IPC. For a polling alternative available over HTTP, use
     /// [`Middleware::watch`]. However, be aware that polling increases
     /// RPC usage drastically.
+    ///
+    /// On chain reorganizations, the node sends the logs of the reorged blocks again, with
+    /// [`Log::removed`] set to `true`. To only receive logs once they are unlikely to be
+    /// reorged, wrap the stream in a [`ReorgSafeLogs`](crate::ReorgSafeLogs).
     async fn subscribe_logs<'a>(
         &'a self,
         filter: &Filter,
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use auto_impl::auto_impl;
 use ethers_core::types::U256;
 use serde::{de::DeserializeOwned, Serialize};
-use serde_json::value::RawValue;
+use serde_json::{value::RawValue, Value};
 
 use crate::{ProviderError, RpcError};
 
@@ -22,6 +22,24 @@ pub trait JsonRpcClient: Debug + Send + Sync {
     where
         T: Debug + Serialize + Send + Sync,
         R: DeserializeOwned + Send;
+
+    /// Sends the requests, given as methods with their params, returning the result of each
+    /// request in the order of the requests.
+    ///
+    /// The default implementation sends the requests one by one, transports supporting
+    /// [JSON-RPC batches](https://www.jsonrpc.org/specification#batch) override it to send them
+    /// in a single round trip. An error is only returned for the whole batch if the batch itself
+    /// failed, e.g. because it could not be sent.
+    async fn request_batch(
+        &self,
+        requests: Vec<(String, Value)>,
+    ) -> Result<Vec<Result<Value, Self::Error>>, Self::Error> {
+        let mut results = Vec::with_capacity(requests.len());
+        for (method, params) in requests {
+            results.push(self.request(&method, params).await);
+        }
+        Ok(results)
+    }
 }
 
 /// A transport implementation supporting pub sub subscriptions.
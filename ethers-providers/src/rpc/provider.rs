@@ -22,19 +22,29 @@ use async_trait::async_trait;
 use ethers_core::{
     abi::{self, Detokenize, ParamType},
     types::{
-        transaction::{eip2718::TypedTransaction, eip2930::AccessListWithGasUsed},
-        Address, Block, BlockId, BlockNumber, BlockTrace, Bytes, Chain, EIP1186ProofResponse,
-        FeeHistory, Filter, FilterBlockOption, GethDebugTracingCallOptions,
-        GethDebugTracingOptions, GethTrace, Log, NameOrAddress, Selector, Signature, Trace,
-        TraceFilter, TraceType, Transaction, TransactionReceipt, TransactionRequest, TxHash,
-        TxpoolContent, TxpoolInspect, TxpoolStatus, H256, U256, U64,
+        spoof,
+        transaction::{
+            eip2718::TypedTransaction, eip2930::AccessListWithGasUsed,
+            eip4844::Eip4844TransactionRequest, eip7702::Eip7702TransactionRequest,
+        },
+        Address, Block, BlockId, BlockNumber, BlockTrace, Bytes, CallManyResponse, Chain,
+        EIP1186ProofResponse, FeeHistory, Filter, FilterBlockOption, GethDebugTracingCallOptions,
+        GethDebugTracingOptions, GethTrace, Log, NameOrAddress, Selector, Signature,
+        SimulatePayload, SimulatedBlock, Trace, TraceFilter, TraceType, Transaction,
+        TransactionReceipt, TransactionRequest, TxHash, TxpoolContent, TxpoolInspect, TxpoolStatus,
+        H256, U256, U64,
     },
     utils,
 };
-use futures_util::{lock::Mutex, try_join};
+use futures_util::{future, lock::Mutex, stream, try_join, StreamExt, TryStreamExt};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
-    collections::VecDeque, convert::TryFrom, fmt::Debug, str::FromStr, sync::Arc, time::Duration,
+    collections::VecDeque,
+    convert::TryFrom,
+    fmt::Debug,
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::Duration,
 };
 use tracing::trace;
 use tracing_futures::Instrument;
@@ -96,13 +106,61 @@ pub struct Provider<P> {
     inner: P,
     ens: Option<Address>,
     interval: Option<Duration>,
+    auto_interval: Option<AutoPollInterval>,
     from: Option<Address>,
+    /// Whether receipts are fetched one by one if `eth_getBlockReceipts` is not supported
+    block_receipts_fallback: bool,
     /// Node client hasn't been checked yet = `None`
     /// Unsupported node client = `Some(None)`
     /// Supported node client = `Some(Some(NodeClient))`
     _node_client: Arc<Mutex<Option<NodeClient>>>,
 }
 
+/// The number of receipts fetched concurrently if `eth_getBlockReceipts` is not supported
+const BLOCK_RECEIPTS_CONCURRENCY: usize = 16;
+
+/// Returns true if the error is a response to an RPC method that the node does not support
+fn is_method_not_found(err: &ProviderError) -> bool {
+    err.as_error_response().map_or(false, |err| {
+        let message = err.message.to_lowercase();
+        err.code == -32601 ||
+            message.contains("method not found") ||
+            message.contains("does not exist")
+    })
+}
+
+/// The block whose state is requested by [`Provider::is_archive_node`]
+const ARCHIVE_PROBE_BLOCK: u64 = 1;
+
+/// Parts of the error messages of nodes that pruned the requested state
+const MISSING_STATE_ERRORS: [&str; 5] = [
+    "missing trie node",
+    "state not available",
+    "state is not available",
+    "historical state",
+    "pruned",
+];
+
+/// Returns true if the error is a response to a state request at a block whose state was pruned
+fn is_missing_state(err: &ProviderError) -> bool {
+    err.as_error_response().map_or(false, |err| {
+        let message = err.message.to_lowercase();
+        MISSING_STATE_ERRORS.iter().any(|pattern| message.contains(pattern))
+    })
+}
+
+/// The number of past blocks over which the block time is averaged to tune the poll interval
+const AUTO_POLL_INTERVAL_BLOCKS: u64 = 10;
+
+/// Bounds and state of the poll interval that is derived from the observed block time
+#[derive(Clone, Debug)]
+struct AutoPollInterval {
+    min: Duration,
+    max: Duration,
+    /// The tuned interval, `None` until the block time was measured
+    tuned: Arc<RwLock<Option<Duration>>>,
+}
+
 impl<P> AsRef<P> for Provider<P> {
     fn as_ref(&self) -> &P {
         &self.inner
@@ -130,7 +188,9 @@ impl<P: JsonRpcClient> Provider<P> {
             inner: provider,
             ens: None,
             interval: None,
+            auto_interval: None,
             from: None,
+            block_receipts_fallback: false,
             _node_client: Arc::new(Mutex::new(None)),
         }
     }
@@ -180,6 +240,58 @@ impl<P: JsonRpcClient> Provider<P> {
         Ok(res)
     }
 
+    /// Estimates the max fee per gas and the max priority fee per gas of EIP-1559 transactions
+    /// with a custom `strategy`, see [`utils::eip1559_fee_history_estimator`] for an example.
+    ///
+    /// The strategy is given the fee history of the last
+    /// [`EIP1559_FEE_ESTIMATION_PAST_BLOCKS`](utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS) blocks,
+    /// with the [`EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE`](utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE)
+    /// of the priority fees of each block as rewards. Its last base fee is the base fee of the
+    /// pending block.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ethers_providers::{Provider, Http};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+    /// let (max_fee_per_gas, max_priority_fee_per_gas) = provider
+    ///     .estimate_eip1559_fees_with(|history| {
+    ///         let base_fee = history.base_fee_per_gas.last().copied().unwrap_or_default();
+    ///         let priority_fee = history
+    ///             .reward
+    ///             .iter()
+    ///             .filter_map(|rewards| rewards.first().copied())
+    ///             .max()
+    ///             .unwrap_or_default();
+    ///         // be aggressive: outbid the recent blocks and allow the base fee to triple
+    ///         (base_fee * 3 + priority_fee, priority_fee)
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn estimate_eip1559_fees_with<F>(
+        &self,
+        strategy: F,
+    ) -> Result<(U256, U256), ProviderError>
+    where
+        F: FnOnce(&FeeHistory) -> (U256, U256),
+    {
+        let fee_history = self
+            .fee_history(
+                utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+                BlockNumber::Latest,
+                &[utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE],
+            )
+            .await?;
+        if fee_history.base_fee_per_gas.is_empty() {
+            return Err(ProviderError::CustomError("EIP-1559 not activated".into()))
+        }
+
+        Ok(strategy(&fee_history))
+    }
+
     async fn get_block_gen<Tx: Default + Serialize + DeserializeOwned + Debug + Send>(
         &self,
         id: BlockId,
@@ -262,6 +374,14 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.from
     }
 
+    async fn request_raw<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned + Debug + Send,
+    {
+        self.request(method, params).await
+    }
+
     async fn client_version(&self) -> Result<String, Self::Error> {
         self.request("web3_clientVersion", ()).await
     }
@@ -291,7 +411,9 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
                 let gas_price = maybe(tx.gas_price(), self.get_gas_price()).await?;
                 tx.set_gas_price(gas_price);
             }
-            TypedTransaction::Eip1559(ref mut inner) => {
+            TypedTransaction::Eip1559(ref mut inner) |
+            TypedTransaction::Eip4844(Eip4844TransactionRequest { tx: ref mut inner, .. }) |
+            TypedTransaction::Eip7702(Eip7702TransactionRequest { tx: ref mut inner, .. }) => {
                 if inner.max_fee_per_gas.is_none() || inner.max_priority_fee_per_gas.is_none() {
                     let (max_fee_per_gas, max_priority_fee_per_gas) =
                         self.estimate_eip1559_fees(None).await?;
@@ -317,6 +439,24 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
             }
         }
 
+        // fill the blob gas price of blob transactions, leaving room for the blob base fee to
+        // double like for the base fee
+        #[cfg(not(feature = "celo"))]
+        if let TypedTransaction::Eip4844(ref mut inner) = tx {
+            if inner.max_fee_per_blob_gas.is_none() {
+                let excess_blob_gas = self
+                    .get_block(BlockNumber::Latest)
+                    .await?
+                    .ok_or_else(|| ProviderError::CustomError("Latest block not found".into()))?
+                    .excess_blob_gas
+                    .ok_or_else(|| ProviderError::CustomError("EIP-4844 not activated".into()))?;
+                let max_fee_per_blob_gas = utils::calc_blob_gasprice(excess_blob_gas)
+                    .and_then(|price| price.checked_mul(2.into()))
+                    .ok_or_else(|| ProviderError::CustomError("blob gas price overflow".into()))?;
+                inner.max_fee_per_blob_gas = Some(max_fee_per_blob_gas);
+            }
+        }
+
         // Set gas to estimated value only if it was not set by the caller,
         // even if the access list has been populated and saves gas
         if tx.gas().is_none() {
@@ -350,7 +490,8 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         block_hash_or_number: T,
     ) -> Result<U256, Self::Error> {
         let id = block_hash_or_number.into();
-        Ok(match id {
+        // nodes return `null` for unknown blocks
+        let count: Option<U256> = match id {
             BlockId::Hash(hash) => {
                 let hash = utils::serialize(&hash);
                 self.request("eth_getUncleCountByBlockHash", [hash]).await?
@@ -359,7 +500,8 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
                 let num = utils::serialize(&num);
                 self.request("eth_getUncleCountByBlockNumber", [num]).await?
             }
-        })
+        };
+        count.ok_or_else(|| ProviderError::CustomError(format!("block {id:?} not found")))
     }
 
     async fn get_uncle<T: Into<BlockId> + Send + Sync>(
@@ -420,7 +562,23 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         &self,
         block: T,
     ) -> Result<Vec<TransactionReceipt>, Self::Error> {
-        self.request("eth_getBlockReceipts", [block.into()]).await
+        let block = block.into();
+        let err = match self.request("eth_getBlockReceipts", [block]).await {
+            Err(err) if self.block_receipts_fallback && is_method_not_found(&err) => err,
+            res => return res,
+        };
+
+        tracing::debug!(%err, "eth_getBlockReceipts is not supported, fetching receipts one by one");
+        let Some(block) = self.get_block(block).await? else { return Ok(Vec::new()) };
+        stream::iter(block.transactions)
+            .map(|hash| async move {
+                self.get_transaction_receipt(hash).await?.ok_or_else(|| {
+                    ProviderError::CustomError(format!("receipt of transaction {hash:?} not found"))
+                })
+            })
+            .buffered(BLOCK_RECEIPTS_CONCURRENCY)
+            .try_collect()
+            .await
     }
 
     async fn parity_block_receipts<T: Into<BlockNumber> + Send + Sync>(
@@ -456,6 +614,16 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         // use the provided fee estimator function, or fallback to the default implementation.
         let (max_fee_per_gas, max_priority_fee_per_gas) = if let Some(es) = estimator {
             es(base_fee_per_gas, fee_history.reward)
+        } else if !fee_history.reward.iter().flatten().any(|reward| !reward.is_zero()) {
+            // without rewards, e.g. if the recent blocks are empty, the priority fee can't be
+            // derived from the fee history, so the node is asked for a suggestion instead
+            match self.request::<_, U256>("eth_maxPriorityFeePerGas", ()).await {
+                Ok(priority_fee) => (
+                    base_fee_per_gas.saturating_mul(2.into()).saturating_add(priority_fee),
+                    priority_fee,
+                ),
+                Err(_) => utils::eip1559_default_estimator(base_fee_per_gas, fee_history.reward),
+            }
         } else {
             utils::eip1559_default_estimator(base_fee_per_gas, fee_history.reward)
         };
@@ -519,6 +687,56 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("eth_call", [tx, block]).await
     }
 
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, ProviderError> {
+        let payload = utils::serialize(&payload);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        self.request("eth_simulateV1", [payload, block]).await
+    }
+
+    async fn call_many(
+        &self,
+        calls: &[(TypedTransaction, Option<spoof::State>)],
+        block: Option<BlockId>,
+    ) -> Result<Vec<CallManyResponse>, ProviderError> {
+        let block = block.unwrap_or_else(|| BlockNumber::Latest.into());
+        // `eth_callMany` applies a single state override set before the first call
+        let mut overrides = serde_json::Map::new();
+        for (_, state) in calls {
+            let Some(serde_json::Value::Object(state)) = state.as_ref().map(utils::serialize)
+            else {
+                continue
+            };
+            for (address, account) in state {
+                if overrides.contains_key(&address) {
+                    return Err(ProviderError::CustomError(format!(
+                        "account {address} is overridden by more than one call"
+                    )))
+                }
+                overrides.insert(address, account);
+            }
+        }
+
+        // the calls are sent as a single bundle, so that they are executed on top of each other
+        let transactions = calls.iter().map(|(tx, _)| tx).collect::<Vec<_>>();
+        let mut params = vec![
+            serde_json::json!([{ "transactions": transactions }]),
+            serde_json::json!({ "blockNumber": block }),
+        ];
+        if !overrides.is_empty() {
+            params.push(overrides.into());
+        }
+        let mut bundles: Vec<Vec<CallManyResponse>> =
+            match self.request("eth_callMany", params).await {
+                Err(err) if is_method_not_found(&err) => return Err(ProviderError::UnsupportedRPC),
+                res => res?,
+            };
+        Ok(bundles.pop().unwrap_or_default())
+    }
+
     async fn estimate_gas(
         &self,
         tx: &TypedTransaction,
@@ -553,6 +771,7 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         let mut tx = tx.into();
         self.fill_transaction(&mut tx, block).await?;
         let tx_hash = self.request("eth_sendTransaction", [tx]).await?;
+        self.maybe_tune_poll_interval().await;
 
         Ok(PendingTransaction::new(tx_hash, self))
     }
@@ -563,6 +782,7 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     ) -> Result<PendingTransaction<'a, P>, ProviderError> {
         let rlp = utils::serialize(&tx);
         let tx_hash = self.request("eth_sendRawTransaction", [rlp]).await?;
+        self.maybe_tune_poll_interval().await;
         Ok(PendingTransaction::new(tx_hash, self))
     }
 
@@ -614,12 +834,14 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         filter: &Filter,
     ) -> Result<FilterWatcher<'a, P, Log>, ProviderError> {
         let id = self.new_filter(FilterKind::Logs(filter)).await?;
+        self.maybe_tune_poll_interval().await;
         let filter = FilterWatcher::new(id, self).interval(self.get_interval());
         Ok(filter)
     }
 
     async fn watch_blocks(&self) -> Result<FilterWatcher<'_, P, H256>, ProviderError> {
         let id = self.new_filter(FilterKind::NewBlocks).await?;
+        self.maybe_tune_poll_interval().await;
         let filter = FilterWatcher::new(id, self).interval(self.get_interval());
         Ok(filter)
     }
@@ -629,6 +851,7 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         &self,
     ) -> Result<FilterWatcher<'_, P, H256>, ProviderError> {
         let id = self.new_filter(FilterKind::PendingTransactions).await?;
+        self.maybe_tune_poll_interval().await;
         let filter = FilterWatcher::new(id, self).interval(self.get_interval());
         Ok(filter)
     }
@@ -800,6 +1023,10 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         let ens_name = ens::reverse_address(address);
         let domain: String =
             self.query_resolver(ParamType::String, &ens_name, ens::NAME_SELECTOR).await?;
+        // the reverse resolver returns an empty name if no reverse record is set
+        if domain.is_empty() {
+            return Err(ProviderError::EnsError(ens_name))
+        }
         let reverse_address = self.resolve_name(&domain).await?;
         if address != reverse_address {
             Err(ProviderError::EnsNotOwned(domain))
@@ -813,8 +1040,6 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
             try_join!(self.resolve_field(ens_name, "avatar"), self.resolve_name(ens_name))?;
         let url = Url::from_str(&field).map_err(|e| ProviderError::CustomError(e.to_string()))?;
         match url.scheme() {
-            "https" | "data" => Ok(url),
-            "ipfs" => erc::http_link_ipfs(url).map_err(ProviderError::CustomError),
             "eip155" => {
                 let token =
                     erc::ERCNFT::from_str(url.path()).map_err(ProviderError::CustomError)?;
@@ -848,22 +1073,16 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
                             ..Default::default()
                         };
                         let data = self.call(&tx.into(), None).await?;
-                        if decode_bytes::<u64>(ParamType::Uint(64), data) == 0 {
+                        if decode_bytes::<U256>(ParamType::Uint(256), data).is_zero() {
                             return Err(ProviderError::CustomError("Incorrect balance.".to_string()))
                         }
                     }
                 }
 
                 let image_url = self.resolve_nft(token).await?;
-                match image_url.scheme() {
-                    "https" | "data" => Ok(image_url),
-                    "ipfs" => erc::http_link_ipfs(image_url).map_err(ProviderError::CustomError),
-                    _ => Err(ProviderError::CustomError(
-                        "Unsupported scheme for the image".to_string(),
-                    )),
-                }
+                erc::http_link(image_url).map_err(ProviderError::CustomError)
             }
-            _ => Err(ProviderError::CustomError("Unsupported scheme".to_string())),
+            _ => erc::http_link(url).map_err(ProviderError::CustomError),
         }
     }
 
@@ -917,9 +1136,11 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         tx_hash: TxHash,
         trace_options: GethDebugTracingOptions,
     ) -> Result<GethTrace, ProviderError> {
+        let tracer = trace_options.tracer.clone();
         let tx_hash = utils::serialize(&tx_hash);
         let trace_options = utils::serialize(&trace_options);
-        self.request("debug_traceTransaction", [tx_hash, trace_options]).await
+        let trace = self.request("debug_traceTransaction", [tx_hash, trace_options]).await?;
+        Ok(GethTrace::from_value_with_tracer(trace, tracer.as_ref()))
     }
 
     async fn debug_trace_call<T: Into<TypedTransaction> + Send + Sync>(
@@ -928,11 +1149,13 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         block: Option<BlockId>,
         trace_options: GethDebugTracingCallOptions,
     ) -> Result<GethTrace, ProviderError> {
+        let tracer = trace_options.tracing_options.tracer.clone();
         let req = req.into();
         let req = utils::serialize(&req);
         let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
         let trace_options = utils::serialize(&trace_options);
-        self.request("debug_traceCall", [req, block, trace_options]).await
+        let trace = self.request("debug_traceCall", [req, block, trace_options]).await?;
+        Ok(GethTrace::from_value_with_tracer(trace, tracer.as_ref()))
     }
 
     async fn debug_trace_block_by_number(
@@ -1246,6 +1469,15 @@ impl<P: JsonRpcClient> Provider<P> {
         self
     }
 
+    /// Sets whether [`get_block_receipts`](Middleware::get_block_receipts) falls back to fetching
+    /// the receipts of the block's transactions one by one if the node does not support
+    /// `eth_getBlockReceipts` (default: false)
+    #[must_use]
+    pub fn block_receipts_fallback(mut self, fallback: bool) -> Self {
+        self.block_receipts_fallback = fallback;
+        self
+    }
+
     /// Sets the default polling interval for event filters and pending transactions
     /// (default: 7 seconds)
     pub fn set_interval<T: Into<Duration>>(&mut self, interval: T) -> &mut Self {
@@ -1264,7 +1496,192 @@ impl<P: JsonRpcClient> Provider<P> {
     /// Gets the polling interval which the provider currently uses for event filters
     /// and pending transactions (default: 7 seconds)
     pub fn get_interval(&self) -> Duration {
-        self.interval.unwrap_or(DEFAULT_POLL_INTERVAL)
+        let interval = self.interval.unwrap_or(DEFAULT_POLL_INTERVAL);
+        match self.auto_interval {
+            Some(ref auto) => {
+                auto.tuned.read().unwrap().unwrap_or(interval).clamp(auto.min, auto.max)
+            }
+            None => interval,
+        }
+    }
+
+    /// Enables tuning the polling interval to half of the observed block time, bounded by `min`
+    /// and `max`.
+    ///
+    /// The block time is averaged over the last blocks the first time the provider starts
+    /// watching a filter or pending transaction, see also [`Provider::tune_poll_interval`].
+    /// Until then the configured interval, clamped to the bounds, is used.
+    ///
+    /// # Panics
+    ///
+    /// If `min` is greater than `max`.
+    pub fn set_auto_poll_interval(&mut self, min: Duration, max: Duration) -> &mut Self {
+        assert!(min <= max, "minimum poll interval must not exceed the maximum");
+        self.auto_interval = Some(AutoPollInterval { min, max, tuned: Default::default() });
+        self
+    }
+
+    /// Measures the average time between the latest blocks and sets the polling interval to half
+    /// of it, bounded by the limits set via [`Provider::set_auto_poll_interval`].
+    ///
+    /// Returns the polling interval the provider uses from now on, this is a no-op if automatic
+    /// tuning is not enabled.
+    pub async fn tune_poll_interval(&self) -> Result<Duration, ProviderError> {
+        let Some(ref auto) = self.auto_interval else { return Ok(self.get_interval()) };
+
+        let latest = self
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| ProviderError::CustomError("Latest block not found".to_string()))?;
+        let number = latest.number.unwrap_or_default();
+        let past_number = number.saturating_sub(AUTO_POLL_INTERVAL_BLOCKS.into());
+        if number == past_number {
+            // no history to measure yet
+            return Ok(self.get_interval())
+        }
+        let past = self
+            .get_block(past_number)
+            .await?
+            .ok_or_else(|| ProviderError::CustomError(format!("Block {past_number} not found")))?;
+
+        // timestamps only have a resolution of seconds, so measure over several blocks
+        let Ok(elapsed) = u64::try_from(latest.timestamp.saturating_sub(past.timestamp)) else {
+            // a bogus timestamp, keep the current interval
+            return Ok(self.get_interval())
+        };
+        let elapsed = Duration::from_secs(elapsed);
+        let block_time = elapsed / (number - past_number).as_u32();
+        trace!(?block_time, "measured block time");
+
+        *auto.tuned.write().unwrap() = Some(block_time / 2);
+        Ok(self.get_interval())
+    }
+
+    /// Waits until the chain reached the `target` block number and returns the block at that
+    /// height.
+    ///
+    /// The block number is polled at the [polling interval](Self::get_interval). If the block
+    /// at `target` is not found once the chain reached it, e.g. because it was reorged out, the
+    /// chain is polled again.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ethers_providers::{Provider, Http};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+    /// let block = provider.wait_for_block(17_000_000).await?;
+    /// println!("block 17M was mined at {}", block.timestamp);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_block(&self, target: u64) -> Result<Block<TxHash>, ProviderError> {
+        self.maybe_tune_poll_interval().await;
+        let mut ticks = crate::interval(self.get_interval());
+        loop {
+            if self.get_block_number().await?.as_u64() >= target {
+                if let Some(block) = self.get_block(target).await? {
+                    return Ok(block)
+                }
+                trace!(block = target, "block not found after the chain reached it");
+            }
+            ticks.next().await;
+        }
+    }
+
+    /// As [`Self::wait_for_block`], but fails if the chain did not reach the `target` block
+    /// within `timeout`.
+    pub async fn wait_for_block_with_timeout(
+        &self,
+        target: u64,
+        timeout: Duration,
+    ) -> Result<Block<TxHash>, ProviderError> {
+        let wait = self.wait_for_block(target);
+        futures_util::pin_mut!(wait);
+        match future::select(wait, futures_timer::Delay::new(timeout)).await {
+            future::Either::Left((res, _)) => res,
+            future::Either::Right(_) => Err(ProviderError::CustomError(format!(
+                "timed out waiting for block {target} after {timeout:?}"
+            ))),
+        }
+    }
+
+    /// Returns true if the node retains the state of old blocks, i.e. is an archive node.
+    ///
+    /// Probes the node by requesting the balance of the zero address at block 1, see
+    /// [`Self::is_archive_node_at`] to probe a different block, e.g. of chains that were
+    /// started recently.
+    pub async fn is_archive_node(&self) -> Result<bool, ProviderError> {
+        self.is_archive_node_at(ARCHIVE_PROBE_BLOCK).await
+    }
+
+    /// Returns true if the node retains the state at the `block`, which must exist.
+    ///
+    /// Errors that report the state as missing, e.g. `missing trie node`, are mapped to `false`,
+    /// all other errors are returned.
+    pub async fn is_archive_node_at(&self, block: u64) -> Result<bool, ProviderError> {
+        match self.get_balance(Address::zero(), Some(block.into())).await {
+            Ok(_) => Ok(true),
+            Err(err) if is_missing_state(&err) => {
+                trace!(?err, block, "node does not retain old state");
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Subscribes to the full pending transactions of the node, falling back to fetching the
+    /// transactions of the pending transaction hashes if the node does not support full
+    /// transactions.
+    ///
+    /// Unlike [`Middleware::subscribe_full_pending_txs`], this also works with nodes that reject
+    /// the `newPendingTransactions` subscription with full transactions, and with nodes that
+    /// accept it but only send hashes. Up to `max_concurrent` transactions are fetched at once,
+    /// see [`FullPendingTransactionStream`](crate::FullPendingTransactionStream).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ethers_providers::{Provider, StreamExt, Ws};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Provider::<Ws>::connect("ws://localhost:8546").await?;
+    /// let mut txs = provider.subscribe_full_pending_txs_with_fallback(16).await?;
+    /// while let Some(tx) = txs.next().await {
+    ///     println!("pending transaction {:?} from {:?}", tx.hash, tx.from);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_full_pending_txs_with_fallback(
+        &self,
+        max_concurrent: usize,
+    ) -> Result<crate::FullPendingTransactionStream<'_, P>, ProviderError>
+    where
+        P: PubsubClient,
+    {
+        let full = [utils::serialize(&"newPendingTransactions"), utils::serialize(&true)];
+        let subscription = match self.subscribe(full).await {
+            Ok(subscription) => subscription,
+            Err(err) => {
+                trace!(?err, "full pending transactions not supported, subscribing to hashes");
+                self.subscribe(["newPendingTransactions"]).await?
+            }
+        };
+        Ok(crate::FullPendingTransactionStream::new(subscription, max_concurrent))
+    }
+
+    /// Tunes the polling interval if it is enabled but wasn't measured yet
+    async fn maybe_tune_poll_interval(&self) {
+        let needs_tuning = self
+            .auto_interval
+            .as_ref()
+            .map(|auto| auto.tuned.read().unwrap().is_none())
+            .unwrap_or_default();
+        if needs_tuning {
+            if let Err(err) = self.tune_poll_interval().await {
+                trace!(?err, "failed to tune poll interval");
+            }
+        }
     }
 }
 
@@ -1536,7 +1953,8 @@ mod tests {
         types::{
             transaction::eip2930::AccessList, Eip1559TransactionRequest,
             GethDebugBuiltInTracerConfig, GethDebugBuiltInTracerType, GethDebugTracerConfig,
-            GethDebugTracerType, PreStateConfig, TransactionRequest, H256,
+            GethDebugTracerType, GethTraceFrame, PreStateConfig, SimulateBlock, SimulatedCall,
+            TransactionRequest, H256,
         },
         utils::{Anvil, Genesis, Geth, GethInstance},
     };
@@ -1810,6 +2228,103 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn lookup_address_verifies_forward_resolution() {
+        use ethers_core::abi::Token;
+
+        let (provider, mock) = Provider::mocked();
+        let address = Address::from_low_u64_be(1);
+        let push = |token: Token| mock.push::<Bytes, Bytes>(abi::encode(&[token]).into()).unwrap();
+        let resolver = Token::Address(Address::from_low_u64_be(2));
+        let name = |name: &str| Token::String(name.to_string());
+
+        // responses are popped from the back: the reverse resolver and its name record, then the
+        // forward resolver, its interface check and the address record
+        push(Token::Address(Address::from_low_u64_be(3)));
+        push(Token::Bool(true));
+        push(resolver.clone());
+        push(name("vitalik.eth"));
+        push(resolver.clone());
+        let err = provider.lookup_address(address).await.unwrap_err();
+        assert!(matches!(err, ProviderError::EnsNotOwned(name) if name == "vitalik.eth"));
+
+        // no reverse record set
+        push(name(""));
+        push(resolver.clone());
+        let err = provider.lookup_address(address).await.unwrap_err();
+        assert!(
+            matches!(err, ProviderError::EnsError(name) if name == ens::reverse_address(address))
+        );
+
+        push(Token::Address(address));
+        push(Token::Bool(true));
+        push(resolver.clone());
+        push(name("vitalik.eth"));
+        push(resolver);
+        assert_eq!(provider.lookup_address(address).await.unwrap(), "vitalik.eth");
+    }
+
+    #[tokio::test]
+    async fn debug_trace_call_with_call_tracer() {
+        let (provider, mock) = Provider::mocked();
+
+        let call_frame = serde_json::json!({
+            "type": "CALL",
+            "from": "0x0000000000000000000000000000000000000001",
+            "to": "0x0000000000000000000000000000000000000002",
+            "gas": "0x7148",
+            "gasUsed": "0x5bfc",
+            "input": "0x",
+            "error": "execution reverted",
+            "revertReason": "not enough",
+            "calls": []
+        });
+        mock.push(call_frame).unwrap();
+
+        let mut opts = GethDebugTracingCallOptions::default();
+        opts.tracing_options.tracer =
+            Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer));
+        let tx = TransactionRequest::new().to(Address::from_low_u64_be(2));
+        let trace = provider.debug_trace_call(tx, None, opts).await.unwrap();
+
+        match trace {
+            GethTrace::Known(GethTraceFrame::CallTracer(frame)) => {
+                assert_eq!(frame.revert_reason.as_deref(), Some("not enough"));
+                assert_eq!(frame.calls, Some(vec![]));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn request_raw_forwards_to_client() {
+        let (provider, mock) = Provider::mocked();
+        let header = serde_json::json!({ "number": "0x1" });
+        mock.push(header.clone()).unwrap();
+
+        // dispatch through a middleware that relies on the default implementation
+        #[derive(Debug)]
+        struct Passthrough(Provider<MockProvider>);
+
+        #[async_trait::async_trait]
+        impl Middleware for Passthrough {
+            type Error = ProviderError;
+            type Provider = MockProvider;
+            type Inner = Provider<MockProvider>;
+
+            fn inner(&self) -> &Self::Inner {
+                &self.0
+            }
+        }
+
+        let client = Passthrough(provider);
+        let res: serde_json::Value =
+            client.request_raw("erigon_getHeaderByNumber", [U64::from(1)]).await.unwrap();
+
+        assert_eq!(res, header);
+        mock.assert_request("erigon_getHeaderByNumber", [U64::from(1)]).unwrap();
+    }
+
     #[tokio::test]
     async fn test_fill_transaction_1559() {
         let (mut provider, mock) = Provider::mocked();
@@ -1883,6 +2398,98 @@ mod tests {
         assert!(matches!(res, Err(ProviderError::JsonRpcClientError(_))));
     }
 
+    #[tokio::test]
+    async fn test_fill_transaction_4844() {
+        let (provider, mock) = Provider::mocked();
+
+        let to: Address = "0x0000000000000000000000000000000000000002".parse().unwrap();
+        let mut tx = Eip4844TransactionRequest::new()
+            .to(to)
+            .gas(21000u64)
+            .max_fee_per_gas(25u64)
+            .max_priority_fee_per_gas(25u64)
+            .into();
+
+        // --- fills the blob gas price from the latest block's excess blob gas
+        let block = Block::<TxHash> {
+            excess_blob_gas: Some((10u64 * 1024 * 1024).into()),
+            ..Default::default()
+        };
+        mock.push(block).unwrap();
+        provider.fill_transaction(&mut tx, None).await.unwrap();
+
+        mock.assert_request("eth_getBlockByNumber", ("latest", false)).unwrap();
+        let inner = tx.as_eip4844_ref().unwrap();
+        assert_eq!(inner.max_fee_per_blob_gas, Some(46u64.into()));
+        assert_eq!(tx.gas_price(), Some(25u64.into()));
+
+        // --- errors on chains without blob transactions
+        let mut tx = Eip4844TransactionRequest::new()
+            .gas(21000u64)
+            .max_fee_per_gas(25u64)
+            .max_priority_fee_per_gas(25u64)
+            .into();
+        mock.push(Block::<TxHash>::default()).unwrap();
+        let res = provider.fill_transaction(&mut tx, None).await;
+        assert!(matches!(res, Err(ProviderError::CustomError(_))));
+
+        // --- errors if the blob gas price overflows
+        let block = Block::<TxHash> { excess_blob_gas: Some(U256::MAX), ..Default::default() };
+        mock.push(block).unwrap();
+        let res = provider.fill_transaction(&mut tx, None).await;
+        assert!(matches!(res, Err(ProviderError::CustomError(e)) if e.contains("overflow")));
+    }
+
+    #[tokio::test]
+    async fn test_auto_poll_interval() {
+        let (mut provider, mock) = Provider::mocked();
+        provider.set_interval(Duration::from_secs(10));
+        provider.set_auto_poll_interval(Duration::from_millis(100), Duration::from_secs(5));
+        // clamped until the block time was measured
+        assert_eq!(provider.get_interval(), Duration::from_secs(5));
+
+        // 10 blocks in 2.5 seconds on average
+        let block = |number: u64, timestamp: u64| Block::<TxHash> {
+            number: Some(number.into()),
+            timestamp: timestamp.into(),
+            ..Default::default()
+        };
+        mock.push(block(90, 1000)).unwrap();
+        mock.push(block(100, 1025)).unwrap();
+        assert_eq!(provider.tune_poll_interval().await.unwrap(), Duration::from_millis(1250));
+        assert_eq!(provider.get_interval(), Duration::from_millis(1250));
+        mock.assert_request("eth_getBlockByNumber", ("latest", false)).unwrap();
+        mock.assert_request("eth_getBlockByNumber", (U64::from(90), false)).unwrap();
+
+        // timestamps that don't fit into seconds keep the current interval
+        mock.push(block(90, 1000)).unwrap();
+        mock.push(Block::<TxHash> { timestamp: U256::MAX, ..block(100, 0) }).unwrap();
+        assert_eq!(provider.tune_poll_interval().await.unwrap(), Duration::from_millis(1250));
+
+        // fast chains are bounded by the minimum
+        mock.push(block(90, 1000)).unwrap();
+        mock.push(block(100, 1000)).unwrap();
+        assert_eq!(provider.tune_poll_interval().await.unwrap(), Duration::from_millis(100));
+
+        // watchers measure the block time once
+        let (mut provider, mock) = Provider::mocked();
+        provider.set_auto_poll_interval(Duration::from_millis(100), Duration::from_secs(5));
+        mock.push(block(0, 1000)).unwrap();
+        mock.push(block(10, 1020)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        let watcher = provider.watch_blocks().await.unwrap();
+        assert_eq!(provider.get_interval(), Duration::from_secs(1));
+        drop(watcher);
+        mock.assert_request("eth_newBlockFilter", Vec::<()>::new()).unwrap();
+        mock.assert_request("eth_getBlockByNumber", ("latest", false)).unwrap();
+        mock.assert_request("eth_getBlockByNumber", (U64::from(0), false)).unwrap();
+
+        mock.push(U256::from(2)).unwrap();
+        let _watcher = provider.watch_blocks().await.unwrap();
+        mock.assert_request("eth_newBlockFilter", Vec::<()>::new()).unwrap();
+        assert!(mock.assert_request("eth_getBlockByNumber", ("latest", false)).is_err());
+    }
+
     #[tokio::test]
     async fn test_fill_transaction_legacy() {
         let (mut provider, mock) = Provider::mocked();
@@ -1917,6 +2524,473 @@ mod tests {
         assert!(tx.access_list().is_none());
     }
 
+    #[tokio::test]
+    async fn test_simulate_v1() {
+        let (provider, mock) = Provider::mocked();
+
+        let to: Address = "0x0000000000000000000000000000000000000002".parse().unwrap();
+        let payload = SimulatePayload::new()
+            .block(SimulateBlock::new([]).call(TransactionRequest::new().to(to).value(1)))
+            .validation();
+        let block = SimulatedBlock {
+            inner: Block { number: Some(1.into()), ..Default::default() },
+            calls: vec![SimulatedCall {
+                gas_used: 21000.into(),
+                status: 1.into(),
+                ..Default::default()
+            }],
+        };
+        mock.push::<Vec<SimulatedBlock>, _>(vec![block.clone()]).unwrap();
+
+        let res = provider.simulate_v1(payload.clone(), None).await.unwrap();
+        assert_eq!(res, vec![block]);
+        assert!(res[0].calls[0].is_success());
+        mock.assert_request("eth_simulateV1", (payload, "latest")).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_fee_breakdown() {
+        let (provider, mock) = Provider::mocked();
+        let block_hash = H256::repeat_byte(1);
+        let receipt = TransactionReceipt {
+            block_hash: Some(block_hash),
+            gas_used: Some(21_000.into()),
+            effective_gas_price: Some(12.into()),
+            ..Default::default()
+        };
+
+        let block = Block::<TxHash> { base_fee_per_gas: Some(10.into()), ..Default::default() };
+        mock.push(block).unwrap();
+        let fees = provider.get_fee_breakdown(&receipt).await.unwrap().unwrap();
+        assert_eq!(fees.base_fee_burned, U256::from(210_000));
+        assert_eq!(fees.priority_fee_paid, U256::from(42_000));
+        mock.assert_request("eth_getBlockByHash", (block_hash, false)).unwrap();
+
+        // blocks without a base fee have no breakdown
+        mock.push(Block::<TxHash>::default()).unwrap();
+        assert_eq!(provider.get_fee_breakdown(&receipt).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_storage_range() {
+        let (provider, mock) = Provider::mocked();
+
+        let address: Address = "0x0000000000000000000000000000000000000002".parse().unwrap();
+        for slot in 5u64..8 {
+            mock.expect(
+                "eth_getStorageAt",
+                (address, U256::from(slot), "latest"),
+                H256::from_low_u64_be(slot * 10),
+            )
+            .unwrap();
+        }
+
+        let range = provider.get_storage_range(address, 5.into(), 3, None).await.unwrap();
+        assert_eq!(
+            range,
+            (5u64..8)
+                .map(|slot| (U256::from(slot), H256::from_low_u64_be(slot * 10)))
+                .collect::<Vec<_>>()
+        );
+        mock.assert_expectations_met().unwrap();
+
+        assert!(provider.get_storage_range(address, 0.into(), 0, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn send_transaction_and_confirm_recovers_revert_reason() {
+        use crate::{JsonRpcError, MockResponse};
+        use ethers_core::abi::{self, Token};
+
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(Duration::from_millis(1));
+
+        let tx = TransactionRequest::new()
+            .from(Address::repeat_byte(1))
+            .to(Address::repeat_byte(2))
+            .gas(21_000)
+            .gas_price(1)
+            .nonce(0);
+        let hash = TxHash::repeat_byte(3);
+        let receipt = TransactionReceipt {
+            transaction_hash: hash,
+            block_number: Some(5.into()),
+            status: Some(0.into()),
+            ..Default::default()
+        };
+        let mut revert_data = vec![0x08, 0xc3, 0x79, 0xa0];
+        revert_data.extend(abi::encode(&[Token::String("not enough".to_string())]));
+
+        // responses are popped from the back of the queue
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted: not enough".to_string(),
+            data: Some(Bytes::from(revert_data.clone()).to_string().into()),
+        }));
+        mock.push(receipt.clone()).unwrap();
+        mock.push(Transaction { hash, block_number: Some(5.into()), ..Default::default() })
+            .unwrap();
+        mock.push(hash).unwrap();
+
+        let err = provider.send_transaction_and_confirm(tx.clone(), None, 1).await.unwrap_err();
+        let ProviderError::TransactionReverted(reverted) = err else {
+            panic!("unexpected error: {err:?}")
+        };
+        assert_eq!(reverted.receipt, receipt);
+        assert_eq!(reverted.revert_data, Some(revert_data.into()));
+        assert_eq!(reverted.reason().as_deref(), Some("not enough"));
+        assert_eq!(reverted.to_string(), format!("transaction {hash:?} reverted: not enough"));
+
+        let tx = TypedTransaction::Legacy(tx);
+        mock.assert_request("eth_sendTransaction", [&tx]).unwrap();
+        mock.assert_request("eth_getTransactionByHash", [hash]).unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [hash]).unwrap();
+        mock.assert_request("eth_call", (&tx, BlockNumber::Number(4.into()))).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_call_many() {
+        use crate::{JsonRpcError, MockResponse};
+
+        let (provider, mock) = Provider::mocked();
+        let token = Address::repeat_byte(1);
+        let approve: TypedTransaction =
+            TransactionRequest::new().to(token).data(vec![0x09, 0x5e, 0xa7, 0xb3]).into();
+        let swap: TypedTransaction = TransactionRequest::new()
+            .to(Address::repeat_byte(2))
+            .data(vec![0x38, 0xed, 0x17, 0x39])
+            .into();
+        let router = Address::repeat_byte(3);
+        let mut state = spoof::state();
+        state.account(token).balance(U256::exp10(18));
+        state.account(router).nonce(1.into());
+
+        let responses = vec![
+            CallManyResponse { value: Some(vec![1].into()), error: None },
+            CallManyResponse { value: None, error: Some("execution reverted".to_string()) },
+        ];
+        mock.push::<Vec<Vec<CallManyResponse>>, _>(vec![responses.clone()]).unwrap();
+        let calls = [
+            (approve.clone(), Some(spoof::balance(token, U256::exp10(18)))),
+            (swap.clone(), Some(spoof::nonce(router, 1.into()))),
+        ];
+        let results = provider.call_many(&calls, Some(5u64.into())).await.unwrap();
+        assert_eq!(results, responses);
+        // the overrides of all calls are merged
+        mock.assert_request(
+            "eth_callMany",
+            (
+                [serde_json::json!({ "transactions": [&approve, &swap] })],
+                serde_json::json!({ "blockNumber": "0x5" }),
+                &state,
+            ),
+        )
+        .unwrap();
+
+        // nodes without eth_callMany are reported as unsupported
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32601,
+            message: "the method eth_callMany does not exist/is not available".to_string(),
+            data: None,
+        }));
+        let calls = [(approve.clone(), None), (swap.clone(), None)];
+        let err = provider.call_many(&calls, None).await.unwrap_err();
+        assert!(matches!(err, ProviderError::UnsupportedRPC));
+        mock.assert_request(
+            "eth_callMany",
+            (
+                [serde_json::json!({ "transactions": [&approve, &swap] })],
+                serde_json::json!({ "blockNumber": "latest" }),
+            ),
+        )
+        .unwrap();
+
+        // the same account can't be overridden by several calls
+        let calls = [
+            (approve.clone(), Some(spoof::balance(token, 1.into()))),
+            (swap, Some(spoof::balance(token, 2.into()))),
+        ];
+        let err = provider.call_many(&calls, None).await.unwrap_err();
+        assert!(matches!(err, ProviderError::CustomError(_)));
+        assert!(mock.assert_request("eth_callMany", ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn detects_archive_nodes() {
+        use crate::{JsonRpcError, MockResponse};
+
+        let error = |message: &str| {
+            MockResponse::Error(JsonRpcError {
+                code: -32000,
+                message: message.to_string(),
+                data: None,
+            })
+        };
+        let (provider, mock) = Provider::mocked();
+
+        mock.push(U256::zero()).unwrap();
+        assert!(provider.is_archive_node().await.unwrap());
+        mock.assert_request("eth_getBalance", (Address::zero(), "0x1")).unwrap();
+
+        mock.push_response(error(
+            "missing trie node 1f3c... (path ) state 0x1f3c... is not available",
+        ));
+        assert!(!provider.is_archive_node().await.unwrap());
+        mock.push_response(error("historical state not available in path scheme yet"));
+        assert!(!provider.is_archive_node_at(100).await.unwrap());
+        mock.assert_request("eth_getBalance", (Address::zero(), "0x1")).unwrap();
+        mock.assert_request("eth_getBalance", (Address::zero(), "0x64")).unwrap();
+
+        // other errors are returned
+        mock.push_response(error("header not found"));
+        assert!(provider.is_archive_node().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn block_receipts_fall_back_to_individual_receipts() {
+        use crate::{JsonRpcError, MockResponse};
+
+        let method_not_found = || {
+            MockResponse::Error(JsonRpcError {
+                code: -32601,
+                message: "the method eth_getBlockReceipts does not exist/is not available"
+                    .to_string(),
+                data: None,
+            })
+        };
+        let hashes = [TxHash::repeat_byte(1), TxHash::repeat_byte(2)];
+        let receipts = hashes
+            .iter()
+            .map(|hash| TransactionReceipt { transaction_hash: *hash, ..Default::default() })
+            .collect::<Vec<_>>();
+
+        // without the fallback the error is returned
+        let (provider, mock) = Provider::mocked();
+        mock.push_response(method_not_found());
+        assert!(provider.get_block_receipts(5u64).await.is_err());
+
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.block_receipts_fallback(true);
+        // responses are popped from the back of the queue
+        mock.push(receipts[1].clone()).unwrap();
+        mock.push(receipts[0].clone()).unwrap();
+        mock.push(Block::<TxHash> { transactions: hashes.to_vec(), ..Default::default() }).unwrap();
+        mock.push_response(method_not_found());
+
+        assert_eq!(provider.get_block_receipts(5u64).await.unwrap(), receipts);
+        mock.assert_request("eth_getBlockReceipts", [U64::from(5)]).unwrap();
+        mock.assert_request("eth_getBlockByNumber", (U64::from(5), false)).unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [hashes[0]]).unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [hashes[1]]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn estimates_eip1559_fees_with_strategy() {
+        let (provider, mock) = Provider::mocked();
+        let history = FeeHistory {
+            base_fee_per_gas: vec![100.into(), 200.into()],
+            gas_used_ratio: vec![1.0],
+            oldest_block: 1.into(),
+            reward: vec![vec![7.into()]],
+        };
+        mock.push(history.clone()).unwrap();
+
+        let fees = provider
+            .estimate_eip1559_fees_with(|history| {
+                let base_fee = *history.base_fee_per_gas.last().unwrap();
+                let priority_fee = history.reward[0][0];
+                (base_fee + priority_fee, priority_fee)
+            })
+            .await
+            .unwrap();
+        assert_eq!(fees, (207.into(), 7.into()));
+        mock.assert_request(
+            "eth_feeHistory",
+            [
+                utils::serialize(&U256::from(utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS)),
+                utils::serialize(&BlockNumber::Latest),
+                utils::serialize(&[utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE]),
+            ],
+        )
+        .unwrap();
+
+        // pre EIP-1559 chains have no base fees
+        mock.push(FeeHistory { base_fee_per_gas: vec![], ..history }).unwrap();
+        assert!(provider.estimate_eip1559_fees_with(|_| unreachable!()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn estimates_eip1559_fees_without_rewards() {
+        let (provider, mock) = Provider::mocked();
+        // responses are popped from the back of the queue
+        mock.push(U256::from(5)).unwrap();
+        mock.push(FeeHistory {
+            base_fee_per_gas: vec![100.into(), 100.into()],
+            gas_used_ratio: vec![0.0],
+            oldest_block: 1.into(),
+            reward: vec![vec![U256::zero()]],
+        })
+        .unwrap();
+        mock.push(Block::<TxHash> { base_fee_per_gas: Some(100.into()), ..Default::default() })
+            .unwrap();
+
+        let fees = provider.estimate_eip1559_fees(None).await.unwrap();
+        assert_eq!(fees, (205.into(), 5.into()));
+        mock.assert_request("eth_getBlockByNumber", ("latest", false)).unwrap();
+        mock.assert_request(
+            "eth_feeHistory",
+            [
+                utils::serialize(&U256::from(utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS)),
+                utils::serialize(&BlockNumber::Latest),
+                utils::serialize(&[utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE]),
+            ],
+        )
+        .unwrap();
+        mock.assert_request("eth_maxPriorityFeePerGas", ()).unwrap();
+
+        // the max fee saturates instead of overflowing
+        mock.push(U256::MAX).unwrap();
+        mock.push(FeeHistory {
+            base_fee_per_gas: vec![100.into(), 100.into()],
+            gas_used_ratio: vec![0.0],
+            oldest_block: 1.into(),
+            reward: vec![vec![U256::zero()]],
+        })
+        .unwrap();
+        mock.push(Block::<TxHash> { base_fee_per_gas: Some(100.into()), ..Default::default() })
+            .unwrap();
+        let fees = provider.estimate_eip1559_fees(None).await.unwrap();
+        assert_eq!(fees, (U256::MAX, U256::MAX));
+    }
+
+    #[tokio::test]
+    async fn get_uncles() {
+        let (provider, mock) = Provider::mocked();
+        let hash = H256::repeat_byte(1);
+
+        mock.push(U256::from(1)).unwrap();
+        assert_eq!(provider.get_uncle_count(hash).await.unwrap(), U256::from(1));
+        mock.assert_request("eth_getUncleCountByBlockHash", [hash]).unwrap();
+
+        // post-merge blocks have no uncles
+        mock.push(U256::zero()).unwrap();
+        assert_eq!(provider.get_uncle_count(5u64).await.unwrap(), U256::zero());
+        mock.assert_request("eth_getUncleCountByBlockNumber", [U64::from(5)]).unwrap();
+
+        // unknown blocks are an error rather than a block without uncles
+        mock.push(serde_json::Value::Null).unwrap();
+        assert!(matches!(
+            provider.get_uncle_count(6u64).await.unwrap_err(),
+            ProviderError::CustomError(_)
+        ));
+        mock.assert_request("eth_getUncleCountByBlockNumber", [U64::from(6)]).unwrap();
+
+        mock.push(serde_json::Value::Null).unwrap();
+        assert_eq!(provider.get_uncle(5u64, 0.into()).await.unwrap(), None);
+        mock.assert_request("eth_getUncleByBlockNumberAndIndex", [U64::from(5), U64::zero()])
+            .unwrap();
+
+        let uncle = Block::<H256> { number: Some(4.into()), ..Default::default() };
+        mock.push(uncle.clone()).unwrap();
+        assert_eq!(provider.get_uncle(hash, 1.into()).await.unwrap(), Some(uncle));
+        mock.assert_request("eth_getUncleByBlockHashAndIndex", (hash, U64::one())).unwrap();
+    }
+
+    #[tokio::test]
+    async fn queries_account_state_at_block() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+        let hash = H256::repeat_byte(2);
+
+        mock.push(U256::from(1)).unwrap();
+        provider.get_balance(address, None).await.unwrap();
+        mock.assert_request("eth_getBalance", (address, "latest")).unwrap();
+
+        // block hashes are sent as EIP-1898 objects
+        mock.push(U256::from(1)).unwrap();
+        provider.get_balance(address, Some(hash.into())).await.unwrap();
+        mock.assert_request("eth_getBalance", (address, serde_json::json!({ "blockHash": hash })))
+            .unwrap();
+
+        mock.push(U256::from(1)).unwrap();
+        provider.get_transaction_count(address, Some(hash.into())).await.unwrap();
+        mock.assert_request(
+            "eth_getTransactionCount",
+            (address, serde_json::json!({ "blockHash": hash })),
+        )
+        .unwrap();
+
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        provider.get_code(address, Some(5u64.into())).await.unwrap();
+        mock.assert_request("eth_getCode", (address, U64::from(5))).unwrap();
+
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        provider.get_code(address, Some(hash.into())).await.unwrap();
+        mock.assert_request("eth_getCode", (address, serde_json::json!({ "blockHash": hash })))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn waits_for_block() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(Duration::from_millis(1));
+        let block = Block::<TxHash> { number: Some(5.into()), ..Default::default() };
+        // responses are popped from the back of the queue
+        mock.push(block.clone()).unwrap();
+        mock.push(U64::from(6)).unwrap();
+        // the block was reorged out
+        mock.push(serde_json::Value::Null).unwrap();
+        mock.push(U64::from(5)).unwrap();
+        mock.push(U64::from(4)).unwrap();
+
+        assert_eq!(provider.wait_for_block(5).await.unwrap(), block);
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getBlockByNumber", (U64::from(5), false)).unwrap();
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getBlockByNumber", (U64::from(5), false)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn wait_for_block_times_out() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(Duration::from_secs(10));
+        mock.push(U64::from(4)).unwrap();
+
+        let err = provider.wait_for_block_with_timeout(5, Duration::from_millis(10)).await;
+        assert!(err.unwrap_err().to_string().contains("timed out waiting for block 5"));
+    }
+
+    #[tokio::test]
+    async fn test_node_status() {
+        let (provider, mock) = Provider::mocked();
+
+        mock.expect("eth_syncing", (), false).unwrap();
+        mock.expect(
+            "eth_syncing",
+            (),
+            serde_json::json!({
+                "startingBlock": "0x1",
+                "currentBlock": "0x2",
+                "highestBlock": "0x3"
+            }),
+        )
+        .unwrap();
+        mock.expect("txpool_status", (), serde_json::json!({ "pending": "0x10", "queued": "0x7" }))
+            .unwrap();
+        mock.expect("net_version", (), "1").unwrap();
+
+        assert_eq!(provider.syncing().await.unwrap(), SyncingStatus::IsFalse);
+        let status = provider.syncing().await.unwrap();
+        assert_eq!(status.progress().unwrap().highest_block, 3u64.into());
+        assert_eq!(
+            provider.txpool_status().await.unwrap(),
+            TxpoolStatus { pending: 16u64.into(), queued: 7u64.into() }
+        );
+        assert_eq!(provider.get_net_version().await.unwrap(), "1");
+        mock.assert_expectations_met().unwrap();
+    }
+
     #[tokio::test]
     async fn mainnet_lookup_address_invalid_resolver() {
         let provider = crate::MAINNET.provider();
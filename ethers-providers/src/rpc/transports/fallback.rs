@@ -0,0 +1,232 @@
+//! A [JsonRpcClient] implementation that sends requests to the first of its providers that is
+//! available
+
+use super::quorum::{JsonRpcClientWrapper, QuorumParams};
+use crate::{errors::ProviderError, JsonRpcClient, RpcError};
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use thiserror::Error;
+use tracing::{trace, warn};
+
+/// A provider that sends each request to the first of its providers and falls back to the next
+/// provider if the request failed.
+///
+/// Only transport failures, e.g. connection errors or timeouts, cause a fallback. If a provider
+/// returned a JSON-RPC error response, e.g. because a call reverted, the error is returned
+/// directly, since any other provider would respond the same way.
+///
+/// Transactions submitted with `eth_sendRawTransaction` are broadcast to _all_ providers, and the
+/// first successful response is returned.
+///
+/// # Example
+///
+/// ```
+/// use ethers_core::types::U64;
+/// use ethers_providers::{FallbackProvider, Http, JsonRpcClient};
+/// use std::str::FromStr;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = FallbackProvider::new([
+///     Http::from_str("http://localhost:8545")?,
+///     Http::from_str("http://localhost:8546")?,
+/// ]);
+/// let block_number: U64 = provider.request("eth_blockNumber", ()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FallbackProvider<T = Box<dyn JsonRpcClientWrapper>> {
+    /// All the internal providers, in the order they're tried
+    providers: Vec<T>,
+}
+
+impl FallbackProvider<Box<dyn JsonRpcClientWrapper>> {
+    /// Create a `FallbackProvider` for different `JsonRpcClient` types
+    pub fn dyn_rpc(
+        providers: impl IntoIterator<Item = Box<dyn JsonRpcClientWrapper>>,
+    ) -> FallbackProvider<Box<dyn JsonRpcClientWrapper>> {
+        FallbackProvider::new(providers)
+    }
+}
+
+impl<T> FallbackProvider<T> {
+    /// Instantiate a new `FallbackProvider` that tries the `providers` in the given order
+    pub fn new(providers: impl IntoIterator<Item = T>) -> Self {
+        Self { providers: providers.into_iter().collect() }
+    }
+
+    /// Return a reference to the providers, in the order they're tried
+    pub fn providers(&self) -> &[T] {
+        &self.providers
+    }
+
+    /// Add a provider that is tried after all the existing providers
+    pub fn add_provider(&mut self, provider: T) {
+        self.providers.push(provider);
+    }
+}
+
+#[derive(Error, Debug)]
+/// Error thrown when all providers of a `FallbackProvider` failed
+pub enum FallbackError {
+    /// Every provider failed, contains the error of each provider in order
+    #[error("All providers failed: {}", format_errors(.0))]
+    AllProvidersFailed(Vec<ProviderError>),
+}
+
+pub(crate) fn format_errors(errors: &[ProviderError]) -> String {
+    errors.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+}
+
+impl RpcError for FallbackError {
+    fn as_error_response(&self) -> Option<&super::JsonRpcError> {
+        None
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        None
+    }
+}
+
+impl From<FallbackError> for ProviderError {
+    fn from(src: FallbackError) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(src))
+    }
+}
+
+/// Sends the request to all `providers` concurrently and returns the first successful response,
+/// or all errors if no provider succeeded
+pub(crate) async fn broadcast<'a, T: JsonRpcClientWrapper + 'a>(
+    providers: impl IntoIterator<Item = &'a T>,
+    method: &str,
+    params: QuorumParams,
+) -> Result<Value, Vec<ProviderError>> {
+    let responses =
+        join_all(providers.into_iter().map(|provider| provider.request(method, params.clone())))
+            .await;
+
+    let mut errors = Vec::new();
+    let mut value = None;
+    for response in responses {
+        match response {
+            Ok(val) if value.is_none() => value = Some(val),
+            Ok(_) => {}
+            Err(err) => {
+                warn!(method, ?err, "failed to broadcast request");
+                errors.push(err);
+            }
+        }
+    }
+    value.ok_or(errors)
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C> JsonRpcClient for FallbackProvider<C>
+where
+    C: JsonRpcClientWrapper,
+{
+    type Error = ProviderError;
+
+    async fn request<T: Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: T,
+    ) -> Result<R, Self::Error> {
+        let params = if std::mem::size_of::<T>() == 0 {
+            // we don't want `()` to become `"null"`.
+            QuorumParams::Zst
+        } else {
+            QuorumParams::Value(serde_json::to_value(params)?)
+        };
+
+        if method == "eth_sendRawTransaction" {
+            let value = broadcast(&self.providers, method, params)
+                .await
+                .map_err(FallbackError::AllProvidersFailed)?;
+            return Ok(serde_json::from_value(value)?)
+        }
+
+        let mut errors = Vec::new();
+        for (idx, provider) in self.providers.iter().enumerate() {
+            match provider.request(method, params.clone()).await {
+                Ok(value) => return Ok(serde_json::from_value(value)?),
+                // the node processed the request, another node would respond the same way
+                Err(err) if err.is_error_response() => return Err(err),
+                Err(err) => {
+                    trace!(method, idx, ?err, "provider failed, falling back to next provider");
+                    errors.push(err);
+                }
+            }
+        }
+        Err(FallbackError::AllProvidersFailed(errors).into())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::{JsonRpcError, MockError, MockProvider, MockResponse};
+    use ethers_core::types::{Bytes, H256, U64};
+
+    #[tokio::test]
+    async fn falls_back_on_transport_errors() {
+        let failing = MockProvider::new();
+        let working = MockProvider::new();
+        working.push(U64::from(42)).unwrap();
+        let provider = FallbackProvider::new([failing.clone(), working.clone()]);
+
+        let block: U64 = JsonRpcClient::request(&provider, "eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, 42.into());
+        failing.assert_request("eth_blockNumber", ()).unwrap();
+        working.assert_request("eth_blockNumber", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn returns_error_responses_without_fallback() {
+        let reverting = MockProvider::new();
+        reverting.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: None,
+        }));
+        let other = MockProvider::new();
+        other.push::<Bytes, _>(Bytes::default()).unwrap();
+        let provider = FallbackProvider::new([reverting, other.clone()]);
+
+        let err =
+            JsonRpcClient::request::<_, Bytes>(&provider, "eth_call", ["0x"]).await.unwrap_err();
+        assert_eq!(err.as_error_response().unwrap().code, 3);
+        assert!(matches!(other.assert_request("eth_call", ["0x"]), Err(MockError::EmptyRequests)));
+    }
+
+    #[tokio::test]
+    async fn reports_all_errors() {
+        let provider = FallbackProvider::new([MockProvider::new(), MockProvider::new()]);
+
+        let err =
+            JsonRpcClient::request::<_, U64>(&provider, "eth_blockNumber", ()).await.unwrap_err();
+        let ProviderError::JsonRpcClientError(err) = err else { panic!("unexpected error") };
+        assert!(err.to_string().starts_with("All providers failed"));
+    }
+
+    #[tokio::test]
+    async fn broadcasts_raw_transactions() {
+        let hash = H256::repeat_byte(1);
+        let providers = [MockProvider::new(), MockProvider::new(), MockProvider::new()];
+        providers[1].push(hash).unwrap();
+        providers[2].push(hash).unwrap();
+        let provider = FallbackProvider::new(providers.clone());
+
+        let res: H256 =
+            JsonRpcClient::request(&provider, "eth_sendRawTransaction", ["0x1234"]).await.unwrap();
+        assert_eq!(res, hash);
+        for mock in providers {
+            mock.assert_request("eth_sendRawTransaction", ["0x1234"]).unwrap();
+        }
+    }
+}
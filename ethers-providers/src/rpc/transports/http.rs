@@ -3,9 +3,15 @@
 use super::common::{Authorization, JsonRpcError, Request, Response};
 use crate::{errors::ProviderError, JsonRpcClient};
 use async_trait::async_trait;
-use reqwest::{header::HeaderValue, Client, Error as ReqwestError};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Client, Error as ReqwestError,
+};
 use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 use std::{
+    collections::HashMap,
+    ops::Range,
     str::FromStr,
     sync::atomic::{AtomicU64, Ordering},
 };
@@ -32,6 +38,7 @@ pub struct Provider {
     id: AtomicU64,
     client: Client,
     url: Url,
+    headers: HeaderMap,
 }
 
 #[derive(Error, Debug)]
@@ -93,7 +100,13 @@ impl JsonRpcClient for Provider {
         let next_id = self.id.fetch_add(1, Ordering::SeqCst);
         let payload = Request::new(next_id, method, params);
 
-        let res = self.client.post(self.url.as_ref()).json(&payload).send().await?;
+        let res = self
+            .client
+            .post(self.url.as_ref())
+            .headers(self.headers.clone())
+            .json(&payload)
+            .send()
+            .await?;
         let body = res.bytes().await?;
 
         let raw = match serde_json::from_slice(&body) {
@@ -119,6 +132,74 @@ impl JsonRpcClient for Provider {
 
         Ok(res)
     }
+
+    /// Sends the requests in a single JSON-RPC batch
+    async fn request_batch(
+        &self,
+        requests: Vec<(String, Value)>,
+    ) -> Result<Vec<Result<Value, ClientError>>, ClientError> {
+        if requests.is_empty() {
+            return Ok(Vec::new())
+        }
+        let first_id = self.id.fetch_add(requests.len() as u64, Ordering::SeqCst);
+        let ids = first_id..first_id + requests.len() as u64;
+        let payload: Vec<_> = ids
+            .clone()
+            .zip(&requests)
+            .map(|(id, (method, params))| Request::new(id, method, params))
+            .collect();
+
+        let res = self
+            .client
+            .post(self.url.as_ref())
+            .headers(self.headers.clone())
+            .json(&payload)
+            .send()
+            .await?;
+        let body = res.bytes().await?;
+        parse_batch_response(ids, &body)
+    }
+}
+
+/// Parses the responses to a batch of requests with the given ids, returning them in the order of
+/// the ids, since servers may respond in any order
+fn parse_batch_response(
+    ids: Range<u64>,
+    body: &[u8],
+) -> Result<Vec<Result<Value, ClientError>>, ClientError> {
+    let text = || String::from_utf8_lossy(body).to_string();
+    let responses: Vec<Response<'_>> = match serde_json::from_slice(body) {
+        Ok(responses) => responses,
+        // servers reject invalid batches with a single error
+        Err(err) => match serde_json::from_slice(body) {
+            Ok(Response::Error { error, .. }) => return Err(error.into()),
+            _ => return Err(ClientError::SerdeJson { err, text: text() }),
+        },
+    };
+
+    let mut results = HashMap::with_capacity(responses.len());
+    for response in responses {
+        let (id, result) = match response {
+            Response::Success { id, result } => (
+                id,
+                serde_json::from_str(result.get())
+                    .map_err(|err| ClientError::SerdeJson { err, text: result.to_string() }),
+            ),
+            Response::Error { id, error } => (id, Err(error.into())),
+            Response::Notification { .. } => continue,
+        };
+        results.insert(id, result);
+    }
+    Ok(ids
+        .map(|id| {
+            results.remove(&id).unwrap_or_else(|| {
+                Err(ClientError::SerdeJson {
+                    err: serde::de::Error::custom(format!("missing response to request {id}")),
+                    text: text(),
+                })
+            })
+        })
+        .collect())
 }
 
 impl Provider {
@@ -147,6 +228,77 @@ impl Provider {
         &mut self.url
     }
 
+    /// The headers that are sent with every request, in addition to the default headers of the
+    /// client
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Mutable access to the headers that are sent with every request
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Initializes a new HTTP Client that sends the provided headers with every request
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_providers::Http;
+    /// use reqwest::header::{HeaderMap, HeaderValue};
+    /// use url::Url;
+    ///
+    /// let url = Url::parse("http://localhost:8545").unwrap();
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-api-key", HeaderValue::from_static("my-key"));
+    /// let provider = Http::new_with_headers(url, headers);
+    /// ```
+    pub fn new_with_headers(url: impl Into<Url>, headers: HeaderMap) -> Self {
+        Self::new(url).with_headers(headers)
+    }
+
+    /// Adds the provided headers to the headers that are sent with every request, replacing
+    /// existing headers of the same name
+    #[must_use]
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Adds a header that is sent with every request, replacing an existing header of the same
+    /// name
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_providers::{Authorization, Http};
+    /// use std::str::FromStr;
+    ///
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Http::from_str("http://localhost:8545")?
+    ///     .with_header("x-api-key", "my-key")?
+    ///     .with_auth(Authorization::bearer("my-token"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_header(
+        mut self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Result<Self, HttpClientError> {
+        let name = HeaderName::from_bytes(name.as_ref().as_bytes())?;
+        self.headers.insert(name, HeaderValue::from_str(value.as_ref())?);
+        Ok(self)
+    }
+
+    /// Sets the `Authorization` header that is sent with every request
+    pub fn with_auth(mut self, auth: Authorization) -> Result<Self, HttpClientError> {
+        let mut auth_value = HeaderValue::from_str(&auth.to_string())?;
+        auth_value.set_sensitive(true);
+        self.headers.insert(reqwest::header::AUTHORIZATION, auth_value);
+        Ok(self)
+    }
+
     /// Initializes a new HTTP Client with authentication
     ///
     /// # Example
@@ -186,7 +338,7 @@ impl Provider {
     /// let provider = Http::new_with_client(url, client);
     /// ```
     pub fn new_with_client(url: impl Into<Url>, client: reqwest::Client) -> Self {
-        Self { id: AtomicU64::new(1), client, url: url.into() }
+        Self { id: AtomicU64::new(1), client, url: url.into(), headers: HeaderMap::new() }
     }
 }
 
@@ -201,7 +353,12 @@ impl FromStr for Provider {
 
 impl Clone for Provider {
     fn clone(&self) -> Self {
-        Self { id: AtomicU64::new(1), client: self.client.clone(), url: self.url.clone() }
+        Self {
+            id: AtomicU64::new(1),
+            client: self.client.clone(),
+            url: self.url.clone(),
+            headers: self.headers.clone(),
+        }
     }
 }
 
@@ -212,7 +369,109 @@ pub enum HttpClientError {
     #[error(transparent)]
     InvalidHeader(#[from] http::header::InvalidHeaderValue),
 
+    /// Thrown if a header name is invalid
+    #[error(transparent)]
+    InvalidHeaderName(#[from] http::header::InvalidHeaderName),
+
     /// Thrown if unable to build client
     #[error(transparent)]
     ClientBuild(#[from] reqwest::Error),
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use ethers_core::types::U64;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
+    /// Serves a single request with the given response body, returning the lowercased request
+    /// headers
+    fn serve_once(body: &'static str) -> (Url, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_lowercase();
+                if line.is_empty() {
+                    break
+                }
+                headers.push(line);
+            }
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            headers
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn sends_headers_with_every_request() {
+        let (url, server) = serve_once(r#"{"jsonrpc":"2.0","id":1,"result":"0x2a"}"#);
+        let provider = Provider::new(url)
+            .with_header("X-Api-Key", "my-key")
+            .unwrap()
+            .with_auth(Authorization::bearer("my-token"))
+            .unwrap();
+
+        let block: U64 = provider.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, U64::from(42));
+
+        let headers = server.join().unwrap();
+        assert!(headers.contains(&"x-api-key: my-key".to_string()));
+        assert!(headers.contains(&"authorization: bearer my-token".to_string()));
+    }
+
+    #[tokio::test]
+    async fn sends_batch_requests() {
+        // the responses are out of order, and the response to the third request is missing
+        let (url, server) = serve_once(
+            r#"[{"jsonrpc":"2.0","id":2,"error":{"code":-32000,"message":"execution reverted"}},{"jsonrpc":"2.0","id":1,"result":"0x2a"}]"#,
+        );
+        let provider = Provider::new(url);
+        let requests = vec![
+            ("eth_blockNumber".to_string(), Value::Array(vec![])),
+            ("eth_call".to_string(), Value::Array(vec![])),
+            ("eth_chainId".to_string(), Value::Array(vec![])),
+        ];
+        let results = provider.request_batch(requests).await.unwrap();
+        server.join().unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), &Value::String("0x2a".to_string()));
+        assert!(matches!(&results[1], Err(ClientError::JsonRpcError(err)) if err.code == -32000));
+        assert!(matches!(&results[2], Err(ClientError::SerdeJson { .. })));
+
+        assert!(provider.request_batch(Vec::new()).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_batches() {
+        let body =
+            br#"{"jsonrpc":"2.0","id":null,"error":{"code":-32600,"message":"empty batch"}}"#;
+        // the id of errors of invalid requests is null
+        assert!(parse_batch_response(1..2, body).is_err());
+        assert!(parse_batch_response(1..2, b"not json").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let provider = Provider::from_str("http://localhost:8545").unwrap();
+        assert!(provider.clone().with_header("invalid header", "value").is_err());
+        assert!(provider.with_header("x-api-key", "invalid\nvalue").is_err());
+    }
+}
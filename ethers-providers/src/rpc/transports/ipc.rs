@@ -9,13 +9,14 @@ use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{value::RawValue, Deserializer};
 use std::{
     cell::RefCell,
+    collections::VecDeque,
     convert::Infallible,
     hash::BuildHasherDefault,
     io,
     path::Path,
     sync::{
         atomic::{AtomicU64, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     thread,
 };
@@ -34,6 +35,14 @@ type FxHashMap<K, V> = std::collections::HashMap<K, V, BuildHasherDefault<FxHash
 type Pending = oneshot::Sender<Result<Box<RawValue>, JsonRpcError>>;
 type Subscription = mpsc::UnboundedSender<Box<RawValue>>;
 
+/// The notification streams of subscriptions that have been created by the server, but not
+/// yet been subscribed to via [`PubsubClient::subscribe`], oldest first
+type SharedUnclaimedStreams = Arc<Mutex<VecDeque<(U256, mpsc::UnboundedReceiver<Box<RawValue>>)>>>;
+
+/// The maximum number of unclaimed notification streams that are buffered, beyond which the
+/// oldest one is dropped along with its subscription
+const MAX_UNCLAIMED_STREAMS: usize = 64;
+
 #[cfg(unix)]
 #[doc(hidden)]
 mod imp {
@@ -214,13 +223,25 @@ use self::imp::*;
 pub struct Ipc {
     id: Arc<AtomicU64>,
     request_tx: mpsc::UnboundedSender<TransportMessage>,
+    unclaimed: SharedUnclaimedStreams,
 }
 
 #[derive(Debug)]
 enum TransportMessage {
-    Request { id: u64, request: Box<[u8]>, sender: Pending },
-    Subscribe { id: U256, sink: Subscription },
-    Unsubscribe { id: U256 },
+    /// A request, `subscribe` is set for `eth_subscribe` requests
+    Request {
+        id: u64,
+        request: Box<[u8]>,
+        sender: Pending,
+        subscribe: bool,
+    },
+    Subscribe {
+        id: U256,
+        sink: Subscription,
+    },
+    Unsubscribe {
+        id: U256,
+    },
 }
 
 impl Ipc {
@@ -234,10 +255,12 @@ impl Ipc {
         let id = Arc::new(AtomicU64::new(1));
         let (request_tx, request_rx) = mpsc::unbounded();
 
+        let unclaimed = SharedUnclaimedStreams::default();
+
         let stream = Stream::connect(path).await?;
-        spawn_ipc_server(stream, request_rx);
+        spawn_ipc_server(stream, request_rx, unclaimed.clone());
 
-        Ok(Self { id, request_tx })
+        Ok(Self { id, request_tx, unclaimed })
     }
 
     fn send(&self, msg: TransportMessage) -> Result<(), IpcError> {
@@ -266,6 +289,7 @@ impl JsonRpcClient for Ipc {
             id: next_id,
             request: serde_json::to_vec(&Request::new(next_id, method, params))?.into_boxed_slice(),
             sender,
+            subscribe: method == "eth_subscribe",
         };
 
         // Send the request to the IPC server to be handled.
@@ -283,17 +307,32 @@ impl PubsubClient for Ipc {
     type NotificationStream = mpsc::UnboundedReceiver<Box<RawValue>>;
 
     fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, IpcError> {
+        let id = id.into();
+        // the stream of subscriptions made via this client has been created along with the
+        // response to `eth_subscribe`, so that no notifications are missed
+        let mut unclaimed = self.unclaimed.lock().unwrap();
+        if let Some(pos) = unclaimed.iter().position(|(sub_id, _)| *sub_id == id) {
+            return Ok(unclaimed.remove(pos).expect("position is in bounds").1)
+        }
+        drop(unclaimed);
+
         let (sink, stream) = mpsc::unbounded();
-        self.send(TransportMessage::Subscribe { id: id.into(), sink })?;
+        self.send(TransportMessage::Subscribe { id, sink })?;
         Ok(stream)
     }
 
     fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), IpcError> {
-        self.send(TransportMessage::Unsubscribe { id: id.into() })
+        let id = id.into();
+        self.unclaimed.lock().unwrap().retain(|(sub_id, _)| *sub_id != id);
+        self.send(TransportMessage::Unsubscribe { id })
     }
 }
 
-fn spawn_ipc_server(stream: Stream, request_rx: mpsc::UnboundedReceiver<TransportMessage>) {
+fn spawn_ipc_server(
+    stream: Stream,
+    request_rx: mpsc::UnboundedReceiver<TransportMessage>,
+    unclaimed: SharedUnclaimedStreams,
+) {
     // 256 Kb should be more than enough for this thread, as all unbounded data
     // growth occurs on heap-allocated data structures and buffers and the call
     // stack is not going to do anything crazy either
@@ -309,16 +348,21 @@ fn spawn_ipc_server(stream: Stream, request_rx: mpsc::UnboundedReceiver<Transpor
                 .build()
                 .expect("failed to create ipc-server-thread async runtime");
 
-            rt.block_on(run_ipc_server(stream, request_rx));
+            rt.block_on(run_ipc_server(stream, request_rx, unclaimed));
         })
         .expect("failed to spawn ipc server thread");
 }
 
-async fn run_ipc_server(mut stream: Stream, request_rx: mpsc::UnboundedReceiver<TransportMessage>) {
+async fn run_ipc_server(
+    mut stream: Stream,
+    request_rx: mpsc::UnboundedReceiver<TransportMessage>,
+    unclaimed: SharedUnclaimedStreams,
+) {
     // the shared state for both reads & writes
     let shared = Shared {
         pending: FxHashMap::with_capacity_and_hasher(64, BuildHasherDefault::default()).into(),
         subs: FxHashMap::with_capacity_and_hasher(64, BuildHasherDefault::default()).into(),
+        unclaimed,
     };
 
     // split the stream and run two independent concurrently (local), thereby
@@ -337,8 +381,10 @@ async fn run_ipc_server(mut stream: Stream, request_rx: mpsc::UnboundedReceiver<
 }
 
 struct Shared {
-    pending: RefCell<FxHashMap<u64, Pending>>,
+    /// The pending requests, and whether they are `eth_subscribe` requests
+    pending: RefCell<FxHashMap<u64, (Pending, bool)>>,
     subs: RefCell<FxHashMap<U256, Subscription>>,
+    unclaimed: SharedUnclaimedStreams,
 }
 
 impl Shared {
@@ -372,8 +418,8 @@ impl Shared {
 
         while let Some(msg) = request_rx.next().await {
             match msg {
-                Request { id, request, sender } => {
-                    let prev = self.pending.borrow_mut().insert(id, sender);
+                Request { id, request, sender, subscribe } => {
+                    let prev = self.pending.borrow_mut().insert(id, (sender, subscribe));
                     assert!(prev.is_none(), "{}", "replaced pending IPC request (id={id})");
 
                     if let Err(err) = writer.write_all(&request).await {
@@ -423,14 +469,34 @@ impl Shared {
 
     fn send_response(&self, id: u64, result: Result<Box<RawValue>, JsonRpcError>) {
         // retrieve the channel sender for responding to the pending request
-        let response_tx = match self.pending.borrow_mut().remove(&id) {
-            Some(tx) => tx,
+        let (response_tx, subscribe) = match self.pending.borrow_mut().remove(&id) {
+            Some(pending) => pending,
             None => {
                 tracing::warn!(%id, "no pending request exists for the response ID");
                 return
             }
         };
 
+        // register the subscription before responding, so that notifications which arrive
+        // before the subscription stream is requested are buffered instead of dropped
+        if let (true, Ok(result)) = (subscribe, &result) {
+            if let Ok(sub_id) = serde_json::from_str::<U256>(result.get()) {
+                let (sink, stream) = mpsc::unbounded();
+                self.subs.borrow_mut().insert(sub_id, sink);
+
+                let mut unclaimed = self.unclaimed.lock().unwrap();
+                // streams that are never claimed, e.g. of raw `eth_subscribe` requests, would
+                // otherwise buffer their notifications forever
+                if unclaimed.len() >= MAX_UNCLAIMED_STREAMS {
+                    if let Some((evicted, _)) = unclaimed.pop_front() {
+                        tracing::warn!(id = %evicted, "dropping unclaimed subscription stream");
+                        self.subs.borrow_mut().remove(&evicted);
+                    }
+                }
+                unclaimed.push_back((sub_id, stream));
+            }
+        }
+
         // a failure to send the response indicates that the pending request has
         // been dropped in the mean time
         let _ = response_tx.send(result.map_err(Into::into));
@@ -564,4 +630,74 @@ mod tests {
         assert_eq!(blocks[2], blocks[1] + 1);
         assert_eq!(blocks[1], blocks[0] + 1);
     }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn buffers_notifications_sent_with_the_subscription_response() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::UnixListener,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fake.ipc");
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let n = stream.read(&mut buf).await.unwrap();
+            let request: serde_json::Value = serde_json::from_slice(&buf[..n]).unwrap();
+            assert_eq!(request["method"], "eth_subscribe");
+            // the first notification arrives before the client has subscribed to the stream
+            let response = format!(
+                r#"{{"jsonrpc":"2.0","id":{},"result":"0x2a"}}{{"jsonrpc":"2.0","method":"eth_subscription","params":{{"subscription":"0x2a","result":1}}}}"#,
+                request["id"]
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            stream
+                .write_all(br#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"subscription":"0x2a","result":2}}"#)
+                .await
+                .unwrap();
+            // keep the connection open until the client is done
+            let _ = stream.read(&mut buf).await;
+        });
+
+        let ipc = Ipc::connect(&path).await.unwrap();
+        let sub_id: U256 = ipc.request("eth_subscribe", ["newHeads"]).await.unwrap();
+        assert_eq!(sub_id, U256::from(42));
+
+        let items: Vec<u64> = ipc
+            .subscribe(sub_id)
+            .unwrap()
+            .take(2)
+            .map(|item| serde_json::from_str::<u64>(item.get()).unwrap())
+            .collect()
+            .await;
+        assert_eq!(items, vec![1, 2]);
+
+        drop(ipc);
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn drops_the_oldest_unclaimed_streams() {
+        let shared = Shared {
+            pending: Default::default(),
+            subs: Default::default(),
+            unclaimed: Default::default(),
+        };
+        let count = MAX_UNCLAIMED_STREAMS as u64 + 2;
+        for id in 0..count {
+            let (sender, _receiver) = oneshot::channel();
+            shared.pending.borrow_mut().insert(id, (sender, true));
+            let sub_id = RawValue::from_string(format!("\"{:#x}\"", id + 100)).unwrap();
+            shared.send_response(id, Ok(sub_id));
+        }
+
+        let unclaimed = shared.unclaimed.lock().unwrap();
+        assert_eq!(unclaimed.len(), MAX_UNCLAIMED_STREAMS);
+        assert_eq!(unclaimed.front().unwrap().0, U256::from(102));
+        assert_eq!(shared.subs.borrow().len(), MAX_UNCLAIMED_STREAMS);
+        assert!(!shared.subs.borrow().contains_key(&U256::from(100)));
+    }
 }
@@ -0,0 +1,203 @@
+//! A [JsonRpcClient] implementation that traces every request of the client it wraps
+
+use crate::{JsonRpcClient, PubsubClient};
+use async_trait::async_trait;
+use ethers_core::types::U256;
+use instant::{Duration, Instant};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+use tracing::Instrument;
+
+/// The default maximum length of the params that are recorded, see
+/// [`LoggingClient::max_params_len`]
+pub const DEFAULT_MAX_PARAMS_LEN: usize = 256;
+
+/// A client that traces every request of the client it wraps.
+///
+/// Each request is sent within a `rpc_request` span at the `DEBUG` level, recording the method
+/// and the params. Once the request completed, an event is emitted with its duration, at the
+/// `DEBUG` level if it succeeded and at the `WARN` level if it failed. Since
+/// `eth_sendRawTransaction` or `eth_call` params can be large, the recorded params are truncated to
+/// [`DEFAULT_MAX_PARAMS_LEN`] bytes by default. The params are only serialized if the span is
+/// enabled.
+///
+/// Subscriptions are passed through to the wrapped client if it is a [`PubsubClient`].
+///
+/// The client also counts the requests, errors and the total time spent per method, which can be
+/// exported to a metrics system, see [`LoggingClient::stats`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_providers::{Http, LoggingClient, Middleware, Provider};
+/// use std::str::FromStr;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = LoggingClient::new(Http::from_str("http://localhost:8545")?);
+/// let provider = Provider::new(client);
+/// provider.get_block_number().await?;
+///
+/// for (method, stats) in provider.as_ref().stats() {
+///     println!("{method}: {} requests in {:?}", stats.requests, stats.total_duration);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoggingClient<T> {
+    inner: T,
+    max_params_len: usize,
+    stats: Arc<Mutex<BTreeMap<String, RequestStats>>>,
+}
+
+/// The statistics of the requests of a method, see [`LoggingClient::stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestStats {
+    /// The number of requests that completed
+    pub requests: u64,
+    /// The number of requests that failed
+    pub errors: u64,
+    /// The total duration of all requests
+    pub total_duration: Duration,
+}
+
+impl<T> LoggingClient<T> {
+    /// Wraps the `inner` client
+    pub fn new(inner: T) -> Self {
+        Self { inner, max_params_len: DEFAULT_MAX_PARAMS_LEN, stats: Default::default() }
+    }
+
+    /// Sets the maximum length of the recorded params, longer params are truncated
+    #[must_use]
+    pub fn max_params_len(mut self, max_params_len: usize) -> Self {
+        self.max_params_len = max_params_len;
+        self
+    }
+
+    /// Returns the wrapped client
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the statistics of all methods that were requested, keyed by method.
+    ///
+    /// The statistics are shared between clones of this client.
+    pub fn stats(&self) -> BTreeMap<String, RequestStats> {
+        self.stats.lock().unwrap().clone()
+    }
+
+    fn record(&self, method: &str, duration: Duration, is_err: bool) {
+        let mut stats = self.stats.lock().unwrap();
+        let stats = stats.entry(method.to_string()).or_default();
+        stats.requests += 1;
+        stats.errors += is_err as u64;
+        stats.total_duration += duration;
+    }
+}
+
+/// Serializes the params, truncated to `max_len` bytes
+fn format_params<T: Serialize>(params: &T, max_len: usize) -> String {
+    let mut params = serde_json::to_string(params).unwrap_or_default();
+    if params.len() > max_len {
+        let len = params.len();
+        let mut end = max_len;
+        while !params.is_char_boundary(end) {
+            end -= 1;
+        }
+        params.truncate(end);
+        params.push_str(&format!("... ({len} bytes)"));
+    }
+    params
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C> JsonRpcClient for LoggingClient<C>
+where
+    C: JsonRpcClient,
+{
+    type Error = C::Error;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let span = tracing::debug_span!("rpc_request", method, params = tracing::field::Empty);
+        if !span.is_disabled() {
+            span.record(
+                "params",
+                tracing::field::display(format_params(&params, self.max_params_len)),
+            );
+        }
+        let start = Instant::now();
+        let res = self.inner.request(method, params).instrument(span.clone()).await;
+        let elapsed = start.elapsed();
+        self.record(method, elapsed, res.is_err());
+
+        let _enter = span.enter();
+        match &res {
+            Ok(_) => tracing::debug!(?elapsed, "request succeeded"),
+            Err(err) => tracing::warn!(?elapsed, %err, "request failed"),
+        }
+        res
+    }
+}
+
+impl<C> PubsubClient for LoggingClient<C>
+where
+    C: PubsubClient,
+{
+    type NotificationStream = C::NotificationStream;
+
+    fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, Self::Error> {
+        self.inner.subscribe(id)
+    }
+
+    fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), Self::Error> {
+        self.inner.unsubscribe(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{JsonRpcError, MockProvider, MockResponse};
+    use ethers_core::types::U64;
+
+    #[test]
+    fn truncates_params() {
+        assert_eq!(format_params(&["0x1234"], 256), r#"["0x1234"]"#);
+        assert_eq!(format_params(&["0x1234"], 4), r#"["0x... (10 bytes)"#);
+        // truncation respects char boundaries
+        assert_eq!(format_params(&["ää"], 3), r#"[""#.to_string() + "... (8 bytes)");
+    }
+
+    #[tokio::test]
+    async fn counts_requests() {
+        let mock = MockProvider::new();
+        let client = LoggingClient::new(mock.clone());
+
+        mock.push(U64::from(1)).unwrap();
+        let block: U64 = client.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, U64::from(1));
+
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: "execution reverted".to_string(),
+            data: None,
+        }));
+        assert!(client.request::<_, U64>("eth_blockNumber", ()).await.is_err());
+        mock.push(U64::from(1)).unwrap();
+        client.request::<_, U64>("eth_chainId", ()).await.unwrap();
+
+        let stats = client.clone().stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats["eth_blockNumber"].requests, stats["eth_blockNumber"].errors), (2, 1));
+        assert_eq!((stats["eth_chainId"].requests, stats["eth_chainId"].errors), (1, 0));
+    }
+}
@@ -5,6 +5,7 @@ use serde_json::Value;
 use std::{
     borrow::Borrow,
     collections::VecDeque,
+    fmt,
     sync::{Arc, Mutex},
 };
 use thiserror::Error;
@@ -29,11 +30,44 @@ pub enum MockResponse {
     Error(super::JsonRpcError),
 }
 
+/// A request the `MockProvider` expects, with the response to return for it.
+struct MockExpectation {
+    method: String,
+    /// Returns whether the params of the request are the expected ones, zero sized params are
+    /// passed as `Value::Null`
+    params: Box<dyn Fn(&Value) -> bool + Send + Sync>,
+    response: MockResponse,
+}
+
+impl fmt::Debug for MockExpectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockExpectation")
+            .field("method", &self.method)
+            .field("response", &self.response)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Mock transport used in test environments.
+///
+/// Responses can either be pushed without specifying the request they answer with [`push`], in
+/// which case they are returned last in, first out, or be tied to an expected request with
+/// [`expect`] or [`expect_request`]. Expected requests must be made in the order they were
+/// expected and are answered first in, first out. A request that does not match the next expected
+/// request fails with [`MockError::UnexpectedRequest`], and
+/// [`assert_expectations_met`](Self::assert_expectations_met) checks that no expected request is
+/// outstanding.
+///
+/// Responses pushed with [`push`] are only used when no request is expected.
+///
+/// [`push`]: Self::push
+/// [`expect`]: Self::expect
+/// [`expect_request`]: Self::expect_request
 pub struct MockProvider {
     requests: Arc<Mutex<VecDeque<(String, MockParams)>>>,
     responses: Arc<Mutex<VecDeque<MockResponse>>>,
+    expectations: Arc<Mutex<VecDeque<MockExpectation>>>,
 }
 
 impl Default for MockProvider {
@@ -48,7 +82,8 @@ impl JsonRpcClient for MockProvider {
     type Error = MockError;
 
     /// Pushes the `(method, params)` to the back of the `requests` queue,
-    /// pops the responses from the back of the `responses` queue
+    /// answers the next expected request if there is one, otherwise pops the responses from the
+    /// back of the `responses` queue
     async fn request<T: Serialize + Send + Sync, R: DeserializeOwned>(
         &self,
         method: &str,
@@ -59,9 +94,29 @@ impl JsonRpcClient for MockProvider {
         } else {
             MockParams::Value(serde_json::to_value(params)?)
         };
+        let value = match &params {
+            MockParams::Value(value) => value.clone(),
+            MockParams::Zst => Value::Null,
+        };
         self.requests.lock().unwrap().push_back((method.to_owned(), params));
-        let mut data = self.responses.lock().unwrap();
-        let element = data.pop_back().ok_or(MockError::EmptyResponses)?;
+
+        let mut expectations = self.expectations.lock().unwrap();
+        let element = match expectations.pop_front() {
+            Some(expectation) => {
+                if expectation.method != method || !(expectation.params)(&value) {
+                    let expected = expectation.method.clone();
+                    expectations.push_front(expectation);
+                    return Err(MockError::UnexpectedRequest {
+                        method: method.to_owned(),
+                        params: value,
+                        expected,
+                    })
+                }
+                expectation.response
+            }
+            None => self.responses.lock().unwrap().pop_back().ok_or(MockError::EmptyResponses)?,
+        };
+        drop(expectations);
         match element {
             MockResponse::Value(value) => {
                 let res: R = serde_json::from_value(value)?;
@@ -98,7 +153,70 @@ impl MockProvider {
         Self {
             requests: Arc::new(Mutex::new(VecDeque::new())),
             responses: Arc::new(Mutex::new(VecDeque::new())),
+            expectations: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Expects a `method` request with exactly the given `params`, which is answered with
+    /// `response`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_core::types::U64;
+    /// use ethers_providers::{Middleware, MockProvider, Provider};
+    ///
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mock = MockProvider::new();
+    /// mock.expect("eth_blockNumber", (), U64::from(1))?
+    ///     .expect("eth_getBalance", ("0x0000000000000000000000000000000000000001", "latest"), 100)?;
+    ///
+    /// let provider = Provider::new(mock.clone());
+    /// assert_eq!(provider.get_block_number().await?, 1.into());
+    /// let balance = provider.get_balance(ethers_core::types::Address::from_low_u64_be(1), None).await?;
+    /// assert_eq!(balance, 100.into());
+    /// mock.assert_expectations_met()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn expect<P: Serialize, R: Serialize>(
+        &self,
+        method: &str,
+        params: P,
+        response: R,
+    ) -> Result<&Self, MockError> {
+        let params = serde_json::to_value(params)?;
+        let response = MockResponse::Value(serde_json::to_value(response)?);
+        Ok(self.expect_request(method, move |value| *value == params, response))
+    }
+
+    /// Expects a `method` request whose params satisfy `matcher`, which is answered with
+    /// `response`.
+    ///
+    /// The params are passed to `matcher` as they were serialized, or as `Value::Null` if the
+    /// request has no params.
+    pub fn expect_request(
+        &self,
+        method: &str,
+        matcher: impl Fn(&Value) -> bool + Send + Sync + 'static,
+        response: MockResponse,
+    ) -> &Self {
+        self.expectations.lock().unwrap().push_back(MockExpectation {
+            method: method.to_owned(),
+            params: Box::new(matcher),
+            response,
+        });
+        self
+    }
+
+    /// Checks that all expected requests were made
+    pub fn assert_expectations_met(&self) -> Result<(), MockError> {
+        let expectations = self.expectations.lock().unwrap();
+        if expectations.is_empty() {
+            return Ok(())
         }
+        let methods = expectations.iter().map(|expectation| expectation.method.clone()).collect();
+        Err(MockError::UnmetExpectations(methods))
     }
 
     /// Pushes the data to the responses
@@ -132,6 +250,21 @@ pub enum MockError {
     /// Custom JsonRpcError
     #[error("JSON-RPC error: {0}")]
     JsonRpcError(super::JsonRpcError),
+
+    /// A request did not match the next expected request
+    #[error("unexpected request `{method}` with params {params}, expected `{expected}`")]
+    UnexpectedRequest {
+        /// The method of the request
+        method: String,
+        /// The params of the request
+        params: Value,
+        /// The method of the next expected request
+        expected: String,
+    },
+
+    /// Some expected requests were not made
+    #[error("expected requests were not made: {0:?}")]
+    UnmetExpectations(Vec<String>),
 }
 
 impl crate::RpcError for MockError {
@@ -160,7 +293,7 @@ impl From<MockError> for ProviderError {
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
     use super::*;
-    use crate::{JsonRpcError, Middleware};
+    use crate::{JsonRpcError, Middleware, RpcError};
     use ethers_core::types::U64;
 
     #[tokio::test]
@@ -215,6 +348,65 @@ mod tests {
         };
     }
 
+    #[tokio::test]
+    async fn answers_expected_requests_in_order() {
+        let mock = MockProvider::new();
+        mock.expect("eth_blockNumber", (), U64::from(1))
+            .unwrap()
+            .expect("eth_chainId", (), U64::from(2))
+            .unwrap();
+        // pushed responses are only used once no request is expected anymore
+        mock.push(U64::from(3)).unwrap();
+
+        let block: U64 = mock.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block.as_u64(), 1);
+        assert!(mock.assert_expectations_met().is_err());
+        let chain_id: U64 = mock.request("eth_chainId", ()).await.unwrap();
+        assert_eq!(chain_id.as_u64(), 2);
+        mock.assert_expectations_met().unwrap();
+
+        let block: U64 = mock.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block.as_u64(), 3);
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_chainId", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_unexpected_requests() {
+        let mock = MockProvider::new();
+        mock.expect("eth_getBalance", ("0x01", "latest"), U64::from(1)).unwrap();
+
+        let err = mock.request::<_, U64>("eth_blockNumber", ()).await.unwrap_err();
+        assert!(
+            matches!(err, MockError::UnexpectedRequest { ref expected, .. } if expected == "eth_getBalance")
+        );
+        let err = mock.request::<_, U64>("eth_getBalance", ("0x02", "latest")).await.unwrap_err();
+        assert!(matches!(err, MockError::UnexpectedRequest { .. }));
+
+        // the expectation is still outstanding
+        match mock.assert_expectations_met().unwrap_err() {
+            MockError::UnmetExpectations(methods) => assert_eq!(methods, vec!["eth_getBalance"]),
+            err => panic!("unexpected error {err}"),
+        }
+        let balance: U64 = mock.request("eth_getBalance", ("0x01", "latest")).await.unwrap();
+        assert_eq!(balance.as_u64(), 1);
+    }
+
+    #[tokio::test]
+    async fn matches_params_of_expected_requests() {
+        let (provider, mock) = crate::Provider::mocked();
+        let error = JsonRpcError { code: -32000, message: "nonce too low".to_string(), data: None };
+        mock.expect_request(
+            "eth_sendRawTransaction",
+            |params| params[0].as_str().map_or(false, |tx| tx.starts_with("0x02")),
+            MockResponse::Error(error),
+        );
+
+        let err = provider.send_raw_transaction(vec![2, 1].into()).await.unwrap_err();
+        assert_eq!(err.as_error_response().unwrap().message, "nonce too low");
+        mock.assert_expectations_met().unwrap();
+    }
+
     #[tokio::test]
     async fn composes_with_provider() {
         let (provider, mock) = crate::Provider::mocked();
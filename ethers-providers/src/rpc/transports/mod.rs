@@ -9,19 +9,34 @@ mod ipc;
 #[cfg(all(feature = "ipc", any(unix, windows)))]
 pub use ipc::{Ipc, IpcError};
 
+mod fallback;
+pub use fallback::{FallbackError, FallbackProvider};
+
+mod pool;
+pub use pool::{
+    MemberStats, ProviderPool, ProviderPoolError, RoutingStrategy, DEFAULT_MAX_BLOCK_LAG,
+    DEFAULT_MAX_LATENCY,
+};
+
 mod quorum;
 pub use quorum::{JsonRpcClientWrapper, Quorum, QuorumError, QuorumProvider, WeightedProvider};
 
 mod rw;
 pub use rw::{RwClient, RwClientError};
 
+mod logging;
+pub use logging::{LoggingClient, RequestStats, DEFAULT_MAX_PARAMS_LEN};
+
 mod retry;
 pub use retry::*;
 
 #[cfg(all(feature = "ws", not(feature = "legacy-ws")))]
 mod ws;
 #[cfg(all(feature = "ws", not(feature = "legacy-ws")))]
-pub use ws::{ConnectionDetails, WsClient as Ws, WsClientError};
+pub use ws::{
+    BufferedWsClient as BufferedWs, ConnectionDetails, OverflowPolicy, WsClient as Ws,
+    WsClientError, WsNotificationStream,
+};
 
 /// archival websocket
 #[cfg(feature = "legacy-ws")]
@@ -0,0 +1,497 @@
+//! A [JsonRpcClient] implementation that routes requests to the healthy members of a pool of
+//! providers
+
+use super::{
+    fallback::{broadcast, format_errors},
+    quorum::{JsonRpcClientWrapper, QuorumParams},
+};
+use crate::{errors::ProviderError, JsonRpcClient, RpcError};
+use async_trait::async_trait;
+use ethers_core::types::U64;
+use futures_timer::Delay;
+use futures_util::{
+    future::{self, join_all, Either},
+    StreamExt,
+};
+use instant::{Duration, Instant};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+use thiserror::Error;
+use tracing::{debug, trace, warn};
+
+/// The default number of blocks a member may lag behind the most recent block of the pool before
+/// it is ejected, see [`ProviderPool::max_block_lag`]
+pub const DEFAULT_MAX_BLOCK_LAG: u64 = 5;
+
+/// The default time a member has to respond to a health check, see
+/// [`ProviderPool::max_latency`]
+pub const DEFAULT_MAX_LATENCY: Duration = Duration::from_secs(5);
+
+/// How a [`ProviderPool`] picks the healthy member a request is sent to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoutingStrategy {
+    /// Rotate through the healthy members
+    #[default]
+    RoundRobin,
+    /// Prefer the healthy member with the lowest latency measured by the last health check
+    LeastLatency,
+}
+
+/// The health and the request counts of a member of a [`ProviderPool`], see
+/// [`ProviderPool::stats`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemberStats {
+    /// Whether requests are routed to the member
+    pub healthy: bool,
+    /// The latency of the last successful health check
+    pub latency: Option<Duration>,
+    /// The block number reported by the last successful health check
+    pub block_number: Option<U64>,
+    /// The number of requests, excluding health checks, sent to the member
+    pub requests: u64,
+    /// The number of failed requests and health checks
+    pub errors: u64,
+}
+
+impl Default for MemberStats {
+    fn default() -> Self {
+        // members are healthy until a health check or a request failed
+        Self { healthy: true, latency: None, block_number: None, requests: 0, errors: 0 }
+    }
+}
+
+#[derive(Debug)]
+struct Member<T> {
+    client: T,
+    stats: RwLock<MemberStats>,
+}
+
+/// A pool of providers that routes each request to a healthy member.
+///
+/// Members are health-checked with [`ProviderPool::health_check`], which requests the block number
+/// of every member. A member is ejected from the pool if the health check failed, timed out after
+/// the [maximum latency](ProviderPool::max_latency), or if it lags more than
+/// [`max_block_lag`](ProviderPool::max_block_lag) blocks behind the most recent block reported by
+/// the other members. Ejected members are re-added once they pass a health check again. Use
+/// [`ProviderPool::run_health_checks`] to check the members periodically.
+///
+/// A member that fails a request with a transport error, e.g. a connection error, is ejected
+/// immediately and the request is retried with the next member. JSON-RPC error responses are
+/// returned directly, since any other member would respond the same way. If all healthy members
+/// failed, the ejected members are tried as a last resort.
+///
+/// Transactions submitted with `eth_sendRawTransaction` are broadcast to all healthy members.
+///
+/// Wrap the pool in a [`Provider`](crate::Provider) to use it as a
+/// [`Middleware`](crate::Middleware).
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_providers::{Http, Middleware, Provider, ProviderPool, RoutingStrategy};
+/// use std::{str::FromStr, time::Duration};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let pool = ProviderPool::new([
+///     Http::from_str("http://localhost:8545")?,
+///     Http::from_str("http://localhost:8546")?,
+/// ])
+/// .strategy(RoutingStrategy::LeastLatency)
+/// .max_block_lag(3);
+///
+/// // health-check the members in the background, clones share the health of the members
+/// let checked = pool.clone();
+/// tokio::spawn(async move { checked.run_health_checks(Duration::from_secs(10)).await });
+///
+/// let provider = Provider::new(pool);
+/// let block_number = provider.get_block_number().await?;
+///
+/// for stats in provider.as_ref().stats() {
+///     println!("healthy: {}, latency: {:?}", stats.healthy, stats.latency);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ProviderPool<T = Box<dyn JsonRpcClientWrapper>> {
+    members: Arc<Vec<Member<T>>>,
+    strategy: RoutingStrategy,
+    max_block_lag: u64,
+    max_latency: Duration,
+    /// The offset of the next round-robin request
+    next: Arc<AtomicUsize>,
+}
+
+impl<T> Clone for ProviderPool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            members: self.members.clone(),
+            strategy: self.strategy,
+            max_block_lag: self.max_block_lag,
+            max_latency: self.max_latency,
+            next: self.next.clone(),
+        }
+    }
+}
+
+impl ProviderPool<Box<dyn JsonRpcClientWrapper>> {
+    /// Create a `ProviderPool` for different `JsonRpcClient` types
+    pub fn dyn_rpc(
+        providers: impl IntoIterator<Item = Box<dyn JsonRpcClientWrapper>>,
+    ) -> ProviderPool<Box<dyn JsonRpcClientWrapper>> {
+        ProviderPool::new(providers)
+    }
+}
+
+impl<T> ProviderPool<T> {
+    /// Instantiate a new `ProviderPool` of the `providers`, which are all considered healthy until
+    /// they failed a health check or a request
+    pub fn new(providers: impl IntoIterator<Item = T>) -> Self {
+        let members = providers
+            .into_iter()
+            .map(|client| Member { client, stats: Default::default() })
+            .collect();
+        Self {
+            members: Arc::new(members),
+            strategy: RoutingStrategy::default(),
+            max_block_lag: DEFAULT_MAX_BLOCK_LAG,
+            max_latency: DEFAULT_MAX_LATENCY,
+            next: Default::default(),
+        }
+    }
+
+    /// Sets how the member a request is sent to is picked
+    #[must_use]
+    pub fn strategy(mut self, strategy: RoutingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the number of blocks a member may lag behind the most recent block of the pool
+    /// before it is ejected
+    #[must_use]
+    pub fn max_block_lag(mut self, max_block_lag: u64) -> Self {
+        self.max_block_lag = max_block_lag;
+        self
+    }
+
+    /// Sets the time a member has to respond to a health check before it is ejected
+    #[must_use]
+    pub fn max_latency(mut self, max_latency: Duration) -> Self {
+        self.max_latency = max_latency;
+        self
+    }
+
+    /// Returns the statistics of each member, in the order the providers were given.
+    ///
+    /// The statistics are shared between clones of this pool.
+    pub fn stats(&self) -> Vec<MemberStats> {
+        self.members.iter().map(|member| member.stats.read().unwrap().clone()).collect()
+    }
+
+    /// Returns the indices of the members in the order they're tried: the healthy members
+    /// according to the routing strategy, followed by the ejected members
+    fn route(&self) -> Vec<usize> {
+        let stats = self.stats();
+        let (mut healthy, ejected): (Vec<_>, Vec<_>) =
+            (0..stats.len()).partition(|idx| stats[*idx].healthy);
+        match self.strategy {
+            RoutingStrategy::RoundRobin if !healthy.is_empty() => {
+                let offset = self.next.fetch_add(1, Ordering::Relaxed) % healthy.len();
+                healthy.rotate_left(offset);
+            }
+            RoutingStrategy::RoundRobin => {}
+            // members without a measured latency are tried last
+            RoutingStrategy::LeastLatency => {
+                healthy.sort_by_key(|idx| (stats[*idx].latency.is_none(), stats[*idx].latency))
+            }
+        }
+        healthy.extend(ejected);
+        healthy
+    }
+}
+
+impl<T: JsonRpcClientWrapper> ProviderPool<T> {
+    /// Requests the block number of every member concurrently, ejecting the members that failed,
+    /// were too slow or lag behind the others, and re-adding the members that recovered.
+    pub async fn health_check(&self) {
+        let probes =
+            join_all(self.members.iter().map(|member| probe(&member.client, self.max_latency)))
+                .await;
+        let max_block = probes.iter().filter_map(|probe| probe.as_ref().ok()).map(|p| p.0).max();
+
+        for (idx, (member, probe)) in self.members.iter().zip(probes).enumerate() {
+            let mut stats = member.stats.write().unwrap();
+            match probe {
+                Ok((block_number, latency)) => {
+                    let lag = max_block.unwrap_or(block_number).saturating_sub(block_number);
+                    let healthy = lag.as_u64() <= self.max_block_lag;
+                    if healthy != stats.healthy {
+                        debug!(
+                            idx,
+                            ?block_number,
+                            ?lag,
+                            ?latency,
+                            healthy,
+                            "member health changed"
+                        );
+                    }
+                    stats.healthy = healthy;
+                    stats.latency = Some(latency);
+                    stats.block_number = Some(block_number);
+                }
+                Err(err) => {
+                    warn!(idx, ?err, "member failed health check");
+                    stats.healthy = false;
+                    stats.errors += 1;
+                }
+            }
+        }
+    }
+
+    /// Runs a [health check](Self::health_check) every `interval`, this future never completes.
+    pub async fn run_health_checks(&self, interval: Duration) {
+        let mut ticks = crate::interval(interval);
+        loop {
+            self.health_check().await;
+            ticks.next().await;
+        }
+    }
+}
+
+/// Requests the block number of the `client`, failing if it did not respond within `timeout`
+async fn probe<T: JsonRpcClientWrapper>(
+    client: &T,
+    timeout: Duration,
+) -> Result<(U64, Duration), ProviderError> {
+    let start = Instant::now();
+    let request = client.request("eth_blockNumber", QuorumParams::Zst);
+    futures_util::pin_mut!(request);
+    let value = match future::select(request, Delay::new(timeout)).await {
+        Either::Left((res, _)) => res?,
+        Either::Right(_) => {
+            return Err(ProviderError::CustomError(format!(
+                "health check timed out after {timeout:?}"
+            )))
+        }
+    };
+    Ok((serde_json::from_value(value)?, start.elapsed()))
+}
+
+#[derive(Error, Debug)]
+/// Error thrown when all members of a `ProviderPool` failed
+pub enum ProviderPoolError {
+    /// Every member failed, contains the error of each member in the order they were tried
+    #[error("All members failed: {}", format_errors(.0))]
+    AllMembersFailed(Vec<ProviderError>),
+}
+
+impl RpcError for ProviderPoolError {
+    fn as_error_response(&self) -> Option<&super::JsonRpcError> {
+        None
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        None
+    }
+}
+
+impl From<ProviderPoolError> for ProviderError {
+    fn from(src: ProviderPoolError) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(src))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C> JsonRpcClient for ProviderPool<C>
+where
+    C: JsonRpcClientWrapper,
+{
+    type Error = ProviderError;
+
+    async fn request<T: Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: T,
+    ) -> Result<R, Self::Error> {
+        let params = if std::mem::size_of::<T>() == 0 {
+            // we don't want `()` to become `"null"`.
+            QuorumParams::Zst
+        } else {
+            QuorumParams::Value(serde_json::to_value(params)?)
+        };
+
+        if method == "eth_sendRawTransaction" {
+            let healthy = self.members.iter().filter(|m| m.stats.read().unwrap().healthy);
+            let clients: Vec<_> = if healthy.clone().next().is_some() {
+                healthy.map(|member| &member.client).collect()
+            } else {
+                self.members.iter().map(|member| &member.client).collect()
+            };
+            let value = broadcast(clients, method, params)
+                .await
+                .map_err(ProviderPoolError::AllMembersFailed)?;
+            return Ok(serde_json::from_value(value)?)
+        }
+
+        let mut errors = Vec::new();
+        for idx in self.route() {
+            let member = &self.members[idx];
+            let res = member.client.request(method, params.clone()).await;
+            member.stats.write().unwrap().requests += 1;
+            match res {
+                Ok(value) => return Ok(serde_json::from_value(value)?),
+                // the node processed the request, another node would respond the same way
+                Err(err) if err.is_error_response() => return Err(err),
+                Err(err) => {
+                    trace!(method, idx, ?err, "member failed, ejecting it");
+                    {
+                        let mut stats = member.stats.write().unwrap();
+                        stats.healthy = false;
+                        stats.errors += 1;
+                    }
+                    errors.push(err);
+                }
+            }
+        }
+        Err(ProviderPoolError::AllMembersFailed(errors).into())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::{JsonRpcError, MockError, MockProvider, MockResponse};
+    use ethers_core::types::{Bytes, H256};
+
+    async fn block_number(pool: &ProviderPool<MockProvider>) -> Result<U64, ProviderError> {
+        JsonRpcClient::request(pool, "eth_blockNumber", ()).await
+    }
+
+    #[tokio::test]
+    async fn routes_round_robin() {
+        let members = [MockProvider::new(), MockProvider::new()];
+        let pool = ProviderPool::new(members.clone());
+
+        for (idx, member) in members.iter().enumerate() {
+            member.push(U64::from(idx)).unwrap();
+        }
+        assert_eq!(block_number(&pool).await.unwrap(), 0.into());
+        assert_eq!(block_number(&pool).await.unwrap(), 1.into());
+        assert!(pool.stats().iter().all(|stats| stats.healthy && stats.requests == 1));
+    }
+
+    #[tokio::test]
+    async fn ejects_stale_members() {
+        let members = [MockProvider::new(), MockProvider::new(), MockProvider::new()];
+        let pool = ProviderPool::new(members.clone()).max_block_lag(5);
+
+        members[0].push(U64::from(100)).unwrap();
+        members[1].push(U64::from(98)).unwrap();
+        members[2].push(U64::from(90)).unwrap();
+        pool.health_check().await;
+        let stats = pool.stats();
+        assert_eq!(
+            stats.iter().map(|stats| stats.healthy).collect::<Vec<_>>(),
+            vec![true, true, false]
+        );
+        assert_eq!(stats[2].block_number, Some(90.into()));
+        assert!(stats[2].latency.is_some());
+
+        // requests are only routed to healthy members
+        for _ in 0..2 {
+            members[0].push(U64::from(100)).unwrap();
+            members[1].push(U64::from(100)).unwrap();
+        }
+        for _ in 0..4 {
+            block_number(&pool).await.unwrap();
+        }
+        assert_eq!(pool.stats().iter().map(|stats| stats.requests).collect::<Vec<_>>(), [2, 2, 0]);
+
+        // members are re-added once they caught up
+        members[0].push(U64::from(101)).unwrap();
+        members[1].push(U64::from(101)).unwrap();
+        members[2].push(U64::from(100)).unwrap();
+        pool.health_check().await;
+        assert!(pool.stats()[2].healthy);
+    }
+
+    #[tokio::test]
+    async fn ejects_failing_members() {
+        // the failing member has no responses queued
+        let members = [MockProvider::new(), MockProvider::new()];
+        let pool = ProviderPool::new(members.clone());
+
+        members[1].push(U64::from(1)).unwrap();
+        assert_eq!(block_number(&pool).await.unwrap(), 1.into());
+        let stats = pool.stats();
+        assert!(!stats[0].healthy);
+        assert_eq!(stats[0].errors, 1);
+
+        // failed health checks eject members as well
+        members[0].push(U64::from(1)).unwrap();
+        pool.health_check().await;
+        let stats = pool.stats();
+        assert!(stats[0].healthy);
+        assert!(!stats[1].healthy);
+
+        // ejected members are tried if no healthy member succeeded
+        members[1].push(U64::from(2)).unwrap();
+        assert_eq!(block_number(&pool).await.unwrap(), 2.into());
+    }
+
+    #[tokio::test]
+    async fn returns_error_responses() {
+        let members = [MockProvider::new(), MockProvider::new()];
+        let pool = ProviderPool::new(members.clone());
+        members[0].push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: None,
+        }));
+
+        let err = JsonRpcClient::request::<_, Bytes>(&pool, "eth_call", ["0x"]).await.unwrap_err();
+        assert_eq!(err.as_error_response().unwrap().code, 3);
+        assert!(pool.stats()[0].healthy);
+        assert!(matches!(
+            members[1].assert_request("eth_call", ["0x"]),
+            Err(MockError::EmptyRequests)
+        ));
+    }
+
+    #[tokio::test]
+    async fn routes_to_least_latency() {
+        let members = [MockProvider::new(), MockProvider::new(), MockProvider::new()];
+        let pool = ProviderPool::new(members).strategy(RoutingStrategy::LeastLatency);
+        for (member, latency) in pool.members.iter().zip([Some(30), None, Some(10)]) {
+            member.stats.write().unwrap().latency = latency.map(Duration::from_millis);
+        }
+        assert_eq!(pool.route(), vec![2, 0, 1]);
+
+        pool.members[2].stats.write().unwrap().healthy = false;
+        assert_eq!(pool.route(), vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn broadcasts_raw_transactions_to_healthy_members() {
+        let hash = H256::repeat_byte(1);
+        let members = [MockProvider::new(), MockProvider::new()];
+        let pool = ProviderPool::new(members.clone());
+        pool.members[1].stats.write().unwrap().healthy = false;
+        members[0].push(hash).unwrap();
+
+        let res: H256 =
+            JsonRpcClient::request(&pool, "eth_sendRawTransaction", ["0x1234"]).await.unwrap();
+        assert_eq!(res, hash);
+        members[0].assert_request("eth_sendRawTransaction", ["0x1234"]).unwrap();
+        assert!(members[1].assert_request("eth_sendRawTransaction", ["0x1234"]).is_err());
+    }
+}
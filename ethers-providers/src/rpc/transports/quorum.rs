@@ -1,3 +1,4 @@
+use super::fallback::broadcast;
 use crate::{errors::ProviderError, JsonRpcClient, PubsubClient};
 use async_trait::async_trait;
 use ethers_core::types::{U256, U64};
@@ -12,10 +13,15 @@ use std::{
     task::{Context, Poll},
 };
 use thiserror::Error;
+use tracing::warn;
 
 /// A provider that bundles multiple providers and only returns a value to the
 /// caller once the quorum has been reached.
 ///
+/// Transactions submitted with `eth_sendRawTransaction` are broadcast to all providers instead,
+/// and the first successful response is returned. To only fall back to other providers on
+/// failure, see [`crate::FallbackProvider`].
+///
 /// # Example
 ///
 /// Create a `QuorumProvider` that uses a homogenous `Provider` type only returns a value if the
@@ -190,29 +196,32 @@ impl<T: JsonRpcClientWrapper> QuorumProvider<T> {
             // at this time no normalization is required for calls with zero parameters.
             return
         };
-        match method {
+        // calls that include the block number in the params at the last index of json array,
+        // or at the first index for `eth_getBlockByNumber`
+        let block = match method {
             "eth_call" |
             "eth_createAccessList" |
             "eth_getStorageAt" |
             "eth_getCode" |
             "eth_getProof" |
+            "eth_getBalance" |
+            "eth_getTransactionCount" |
             "trace_call" |
-            "trace_block" => {
-                // calls that include the block number in the params at the last index of json array
-                if let Some(block) = params.as_array_mut().and_then(|arr| arr.last_mut()) {
-                    if Some("latest") == block.as_str() {
-                        // replace `latest` with the minimum block height of all providers
-                        if let Ok(minimum) = self
-                            .get_minimum_block_number()
-                            .await
-                            .and_then(|num| Ok(serde_json::to_value(num)?))
-                        {
-                            *block = minimum
-                        }
-                    }
+            "trace_block" => params.as_array_mut().and_then(|arr| arr.last_mut()),
+            "eth_getBlockByNumber" => params.as_array_mut().and_then(|arr| arr.first_mut()),
+            _ => None,
+        };
+        if let Some(block) = block {
+            if Some("latest") == block.as_str() {
+                // replace `latest` with the minimum block height of all providers
+                if let Ok(minimum) = self
+                    .get_minimum_block_number()
+                    .await
+                    .and_then(|num| Ok(serde_json::to_value(num)?))
+                {
+                    *block = minimum
                 }
             }
-            _ => {}
         }
     }
 }
@@ -274,6 +283,8 @@ type PendingRequest<'a> =
 /// reached a quorum.
 struct QuorumRequest<'a, T> {
     inner: &'a QuorumProvider<T>,
+    /// The method that was requested
+    method: &'a str,
     /// The different answers with their cumulative weight
     responses: Vec<(Value, u64)>,
     /// All the errors the provider yielded
@@ -283,8 +294,21 @@ struct QuorumRequest<'a, T> {
 }
 
 impl<'a, T> QuorumRequest<'a, T> {
-    fn new(inner: &'a QuorumProvider<T>, requests: Vec<PendingRequest<'a>>) -> Self {
-        Self { responses: Vec::new(), errors: Vec::new(), inner, requests }
+    fn new(
+        inner: &'a QuorumProvider<T>,
+        method: &'a str,
+        requests: Vec<PendingRequest<'a>>,
+    ) -> Self {
+        Self { responses: Vec::new(), errors: Vec::new(), inner, method, requests }
+    }
+
+    /// Reports the responses that disagree with the value the quorum was reached for
+    fn report_disagreements(&self, value: &Value) {
+        let disagreeing =
+            self.responses.iter().filter(|(v, _)| v != value).map(|(v, _)| v).collect::<Vec<_>>();
+        if !disagreeing.is_empty() {
+            warn!(method = self.method, ?value, ?disagreeing, "providers disagree on response");
+        }
     }
 }
 
@@ -303,12 +327,14 @@ impl<'a, T> Future for QuorumRequest<'a, T> {
                         *weight += response_weight;
                         if *weight >= this.inner.quorum_weight {
                             // reached quorum with multiple responses
+                            this.report_disagreements(&val);
                             return Poll::Ready(Ok(val))
                         } else {
                             this.responses.push((val, response_weight));
                         }
                     } else if response_weight >= this.inner.quorum_weight {
                         // reached quorum with single response
+                        this.report_disagreements(&val);
                         return Poll::Ready(Ok(val))
                     } else {
                         this.responses.push((val, response_weight));
@@ -472,6 +498,13 @@ where
         } else {
             QuorumParams::Value(serde_json::to_value(params)?)
         };
+        if method == "eth_sendRawTransaction" {
+            let value = broadcast(self.providers.iter().map(|p| &p.inner), method, params)
+                .await
+                .map_err(|errors| QuorumError::NoQuorumReached { values: Vec::new(), errors })?;
+            return Ok(serde_json::from_value(value)?)
+        }
+
         self.normalize_request(method, &mut params).await;
 
         let requests = self
@@ -485,7 +518,7 @@ where
             })
             .collect::<Vec<_>>();
 
-        let value = QuorumRequest::new(self, requests).await?;
+        let value = QuorumRequest::new(self, method, requests).await?;
         Ok(serde_json::from_value(value)?)
     }
 }
@@ -672,4 +705,50 @@ mod tests {
     async fn all_quorum() {
         test_quorum(Quorum::All).await
     }
+
+    #[tokio::test]
+    async fn broadcasts_raw_transactions() {
+        let hash = ethers_core::types::H256::repeat_byte(1);
+        let mut mocked = Vec::new();
+        for _ in 0..3 {
+            mocked.push(MockProvider::new());
+        }
+        // only a single provider accepts the transaction
+        mocked[0].push(hash).unwrap();
+        let quorum = QuorumProvider::builder()
+            .add_providers(mocked.iter().cloned().map(WeightedProvider::new))
+            .quorum(Quorum::All)
+            .build();
+
+        let provider = Provider::quorum(quorum);
+        let res = provider.send_raw_transaction(vec![0x12, 0x34].into()).await.unwrap();
+        assert_eq!(res.tx_hash(), hash);
+        for mock in mocked {
+            mock.assert_request("eth_sendRawTransaction", ["0x1234"]).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn normalizes_latest_block_tag() {
+        let balance = ethers_core::types::U256::from(100);
+        let mut mocked = Vec::new();
+        for block in [10u64, 12] {
+            let mock = MockProvider::new();
+            mock.push(balance).unwrap();
+            mock.push(U64::from(block)).unwrap();
+            mocked.push(mock);
+        }
+        let quorum = QuorumProvider::builder()
+            .add_providers(mocked.iter().cloned().map(WeightedProvider::new))
+            .quorum(Quorum::All)
+            .build();
+
+        let provider = Provider::quorum(quorum);
+        let address = ethers_core::types::Address::zero();
+        assert_eq!(provider.get_balance(address, None).await.unwrap(), balance);
+        for mock in mocked {
+            mock.assert_request("eth_blockNumber", ()).unwrap();
+            mock.assert_request("eth_getBalance", (address, U64::from(10))).unwrap();
+        }
+    }
 }
@@ -5,6 +5,7 @@ use super::{common::JsonRpcError, http::ClientError};
 use crate::{errors::ProviderError, JsonRpcClient};
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
 use std::{
     fmt::Debug,
     sync::atomic::{AtomicU32, Ordering},
@@ -353,6 +354,34 @@ where
             }
         }
     }
+
+    /// Sends the requests in a single batch of the inner client. The requests that failed with
+    /// a retryable error, or all of them if the batch failed, are then retried one by one.
+    async fn request_batch(
+        &self,
+        requests: Vec<(String, Value)>,
+    ) -> Result<Vec<Result<Value, Self::Error>>, Self::Error> {
+        let batch: Vec<_> = match self.inner.request_batch(requests.clone()).await {
+            Ok(results) => results.into_iter().map(Some).collect(),
+            Err(err) => {
+                trace!(err = ?err, "batch failed, retrying the requests one by one");
+                std::iter::repeat_with(|| None).take(requests.len()).collect()
+            }
+        };
+
+        let mut results = Vec::with_capacity(requests.len());
+        for ((method, params), result) in requests.into_iter().zip(batch) {
+            let result = match result {
+                Some(Ok(value)) => Ok(value),
+                Some(Err(err)) if !self.policy.should_retry(&err) => {
+                    Err(RetryClientError::ProviderError(err.into()))
+                }
+                _ => self.request(&method, params).await,
+            };
+            results.push(result);
+        }
+        Ok(results)
+    }
 }
 
 /// Implements [RetryPolicy] that will retry requests that errored with
@@ -365,32 +394,6 @@ pub struct HttpRateLimitRetryPolicy;
 
 impl RetryPolicy<ClientError> for HttpRateLimitRetryPolicy {
     fn should_retry(&self, error: &ClientError) -> bool {
-        fn should_retry_json_rpc_error(err: &JsonRpcError) -> bool {
-            let JsonRpcError { code, message, .. } = err;
-            // alchemy throws it this way
-            if *code == 429 {
-                return true
-            }
-
-            // This is an infura error code for `exceeded project rate limit`
-            if *code == -32005 {
-                return true
-            }
-
-            // alternative alchemy error for specific IPs
-            if *code == -32016 && message.contains("rate limit") {
-                return true
-            }
-
-            match message.as_str() {
-                // this is commonly thrown by infura and is apparently a load balancer issue, see also <https://github.com/MetaMask/metamask-extension/issues/7234>
-                "header not found" => true,
-                // also thrown by infura if out of budget for the day and ratelimited
-                "daily request count exceeded, request rate limited" => true,
-                _ => false,
-            }
-        }
-
         match error {
             ClientError::ReqwestError(err) => {
                 err.status() == Some(http::StatusCode::TOO_MANY_REQUESTS)
@@ -413,25 +416,77 @@ impl RetryPolicy<ClientError> for HttpRateLimitRetryPolicy {
     }
 
     fn backoff_hint(&self, error: &ClientError) -> Option<Duration> {
-        if let ClientError::JsonRpcError(JsonRpcError { data, .. }) = error {
-            let data = data.as_ref()?;
-
-            // if daily rate limit exceeded, infura returns the requested backoff in the error
-            // response
-            let backoff_seconds = &data["rate"]["backoff_seconds"];
-            // infura rate limit error
-            if let Some(seconds) = backoff_seconds.as_u64() {
-                return Some(Duration::from_secs(seconds))
-            }
-            if let Some(seconds) = backoff_seconds.as_f64() {
-                return Some(Duration::from_secs(seconds as u64 + 1))
-            }
+        if let ClientError::JsonRpcError(err) = error {
+            return json_rpc_error_backoff_hint(err)
         }
-
         None
     }
 }
 
+/// Classifies an error that already went through the provider, e.g. in a middleware stack.
+///
+/// HTTP errors are retried on status code 429, JSON-RPC error responses follow the same rules as
+/// for the [ClientError].
+impl RetryPolicy<ProviderError> for HttpRateLimitRetryPolicy {
+    fn should_retry(&self, error: &ProviderError) -> bool {
+        if let ProviderError::HTTPError(err) = error {
+            return err.status() == Some(http::StatusCode::TOO_MANY_REQUESTS)
+        }
+        crate::RpcError::as_error_response(error)
+            .map(should_retry_json_rpc_error)
+            .unwrap_or_default()
+    }
+
+    fn backoff_hint(&self, error: &ProviderError) -> Option<Duration> {
+        crate::RpcError::as_error_response(error).and_then(json_rpc_error_backoff_hint)
+    }
+}
+
+/// Whether the JSON-RPC error response signals that the request was rate limited
+fn should_retry_json_rpc_error(err: &JsonRpcError) -> bool {
+    let JsonRpcError { code, message, .. } = err;
+    // alchemy throws it this way
+    if *code == 429 {
+        return true
+    }
+
+    // This is an infura error code for `exceeded project rate limit`
+    if *code == -32005 {
+        return true
+    }
+
+    // alternative alchemy error for specific IPs
+    if *code == -32016 && message.contains("rate limit") {
+        return true
+    }
+
+    match message.as_str() {
+        // this is commonly thrown by infura and is apparently a load balancer issue, see also <https://github.com/MetaMask/metamask-extension/issues/7234>
+        "header not found" => true,
+        // also thrown by infura if out of budget for the day and ratelimited
+        "daily request count exceeded, request rate limited" => true,
+        _ => false,
+    }
+}
+
+/// Extracts the requested backoff from the JSON-RPC error response, if any
+fn json_rpc_error_backoff_hint(err: &JsonRpcError) -> Option<Duration> {
+    let data = err.data.as_ref()?;
+
+    // if daily rate limit exceeded, infura returns the requested backoff in the error
+    // response
+    let backoff_seconds = &data["rate"]["backoff_seconds"];
+    // infura rate limit error
+    if let Some(seconds) = backoff_seconds.as_u64() {
+        return Some(Duration::from_secs(seconds))
+    }
+    if let Some(seconds) = backoff_seconds.as_f64() {
+        return Some(Duration::from_secs(seconds as u64 + 1))
+    }
+
+    None
+}
+
 /// Calculates an offset in seconds by taking into account the number of currently queued requests,
 /// number of requests that were ahead in the queue when the request was first issued, the average
 /// cost a weighted request (heuristic), and the number of available compute units per seconds.
@@ -484,6 +539,7 @@ fn maybe_connectivity(err: &ProviderError) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{MockError, MockProvider};
     // assumed average cost of a request
     const AVG_COST: u64 = 17u64;
     const COMPUTE_UNITS: u64 = 330u64;
@@ -577,4 +633,56 @@ mod tests {
         let should_retry = HttpRateLimitRetryPolicy.should_retry(&err);
         assert!(should_retry);
     }
+
+    #[test]
+    fn can_classify_provider_errors() {
+        let rate_limited =
+            ProviderError::JsonRpcClientError(Box::new(ClientError::JsonRpcError(JsonRpcError {
+                code: 429,
+                message: "Too many requests".to_string(),
+                data: None,
+            })));
+        assert!(HttpRateLimitRetryPolicy.should_retry(&rate_limited));
+
+        let reverted =
+            ProviderError::JsonRpcClientError(Box::new(ClientError::JsonRpcError(JsonRpcError {
+                code: 3,
+                message: "execution reverted".to_string(),
+                data: None,
+            })));
+        assert!(!HttpRateLimitRetryPolicy.should_retry(&reverted));
+
+        let custom = ProviderError::CustomError("rate limit".to_string());
+        assert!(!HttpRateLimitRetryPolicy.should_retry(&custom));
+    }
+
+    #[derive(Debug)]
+    struct NoRetryPolicy;
+
+    impl RetryPolicy<MockError> for NoRetryPolicy {
+        fn should_retry(&self, _error: &MockError) -> bool {
+            false
+        }
+
+        fn backoff_hint(&self, _error: &MockError) -> Option<Duration> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn forwards_batches() {
+        let mock = MockProvider::new();
+        let client = RetryClientBuilder::default().build(mock.clone(), Box::new(NoRetryPolicy));
+
+        mock.push(1u64).unwrap();
+        let requests = vec![
+            ("eth_chainId".to_string(), Value::Array(vec![])),
+            ("eth_blockNumber".to_string(), Value::Array(vec![])),
+        ];
+        let results = client.request_batch(requests).await.unwrap();
+        assert_eq!(results[0].as_ref().unwrap(), &Value::from(1u64));
+        assert!(matches!(results[1], Err(RetryClientError::ProviderError(_))));
+        mock.assert_request("eth_chainId", Value::Array(vec![])).unwrap();
+        mock.assert_request("eth_blockNumber", Value::Array(vec![])).unwrap();
+    }
 }
@@ -4,6 +4,7 @@
 use crate::{errors::ProviderError, JsonRpcClient};
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 use thiserror::Error;
 
 /// A client containing two clients.
@@ -139,4 +140,23 @@ where
             _ => self.r.request(method, params).await.map_err(RwClientError::Read),
         }
     }
+
+    /// Sends the requests in a single batch of the _read_ client, unless they include a _write_
+    /// operation, in which case they are sent one by one
+    async fn request_batch(
+        &self,
+        requests: Vec<(String, Value)>,
+    ) -> Result<Vec<Result<Value, Self::Error>>, Self::Error> {
+        if requests.iter().any(|(method, _)| {
+            matches!(method.as_str(), "eth_sendTransaction" | "eth_sendRawTransaction")
+        }) {
+            let mut results = Vec::with_capacity(requests.len());
+            for (method, params) in requests {
+                results.push(self.request(&method, params).await);
+            }
+            return Ok(results)
+        }
+        let results = self.r.request_batch(requests).await.map_err(RwClientError::Read)?;
+        Ok(results.into_iter().map(|res| res.map_err(RwClientError::Read)).collect())
+    }
 }
@@ -1,16 +1,84 @@
 use futures_channel::{mpsc, oneshot};
-use futures_util::{select, sink::SinkExt, stream::StreamExt, FutureExt};
+use futures_util::{future::poll_fn, select, sink::SinkExt, stream::StreamExt, FutureExt};
 
 use serde_json::value::RawValue;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
 
 use super::{types::*, WsClientError};
 use tracing::{error, trace};
 
+/// The number of items received via WS that wait for the `RequestManager` to handle them, once
+/// backpressure is enabled. Beyond that, the backend stops reading from the WS until the manager
+/// catches up, which happens when a subscription's buffer is full and blocks.
+const TO_HANDLE_BUFFER: usize = 256;
+
+/// Limits the number of items the backend hands to the `RequestManager` before they are handled.
+///
+/// Disabled by default, in which case the backend reads from the WS without waiting for the
+/// manager. It is enabled by the first subscription using [`OverflowPolicy::Block`].
+///
+/// [`OverflowPolicy::Block`]: super::OverflowPolicy::Block
+#[derive(Debug, Default)]
+pub struct Backpressure {
+    enabled: AtomicBool,
+    // Items sent to the manager but not yet received by it
+    pending: AtomicUsize,
+    // Set when the backend is waiting for the manager
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Backpressure {
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    fn sent(&self) {
+        self.pending.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Called by the manager for every item it receives
+    pub fn received(&self) {
+        self.pending.fetch_sub(1, Ordering::SeqCst);
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.is_enabled() && self.pending.load(Ordering::SeqCst) >= TO_HANDLE_BUFFER
+    }
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if !self.is_full() {
+            return Poll::Ready(())
+        }
+        *self.waker.lock().unwrap() = Some(cx.waker().clone());
+        // the manager may have caught up before the waker was registered
+        if self.is_full() {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
 /// `BackendDriver` drives a specific `WsBackend`. It can be used to issue
 /// requests, receive responses, see errors, and shut down the backend.
 pub struct BackendDriver {
     // Pubsub items from the backend, received via WS
     pub to_handle: mpsc::UnboundedReceiver<PubSubItem>,
+    // Flow control of `to_handle`, shared with the backend
+    pub backpressure: Arc<Backpressure>,
     // Notification from the backend of a terminal error
     pub error: oneshot::Receiver<()>,
 
@@ -38,6 +106,8 @@ pub struct WsBackend {
 
     // channel to the manager, through which to send items received via WS
     handler: mpsc::UnboundedSender<PubSubItem>,
+    // flow control of `handler`, shared with the manager
+    backpressure: Arc<Backpressure>,
     // notify manager of an error causing this task to halt
     error: oneshot::Sender<()>,
 
@@ -84,10 +154,24 @@ impl WsBackend {
         let (dispatcher, to_dispatch) = mpsc::unbounded();
         let (error_tx, error_rx) = oneshot::channel();
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let backpressure = Arc::new(Backpressure::default());
 
         (
-            WsBackend { server, handler, error: error_tx, to_dispatch, shutdown: shutdown_rx },
-            BackendDriver { to_handle, error: error_rx, dispatcher, shutdown: shutdown_tx },
+            WsBackend {
+                server,
+                handler,
+                backpressure: backpressure.clone(),
+                error: error_tx,
+                to_dispatch,
+                shutdown: shutdown_rx,
+            },
+            BackendDriver {
+                to_handle,
+                backpressure,
+                error: error_rx,
+                dispatcher,
+                shutdown: shutdown_tx,
+            },
         )
     }
 
@@ -96,6 +180,9 @@ impl WsBackend {
         match serde_json::from_str(&t) {
             Ok(item) => {
                 trace!(%item, "Deserialized message");
+                // waits until the manager catches up, if backpressure is enabled
+                poll_fn(|cx| self.backpressure.poll_ready(cx)).await;
+                self.backpressure.sent();
                 let res = self.handler.unbounded_send(item);
                 if res.is_err() {
                     return Err(WsClientError::DeadChannel)
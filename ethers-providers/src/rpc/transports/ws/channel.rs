@@ -0,0 +1,333 @@
+use futures_channel::mpsc;
+use futures_core::Stream;
+use serde_json::value::RawValue;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// What happens when a notification arrives for a subscription whose buffer is full, see
+/// [`WsClient::with_subscription_buffer`](super::WsClient::with_subscription_buffer)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Stop processing messages from the server until the subscriber made room in the buffer.
+    ///
+    /// This applies backpressure to the connection: while the buffer is full, no responses to
+    /// requests or notifications of other subscriptions are received either. A subscriber that
+    /// waits for a request made over the same connection before consuming its stream therefore
+    /// deadlocks.
+    #[default]
+    Block,
+    /// Drop the oldest buffered notification to make room for the new one
+    DropOldest,
+    /// End the stream once the buffered notifications are consumed, dropping the new one and
+    /// all subsequent notifications
+    Error,
+}
+
+/// The outcome of handing a notification to a subscriber
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum SendOutcome {
+    /// The notification was buffered
+    Sent,
+    /// The buffer was full and the notification was dropped, or ended the stream
+    Dropped,
+    /// The subscriber has gone away
+    Closed,
+}
+
+#[derive(Debug)]
+struct State {
+    items: VecDeque<Box<RawValue>>,
+    /// Set when the subscriber is waiting for a notification
+    rx_waker: Option<Waker>,
+    /// Set when the sender is waiting for room in the buffer
+    tx_waker: Option<Waker>,
+    sender_dropped: bool,
+    receiver_dropped: bool,
+    overflowed: bool,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    /// The number of dropped notifications of this subscription
+    dropped: AtomicU64,
+    /// The number of dropped notifications of all subscriptions of the client
+    total_dropped: Arc<AtomicU64>,
+}
+
+/// Creates the notification channel of a subscription, unbounded if `buffer` is `None`
+pub(super) fn channel(
+    buffer: Option<(usize, OverflowPolicy)>,
+    total_dropped: Arc<AtomicU64>,
+) -> (NotificationSender, NotificationReceiver) {
+    match buffer {
+        Some((capacity, policy)) => {
+            let (tx, rx) = buffered(capacity, policy, total_dropped);
+            (tx, NotificationReceiver::Buffered(rx))
+        }
+        None => {
+            let (tx, rx) = mpsc::unbounded();
+            (NotificationSender::Unbounded(tx), NotificationReceiver::Unbounded(rx))
+        }
+    }
+}
+
+fn buffered(
+    capacity: usize,
+    policy: OverflowPolicy,
+    total_dropped: Arc<AtomicU64>,
+) -> (NotificationSender, WsNotificationStream) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            items: VecDeque::new(),
+            rx_waker: None,
+            tx_waker: None,
+            sender_dropped: false,
+            receiver_dropped: false,
+            overflowed: false,
+        }),
+        capacity: capacity.max(1),
+        policy,
+        dropped: AtomicU64::new(0),
+        total_dropped,
+    });
+    let tx = NotificationSender::Buffered(BufferedSender { shared: shared.clone() });
+    (tx, WsNotificationStream { shared })
+}
+
+/// The sending half of a subscription's notification channel, held by the request manager
+#[derive(Debug)]
+pub(super) enum NotificationSender {
+    Unbounded(mpsc::UnboundedSender<Box<RawValue>>),
+    Buffered(BufferedSender),
+}
+
+impl NotificationSender {
+    /// Hands the notification to the subscriber according to the channel's overflow policy.
+    ///
+    /// Only waits if the buffer is full and the policy is [`OverflowPolicy::Block`].
+    pub(super) async fn send(&self, item: Box<RawValue>) -> SendOutcome {
+        match self {
+            Self::Unbounded(tx) => match tx.unbounded_send(item) {
+                Ok(()) => SendOutcome::Sent,
+                Err(_) => SendOutcome::Closed,
+            },
+            Self::Buffered(tx) => SendFuture { shared: &tx.shared, item: Some(item) }.await,
+        }
+    }
+}
+
+/// The receiving half of a subscription's notification channel, until the client takes it
+#[derive(Debug)]
+pub(super) enum NotificationReceiver {
+    Unbounded(mpsc::UnboundedReceiver<Box<RawValue>>),
+    Buffered(WsNotificationStream),
+}
+
+/// The sending half of a buffered notification channel
+#[derive(Debug)]
+pub(super) struct BufferedSender {
+    shared: Arc<Shared>,
+}
+
+impl Drop for BufferedSender {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.sender_dropped = true;
+        if let Some(waker) = state.rx_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Future of [`NotificationSender::send`]
+struct SendFuture<'a> {
+    shared: &'a Arc<Shared>,
+    item: Option<Box<RawValue>>,
+}
+
+impl Future for SendFuture<'_> {
+    type Output = SendOutcome;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let shared = self.shared;
+        let mut state = shared.state.lock().unwrap();
+        if state.receiver_dropped {
+            return Poll::Ready(SendOutcome::Closed)
+        }
+
+        let item = self.item.take().expect("polled after completion");
+        let outcome = match shared.policy {
+            _ if state.overflowed => SendOutcome::Dropped,
+            policy if state.items.len() >= shared.capacity => match policy {
+                OverflowPolicy::Block => {
+                    state.tx_waker = Some(cx.waker().clone());
+                    drop(state);
+                    self.item = Some(item);
+                    return Poll::Pending
+                }
+                OverflowPolicy::DropOldest => {
+                    state.items.pop_front();
+                    state.items.push_back(item);
+                    SendOutcome::Dropped
+                }
+                OverflowPolicy::Error => {
+                    state.overflowed = true;
+                    SendOutcome::Dropped
+                }
+            },
+            _ => {
+                state.items.push_back(item);
+                SendOutcome::Sent
+            }
+        };
+        if let Some(waker) = state.rx_waker.take() {
+            waker.wake();
+        }
+        drop(state);
+
+        if outcome == SendOutcome::Dropped {
+            shared.dropped.fetch_add(1, Ordering::Relaxed);
+            shared.total_dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        Poll::Ready(outcome)
+    }
+}
+
+/// The stream of notifications of a [`BufferedWsClient`](super::BufferedWsClient) subscription.
+///
+/// The notifications are buffered according to the client's
+/// [subscription buffer](super::WsClient::with_subscription_buffer).
+#[derive(Debug)]
+pub struct WsNotificationStream {
+    shared: Arc<Shared>,
+}
+
+impl WsNotificationStream {
+    /// Returns the number of notifications of this subscription that were dropped because its
+    /// buffer was full
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Returns true if the buffer overflowed with [`OverflowPolicy::Error`], in which case the
+    /// stream ends after the buffered notifications
+    pub fn is_overflowed(&self) -> bool {
+        self.shared.state.lock().unwrap().overflowed
+    }
+}
+
+impl Stream for WsNotificationStream {
+    type Item = Box<RawValue>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if let Some(item) = state.items.pop_front() {
+            if let Some(waker) = state.tx_waker.take() {
+                waker.wake();
+            }
+            return Poll::Ready(Some(item))
+        }
+        if state.sender_dropped || state.overflowed {
+            return Poll::Ready(None)
+        }
+        state.rx_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for WsNotificationStream {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.receiver_dropped = true;
+        state.items.clear();
+        if let Some(waker) = state.tx_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{FutureExt, StreamExt};
+
+    fn item(i: u64) -> Box<RawValue> {
+        serde_json::value::to_raw_value(&i).unwrap()
+    }
+
+    fn collect(rx: &mut (impl Stream<Item = Box<RawValue>> + Unpin)) -> Vec<String> {
+        std::iter::from_fn(|| rx.next().now_or_never().flatten())
+            .map(|item| item.get().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn drops_oldest() {
+        let total = Arc::new(AtomicU64::new(0));
+        let (tx, mut rx) = buffered(2, OverflowPolicy::DropOldest, total.clone());
+        for i in 0..4 {
+            tx.send(item(i)).now_or_never().unwrap();
+        }
+        assert_eq!(collect(&mut rx), ["2", "3"]);
+        assert_eq!(rx.dropped(), 2);
+        assert_eq!(total.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn ends_stream_on_overflow() {
+        let (tx, mut rx) = buffered(1, OverflowPolicy::Error, Default::default());
+        assert_eq!(tx.send(item(0)).now_or_never(), Some(SendOutcome::Sent));
+        assert_eq!(tx.send(item(1)).now_or_never(), Some(SendOutcome::Dropped));
+        assert_eq!(tx.send(item(2)).now_or_never(), Some(SendOutcome::Dropped));
+        assert!(rx.is_overflowed());
+        assert_eq!(collect(&mut rx), ["0"]);
+        assert!(rx.next().now_or_never().unwrap().is_none());
+        assert_eq!(rx.dropped(), 2);
+    }
+
+    #[test]
+    fn blocks_until_consumed() {
+        let (tx, mut rx) = buffered(1, OverflowPolicy::Block, Default::default());
+        assert_eq!(tx.send(item(0)).now_or_never(), Some(SendOutcome::Sent));
+
+        let mut send = Box::pin(tx.send(item(1)));
+        assert!((&mut send).now_or_never().is_none());
+        assert_eq!(collect(&mut rx), ["0"]);
+        assert_eq!(send.now_or_never(), Some(SendOutcome::Sent));
+        assert_eq!(collect(&mut rx), ["1"]);
+        assert_eq!(rx.dropped(), 0);
+    }
+
+    #[test]
+    fn closes_when_receiver_drops() {
+        let (tx, rx) = buffered(1, OverflowPolicy::Block, Default::default());
+        assert_eq!(tx.send(item(0)).now_or_never(), Some(SendOutcome::Sent));
+        drop(rx);
+        assert_eq!(tx.send(item(1)).now_or_never(), Some(SendOutcome::Closed));
+    }
+
+    #[test]
+    fn unbounded_by_default() {
+        let (tx, rx) = channel(None, Default::default());
+        let NotificationReceiver::Unbounded(mut rx) = rx else {
+            panic!("expected an unbounded channel")
+        };
+        for i in 0..100 {
+            assert_eq!(tx.send(item(i)).now_or_never(), Some(SendOutcome::Sent));
+        }
+        drop(tx);
+        assert_eq!(collect(&mut rx).len(), 100);
+        assert!(rx.next().now_or_never().unwrap().is_none());
+    }
+}
@@ -2,12 +2,14 @@
 use super::WebSocketConfig;
 use super::{
     backend::{BackendDriver, WsBackend},
+    channel::{self, NotificationReceiver, OverflowPolicy, SendOutcome},
     ActiveSub, ConnectionDetails, InFlight, Instruction, Notification, PubSubItem, Response, SubId,
     WsClient, WsClientError,
 };
 use crate::JsonRpcError;
 use ethers_core::types::U256;
 use futures_channel::{mpsc, oneshot};
+use futures_timer::Delay;
 use futures_util::{select_biased, StreamExt};
 use serde_json::value::{to_raw_value, RawValue};
 use std::{
@@ -16,12 +18,20 @@ use std::{
         atomic::{AtomicU64, Ordering},
         Arc, Mutex,
     },
+    time::Duration,
 };
 
-pub type SharedChannelMap = Arc<Mutex<HashMap<U256, mpsc::UnboundedReceiver<Box<RawValue>>>>>;
+pub type SharedChannelMap = Arc<Mutex<HashMap<U256, NotificationReceiver>>>;
 
 pub const DEFAULT_RECONNECTS: usize = 5;
 
+/// The delay before retrying a failed reconnection attempt. It is doubled after each consecutive
+/// failure, up to [`MAX_RECONNECT_BACKOFF`]
+pub const DEFAULT_RECONNECT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// The maximum delay between two reconnection attempts
+pub const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
 /// This struct manages the relationship between the u64 request ID, and U256
 /// server-side subscription ID. It does this by aliasing the server ID to the
 /// request ID, and returning the Request ID to the caller (hiding the server
@@ -35,11 +45,19 @@ pub struct SubscriptionManager {
     aliases: HashMap<U256, u64>,
     // Used to share notification channels with the WsClient(s)
     channel_map: SharedChannelMap,
+    // How many notifications have been dropped because of full buffers, shared with the
+    // WsClient(s)
+    dropped_notifications: Arc<AtomicU64>,
 }
 
 impl SubscriptionManager {
-    fn new(channel_map: SharedChannelMap) -> Self {
-        Self { subs: Default::default(), aliases: Default::default(), channel_map }
+    fn new(channel_map: SharedChannelMap, dropped_notifications: Arc<AtomicU64>) -> Self {
+        Self {
+            subs: Default::default(),
+            aliases: Default::default(),
+            channel_map,
+            dropped_notifications,
+        }
     }
 
     fn count(&self) -> usize {
@@ -88,7 +106,7 @@ impl SubscriptionManager {
     }
 
     #[tracing::instrument(skip_all, fields(server_id = ?notification.subscription))]
-    fn handle_notification(&mut self, notification: Notification) {
+    async fn handle_notification(&mut self, notification: Notification) {
         let server_id = notification.subscription;
 
         // If no alias, just return
@@ -111,11 +129,15 @@ impl SubscriptionManager {
         let active = sub_opt.unwrap();
 
         tracing::debug!(id, "Forwarding notification to listener");
-        // send the notification over the channel
-        let send_res = active.channel.unbounded_send(notification.result);
+        // send the notification over the channel, this waits for the listener if its buffer is
+        // full and blocks
+        let send_res = active.channel.send(notification.result).await;
+        if send_res == SendOutcome::Dropped {
+            tracing::debug!(id, "Listener buffer full. Dropped notification");
+        }
 
         // receiver has dropped, so we drop the sub
-        if send_res.is_err() {
+        if send_res == SendOutcome::Closed {
             tracing::debug!(id, "Listener dropped. Dropping alias and subs");
             // TODO: end subcription here?
             self.aliases.remove(&server_id);
@@ -146,8 +168,9 @@ impl SubscriptionManager {
         &mut self,
         id: u64,
         params: Box<RawValue>,
+        buffer: Option<(usize, OverflowPolicy)>,
     ) -> Result<Box<RawValue>, WsClientError> {
-        let (tx, rx) = mpsc::unbounded();
+        let (tx, rx) = channel::channel(buffer, self.dropped_notifications.clone());
 
         let active_sub = ActiveSub { params, channel: tx, current_server_id: None };
         let req = active_sub.serialize_raw(id)?;
@@ -190,6 +213,10 @@ pub struct RequestManager {
     id: AtomicU64,
     // How many times we should reconnect the backend before erroring
     reconnects: usize,
+    // The delay before retrying a failed reconnection attempt
+    reconnect_backoff: Duration,
+    // How many times the backend has been reconnected, shared with the WsClient(s)
+    reconnections: Arc<AtomicU64>,
     // Subscription manager
     subs: SubscriptionManager,
     // Requests for which a response has not been receivedc
@@ -211,7 +238,7 @@ impl RequestManager {
     }
 
     pub async fn connect(conn: ConnectionDetails) -> Result<(Self, WsClient), WsClientError> {
-        Self::connect_with_reconnects(conn, DEFAULT_RECONNECTS).await
+        Self::connect_with_reconnects(conn, DEFAULT_RECONNECTS, DEFAULT_RECONNECT_BACKOFF).await
     }
 
     async fn connect_internal(
@@ -254,21 +281,31 @@ impl RequestManager {
     pub async fn connect_with_reconnects(
         conn: ConnectionDetails,
         reconnects: usize,
+        reconnect_backoff: Duration,
     ) -> Result<(Self, WsClient), WsClientError> {
         let (backend, (instructions_tx, instructions_rx), channel_map) =
             Self::connect_internal(conn.clone()).await?;
+        let reconnections = Arc::new(AtomicU64::new(0));
+        let dropped_notifications = Arc::new(AtomicU64::new(0));
 
         Ok((
             Self {
                 id: Default::default(),
                 reconnects,
-                subs: SubscriptionManager::new(channel_map.clone()),
+                reconnect_backoff,
+                reconnections: reconnections.clone(),
+                subs: SubscriptionManager::new(channel_map.clone(), dropped_notifications.clone()),
                 reqs: Default::default(),
                 backend,
                 conn,
                 instructions: instructions_rx,
             },
-            WsClient { instructions: instructions_tx, channel_map },
+            WsClient {
+                instructions: instructions_tx,
+                channel_map,
+                reconnections,
+                dropped_notifications,
+            },
         ))
     }
 
@@ -276,22 +313,32 @@ impl RequestManager {
     pub async fn connect_with_reconnects(
         conn: ConnectionDetails,
         reconnects: usize,
+        reconnect_backoff: Duration,
     ) -> Result<(Self, WsClient), WsClientError> {
         let (backend, (instructions_tx, instructions_rx), channel_map) =
             Self::connect_internal(conn.clone()).await?;
+        let reconnections = Arc::new(AtomicU64::new(0));
+        let dropped_notifications = Arc::new(AtomicU64::new(0));
 
         Ok((
             Self {
                 id: Default::default(),
                 reconnects,
-                subs: SubscriptionManager::new(channel_map.clone()),
+                reconnect_backoff,
+                reconnections: reconnections.clone(),
+                subs: SubscriptionManager::new(channel_map.clone(), dropped_notifications.clone()),
                 reqs: Default::default(),
                 backend,
                 conn,
                 config: None,
                 instructions: instructions_rx,
             },
-            WsClient { instructions: instructions_tx, channel_map },
+            WsClient {
+                instructions: instructions_tx,
+                channel_map,
+                reconnections,
+                dropped_notifications,
+            },
         ))
     }
 
@@ -300,7 +347,13 @@ impl RequestManager {
         conn: ConnectionDetails,
         config: WebSocketConfig,
     ) -> Result<(Self, WsClient), WsClientError> {
-        Self::connect_with_config_and_reconnects(conn, config, DEFAULT_RECONNECTS).await
+        Self::connect_with_config_and_reconnects(
+            conn,
+            config,
+            DEFAULT_RECONNECTS,
+            DEFAULT_RECONNECT_BACKOFF,
+        )
+        .await
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -308,22 +361,32 @@ impl RequestManager {
         conn: ConnectionDetails,
         config: WebSocketConfig,
         reconnects: usize,
+        reconnect_backoff: Duration,
     ) -> Result<(Self, WsClient), WsClientError> {
         let (backend, (instructions_tx, instructions_rx), channel_map) =
             Self::connect_with_config_internal(conn.clone(), config.clone()).await?;
+        let reconnections = Arc::new(AtomicU64::new(0));
+        let dropped_notifications = Arc::new(AtomicU64::new(0));
 
         Ok((
             Self {
                 id: Default::default(),
                 reconnects,
-                subs: SubscriptionManager::new(channel_map.clone()),
+                reconnect_backoff,
+                reconnections: reconnections.clone(),
+                subs: SubscriptionManager::new(channel_map.clone(), dropped_notifications.clone()),
                 reqs: Default::default(),
                 backend,
                 conn,
                 config: Some(config),
                 instructions: instructions_rx,
             },
-            WsClient { instructions: instructions_tx, channel_map },
+            WsClient {
+                instructions: instructions_tx,
+                channel_map,
+                reconnections,
+                dropped_notifications,
+            },
         ))
     }
 
@@ -341,15 +404,36 @@ impl RequestManager {
         }
     }
 
-    async fn reconnect(&mut self) -> Result<(), WsClientError> {
-        if self.reconnects == 0 {
-            return Err(WsClientError::TooManyReconnects)
+    /// Establishes a new backend, retrying failed attempts with an exponential
+    /// backoff until the reconnect limit is reached
+    async fn connect_new_backend(&mut self) -> Result<(WsBackend, BackendDriver), WsClientError> {
+        let mut backoff = self.reconnect_backoff;
+        loop {
+            if self.reconnects == 0 {
+                return Err(WsClientError::TooManyReconnects)
+            }
+            self.reconnects -= 1;
+
+            tracing::info!(
+                remaining = self.reconnects,
+                url = self.conn.url,
+                "Reconnecting to backend"
+            );
+            match self.reconnect_backend().await {
+                Ok(backend) => return Ok(backend),
+                Err(err) if self.reconnects > 0 => {
+                    tracing::warn!(%err, ?backoff, "Reconnection attempt failed, retrying");
+                    Delay::new(backoff).await;
+                    backoff = backoff.saturating_mul(2).min(MAX_RECONNECT_BACKOFF);
+                }
+                Err(err) => return Err(err),
+            }
         }
-        self.reconnects -= 1;
+    }
 
-        tracing::info!(remaining = self.reconnects, url = self.conn.url, "Reconnecting to backend");
+    async fn reconnect(&mut self) -> Result<(), WsClientError> {
         // create the new backend
-        let (s, mut backend) = self.reconnect_backend().await?;
+        let (s, mut backend) = self.connect_new_backend().await?;
 
         // spawn the new backend
         s.spawn();
@@ -360,10 +444,16 @@ impl RequestManager {
         // rename for clarity
         let mut old_backend = backend;
 
+        // Keep applying backpressure if any subscription needs it
+        if old_backend.backpressure.is_enabled() {
+            self.backend.backpressure.enable();
+        }
+
         // Drain anything in the backend
         tracing::debug!("Draining old backend to_handle channel");
         while let Some(to_handle) = old_backend.to_handle.next().await {
-            self.handle(to_handle);
+            old_backend.backpressure.received();
+            self.handle(to_handle).await;
         }
 
         // issue a shutdown command (even though it's likely gone)
@@ -392,6 +482,8 @@ impl RequestManager {
                 .unbounded_send(req.serialize_raw(*id)?)
                 .map_err(|_| WsClientError::DeadChannel)?;
         }
+        // notifications sent by the server while we were disconnected are lost
+        self.reconnections.fetch_add(1, Ordering::SeqCst);
         tracing::info!(subs = self.subs.count(), reqs = req_cnt, "Re-connection complete");
 
         Ok(())
@@ -420,11 +512,11 @@ impl RequestManager {
         }
     }
 
-    fn handle(&mut self, item: PubSubItem) {
+    async fn handle(&mut self, item: PubSubItem) {
         match item {
             PubSubItem::Success { id, result } => self.req_success(id, result),
             PubSubItem::Error { id, error } => self.req_fail(id, error),
-            PubSubItem::Notification { params } => self.subs.handle_notification(params),
+            PubSubItem::Notification { params } => self.subs.handle_notification(params).await,
         }
     }
 
@@ -435,6 +527,7 @@ impl RequestManager {
         method: String,
         params: Box<RawValue>,
         sender: oneshot::Sender<Response>,
+        subscription_buffer: Option<(usize, OverflowPolicy)>,
     ) -> Result<(), WsClientError> {
         let in_flight = InFlight { method, params, channel: sender };
         let req = in_flight.serialize_raw(id)?;
@@ -442,7 +535,15 @@ impl RequestManager {
         // Ordering matters here. We want this block above the unbounded send,
         // and after the serialization
         if in_flight.method == "eth_subscribe" {
-            self.subs.service_subscription_request(id, in_flight.params.clone())?;
+            self.subs.service_subscription_request(
+                id,
+                in_flight.params.clone(),
+                subscription_buffer,
+            )?;
+            // a blocked subscription must be able to stop the backend from reading further
+            if matches!(subscription_buffer, Some((_, OverflowPolicy::Block))) {
+                self.backend.backpressure.enable();
+            }
         }
 
         // Must come after self.subs.service_subscription_request. Do not re-order
@@ -455,9 +556,9 @@ impl RequestManager {
 
     fn service_instruction(&mut self, instruction: Instruction) -> Result<(), WsClientError> {
         match instruction {
-            Instruction::Request { method, params, sender } => {
+            Instruction::Request { method, params, sender, subscription_buffer } => {
                 let id = self.next_id();
-                self.service_request(id, method, params, sender)?;
+                self.service_request(id, method, params, sender, subscription_buffer)?;
             }
             Instruction::Unsubscribe { id } => {
                 if let Some(req) = self.subs.end_subscription(id.low_u64()) {
@@ -480,7 +581,10 @@ impl RequestManager {
                 select_biased! {
                     item_opt = self.backend.to_handle.next() => {
                         match item_opt {
-                            Some(item) => self.handle(item),
+                            Some(item) => {
+                                self.backend.backpressure.received();
+                                self.handle(item).await
+                            }
                             // Backend is gone, so reconnect
                             None => if let Err(e) = self.reconnect().await {
                                 break Err(e);
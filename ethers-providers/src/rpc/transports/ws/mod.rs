@@ -2,10 +2,21 @@
 
 mod backend;
 
+mod channel;
+use channel::NotificationReceiver;
+pub use channel::{OverflowPolicy, WsNotificationStream};
+
 mod manager;
 
-use manager::{RequestManager, SharedChannelMap};
-use std::fmt;
+use manager::{RequestManager, SharedChannelMap, DEFAULT_RECONNECT_BACKOFF};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 mod types;
 pub use types::ConnectionDetails;
@@ -30,6 +41,10 @@ pub struct WsClient {
     instructions: mpsc::UnboundedSender<Instruction>,
     // Used to receive sub notifications channels with the backend
     channel_map: SharedChannelMap,
+    // How many times the connection has been re-established
+    reconnections: Arc<AtomicU64>,
+    // How many notifications have been dropped because of full subscription buffers
+    dropped_notifications: Arc<AtomicU64>,
 }
 
 impl WsClient {
@@ -45,7 +60,21 @@ impl WsClient {
         conn: impl Into<ConnectionDetails>,
         reconnects: usize,
     ) -> Result<Self, WsClientError> {
-        let (man, this) = RequestManager::connect_with_reconnects(conn.into(), reconnects).await?;
+        Self::connect_with_reconnects_and_backoff(conn, reconnects, DEFAULT_RECONNECT_BACKOFF).await
+    }
+
+    /// Establishes a new websocket connection with auto-reconnects.
+    ///
+    /// A failed reconnection attempt is retried after `backoff`, which is doubled after each
+    /// consecutive failure, up to 30 seconds. Every attempt counts towards the
+    /// `reconnects` limit.
+    pub async fn connect_with_reconnects_and_backoff(
+        conn: impl Into<ConnectionDetails>,
+        reconnects: usize,
+        backoff: Duration,
+    ) -> Result<Self, WsClientError> {
+        let (man, this) =
+            RequestManager::connect_with_reconnects(conn.into(), reconnects, backoff).await?;
         man.spawn();
         Ok(this)
     }
@@ -74,19 +103,72 @@ impl WsClient {
             conn.into(),
             config.into(),
             reconnects,
+            DEFAULT_RECONNECT_BACKOFF,
         )
         .await?;
         man.spawn();
         Ok(this)
     }
 
+    /// Returns how many times the connection has been re-established.
+    ///
+    /// On reconnection all active subscriptions are re-issued and keep delivering notifications
+    /// on their existing streams. However, notifications emitted by the server while the
+    /// connection was down are lost, so a change of this value indicates a possible gap in the
+    /// subscription streams.
+    pub fn reconnections(&self) -> u64 {
+        self.reconnections.load(Ordering::SeqCst)
+    }
+
+    /// Returns a client over the same connection whose subscriptions buffer up to `capacity`
+    /// notifications, applying `policy` when a notification arrives while the buffer is full.
+    ///
+    /// By default, notifications are buffered without bound until the subscription stream
+    /// consumes them. The subscriptions of the returned client use a [`WsNotificationStream`]
+    /// instead, so it is a different [`PubsubClient`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ethers_providers::{Middleware, OverflowPolicy, Provider, Ws};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let ws = Ws::connect("ws://localhost:8545")
+    ///     .await?
+    ///     .with_subscription_buffer(1024, OverflowPolicy::DropOldest);
+    /// let provider = Provider::new(ws);
+    ///
+    /// let blocks = provider.subscribe_blocks().await?;
+    /// // ...
+    /// println!("dropped {} notifications", provider.as_ref().dropped_notifications());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_subscription_buffer(
+        self,
+        capacity: usize,
+        policy: OverflowPolicy,
+    ) -> BufferedWsClient {
+        BufferedWsClient { inner: self, buffer: (capacity, policy) }
+    }
+
     #[tracing::instrument(skip(self, params), err)]
-    async fn make_request<R>(&self, method: &str, params: Box<RawValue>) -> Result<R, WsClientError>
+    async fn make_request<R>(
+        &self,
+        method: &str,
+        params: Box<RawValue>,
+        subscription_buffer: Option<(usize, OverflowPolicy)>,
+    ) -> Result<R, WsClientError>
     where
         R: DeserializeOwned,
     {
         let (tx, rx) = oneshot::channel();
-        let instruction = Instruction::Request { method: method.to_owned(), params, sender: tx };
+        let instruction = Instruction::Request {
+            method: method.to_owned(),
+            params,
+            sender: tx,
+            subscription_buffer,
+        };
         self.instructions
             .unbounded_send(instruction)
             .map_err(|_| WsClientError::UnexpectedClose)?;
@@ -116,7 +198,7 @@ impl JsonRpcClient for WsClient {
         R: DeserializeOwned,
     {
         let params = to_raw_value(&params)?;
-        let res = self.make_request(method, params).await?;
+        let res = self.make_request(method, params, None).await?;
 
         Ok(res)
     }
@@ -129,7 +211,16 @@ impl PubsubClient for WsClient {
         // due to the behavior of the request manager, we know this map has
         // been populated by the time the `request()` call returns
         let id = id.into();
-        self.channel_map.lock().unwrap().remove(&id).ok_or(WsClientError::UnknownSubscription(id))
+        let mut channel_map = self.channel_map.lock().unwrap();
+        match channel_map.remove(&id) {
+            Some(NotificationReceiver::Unbounded(rx)) => Ok(rx),
+            // subscribed through a `BufferedWsClient`, leave it for that one
+            Some(rx) => {
+                channel_map.insert(id, rx);
+                Err(WsClientError::UnknownSubscription(id))
+            }
+            None => Err(WsClientError::UnknownSubscription(id)),
+        }
     }
 
     fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), WsClientError> {
@@ -139,6 +230,78 @@ impl PubsubClient for WsClient {
     }
 }
 
+/// A [`WsClient`] whose subscriptions buffer a bounded number of notifications, see
+/// [`WsClient::with_subscription_buffer`]
+#[derive(Clone)]
+pub struct BufferedWsClient {
+    inner: WsClient,
+    buffer: (usize, OverflowPolicy),
+}
+
+impl BufferedWsClient {
+    /// Returns how many times the connection has been re-established, see
+    /// [`WsClient::reconnections`]
+    pub fn reconnections(&self) -> u64 {
+        self.inner.reconnections()
+    }
+
+    /// Returns how many notifications of all subscriptions of the connection have been dropped
+    /// because their buffer was full
+    pub fn dropped_notifications(&self) -> u64 {
+        self.inner.dropped_notifications.load(Ordering::Relaxed)
+    }
+
+    /// Returns the client over the same connection whose new subscriptions are unbounded
+    pub fn into_inner(self) -> WsClient {
+        self.inner
+    }
+}
+
+impl fmt::Debug for BufferedWsClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferedWs").field("buffer", &self.buffer).finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl JsonRpcClient for BufferedWsClient {
+    type Error = WsClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, WsClientError>
+    where
+        T: Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let params = to_raw_value(&params)?;
+        let res = self.inner.make_request(method, params, Some(self.buffer)).await?;
+
+        Ok(res)
+    }
+}
+
+impl PubsubClient for BufferedWsClient {
+    type NotificationStream = WsNotificationStream;
+
+    fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, WsClientError> {
+        let id = id.into();
+        let mut channel_map = self.inner.channel_map.lock().unwrap();
+        match channel_map.remove(&id) {
+            Some(NotificationReceiver::Buffered(rx)) => Ok(rx),
+            // subscribed through a `WsClient`, leave it for that one
+            Some(rx) => {
+                channel_map.insert(id, rx);
+                Err(WsClientError::UnknownSubscription(id))
+            }
+            None => Err(WsClientError::UnknownSubscription(id)),
+        }
+    }
+
+    fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), WsClientError> {
+        self.inner.unsubscribe(id)
+    }
+}
+
 impl crate::Provider<WsClient> {
     /// Direct connection to a websocket endpoint. Defaults to 5 reconnects.
     ///
@@ -181,6 +344,18 @@ impl crate::Provider<WsClient> {
         Ok(Self::new(ws))
     }
 
+    /// Direct connection to a websocket endpoint, with a set number of
+    /// reconnection attempts and the delay before retrying a failed attempt,
+    /// see [`WsClient::connect_with_reconnects_and_backoff`]
+    pub async fn connect_with_reconnects_and_backoff(
+        url: impl Into<ConnectionDetails>,
+        reconnects: usize,
+        backoff: Duration,
+    ) -> Result<Self, ProviderError> {
+        let ws = crate::Ws::connect_with_reconnects_and_backoff(url, reconnects, backoff).await?;
+        Ok(Self::new(ws))
+    }
+
     /// Connect to a WS RPC provider with authentication details
     #[cfg(not(target_arch = "wasm32"))]
     pub async fn connect_with_auth(
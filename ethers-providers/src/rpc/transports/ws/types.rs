@@ -1,6 +1,7 @@
+use super::channel::{NotificationSender, OverflowPolicy};
 use crate::{common::Request, JsonRpcError};
 use ethers_core::types::U256;
-use futures_channel::{mpsc, oneshot};
+use futures_channel::oneshot;
 use serde::{de, Deserialize};
 use serde_json::value::{to_raw_value, RawValue};
 use std::fmt;
@@ -220,7 +221,7 @@ impl InFlight {
 #[derive(Debug)]
 pub(super) struct ActiveSub {
     pub params: Box<RawValue>,
-    pub channel: mpsc::UnboundedSender<Box<RawValue>>,
+    pub channel: NotificationSender,
     pub current_server_id: Option<U256>,
 }
 
@@ -237,7 +238,13 @@ impl ActiveSub {
 /// Instructions for the `WsServer`.
 pub enum Instruction {
     /// JSON-RPC request
-    Request { method: String, params: Box<RawValue>, sender: oneshot::Sender<Response> },
+    Request {
+        method: String,
+        params: Box<RawValue>,
+        sender: oneshot::Sender<Response>,
+        /// The notification buffer, if the request is a subscription
+        subscription_buffer: Option<(usize, OverflowPolicy)>,
+    },
     /// Cancel an existing subscription
     Unsubscribe { id: U256 },
 }
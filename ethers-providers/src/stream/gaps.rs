@@ -0,0 +1,261 @@
+use crate::{
+    utils::PinBoxFut, JsonRpcClient, Middleware, Provider, PubsubClient, SubscriptionStream,
+};
+use ethers_core::types::{Block, TxHash, U64};
+use futures_core::stream::Stream;
+use pin_project::pin_project;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// An item of a [`BlockGapStream`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+pub enum BlockUpdate {
+    /// A new block, or a block that was skipped by the stream of heads and fetched
+    Block(Block<TxHash>),
+    /// The inclusive range of block numbers that were skipped by the stream of heads and were
+    /// not fetched, emitted before the head following the gap
+    Gap {
+        /// The first skipped block number
+        from: U64,
+        /// The last skipped block number
+        to: U64,
+    },
+}
+
+/// Whether a [`BlockGapStream`] fetches the blocks of gaps or only reports them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GapMode {
+    /// Fetch the skipped blocks, emitting a [`BlockUpdate::Gap`] for the remaining blocks if a
+    /// block could not be fetched
+    Fetch,
+    /// Emit a [`BlockUpdate::Gap`] for the skipped blocks
+    Report,
+}
+
+/// A stream of blocks that makes gaps in the stream of new heads explicit, so that consumers
+/// like indexers do not silently miss blocks.
+///
+/// Nodes may skip heads, e.g. when the consumer is slow or when several blocks are imported at
+/// once. If the number of a new head jumps by more than one, the skipped blocks are either
+/// fetched and emitted in order before the head, or reported as a [`BlockUpdate::Gap`],
+/// depending on the [`GapMode`]. Heads that do not increase the block number, e.g. after a reorg,
+/// are emitted as is.
+///
+/// [`Middleware::watch_blocks`] does not need gap handling, since block filters return the hashes
+/// of all blocks since the last poll.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_providers::{BlockUpdate, Middleware, Provider, StreamExt, Ws};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("ws://localhost:8545").await?;
+/// let mut blocks = provider.subscribe_blocks().await?.fill_gaps();
+/// while let Some(update) = blocks.next().await {
+///     match update {
+///         BlockUpdate::Block(block) => println!("block {:?}", block.number),
+///         BlockUpdate::Gap { from, to } => println!("failed to fetch blocks {from} to {to}"),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct BlockGapStream<'a, P, St> {
+    provider: &'a Provider<P>,
+    #[pin]
+    heads: St,
+    mode: GapMode,
+    /// The number of the last head
+    last: Option<U64>,
+    /// The next and the last block of the gap that is being fetched, and the head following it
+    filling: Option<(U64, U64, Block<TxHash>)>,
+    fetch: Option<PinBoxFut<'a, Option<Block<TxHash>>>>,
+    ready: VecDeque<BlockUpdate>,
+}
+
+impl<'a, P, St> BlockGapStream<'a, P, St>
+where
+    P: JsonRpcClient,
+    St: Stream<Item = Block<TxHash>>,
+{
+    /// Wraps the stream of new `heads`, using the `provider` to fetch the skipped blocks
+    pub fn new(provider: &'a Provider<P>, heads: St, mode: GapMode) -> Self {
+        Self {
+            provider,
+            heads,
+            mode,
+            last: None,
+            filling: None,
+            fetch: None,
+            ready: VecDeque::new(),
+        }
+    }
+}
+
+impl<'a, P, St> Stream for BlockGapStream<'a, P, St>
+where
+    P: JsonRpcClient,
+    St: Stream<Item = Block<TxHash>>,
+{
+    type Item = BlockUpdate;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(update) = this.ready.pop_front() {
+                return Poll::Ready(Some(update))
+            }
+
+            if let Some(fetch) = this.fetch.as_mut() {
+                let res = match fetch.as_mut().poll(cx) {
+                    Poll::Ready(res) => res,
+                    Poll::Pending => return Poll::Pending,
+                };
+                *this.fetch = None;
+                let Some((next, to, head)) = this.filling.take() else { continue };
+                match res {
+                    Ok(Some(block)) => {
+                        this.ready.push_back(BlockUpdate::Block(block));
+                        if next < to {
+                            *this.filling = Some((next + 1, to, head));
+                        } else {
+                            this.ready.push_back(BlockUpdate::Block(head));
+                        }
+                    }
+                    // report the rest of the gap instead of retrying forever
+                    _ => {
+                        this.ready.push_back(BlockUpdate::Gap { from: next, to });
+                        this.ready.push_back(BlockUpdate::Block(head));
+                    }
+                }
+                continue
+            }
+
+            if let Some((next, _, _)) = this.filling {
+                *this.fetch = Some(Box::pin(this.provider.get_block(*next)));
+                continue
+            }
+
+            let head = match this.heads.as_mut().poll_next(cx) {
+                Poll::Ready(Some(head)) => head,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            let Some(number) = head.number else {
+                this.ready.push_back(BlockUpdate::Block(head));
+                continue
+            };
+            let gap = this
+                .last
+                .and_then(|last| last.checked_add(U64::one()))
+                .filter(|next| number > *next)
+                .map(|next| (next, number - 1));
+            *this.last = Some(number);
+            match (gap, *this.mode) {
+                (Some((from, to)), GapMode::Fetch) => *this.filling = Some((from, to, head)),
+                (Some((from, to)), GapMode::Report) => {
+                    this.ready.push_back(BlockUpdate::Gap { from, to });
+                    this.ready.push_back(BlockUpdate::Block(head));
+                }
+                (None, _) => this.ready.push_back(BlockUpdate::Block(head)),
+            }
+        }
+    }
+}
+
+impl<'a, P> SubscriptionStream<'a, P, Block<TxHash>>
+where
+    P: PubsubClient,
+{
+    /// Returns a stream of the new heads that fetches and emits the blocks skipped by the
+    /// subscription, see [`BlockGapStream`]
+    pub fn fill_gaps(self) -> BlockGapStream<'a, P, Self> {
+        BlockGapStream::new(self.provider, self, GapMode::Fetch)
+    }
+
+    /// Returns a stream of the new heads that reports the blocks skipped by the subscription as
+    /// a [`BlockUpdate::Gap`], see [`BlockGapStream`]
+    pub fn report_gaps(self) -> BlockGapStream<'a, P, Self> {
+        BlockGapStream::new(self.provider, self, GapMode::Report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{JsonRpcError, MockResponse};
+    use futures_util::{stream, StreamExt};
+
+    fn head(number: u64) -> Block<TxHash> {
+        Block { number: Some(number.into()), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn fetches_skipped_blocks() {
+        let (provider, mock) = Provider::mocked();
+        let heads = stream::iter([head(1), head(2), head(5), head(5), head(6)]);
+        let blocks = BlockGapStream::new(&provider, heads, GapMode::Fetch);
+
+        mock.push(head(4)).unwrap();
+        mock.push(head(3)).unwrap();
+        let numbers: Vec<_> = blocks
+            .map(|update| match update {
+                BlockUpdate::Block(block) => block.number.unwrap().as_u64(),
+                BlockUpdate::Gap { .. } => unreachable!(),
+            })
+            .collect()
+            .await;
+        // heads that do not increase the block number are emitted as is
+        assert_eq!(numbers, vec![1, 2, 3, 4, 5, 5, 6]);
+        mock.assert_request("eth_getBlockByNumber", ("0x3", false)).unwrap();
+        mock.assert_request("eth_getBlockByNumber", ("0x4", false)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn reports_gaps() {
+        let (provider, mock) = Provider::mocked();
+        let heads = stream::iter([head(1), head(4)]);
+        let updates: Vec<_> =
+            BlockGapStream::new(&provider, heads, GapMode::Report).collect().await;
+        assert_eq!(
+            updates,
+            vec![
+                BlockUpdate::Block(head(1)),
+                BlockUpdate::Gap { from: 2.into(), to: 3.into() },
+                BlockUpdate::Block(head(4)),
+            ]
+        );
+
+        // no gap follows the highest block number
+        let heads = stream::iter([head(u64::MAX), head(1)]);
+        let updates: Vec<_> =
+            BlockGapStream::new(&provider, heads, GapMode::Report).collect().await;
+        assert_eq!(updates, vec![BlockUpdate::Block(head(u64::MAX)), BlockUpdate::Block(head(1))]);
+
+        // blocks that could not be fetched are reported
+        let heads = stream::iter([head(1), head(5)]);
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: "header not found".to_string(),
+            data: None,
+        }));
+        mock.push(head(2)).unwrap();
+        let updates: Vec<_> = BlockGapStream::new(&provider, heads, GapMode::Fetch).collect().await;
+        assert_eq!(
+            updates,
+            vec![
+                BlockUpdate::Block(head(1)),
+                BlockUpdate::Block(head(2)),
+                BlockUpdate::Gap { from: 3.into(), to: 4.into() },
+                BlockUpdate::Block(head(5)),
+            ]
+        );
+    }
+}
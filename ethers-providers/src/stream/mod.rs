@@ -1,3 +1,7 @@
+pub mod gaps;
+
+pub mod reorg;
+
 pub mod tx_stream;
 
 pub mod watcher;
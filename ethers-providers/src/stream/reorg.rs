@@ -0,0 +1,270 @@
+use ethers_core::types::{Block, Log, TxHash, H256, U256};
+use futures_core::stream::Stream;
+use pin_project::pin_project;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Orders the held logs by their position in the chain
+type LogKey = (u64, U256, H256);
+
+/// A stream of logs that are only emitted once they are buried under a number of blocks, to
+/// shield consumers from chain reorganizations.
+///
+/// Logs are held until their block has the given number of `confirmations`, as measured by the
+/// stream of new heads. Logs that the node reports as `removed` while they are held are dropped,
+/// so a log that is re-included in a different block after a reorg is only emitted once. If a
+/// log is removed after it was emitted, i.e. by a reorg deeper than the confirmations, the
+/// removal is emitted as well, with [`Log::removed`] set to `true`, so that consumers can retract
+/// it.
+///
+/// Logs are emitted in the order of their block number and log index. Pending logs without a
+/// block number are ignored. Held logs are discarded when the log stream ends, and are not
+/// emitted anymore if the stream of heads ends.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_core::types::Filter;
+/// use ethers_providers::{Middleware, Provider, ReorgSafeLogs, StreamExt, Ws};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("ws://localhost:8545").await?;
+/// let filter = Filter::new().event("Transfer(address,address,uint256)");
+///
+/// let logs = provider.subscribe_logs(&filter).await?;
+/// let heads = provider.subscribe_blocks().await?;
+/// let mut logs = ReorgSafeLogs::new(logs, heads, 12);
+/// while let Some(log) = logs.next().await {
+///     if log.removed == Some(true) {
+///         // the log was reorged out after it was 12 blocks deep
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct ReorgSafeLogs<L, H> {
+    #[pin]
+    logs: L,
+    #[pin]
+    heads: H,
+    heads_done: bool,
+    logs_done: bool,
+    confirmations: u64,
+    /// The highest block whose logs have been emitted
+    confirmed: Option<u64>,
+    held: BTreeMap<LogKey, Log>,
+    ready: VecDeque<Log>,
+}
+
+impl<L, H> ReorgSafeLogs<L, H>
+where
+    L: Stream<Item = Log>,
+    H: Stream<Item = Block<TxHash>>,
+{
+    /// Wraps the stream of `logs`, emitting them once their block has `confirmations`, as
+    /// measured by the stream of new `heads`. A single confirmation emits logs as soon as their
+    /// block is received.
+    pub fn new(logs: L, heads: H, confirmations: u64) -> Self {
+        Self {
+            logs,
+            heads,
+            heads_done: false,
+            logs_done: false,
+            confirmations: confirmations.max(1),
+            confirmed: None,
+            held: BTreeMap::new(),
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Returns the number of logs that are held until they are confirmed
+    pub fn held(&self) -> usize {
+        self.held.len()
+    }
+}
+
+impl<L, H> Stream for ReorgSafeLogs<L, H>
+where
+    L: Stream<Item = Log>,
+    H: Stream<Item = Block<TxHash>>,
+{
+    type Item = Log;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(log) = this.ready.pop_front() {
+                return Poll::Ready(Some(log))
+            }
+            if *this.logs_done {
+                return Poll::Ready(None)
+            }
+
+            // drain the logs before advancing the tip, since the logs of a block usually arrive
+            // around the same time as its head
+            match this.logs.as_mut().poll_next(cx) {
+                Poll::Ready(Some(log)) => {
+                    let Some(block) = log.block_number else { continue };
+                    let block = block.as_u64();
+                    let key = (
+                        block,
+                        log.log_index.unwrap_or_default(),
+                        log.block_hash.unwrap_or_default(),
+                    );
+                    let is_confirmed = this.confirmed.map_or(false, |confirmed| block <= confirmed);
+                    if log.removed == Some(true) {
+                        // only retract logs that were already emitted
+                        if this.held.remove(&key).is_none() && is_confirmed {
+                            this.ready.push_back(log);
+                        }
+                    } else if is_confirmed {
+                        this.ready.push_back(log);
+                    } else {
+                        this.held.insert(key, log);
+                    }
+                    continue
+                }
+                Poll::Ready(None) => {
+                    *this.logs_done = true;
+                    continue
+                }
+                Poll::Pending => {}
+            }
+
+            if *this.heads_done {
+                return Poll::Pending
+            }
+            match this.heads.as_mut().poll_next(cx) {
+                Poll::Ready(Some(head)) => {
+                    let Some(tip) = head.number else { continue };
+                    // a log has `tip - block + 1` confirmations, with at least one required
+                    let Some(confirmed) = tip.as_u64().checked_sub(*this.confirmations - 1) else {
+                        continue
+                    };
+                    if this.confirmed.map_or(true, |prev| confirmed > prev) {
+                        *this.confirmed = Some(confirmed);
+                        let rest = match confirmed.checked_add(1) {
+                            Some(next) => this.held.split_off(&(next, U256::zero(), H256::zero())),
+                            None => BTreeMap::new(),
+                        };
+                        this.ready.extend(mem::replace(this.held, rest).into_values());
+                    }
+                }
+                Poll::Ready(None) => *this.heads_done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{stream, FutureExt, StreamExt};
+    use std::sync::{Arc, Mutex};
+
+    /// A stream yielding the items sent to it, ending on `None`
+    fn channel<T>() -> (Arc<Mutex<VecDeque<Option<T>>>>, impl Stream<Item = T>) {
+        let queue = Arc::new(Mutex::new(VecDeque::new()));
+        let rx = queue.clone();
+        let stream = stream::poll_fn(move |_| match rx.lock().unwrap().pop_front() {
+            Some(item) => Poll::Ready(item),
+            None => Poll::Pending,
+        });
+        (queue, stream)
+    }
+
+    fn log(block: u64, index: u64, hash: u8, removed: bool) -> Log {
+        Log {
+            block_number: Some(block.into()),
+            block_hash: Some(H256::repeat_byte(hash)),
+            log_index: Some(index.into()),
+            removed: Some(removed),
+            ..Default::default()
+        }
+    }
+
+    fn head(number: u64) -> Block<TxHash> {
+        Block { number: Some(number.into()), ..Default::default() }
+    }
+
+    #[test]
+    fn holds_logs_until_confirmed() {
+        let (logs_tx, logs) = channel();
+        let (heads_tx, heads) = channel();
+        let mut stream = Box::pin(ReorgSafeLogs::new(logs, heads, 3));
+
+        logs_tx.lock().unwrap().push_back(Some(log(10, 1, 1, false)));
+        logs_tx.lock().unwrap().push_back(Some(log(10, 0, 1, false)));
+        logs_tx.lock().unwrap().push_back(Some(log(11, 0, 2, false)));
+        heads_tx.lock().unwrap().push_back(Some(head(11)));
+        assert!(stream.next().now_or_never().is_none());
+        assert_eq!(stream.held(), 3);
+
+        // block 10 has 3 confirmations at block 12
+        heads_tx.lock().unwrap().push_back(Some(head(12)));
+        assert_eq!(stream.next().now_or_never(), Some(Some(log(10, 0, 1, false))));
+        assert_eq!(stream.next().now_or_never(), Some(Some(log(10, 1, 1, false))));
+        assert!(stream.next().now_or_never().is_none());
+        assert_eq!(stream.held(), 1);
+
+        // logs of confirmed blocks are emitted right away
+        logs_tx.lock().unwrap().push_back(Some(log(9, 0, 3, false)));
+        assert_eq!(stream.next().now_or_never(), Some(Some(log(9, 0, 3, false))));
+    }
+
+    #[test]
+    fn drops_logs_removed_before_confirmation() {
+        let (logs_tx, logs) = channel();
+        let (heads_tx, heads) = channel();
+        let mut stream = Box::pin(ReorgSafeLogs::new(logs, heads, 2));
+
+        logs_tx.lock().unwrap().push_back(Some(log(5, 0, 1, false)));
+        // the reorg moves the log to a block with another hash
+        logs_tx.lock().unwrap().push_back(Some(log(5, 0, 1, true)));
+        logs_tx.lock().unwrap().push_back(Some(log(5, 0, 2, false)));
+        heads_tx.lock().unwrap().push_back(Some(head(6)));
+
+        assert_eq!(stream.next().now_or_never(), Some(Some(log(5, 0, 2, false))));
+        assert!(stream.next().now_or_never().is_none());
+    }
+
+    #[test]
+    fn retracts_logs_removed_after_confirmation() {
+        let (logs_tx, logs) = channel();
+        let (heads_tx, heads) = channel();
+        let mut stream = Box::pin(ReorgSafeLogs::new(logs, heads, 1));
+
+        logs_tx.lock().unwrap().push_back(Some(log(5, 0, 1, false)));
+        heads_tx.lock().unwrap().push_back(Some(head(5)));
+        assert_eq!(stream.next().now_or_never(), Some(Some(log(5, 0, 1, false))));
+
+        logs_tx.lock().unwrap().push_back(Some(log(5, 0, 1, true)));
+        assert_eq!(stream.next().now_or_never(), Some(Some(log(5, 0, 1, true))));
+
+        // removals of logs that were never seen are ignored
+        logs_tx.lock().unwrap().push_back(Some(log(7, 0, 1, true)));
+        assert!(stream.next().now_or_never().is_none());
+
+        logs_tx.lock().unwrap().push_back(None);
+        assert_eq!(stream.next().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn confirms_logs_at_max_block() {
+        let (logs_tx, logs) = channel();
+        let (heads_tx, heads) = channel();
+        let mut stream = Box::pin(ReorgSafeLogs::new(logs, heads, 1));
+
+        logs_tx.lock().unwrap().push_back(Some(log(u64::MAX, 0, 1, false)));
+        heads_tx.lock().unwrap().push_back(Some(head(u64::MAX)));
+        assert_eq!(stream.next().now_or_never(), Some(Some(log(u64::MAX, 0, 1, false))));
+        assert_eq!(stream.held(), 0);
+    }
+}
@@ -7,11 +7,12 @@ use std::{
 use futures_core::{stream::Stream, Future};
 use futures_util::{
     self,
-    stream::{FuturesUnordered, StreamExt},
+    stream::{FuturesOrdered, FuturesUnordered, StreamExt},
     FutureExt,
 };
 
 use ethers_core::types::{Transaction, TxHash};
+use serde::Deserialize;
 
 use crate::{
     FilterWatcher, JsonRpcClient, Middleware, Provider, ProviderError, PubsubClient,
@@ -45,11 +46,46 @@ pub(crate) type TransactionFut<'a> = Pin<Box<dyn Future<Output = TransactionResu
 
 pub(crate) type TransactionResult = Result<Transaction, GetTransactionError>;
 
+/// The set of currently running `get_transaction` futures of a `TransactionStream`
+pub(crate) enum PendingTransactions<'a> {
+    /// Yields transactions as soon as they're fetched
+    Unordered(FuturesUnordered<TransactionFut<'a>>),
+    /// Yields transactions in the order their hashes were received
+    Ordered(FuturesOrdered<TransactionFut<'a>>),
+}
+
+impl<'a> PendingTransactions<'a> {
+    fn len(&self) -> usize {
+        match self {
+            PendingTransactions::Unordered(pending) => pending.len(),
+            PendingTransactions::Ordered(pending) => pending.len(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&mut self, fut: TransactionFut<'a>) {
+        match self {
+            PendingTransactions::Unordered(pending) => pending.push(fut),
+            PendingTransactions::Ordered(pending) => pending.push_back(fut),
+        }
+    }
+
+    fn poll_next_unpin(&mut self, cx: &mut Context<'_>) -> Poll<Option<TransactionResult>> {
+        match self {
+            PendingTransactions::Unordered(pending) => pending.poll_next_unpin(cx),
+            PendingTransactions::Ordered(pending) => pending.poll_next_unpin(cx),
+        }
+    }
+}
+
 /// Drains a stream of transaction hashes and yields entire `Transaction`.
 #[must_use = "streams do nothing unless polled"]
 pub struct TransactionStream<'a, P, St> {
     /// Currently running futures pending completion.
-    pub(crate) pending: FuturesUnordered<TransactionFut<'a>>,
+    pub(crate) pending: PendingTransactions<'a>,
     /// Temporary buffered transaction that get started as soon as another future finishes.
     pub(crate) buffered: VecDeque<TxHash>,
     /// The provider that gets the transaction
@@ -60,21 +96,59 @@ pub struct TransactionStream<'a, P, St> {
     stream_done: bool,
     /// max allowed futures to execute at once.
     pub(crate) max_concurrent: usize,
+    /// Whether hashes for which no transaction was found are silently dropped
+    pub(crate) skip_missing: bool,
 }
 
 impl<'a, P: JsonRpcClient, St> TransactionStream<'a, P, St> {
-    /// Create a new `TransactionStream` instance
+    /// Create a new `TransactionStream` instance that yields transactions in the order they're
+    /// fetched
     pub fn new(provider: &'a Provider<P>, stream: St, max_concurrent: usize) -> Self {
+        Self::with_pending(
+            PendingTransactions::Unordered(Default::default()),
+            provider,
+            stream,
+            max_concurrent,
+        )
+    }
+
+    /// Create a new `TransactionStream` instance that yields transactions in the order their
+    /// hashes are received from the `stream`, while still fetching up to `max_concurrent`
+    /// transactions at once
+    pub fn new_ordered(provider: &'a Provider<P>, stream: St, max_concurrent: usize) -> Self {
+        Self::with_pending(
+            PendingTransactions::Ordered(Default::default()),
+            provider,
+            stream,
+            max_concurrent,
+        )
+    }
+
+    fn with_pending(
+        pending: PendingTransactions<'a>,
+        provider: &'a Provider<P>,
+        stream: St,
+        max_concurrent: usize,
+    ) -> Self {
         Self {
-            pending: Default::default(),
+            pending,
             buffered: Default::default(),
             provider,
             stream,
             stream_done: false,
             max_concurrent,
+            skip_missing: false,
         }
     }
 
+    /// Skip transaction hashes for which the provider returned no transaction, e.g. because it
+    /// was already mined or dropped from the mempool, instead of yielding
+    /// [`GetTransactionError::NotFound`]
+    pub fn skip_missing(mut self) -> Self {
+        self.skip_missing = true;
+        self
+    }
+
     /// Push a future into the set
     pub(crate) fn push_tx(&mut self, tx: TxHash) {
         let fut = self.provider.get_transaction(tx).then(move |res| match res {
@@ -96,45 +170,52 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
 
-        // drain buffered transactions first
-        while this.pending.len() < this.max_concurrent {
-            if let Some(tx) = this.buffered.pop_front() {
-                this.push_tx(tx);
-            } else {
-                break
+        loop {
+            // drain buffered transactions first
+            while this.pending.len() < this.max_concurrent {
+                if let Some(tx) = this.buffered.pop_front() {
+                    this.push_tx(tx);
+                } else {
+                    break
+                }
             }
-        }
 
-        if !this.stream_done {
-            loop {
-                match Stream::poll_next(Pin::new(&mut this.stream), cx) {
-                    Poll::Ready(Some(tx)) => {
-                        if this.pending.len() < this.max_concurrent {
-                            this.push_tx(tx);
-                        } else {
-                            this.buffered.push_back(tx);
+            if !this.stream_done {
+                loop {
+                    match Stream::poll_next(Pin::new(&mut this.stream), cx) {
+                        Poll::Ready(Some(tx)) => {
+                            if this.pending.len() < this.max_concurrent {
+                                this.push_tx(tx);
+                            } else {
+                                this.buffered.push_back(tx);
+                            }
                         }
+                        Poll::Ready(None) => {
+                            this.stream_done = true;
+                            break
+                        }
+                        _ => break,
                     }
-                    Poll::Ready(None) => {
-                        this.stream_done = true;
-                        break
-                    }
-                    _ => break,
                 }
             }
-        }
 
-        // poll running futures
-        if let tx @ Poll::Ready(Some(_)) = this.pending.poll_next_unpin(cx) {
-            return tx
-        }
+            // poll running futures
+            match this.pending.poll_next_unpin(cx) {
+                Poll::Ready(Some(Err(GetTransactionError::NotFound(_)))) if this.skip_missing => {
+                    // a slot was freed up, refill it before polling again
+                    continue
+                }
+                tx @ Poll::Ready(Some(_)) => return tx,
+                _ => {}
+            }
 
-        if this.stream_done && this.pending.is_empty() {
-            // all done
-            return Poll::Ready(None)
-        }
+            if this.stream_done && this.pending.is_empty() && this.buffered.is_empty() {
+                // all done
+                return Poll::Ready(None)
+            }
 
-        Poll::Pending
+            return Poll::Pending
+        }
     }
 }
 
@@ -151,6 +232,15 @@ where
     pub fn transactions_unordered(self, n: usize) -> TransactionStream<'a, P, Self> {
         TransactionStream::new(self.provider, self, n)
     }
+
+    /// Returns a stream that yields the `Transaction`s for the transaction hashes this stream
+    /// yields, in the order the hashes were received.
+    ///
+    /// Up to n transactions are fetched concurrently. Hashes for which no transaction could be
+    /// found, e.g. because it was already mined or dropped, are skipped.
+    pub fn fetch_transactions(self, n: usize) -> TransactionStream<'a, P, Self> {
+        TransactionStream::new_ordered(self.provider, self, n).skip_missing()
+    }
 }
 
 impl<'a, P> SubscriptionStream<'a, P, TxHash>
@@ -166,6 +256,93 @@ where
     pub fn transactions_unordered(self, n: usize) -> TransactionStream<'a, P, Self> {
         TransactionStream::new(self.provider, self, n)
     }
+
+    /// Returns a stream that yields the `Transaction`s for the transaction hashes this stream
+    /// yields, in the order the hashes were received.
+    ///
+    /// Up to n transactions are fetched concurrently. Hashes for which no transaction could be
+    /// found, e.g. because it was already mined or dropped, are skipped.
+    pub fn fetch_transactions(self, n: usize) -> TransactionStream<'a, P, Self> {
+        TransactionStream::new_ordered(self.provider, self, n).skip_missing()
+    }
+}
+
+/// A notification of a `newPendingTransactions` subscription, which is the full transaction if the
+/// node supports it and its hash otherwise
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum PendingTransactionNotification {
+    Hash(TxHash),
+    Transaction(Box<Transaction>),
+}
+
+/// A stream of the full pending transactions of the node, see
+/// [`Provider::subscribe_full_pending_txs_with_fallback`].
+///
+/// Transactions the node sent in full are yielded right away, while the transactions the node
+/// only sent the hash of are fetched, with up to `max_concurrent` requests at once. Transactions
+/// that could not be fetched, e.g. because they were already dropped from the mempool, are
+/// skipped.
+#[must_use = "streams do nothing unless polled"]
+pub struct FullPendingTransactionStream<'a, P: PubsubClient> {
+    subscription: SubscriptionStream<'a, P, PendingTransactionNotification>,
+    subscription_done: bool,
+    /// Fetches the transactions whose hashes were received
+    fetch: TransactionStream<'a, P, futures_util::stream::Empty<TxHash>>,
+}
+
+impl<'a, P: PubsubClient> FullPendingTransactionStream<'a, P> {
+    pub(crate) fn new(
+        subscription: SubscriptionStream<'a, P, PendingTransactionNotification>,
+        max_concurrent: usize,
+    ) -> Self {
+        let fetch = TransactionStream::new(
+            subscription.provider,
+            futures_util::stream::empty(),
+            max_concurrent,
+        )
+        .skip_missing();
+        Self { subscription, subscription_done: false, fetch }
+    }
+
+    /// Returns the id of the underlying subscription
+    pub fn id(&self) -> ethers_core::types::U256 {
+        self.subscription.id
+    }
+}
+
+impl<'a, P: PubsubClient> Stream for FullPendingTransactionStream<'a, P> {
+    type Item = Transaction;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if !this.subscription_done {
+                match this.subscription.poll_next_unpin(cx) {
+                    Poll::Ready(Some(PendingTransactionNotification::Transaction(tx))) => {
+                        return Poll::Ready(Some(*tx))
+                    }
+                    Poll::Ready(Some(PendingTransactionNotification::Hash(hash))) => {
+                        this.fetch.buffered.push_back(hash);
+                        continue
+                    }
+                    Poll::Ready(None) => this.subscription_done = true,
+                    Poll::Pending => {}
+                }
+            }
+
+            return match this.fetch.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(tx))) => Poll::Ready(Some(tx)),
+                Poll::Ready(Some(Err(err))) => {
+                    tracing::debug!(%err, "failed to fetch pending transaction");
+                    continue
+                }
+                // no transactions are being fetched
+                Poll::Ready(None) if this.subscription_done => Poll::Ready(None),
+                Poll::Ready(None) | Poll::Pending => Poll::Pending,
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -239,6 +416,123 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn fetch_transactions_skips_missing_in_order() {
+        use crate::MockProvider;
+
+        let (provider, mock) = Provider::mocked();
+        let hashes = [TxHash::repeat_byte(1), TxHash::repeat_byte(2), TxHash::repeat_byte(3)];
+
+        // responses are popped from the back of the queue
+        mock.push(Transaction { hash: hashes[2], ..Default::default() }).unwrap();
+        mock.push::<Option<Transaction>, _>(None).unwrap();
+        mock.push(Transaction { hash: hashes[0], ..Default::default() }).unwrap();
+
+        let stream = TransactionStream::<MockProvider, _>::new_ordered(
+            &provider,
+            futures_util::stream::iter(hashes),
+            1,
+        )
+        .skip_missing();
+        let res =
+            stream.collect::<Vec<_>>().await.into_iter().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(res.into_iter().map(|tx| tx.hash).collect::<Vec<_>>(), [hashes[0], hashes[2]]);
+        for hash in hashes {
+            mock.assert_request("eth_getTransactionByHash", [hash]).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_transaction_errors_by_default() {
+        let (provider, mock) = Provider::mocked();
+        let hash = TxHash::repeat_byte(1);
+        mock.push::<Option<Transaction>, _>(None).unwrap();
+
+        let mut stream = TransactionStream::new(&provider, futures_util::stream::iter([hash]), 1);
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(GetTransactionError::NotFound(h))) if h == hash
+        ));
+        assert!(stream.next().await.is_none());
+    }
+
+    /// A pubsub client answering requests with a [`MockProvider`] and streaming the given
+    /// notifications for the first subscription
+    #[derive(Debug)]
+    struct MockPubsub {
+        mock: crate::MockProvider,
+        notifications: std::sync::Mutex<Vec<Box<serde_json::value::RawValue>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl JsonRpcClient for MockPubsub {
+        type Error = crate::MockError;
+
+        async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+        where
+            T: std::fmt::Debug + serde::Serialize + Send + Sync,
+            R: serde::de::DeserializeOwned + Send,
+        {
+            self.mock.request(method, params).await
+        }
+    }
+
+    impl PubsubClient for MockPubsub {
+        type NotificationStream =
+            futures_util::stream::Iter<std::vec::IntoIter<Box<serde_json::value::RawValue>>>;
+
+        fn subscribe<T: Into<ethers_core::types::U256>>(
+            &self,
+            _: T,
+        ) -> Result<Self::NotificationStream, Self::Error> {
+            Ok(futures_util::stream::iter(std::mem::take(&mut *self.notifications.lock().unwrap())))
+        }
+
+        fn unsubscribe<T: Into<ethers_core::types::U256>>(&self, _: T) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn full_pending_transactions_fall_back_to_hashes() {
+        use crate::{JsonRpcError, MockResponse};
+        use serde_json::value::to_raw_value;
+
+        let full = Transaction { hash: TxHash::repeat_byte(1), ..Default::default() };
+        let fetched = Transaction { hash: TxHash::repeat_byte(2), ..Default::default() };
+        let missing = TxHash::repeat_byte(3);
+        let mock = crate::MockProvider::new();
+        let provider = Provider::new(MockPubsub {
+            mock: mock.clone(),
+            // nodes may send hashes even if full transactions were requested
+            notifications: std::sync::Mutex::new(vec![
+                to_raw_value(&full).unwrap(),
+                to_raw_value(&fetched.hash).unwrap(),
+                to_raw_value(&missing).unwrap(),
+            ]),
+        });
+
+        // responses are popped from the back of the queue
+        mock.push::<Option<Transaction>, _>(None).unwrap();
+        mock.push(fetched.clone()).unwrap();
+        mock.push(ethers_core::types::U256::from(1)).unwrap();
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32602,
+            message: "invalid params".to_string(),
+            data: None,
+        }));
+
+        let stream = provider.subscribe_full_pending_txs_with_fallback(1).await.unwrap();
+        assert_eq!(stream.id(), 1.into());
+        assert_eq!(stream.collect::<Vec<_>>().await, vec![full, fetched.clone()]);
+
+        mock.assert_request("eth_subscribe", ("newPendingTransactions", true)).unwrap();
+        mock.assert_request("eth_subscribe", ["newPendingTransactions"]).unwrap();
+        mock.assert_request("eth_getTransactionByHash", [fetched.hash]).unwrap();
+        mock.assert_request("eth_getTransactionByHash", [missing]).unwrap();
+    }
+
     #[tokio::test]
     async fn can_stream_transactions() {
         let anvil = Anvil::new().block_time(2u64).spawn();
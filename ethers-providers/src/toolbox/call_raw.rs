@@ -2,7 +2,7 @@
 
 use crate::{utils::PinBoxFut, JsonRpcClient, Provider, ProviderError};
 use ethers_core::{
-    types::{transaction::eip2718::TypedTransaction, BlockId, BlockNumber, Bytes},
+    types::{transaction::eip2718::TypedTransaction, BlockId, BlockNumber, BlockOverrides, Bytes},
     utils,
 };
 use pin_project::pin_project;
@@ -33,6 +33,15 @@ pub trait RawCall<'a> {
     }
 }
 
+/// Provides a method for overriding the block context of the `eth_call` rpc method, in addition
+/// to the [`RawCall`] methods
+pub trait RawCallBlockOverrides<'a>: RawCall<'a> {
+    /// Sets the [block overrides](https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-eth#eth-call),
+    /// e.g. to execute the call as if the block had a later timestamp.
+    /// Note that not all client implementations will support this as a parameter.
+    fn block_overrides(self, overrides: &'a BlockOverrides) -> Self;
+}
+
 /// A builder which implements [`RawCall`] methods for overriding `eth_call` parameters.
 ///
 /// `CallBuilder` also implements [`std::future::Future`], so `.await`ing a `CallBuilder` will
@@ -97,6 +106,14 @@ impl<'a, P> RawCall<'a> for CallBuilder<'a, P> {
     }
 }
 
+impl<'a, P> RawCallBlockOverrides<'a> for CallBuilder<'a, P> {
+    /// Sets the [block overrides](https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-eth#eth-call).
+    /// Note that not all client implementations will support this as a parameter.
+    fn block_overrides(self, overrides: &'a BlockOverrides) -> Self {
+        self.map_input(|call| call.input.block_overrides = Some(overrides))
+    }
+}
+
 impl<'a, P: JsonRpcClient> Future for CallBuilder<'a, P> {
     type Output = Result<Bytes, ProviderError>;
 
@@ -142,11 +159,12 @@ struct CallInput<'a> {
     tx: &'a TypedTransaction,
     block: Option<BlockId>,
     state: Option<&'a spoof::State>,
+    block_overrides: Option<&'a BlockOverrides>,
 }
 
 impl<'a> CallInput<'a> {
     fn new(tx: &'a TypedTransaction) -> Self {
-        Self { tx, block: None, state: None }
+        Self { tx, block: None, state: None, block_overrides: None }
     }
 }
 
@@ -155,7 +173,13 @@ impl<'a> Serialize for CallInput<'a> {
     where
         S: serde::ser::Serializer,
     {
-        let len = 2 + self.state.is_some() as usize;
+        // the params are positional, so the states are set to an empty override set if only the
+        // block is overridden
+        let len = match (self.state, self.block_overrides) {
+            (_, Some(_)) => 4,
+            (Some(_), None) => 3,
+            (None, None) => 2,
+        };
 
         let mut tup = serializer.serialize_tuple(len)?;
         tup.serialize_element(self.tx)?;
@@ -163,8 +187,13 @@ impl<'a> Serialize for CallInput<'a> {
         let block = self.block.unwrap_or_else(|| BlockNumber::Latest.into());
         tup.serialize_element(&block)?;
 
-        if let Some(state) = self.state {
-            tup.serialize_element(state)?;
+        match (self.state, self.block_overrides) {
+            (Some(state), _) => tup.serialize_element(state)?,
+            (None, Some(_)) => tup.serialize_element(&spoof::State::default())?,
+            (None, None) => {}
+        }
+        if let Some(overrides) = self.block_overrides {
+            tup.serialize_element(overrides)?;
         }
         tup.end()
     }
@@ -213,6 +242,17 @@ where
     }
 }
 
+impl<'a, T, F> RawCallBlockOverrides<'a> for Map<T, F>
+where
+    T: RawCallBlockOverrides<'a>,
+{
+    /// Sets the [block overrides](https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-eth#eth-call).
+    /// Note that not all client implementations will support this as a parameter.
+    fn block_overrides(self, overrides: &'a BlockOverrides) -> Self {
+        Self { inner: self.inner.block_overrides(overrides), f: self.f }
+    }
+}
+
 impl<T, F, Y> Future for Map<T, F>
 where
     T: Future,
@@ -244,10 +284,16 @@ mod tests {
         TypedTransaction,
         Option<BlockId>,
         #[serde(default)] Option<spoof::State>,
+        #[serde(default)] Option<BlockOverrides>,
     );
     impl<'a> From<&'a CallInputOwned> for CallInput<'a> {
         fn from(src: &'a CallInputOwned) -> Self {
-            Self { tx: &src.0, block: src.1, state: src.2.as_ref() }
+            Self {
+                tx: &src.0,
+                block: src.1,
+                state: src.2.as_ref(),
+                block_overrides: src.3.as_ref(),
+            }
         }
     }
 
@@ -259,7 +305,12 @@ mod tests {
         let de = CallInput::from(&de);
 
         assert_eq!(input.tx, de.tx);
-        assert_eq!(input.state, de.state);
+        assert_eq!(input.block_overrides, de.block_overrides);
+        if input.block_overrides.is_none() {
+            assert_eq!(input.state, de.state);
+        } else {
+            assert_eq!(input.state.cloned().unwrap_or_default(), de.state.cloned().unwrap());
+        }
 
         let block = input.block.or_else(|| Some(BlockNumber::Latest.into()));
         assert_eq!(block, de.block);
@@ -296,6 +347,70 @@ mod tests {
         state.account(adr1);
         let call = provider.call_raw(&tx).state(&state);
         test_encode(call);
+
+        let overrides = BlockOverrides { time: Some(1_700_000_000.into()), ..Default::default() };
+        let call = provider.call_raw(&tx).state(&state).block_overrides(&overrides);
+        test_encode(call);
+        let call = provider.call_raw(&tx).block_overrides(&overrides);
+        test_encode(call);
+    }
+
+    #[test]
+    fn test_serialize_block_overrides() {
+        let tx = TypedTransaction::default();
+        let (provider, _) = Provider::mocked();
+
+        // unset fields are omitted, and the state is empty if only the block is overridden
+        let overrides = BlockOverrides {
+            number: Some(100.into()),
+            coinbase: Some(Address::zero()),
+            ..Default::default()
+        };
+        let input = provider.call_raw(&tx).block_overrides(&overrides).unwrap().input;
+        assert_eq!(
+            utils::serialize(&input),
+            serde_json::json!([
+                tx,
+                "latest",
+                {},
+                { "number": "0x64", "coinbase": format!("{:?}", Address::zero()) }
+            ])
+        );
+
+        // without overrides the params do not change
+        let input = provider.call_raw(&tx).unwrap().input;
+        assert_eq!(utils::serialize(&input), serde_json::json!([tx, "latest"]));
+    }
+
+    #[test]
+    fn test_serialize_overrides() {
+        let adr: Address = "0x6fC21092DA55B392b045eD78F4732bff3C580e2c".parse().unwrap();
+        let key = H256::from_low_u64_be(1);
+        let val = H256::from_low_u64_be(2);
+
+        // unset fields are omitted
+        let state = spoof::balance(adr, 100.into());
+        assert_eq!(
+            serde_json::to_value(&state).unwrap(),
+            serde_json::json!({ format!("{adr:?}"): { "balance": "0x64" } })
+        );
+
+        let mut state = spoof::state();
+        state.account(adr).store(key, val);
+        assert_eq!(
+            serde_json::to_value(&state).unwrap(),
+            serde_json::json!({ format!("{adr:?}"): { "stateDiff": { format!("{key:?}"): val } } })
+        );
+
+        // replacing the storage keeps previously stored slots
+        let mut state = spoof::state();
+        state.account(adr).store(key, val).replace_storage(val, key);
+        assert_eq!(
+            serde_json::to_value(&state).unwrap(),
+            serde_json::json!({
+                format!("{adr:?}"): { "state": { format!("{key:?}"): val, format!("{val:?}"): key } }
+            })
+        );
     }
 
     #[tokio::test]
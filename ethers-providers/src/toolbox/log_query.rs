@@ -11,6 +11,10 @@ use thiserror::Error;
 /// A log query provides streaming access to historical logs via a paginated
 /// request. For streaming access to future logs, use [`Middleware::watch`] or
 /// [`Middleware::subscribe_logs`]
+///
+/// The block range of the filter is split into pages of `page_size` blocks, which are queried
+/// in order. If the node rejects a page because it contains too many logs, or the block range is
+/// too large, the page size is halved and the page is queried again.
 pub struct LogQuery<'a, P> {
     provider: &'a Provider<P>,
     filter: Filter,
@@ -25,6 +29,7 @@ enum LogQueryState<'a> {
     Initial,
     LoadLastBlock(PinBoxFut<'a, U64>),
     LoadLogs(PinBoxFut<'a, Vec<Log>>),
+    LoadPage { to_block: U64, fut: PinBoxFut<'a, Vec<Log>> },
     Consume,
 }
 
@@ -47,9 +52,43 @@ where
 
     /// set page size for pagination
     pub fn with_page_size(mut self, page_size: u64) -> Self {
-        self.page_size = page_size;
+        self.page_size = page_size.max(1);
         self
     }
+
+    /// Returns the state loading the next page, or `None` if all pages were loaded
+    fn load_next_page(&self) -> Option<LogQueryState<'a>> {
+        // can safely assume these will always be set when paginating
+        let from_block = self.from_block.unwrap();
+        let last_block = self.last_block.unwrap();
+        if from_block > last_block {
+            return None
+        }
+        let to_block = (from_block + (self.page_size - 1)).min(last_block);
+
+        let filter = self.filter.clone().from_block(from_block).to_block(to_block);
+        let provider = self.provider;
+        #[allow(clippy::redundant_async_block)]
+        let fut = Box::pin(async move { provider.get_logs(&filter).await });
+        Some(LogQueryState::LoadPage { to_block, fut })
+    }
+}
+
+/// Whether the node rejected the `eth_getLogs` request because the block range was too large or
+/// it matched too many logs
+fn is_too_many_results(err: &ProviderError) -> bool {
+    const MESSAGES: [&str; 5] = [
+        "query returned more than",
+        "too many results",
+        "block range",
+        "range is too large",
+        "response size exceeded",
+    ];
+    let err = match crate::RpcError::as_error_response(err) {
+        Some(err) => err.message.to_lowercase(),
+        None => return false,
+    };
+    MESSAGES.iter().any(|msg| err.contains(msg))
 }
 
 macro_rules! rewake_with_new_state {
@@ -96,20 +135,20 @@ where
             LogQueryState::LoadLastBlock(fut) => {
                 match futures_util::ready!(fut.as_mut().poll(ctx)) {
                     Ok(last_block) => {
+                        // don't query past the end of the filter's range
+                        let last_block = match self.filter.get_to_block() {
+                            Some(to_block) => to_block.min(last_block),
+                            None => last_block,
+                        };
                         self.last_block = Some(last_block);
 
-                        // this is okay because we will only enter this state when the filter is
-                        // paginatable i.e. from block is set
-                        let from_block = self.filter.get_from_block().unwrap();
-                        let to_block = from_block + self.page_size;
-                        self.from_block = Some(to_block + 1);
-
-                        let filter = self.filter.clone().from_block(from_block).to_block(to_block);
-                        let provider = self.provider;
                         // load first page of logs
-                        #[allow(clippy::redundant_async_block)]
-                        let fut = Box::pin(async move { provider.get_logs(&filter).await });
-                        rewake_with_new_state!(ctx, self, LogQueryState::LoadLogs(fut));
+                        match self.load_next_page() {
+                            Some(state) => {
+                                rewake_with_new_state!(ctx, self, state);
+                            }
+                            None => Poll::Ready(None),
+                        }
                     }
                     Err(err) => Poll::Ready(Some(Err(LogQueryError::LoadLastBlockError(err)))),
                 }
@@ -121,6 +160,23 @@ where
                 }
                 Err(err) => Poll::Ready(Some(Err(LogQueryError::LoadLogsError(err)))),
             },
+            LogQueryState::LoadPage { to_block, fut } => {
+                let to_block = *to_block;
+                match futures_util::ready!(fut.as_mut().poll(ctx)) {
+                    Ok(logs) => {
+                        self.current_logs = VecDeque::from(logs);
+                        self.from_block = Some(to_block + 1);
+                        rewake_with_new_state!(ctx, self, LogQueryState::Consume);
+                    }
+                    Err(err) if self.page_size > 1 && is_too_many_results(&err) => {
+                        // retry the same range with smaller pages
+                        self.page_size /= 2;
+                        let state = self.load_next_page().expect("page was not loaded yet");
+                        rewake_with_new_state!(ctx, self, state);
+                    }
+                    Err(err) => Poll::Ready(Some(Err(LogQueryError::LoadLogsError(err)))),
+                }
+            }
             LogQueryState::Consume => {
                 let log = self.current_logs.pop_front();
                 if log.is_none() {
@@ -128,24 +184,14 @@ where
                     if !self.filter.is_paginatable() {
                         Poll::Ready(None)
                     } else {
-                        // load new logs if there are still more pages to go through
-                        // can safely assume this will always be set in this state
-                        let from_block = self.from_block.unwrap();
-                        let to_block = from_block + self.page_size;
-
-                        // no more pages to load, and everything is consumed
-                        // can safely assume this will always be set in this state
-                        if from_block > self.last_block.unwrap() {
-                            return Poll::Ready(None)
+                        // load new logs if there are still more pages to go through, otherwise
+                        // everything is consumed
+                        match self.load_next_page() {
+                            Some(state) => {
+                                rewake_with_new_state!(ctx, self, state);
+                            }
+                            None => Poll::Ready(None),
                         }
-                        // load next page
-                        self.from_block = Some(to_block + 1);
-
-                        let filter = self.filter.clone().from_block(from_block).to_block(to_block);
-                        let provider = self.provider;
-                        #[allow(clippy::redundant_async_block)]
-                        let fut = Box::pin(async move { provider.get_logs(&filter).await });
-                        rewake_with_new_state!(ctx, self, LogQueryState::LoadLogs(fut));
                     }
                 } else {
                     Poll::Ready(log.map(Ok))
@@ -154,3 +200,40 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{JsonRpcError, MockResponse};
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn halves_page_size_on_too_many_results() {
+        let (provider, mock) = Provider::mocked();
+        let filter = Filter::new().from_block(0).to_block(9);
+        let log = |block: u64| Log { block_number: Some(block.into()), ..Default::default() };
+
+        // responses are popped from the back
+        mock.push::<Vec<Log>, _>(vec![log(7)]).unwrap();
+        mock.push::<Vec<Log>, _>(vec![log(1)]).unwrap();
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: -32005,
+            message: "query returned more than 10000 results".to_string(),
+            data: None,
+        }));
+        mock.push(U64::from(100)).unwrap();
+
+        let logs: Vec<_> = LogQuery::new(&provider, &filter)
+            .with_page_size(10)
+            .map(|log| log.unwrap().block_number.unwrap())
+            .collect()
+            .await;
+        assert_eq!(logs, vec![U64::from(1), U64::from(7)]);
+
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        for (from, to) in [(0, 9), (0, 4), (5, 9)] {
+            let page = filter.clone().from_block(from).to_block(to);
+            mock.assert_request("eth_getLogs", [page]).unwrap();
+        }
+    }
+}
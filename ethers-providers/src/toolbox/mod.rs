@@ -1,5 +1,7 @@
 mod pending_transaction;
-pub use pending_transaction::PendingTransaction;
+pub use pending_transaction::{
+    PendingTransaction, ReplaceablePendingTransaction, TransactionOutcome,
+};
 
 mod pending_escalator;
 pub use pending_escalator::EscalatingPending;
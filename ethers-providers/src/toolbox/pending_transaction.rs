@@ -2,7 +2,7 @@ use crate::{
     utils::{interval, PinBoxFut},
     JsonRpcClient, Middleware, Provider, ProviderError,
 };
-use ethers_core::types::{Transaction, TransactionReceipt, TxHash, U64};
+use ethers_core::types::{Address, Transaction, TransactionReceipt, TxHash, U256, U64};
 use futures_core::stream::Stream;
 use futures_timer::Delay;
 use futures_util::stream::StreamExt;
@@ -34,6 +34,10 @@ use std::{
 ///     .log_msg("Pending transfer hash") // print pending tx hash with message
 ///     .await?;                          // Result<Option<TransactionReceipt>, _>
 /// ```
+///
+/// If the transaction may get replaced, e.g. because it was sped up or cancelled from a wallet,
+/// use [`PendingTransaction::watch_replacement`] to also resolve if another transaction was mined
+/// in its stead.
 #[pin_project]
 pub struct PendingTransaction<'a, P> {
     tx_hash: TxHash,
@@ -42,10 +46,18 @@ pub struct PendingTransaction<'a, P> {
     state: PendingTxState<'a>,
     interval: Box<dyn Stream<Item = ()> + Send + Unpin>,
     retries_remaining: usize,
+    /// The sender and nonce of the transaction, if replacements should be detected
+    replacement: Option<(Address, U256)>,
+    /// The latest block at which the nonce was not yet used by any mined transaction
+    replacement_scan_from: Option<U64>,
 }
 
 const DEFAULT_RETRIES: usize = 3;
 
+/// The maximum number of blocks that are searched for a replacement transaction, if it's unknown
+/// when the transaction was replaced
+const MAX_REPLACEMENT_SCAN_BLOCKS: u64 = 128;
+
 impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
     /// Creates a new pending transaction poller from a hash and a provider
     pub fn new(tx_hash: TxHash, provider: &'a Provider<P>) -> Self {
@@ -58,6 +70,8 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
             state: PendingTxState::InitialDelay(delay),
             interval: Box::new(interval(provider.get_interval())),
             retries_remaining: DEFAULT_RETRIES,
+            replacement: None,
+            replacement_scan_from: None,
         }
     }
 
@@ -102,6 +116,26 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
         self.retries_remaining = retries;
         self
     }
+
+    /// Detects if the transaction gets replaced by another transaction of the same sender with
+    /// the same nonce.
+    ///
+    /// Once the transaction is no longer known to the node, the new blocks are searched for a
+    /// mined transaction of `from` with `nonce`. The returned future then resolves to
+    /// [`TransactionOutcome::Replaced`] with the receipt of the replacement, after it has the
+    /// configured number of confirmations.
+    ///
+    /// While the nonce has not been used by any mined transaction, the pending transaction is not
+    /// considered dropped from the mempool.
+    #[must_use]
+    pub fn watch_replacement(
+        mut self,
+        from: Address,
+        nonce: U256,
+    ) -> ReplaceablePendingTransaction<'a, P> {
+        self.replacement = Some((from, nonce));
+        ReplaceablePendingTransaction { inner: self }
+    }
 }
 
 impl<'a, P> PendingTransaction<'a, P> {
@@ -178,6 +212,18 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
                 );
 
                 let tx_opt = tx_res.unwrap();
+                // If the tx is no longer in the mempool, check whether it was replaced
+                if let (None, Some((from, nonce))) = (&tx_opt, *this.replacement) {
+                    tracing::debug!("Checking for replacement of pending tx {:?}", *this.tx_hash);
+                    let fut = Box::pin(find_replacement(
+                        *this.provider,
+                        from,
+                        nonce,
+                        *this.replacement_scan_from,
+                    ));
+                    rewake_with_new_state!(ctx, this, PendingTxState::CheckingReplacement(fut));
+                }
+
                 // If the tx is no longer in the mempool, return Ok(None)
                 if tx_opt.is_none() {
                     if *this.retries_remaining == 0 {
@@ -204,6 +250,32 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
                 let fut = Box::pin(this.provider.get_transaction_receipt(*this.tx_hash));
                 rewake_with_new_state!(ctx, this, PendingTxState::GettingReceipt(fut));
             }
+            PendingTxState::CheckingReplacement(fut) => {
+                match futures_util::ready!(fut.as_mut().poll(ctx)) {
+                    Ok(Replacement::Mined(receipt)) => {
+                        if receipt.transaction_hash != *this.tx_hash {
+                            tracing::debug!(
+                                "Pending tx {:?} was replaced by {:?}",
+                                *this.tx_hash,
+                                receipt.transaction_hash
+                            );
+                        }
+                        *this.state = PendingTxState::CheckingReceipt(Some(*receipt));
+                    }
+                    Ok(Replacement::Pending(block)) => {
+                        *this.replacement_scan_from = Some(block);
+                        *this.state = PendingTxState::PausedGettingTx;
+                    }
+                    Ok(Replacement::NotFound) => {
+                        tracing::debug!("No replacement found for pending tx {:?}", *this.tx_hash);
+                        *this.state = PendingTxState::Completed;
+                        return Poll::Ready(Ok(None))
+                    }
+                    // If the provider errors, just try again after the interval.
+                    Err(_) => *this.state = PendingTxState::PausedGettingTx,
+                }
+                ctx.waker().wake_by_ref();
+            }
             PendingTxState::PausedGettingReceipt => {
                 // Wait the polling period so that we do not spam the chain when no
                 // new block has been mined
@@ -321,6 +393,117 @@ impl<'a, P> Deref for PendingTransaction<'a, P> {
     }
 }
 
+/// A [`PendingTransaction`] that also resolves if it gets replaced by another transaction, see
+/// [`PendingTransaction::watch_replacement`].
+#[pin_project]
+#[derive(Debug)]
+pub struct ReplaceablePendingTransaction<'a, P> {
+    #[pin]
+    inner: PendingTransaction<'a, P>,
+}
+
+impl<'a, P: JsonRpcClient> Future for ReplaceablePendingTransaction<'a, P> {
+    type Output = Result<Option<TransactionOutcome>, ProviderError>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
+        let inner = self.project().inner;
+        let tx_hash = inner.tx_hash;
+        let receipt = futures_util::ready!(inner.poll(ctx))?;
+        Poll::Ready(Ok(receipt.map(|receipt| {
+            if receipt.transaction_hash == tx_hash {
+                TransactionOutcome::Mined(receipt)
+            } else {
+                TransactionOutcome::Replaced { original: tx_hash, receipt }
+            }
+        })))
+    }
+}
+
+impl<'a, P> Deref for ReplaceablePendingTransaction<'a, P> {
+    type Target = TxHash;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner.tx_hash
+    }
+}
+
+/// How a [`ReplaceablePendingTransaction`] was resolved
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransactionOutcome {
+    /// The transaction itself was mined
+    Mined(TransactionReceipt),
+    /// Another transaction of the same sender with the same nonce was mined instead
+    Replaced {
+        /// The hash of the transaction that was replaced
+        original: TxHash,
+        /// The receipt of the replacement transaction
+        receipt: TransactionReceipt,
+    },
+}
+
+impl TransactionOutcome {
+    /// Returns the receipt of the mined transaction, which may be the replacement
+    pub fn receipt(&self) -> &TransactionReceipt {
+        match self {
+            TransactionOutcome::Mined(receipt) => receipt,
+            TransactionOutcome::Replaced { receipt, .. } => receipt,
+        }
+    }
+
+    /// Returns true if the transaction was replaced by another transaction
+    pub fn is_replaced(&self) -> bool {
+        matches!(self, TransactionOutcome::Replaced { .. })
+    }
+}
+
+/// The result of searching for a transaction that used the nonce of a pending transaction
+enum Replacement {
+    /// No transaction with the nonce was mined yet, as of the given block
+    Pending(U64),
+    /// The receipt of the mined transaction with the nonce
+    Mined(Box<TransactionReceipt>),
+    /// A transaction with the nonce was mined, but couldn't be found in the searched blocks
+    NotFound,
+}
+
+/// Searches the blocks after `scan_from` for a mined transaction of `from` with `nonce`
+async fn find_replacement<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    from: Address,
+    nonce: U256,
+    scan_from: Option<U64>,
+) -> Result<Replacement, ProviderError> {
+    let latest = provider.get_block_number().await?;
+    let count = provider.get_transaction_count(from, Some(latest.into())).await?;
+    if count <= nonce {
+        return Ok(Replacement::Pending(latest))
+    }
+
+    let lowest = scan_from
+        .map(|block| block + 1)
+        .unwrap_or_else(|| latest.saturating_sub(MAX_REPLACEMENT_SCAN_BLOCKS.into()));
+    let mut number = latest;
+    while number >= lowest {
+        let block = provider.get_block_with_txs(number).await?;
+        let replacement = block
+            .into_iter()
+            .flat_map(|block| block.transactions)
+            .find(|tx| tx.from == from && tx.nonce == nonce);
+        if let Some(tx) = replacement {
+            return Ok(match provider.get_transaction_receipt(tx.hash).await? {
+                Some(receipt) => Replacement::Mined(Box::new(receipt)),
+                // the receipt may not be available yet
+                None => Replacement::Pending(number.saturating_sub(1.into())),
+            })
+        }
+        if number.is_zero() {
+            break
+        }
+        number -= U64::one();
+    }
+    Ok(Replacement::NotFound)
+}
+
 // We box the TransactionReceipts to keep the enum small.
 enum PendingTxState<'a> {
     /// Initial delay to ensure the GettingTx loop doesn't immediately fail
@@ -332,6 +515,9 @@ enum PendingTxState<'a> {
     /// Polling The blockchain to see if the Tx has confirmed or dropped
     GettingTx(PinBoxFut<'a, Option<Transaction>>),
 
+    /// Searching the blockchain for a transaction that replaced the pending tx
+    CheckingReplacement(PinBoxFut<'a, Replacement>),
+
     /// Waiting for interval to elapse before calling API again
     PausedGettingReceipt,
 
@@ -359,6 +545,7 @@ impl<'a> fmt::Debug for PendingTxState<'a> {
             PendingTxState::InitialDelay(_) => "InitialDelay",
             PendingTxState::PausedGettingTx => "PausedGettingTx",
             PendingTxState::GettingTx(_) => "GettingTx",
+            PendingTxState::CheckingReplacement(_) => "CheckingReplacement",
             PendingTxState::PausedGettingReceipt => "PausedGettingReceipt",
             PendingTxState::GettingReceipt(_) => "GettingReceipt",
             PendingTxState::GettingBlockNumber(_, _) => "GettingBlockNumber",
@@ -370,3 +557,90 @@ impl<'a> fmt::Debug for PendingTxState<'a> {
         f.debug_struct("PendingTxState").field("state", &state).finish()
     }
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use ethers_core::types::Block;
+
+    #[tokio::test]
+    async fn resolves_to_replacement_receipt() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(Duration::from_millis(1));
+
+        let from = Address::repeat_byte(1);
+        let nonce = U256::from(7);
+        let original = TxHash::repeat_byte(2);
+        let replacement = Transaction {
+            hash: TxHash::repeat_byte(3),
+            from,
+            nonce,
+            block_number: Some(10.into()),
+            ..Default::default()
+        };
+        let other = Transaction {
+            hash: TxHash::repeat_byte(4),
+            from,
+            nonce: 6.into(),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            transaction_hash: replacement.hash,
+            block_number: Some(10.into()),
+            ..Default::default()
+        };
+
+        // responses are popped from the back of the queue
+        mock.push(receipt.clone()).unwrap();
+        mock.push(Block {
+            number: Some(10.into()),
+            transactions: vec![replacement.clone()],
+            ..Default::default()
+        })
+        .unwrap();
+        mock.push(Block {
+            number: Some(11.into()),
+            transactions: vec![other],
+            ..Default::default()
+        })
+        .unwrap();
+        mock.push(U256::from(8)).unwrap();
+        mock.push(U64::from(11)).unwrap();
+        mock.push(None::<Transaction>).unwrap();
+        // the nonce is still unused during the first check
+        mock.push(U256::from(7)).unwrap();
+        mock.push(U64::from(9)).unwrap();
+        mock.push(None::<Transaction>).unwrap();
+
+        let res = PendingTransaction::new(original, &provider)
+            .watch_replacement(from, nonce)
+            .await
+            .unwrap();
+        assert_eq!(res, Some(TransactionOutcome::Replaced { original, receipt }));
+
+        mock.assert_request("eth_getTransactionByHash", [original]).unwrap();
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getTransactionCount", (from, U64::from(9))).unwrap();
+        mock.assert_request("eth_getTransactionByHash", [original]).unwrap();
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getTransactionCount", (from, U64::from(11))).unwrap();
+        // only the blocks after the last check are searched
+        mock.assert_request("eth_getBlockByNumber", (U64::from(11), true)).unwrap();
+        mock.assert_request("eth_getBlockByNumber", (U64::from(10), true)).unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [replacement.hash]).unwrap();
+        assert!(mock.assert_request("eth_blockNumber", ()).is_err());
+    }
+
+    #[tokio::test]
+    async fn dropped_without_replacement_tracking() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(Duration::from_millis(1));
+        for _ in 0..2 {
+            mock.push(None::<Transaction>).unwrap();
+        }
+
+        let res = PendingTransaction::new(TxHash::zero(), &provider).retries(1).await.unwrap();
+        assert_eq!(res, None);
+    }
+}
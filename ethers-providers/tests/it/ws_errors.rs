@@ -1,7 +1,13 @@
 use ethers_core::{types::Filter, utils::Anvil};
 use ethers_providers::{Middleware, Provider, StreamExt};
 use futures_util::SinkExt;
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::net::{TcpListener, TcpStream};
 use tokio_tungstenite::{
     accept_async,
@@ -87,3 +93,58 @@ async fn resubscribe_on_ws_reconnect() {
     // Wait for the next block on existing subscription. Will fail w/o resubscription:
     blocks.next().await.expect("no block notice after reconnect");
 }
+
+const RESUBSCRIBE_WS_ENDPOINT: &str = "127.0.0.1:9003";
+
+/// Answers every `eth_subscribe` with a new server-side subscription id followed by a single
+/// notification. The first connection is dropped right after.
+async fn handle_sub_conn(stream: TcpStream, conn_number: usize) -> Result<(), Error> {
+    let mut ws_stream = accept_async(stream).await?;
+
+    while let Some(msg) = ws_stream.next().await {
+        let req: serde_json::Value = serde_json::from_str(msg?.to_text()?).unwrap();
+        let server_id = format!("0x{:x}", conn_number + 1);
+        let res = serde_json::json!({"jsonrpc": "2.0", "id": req["id"], "result": server_id});
+        ws_stream.send(Message::Text(res.to_string())).await?;
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_subscription",
+            "params": {"subscription": server_id, "result": format!("0x{conn_number:x}")}
+        });
+        ws_stream.send(Message::Text(notification.to_string())).await?;
+
+        if conn_number == 0 {
+            ws_stream.close(None).await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resume_subscription_on_ws_reconnect() {
+    let listener = TcpListener::bind(&RESUBSCRIBE_WS_ENDPOINT).await.expect("Can't listen");
+    let connections = Arc::new(AtomicUsize::new(0));
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let conn_number = connections.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(handle_sub_conn(stream, conn_number));
+        }
+    });
+
+    let provider = Provider::connect_with_reconnects_and_backoff(
+        format!("ws://{RESUBSCRIBE_WS_ENDPOINT}"),
+        2,
+        Duration::from_millis(10),
+    )
+    .await
+    .unwrap();
+    assert_eq!(provider.as_ref().reconnections(), 0);
+
+    let mut stream = provider.subscribe::<_, String>(["newHeads"]).await.unwrap();
+    assert_eq!(stream.next().await.unwrap(), "0x0");
+
+    // the same stream keeps receiving notifications after the reconnection
+    assert_eq!(stream.next().await.unwrap(), "0x1");
+    assert_eq!(provider.as_ref().reconnections(), 1);
+}
@@ -3,8 +3,12 @@
 use ethers_core::{
     k256::ecdsa::{Error as K256Error, Signature as KSig, VerifyingKey},
     types::{
-        transaction::{eip2718::TypedTransaction, eip712::Eip712},
-        Address, Signature as EthSig, H256,
+        transaction::{
+            eip2718::TypedTransaction,
+            eip712::Eip712,
+            eip7702::{Authorization, SignedAuthorization},
+        },
+        Address, Signature as EthSig, SignatureError, H256,
     },
     utils::hash_message,
 };
@@ -15,7 +19,7 @@ use rusoto_kms::{
 use tracing::{debug, instrument, trace};
 
 mod utils;
-use utils::{apply_eip155, verifying_key_to_address};
+use utils::{apply_eip155, apply_message_v, verifying_key_to_address};
 
 /// An ethers Signer that uses keys held in Amazon AWS KMS.
 ///
@@ -92,6 +96,9 @@ pub enum AwsSignerError {
     /// Error type from Eip712Error message
     #[error("error encoding eip712 struct: {0:?}")]
     Eip712Error(String),
+    /// Error when attaching a signature
+    #[error(transparent)]
+    SignatureError(#[from] SignatureError),
 }
 
 impl From<String> for AwsSignerError {
@@ -202,17 +209,23 @@ impl AwsSigner {
         self.sign_digest_with_key(self.key_id.clone(), digest).await
     }
 
+    /// Sign a digest with this signer's key and recover the `v` value, which
+    /// is set to the raw recovery id
+    #[instrument(err, skip(digest), fields(digest = %hex::encode(digest)))]
+    async fn sign_digest_with_recovery(&self, digest: H256) -> Result<EthSig, AwsSignerError> {
+        let sig = self.sign_digest(digest.into()).await?;
+        utils::sig_from_digest_bytes_trial_recovery(&sig, digest.into(), &self.pubkey)
+            .map_err(|e| *e)
+    }
+
     /// Sign a digest with this signer's key and add the eip155 `v` value
     /// corresponding to the input chain_id
-    #[instrument(err, skip(digest), fields(digest = %hex::encode(digest)))]
     async fn sign_digest_with_eip155(
         &self,
         digest: H256,
         chain_id: u64,
     ) -> Result<EthSig, AwsSignerError> {
-        let sig = self.sign_digest(digest.into()).await?;
-        let mut sig =
-            utils::sig_from_digest_bytes_trial_recovery(&sig, digest.into(), &self.pubkey);
+        let mut sig = self.sign_digest_with_recovery(digest).await?;
         apply_eip155(&mut sig, chain_id);
         Ok(sig)
     }
@@ -232,7 +245,9 @@ impl super::Signer for AwsSigner {
         trace!("{:?}", message_hash);
         trace!("{:?}", message);
 
-        self.sign_digest_with_eip155(message_hash, self.chain_id).await
+        let mut sig = self.sign_digest_with_recovery(message_hash).await?;
+        apply_message_v(&mut sig);
+        Ok(sig)
     }
 
     #[instrument(err)]
@@ -252,9 +267,8 @@ impl super::Signer for AwsSigner {
         let digest =
             payload.encode_eip712().map_err(|e| Self::Error::Eip712Error(e.to_string()))?;
 
-        let sig = self.sign_digest(digest).await?;
-        let sig = utils::sig_from_digest_bytes_trial_recovery(&sig, digest, &self.pubkey);
-
+        let mut sig = self.sign_digest_with_recovery(digest.into()).await?;
+        apply_message_v(&mut sig);
         Ok(sig)
     }
 
@@ -274,6 +288,27 @@ impl super::Signer for AwsSigner {
     }
 }
 
+#[async_trait::async_trait]
+impl super::HashSigner for AwsSigner {
+    #[instrument(err)]
+    async fn sign_hash(&self, hash: H256) -> Result<EthSig, Self::Error> {
+        let mut sig = self.sign_digest_with_recovery(hash).await?;
+        apply_message_v(&mut sig);
+        Ok(sig)
+    }
+}
+
+#[async_trait::async_trait]
+impl super::AuthorizationSigner for AwsSigner {
+    async fn sign_authorization(
+        &self,
+        auth: Authorization,
+    ) -> Result<SignedAuthorization, Self::Error> {
+        let sig = self.sign_digest_with_recovery(auth.signature_hash()).await?;
+        Ok(auth.into_signed(sig)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
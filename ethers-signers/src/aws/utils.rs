@@ -29,23 +29,26 @@ fn check_candidate(
         .unwrap_or(false)
 }
 
-/// Recover an rsig from a signature under a known key by trial/error
+/// Recover an rsig from a signature under a known key by trial/error. The
+/// returned signature's `v` is the raw recovery id, i.e. `0` or `1`
 pub(super) fn sig_from_digest_bytes_trial_recovery(
     sig: &KSig,
     digest: [u8; 32],
     vk: &VerifyingKey,
-) -> EthSig {
+) -> Result<EthSig, Box<AwsSignerError>> {
     let r_bytes: FieldBytes = sig.r().into();
     let s_bytes: FieldBytes = sig.s().into();
     let r = U256::from_big_endian(r_bytes.as_slice());
     let s = U256::from_big_endian(s_bytes.as_slice());
 
     if check_candidate(sig, RecoveryId::from_byte(0).unwrap(), digest, vk) {
-        EthSig { r, s, v: 0 }
+        Ok(EthSig { r, s, v: 0 })
     } else if check_candidate(sig, RecoveryId::from_byte(1).unwrap(), digest, vk) {
-        EthSig { r, s, v: 1 }
+        Ok(EthSig { r, s, v: 1 })
     } else {
-        panic!("bad sig");
+        Err(Box::new(AwsSignerError::from(
+            "Signature does not recover to the signer's key".to_owned(),
+        )))
     }
 }
 
@@ -55,6 +58,12 @@ pub(super) fn apply_eip155(sig: &mut EthSig, chain_id: u64) {
     sig.v = v;
 }
 
+/// Modify the v value of a signature to the `27` or `28` expected for signed
+/// messages and typed data
+pub(super) fn apply_message_v(sig: &mut EthSig) {
+    sig.v += 27;
+}
+
 /// Convert a verifying key to an ethereum address
 pub(super) fn verifying_key_to_address(key: &VerifyingKey) -> Address {
     // false for uncompressed
@@ -86,3 +95,48 @@ pub(super) fn decode_signature(resp: SignResponse) -> Result<KSig, AwsSignerErro
     let sig = KSig::from_der(&raw)?;
     Ok(sig.normalize_s().unwrap_or(sig))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{k256::ecdsa::SigningKey, types::H256};
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&[1u8; 32].into()).unwrap()
+    }
+
+    #[test]
+    fn decodes_signature_with_low_s() {
+        let digest = keccak256("hello");
+        let (sig, _) = key().sign_prehash_recoverable(&digest).unwrap();
+        // flip `s` to the high half of the curve order, as KMS may return it
+        let high_s = KSig::from_scalars(sig.r(), -*sig.s()).unwrap();
+        assert!(high_s.normalize_s().is_some());
+
+        let resp = SignResponse {
+            signature: Some(high_s.to_der().as_bytes().to_vec().into()),
+            ..Default::default()
+        };
+        let decoded = decode_signature(resp).unwrap();
+        assert_eq!(decoded, sig.normalize_s().unwrap_or(sig));
+        assert!(decoded.normalize_s().is_none());
+    }
+
+    #[test]
+    fn recovers_v_by_trial() {
+        let key = key();
+        let address = verifying_key_to_address(key.verifying_key());
+        let digest = keccak256("hello");
+        let (sig, recovery_id) = key.sign_prehash_recoverable(&digest).unwrap();
+
+        let mut eth_sig =
+            sig_from_digest_bytes_trial_recovery(&sig, digest, key.verifying_key()).unwrap();
+        assert_eq!(eth_sig.v, u8::from(recovery_id) as u64);
+
+        apply_message_v(&mut eth_sig);
+        assert_eq!(eth_sig.recover(H256::from(digest)).unwrap(), address);
+
+        let other = SigningKey::from_bytes(&[2u8; 32].into()).unwrap();
+        sig_from_digest_bytes_trial_recovery(&sig, digest, other.verifying_key()).unwrap_err();
+    }
+}
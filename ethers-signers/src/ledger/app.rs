@@ -8,7 +8,10 @@ use futures_util::lock::Mutex;
 
 use ethers_core::{
     types::{
-        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        transaction::{
+            eip2718::TypedTransaction,
+            eip712::{Eip712, TypedData},
+        },
         Address, NameOrAddress, Signature, Transaction, TransactionRequest, TxHash, H256, U256,
     },
     utils::keccak256,
@@ -40,6 +43,10 @@ impl std::fmt::Display for LedgerEthereum {
 }
 
 const EIP712_MIN_VERSION: &str = ">=1.6.0";
+const EIP712_FULL_MIN_VERSION: &str = ">=1.9.19";
+
+// P2 value of the EIP712 signing command for the full implementation
+const P2_EIP712_FULL: u8 = 0x01;
 
 impl LedgerEthereum {
     /// Instantiate the application by acquiring a lock on the ledger device.
@@ -157,9 +164,10 @@ impl LedgerEthereum {
             };
 
             signature.v = match tx {
-                TypedTransaction::Eip2930(_) | TypedTransaction::Eip1559(_) => {
-                    (ecc_parity % 2 != 1) as u64
-                }
+                TypedTransaction::Eip2930(_) |
+                TypedTransaction::Eip1559(_) |
+                TypedTransaction::Eip4844(_) |
+                TypedTransaction::Eip7702(_) => (ecc_parity % 2 != 1) as u64,
                 TypedTransaction::Legacy(_) => eip155_chain_id + ecc_parity,
                 #[cfg(feature = "optimism")]
                 TypedTransaction::DepositTransaction(_) => 0,
@@ -207,6 +215,46 @@ impl LedgerEthereum {
         self.sign_payload(INS::SIGN_ETH_EIP_712, &payload).await
     }
 
+    /// Signs EIP712 typed data.
+    ///
+    /// Ethereum app versions that support it are fed the full type definitions and the message
+    /// field by field, so that the message can be reviewed on the device before signing. Older
+    /// versions fall back to signing the domain separator and struct hash, see
+    /// [`Self::sign_typed_struct`].
+    pub async fn sign_typed_data_full(
+        &self,
+        payload: &TypedData,
+    ) -> Result<Signature, LedgerError> {
+        let req = semver::VersionReq::parse(EIP712_FULL_MIN_VERSION)?;
+        let version = semver::Version::parse(&self.version().await?)?;
+        if !req.matches(&version) {
+            tracing::debug!(%version, "Full EIP712 signing unsupported, signing hashed message");
+            return self.sign_typed_struct(payload).await
+        }
+
+        let commands = super::eip712::typed_data_commands(payload)?;
+
+        let transport = self.transport.lock().await;
+        tracing::debug!(count = commands.len(), "Dispatching EIP712 structs to device");
+        for command in commands {
+            let answer = block_on(transport.exchange(&command))?;
+            if answer.data().is_none() {
+                return Err(LedgerError::UnexpectedNullResponse)
+            }
+        }
+
+        let command = APDUCommand {
+            ins: INS::SIGN_ETH_EIP_712 as u8,
+            p1: P1_FIRST,
+            p2: P2_EIP712_FULL,
+            data: APDUData::new(&Self::path_to_bytes(&self.derivation)),
+            response_len: None,
+        };
+        tracing::debug!("Dispatching EIP712 signing request to device");
+        let answer = block_on(transport.exchange(&command))?;
+        Self::signature_from_answer(answer.data().ok_or(LedgerError::UnexpectedNullResponse)?)
+    }
+
     #[tracing::instrument(err, skip_all, fields(command = %command, payload = hex::encode(payload)))]
     // Helper function for signing either transaction data, personal messages or EIP712 derived
     // structs
@@ -258,7 +306,11 @@ impl LedgerEthereum {
         }
         drop(guard);
         let answer = answer.expect("payload is non-empty, therefore loop ran");
-        let result = answer.data().expect("check in loop");
+        Self::signature_from_answer(answer.data().expect("check in loop"))
+    }
+
+    // helper which parses the signature returned by the device
+    fn signature_from_answer(result: &[u8]) -> Result<Signature, LedgerError> {
         if result.len() < 65 {
             return Err(LedgerError::ShortResponse { got: result.len(), at_least: 65 })
         }
@@ -0,0 +1,454 @@
+//! Encoding of [`TypedData`] into the APDUs of the Ledger Ethereum app's full EIP-712
+//! implementation.
+//!
+//! See <https://github.com/LedgerHQ/app-ethereum/blob/develop/doc/ethapp.adoc>
+use super::types::{LedgerError, INS};
+use coins_ledger::common::{APDUCommand, APDUData};
+use ethers_core::types::{
+    serde_helpers::StringifiedNumeric,
+    transaction::eip712::{Eip712DomainType, TypedData, Types},
+    Address, Bytes, I256, U256,
+};
+use serde_json::Value;
+
+const EIP712_DOMAIN: &str = "EIP712Domain";
+
+// P2 values of the struct definition command
+const P2_DEF_NAME: u8 = 0x00;
+const P2_DEF_FIELD: u8 = 0xFF;
+
+// P1 and P2 values of the struct implementation command
+const P1_COMPLETE: u8 = 0x00;
+const P1_PARTIAL: u8 = 0x01;
+const P2_IMPL_ROOT: u8 = 0x00;
+const P2_IMPL_ARRAY: u8 = 0x0F;
+const P2_IMPL_FIELD: u8 = 0xFF;
+
+// Flags and ids of the type description byte of a field definition
+const TYPE_ARRAY: u8 = 0x80;
+const TYPE_SIZE: u8 = 0x40;
+const TYPE_CUSTOM: u8 = 0;
+const TYPE_INT: u8 = 1;
+const TYPE_UINT: u8 = 2;
+const TYPE_ADDRESS: u8 = 3;
+const TYPE_BOOL: u8 = 4;
+const TYPE_STRING: u8 = 5;
+const TYPE_FIXED_BYTES: u8 = 6;
+const TYPE_DYNAMIC_BYTES: u8 = 7;
+
+const MAX_APDU_DATA: usize = 255;
+
+/// Returns the struct definition and struct implementation commands that have to be sent to the
+/// device, in order, before the typed data can be signed in full mode.
+pub(crate) fn typed_data_commands(data: &TypedData) -> Result<Vec<APDUCommand>, LedgerError> {
+    let mut types = data.types.clone();
+    types.entry(EIP712_DOMAIN.to_string()).or_insert_with(|| data.domain.field_types());
+
+    let mut commands = Vec::new();
+    for (name, fields) in &types {
+        commands.push(command(INS::EIP712_SEND_STRUCT_DEFINITION, 0, P2_DEF_NAME, name.as_bytes()));
+        for field in fields {
+            let definition = field_definition(&types, field)?;
+            commands.push(command(
+                INS::EIP712_SEND_STRUCT_DEFINITION,
+                0,
+                P2_DEF_FIELD,
+                &definition,
+            ));
+        }
+    }
+
+    let domain = Value::Object(domain_value(data));
+    let message = Value::Object(data.message.clone().into_iter().collect());
+    for (name, value) in [(EIP712_DOMAIN, &domain), (data.primary_type.as_str(), &message)] {
+        commands.push(command(
+            INS::EIP712_SEND_STRUCT_IMPLEMENTATION,
+            P1_COMPLETE,
+            P2_IMPL_ROOT,
+            name.as_bytes(),
+        ));
+        struct_implementation(&types, name, value, &mut commands)?;
+    }
+
+    Ok(commands)
+}
+
+fn command(ins: INS, p1: u8, p2: u8, data: &[u8]) -> APDUCommand {
+    APDUCommand { ins: ins as u8, p1, p2, data: APDUData::new(data), response_len: None }
+}
+
+fn eip712_error(msg: impl Into<String>) -> LedgerError {
+    LedgerError::Eip712Error(msg.into())
+}
+
+/// Returns a length that the device expects as a single byte, failing if it exceeds 255 instead
+/// of truncating it
+fn length_byte(len: usize, what: impl FnOnce() -> String) -> Result<u8, LedgerError> {
+    u8::try_from(len).map_err(|_| eip712_error(format!("Length {len} of {} exceeds 255", what())))
+}
+
+fn domain_value(data: &TypedData) -> serde_json::Map<String, Value> {
+    let domain = &data.domain;
+    let mut value = serde_json::Map::new();
+    if let Some(name) = &domain.name {
+        value.insert("name".to_string(), name.clone().into());
+    }
+    if let Some(version) = &domain.version {
+        value.insert("version".to_string(), version.clone().into());
+    }
+    if let Some(chain_id) = domain.chain_id {
+        value.insert("chainId".to_string(), chain_id.to_string().into());
+    }
+    if let Some(verifying_contract) = domain.verifying_contract {
+        value.insert("verifyingContract".to_string(), format!("{verifying_contract:?}").into());
+    }
+    if let Some(salt) = domain.salt {
+        value.insert("salt".to_string(), Bytes::from(salt.to_vec()).to_string().into());
+    }
+    value
+}
+
+/// The base type of a field type and its array levels, `None` for dynamic levels
+fn split_array_levels(ty: &str) -> Result<(&str, Vec<Option<u8>>), LedgerError> {
+    let (base, levels) = ty.split_at(ty.find('[').unwrap_or(ty.len()));
+    let levels = levels
+        .split_terminator(']')
+        .map(|level| {
+            let size = level
+                .strip_prefix('[')
+                .ok_or_else(|| eip712_error(format!("Invalid array type `{ty}`")))?;
+            if size.is_empty() {
+                return Ok(None)
+            }
+            size.parse()
+                .map(Some)
+                .map_err(|_| eip712_error(format!("Invalid array size in type `{ty}`")))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((base, levels))
+}
+
+/// The type id of a primitive type and its size in bytes, if it has one
+fn primitive_type(ty: &str) -> Result<(u8, Option<u8>), LedgerError> {
+    let unknown = || eip712_error(format!("Unknown type `{ty}`"));
+    // integer types are given in bits, `uint` and `int` are aliases of `uint256` and `int256`
+    let int_size = |bits: &str| match bits {
+        "" => Ok(32),
+        bits => bits
+            .parse::<u16>()
+            .ok()
+            .filter(|bits| bits % 8 == 0 && (8..=256).contains(bits))
+            .and_then(|bits| u8::try_from(bits / 8).ok())
+            .ok_or_else(unknown),
+    };
+    let res = match ty {
+        "address" => (TYPE_ADDRESS, None),
+        "bool" => (TYPE_BOOL, None),
+        "string" => (TYPE_STRING, None),
+        "bytes" => (TYPE_DYNAMIC_BYTES, None),
+        _ => {
+            if let Some(size) = ty.strip_prefix("bytes") {
+                (TYPE_FIXED_BYTES, Some(size.parse().map_err(|_| unknown())?))
+            } else if let Some(bits) = ty.strip_prefix("uint") {
+                (TYPE_UINT, Some(int_size(bits)?))
+            } else if let Some(bits) = ty.strip_prefix("int") {
+                (TYPE_INT, Some(int_size(bits)?))
+            } else {
+                return Err(unknown())
+            }
+        }
+    };
+    Ok(res)
+}
+
+fn field_definition(types: &Types, field: &Eip712DomainType) -> Result<Vec<u8>, LedgerError> {
+    let (base, levels) = split_array_levels(&field.r#type)?;
+    let (type_id, size) =
+        if types.contains_key(base) { (TYPE_CUSTOM, None) } else { primitive_type(base)? };
+
+    let mut desc = type_id;
+    if !levels.is_empty() {
+        desc |= TYPE_ARRAY;
+    }
+    if size.is_some() {
+        desc |= TYPE_SIZE;
+    }
+
+    let mut definition = vec![desc];
+    if type_id == TYPE_CUSTOM {
+        definition.push(length_byte(base.len(), || format!("type name `{base}`"))?);
+        definition.extend_from_slice(base.as_bytes());
+    }
+    if let Some(size) = size {
+        definition.push(size);
+    }
+    if !levels.is_empty() {
+        definition
+            .push(length_byte(levels.len(), || format!("array levels of `{}`", field.r#type))?);
+        for level in levels {
+            match level {
+                Some(size) => definition.extend_from_slice(&[1, size]),
+                None => definition.push(0),
+            }
+        }
+    }
+    definition.push(length_byte(field.name.len(), || format!("field name `{}`", field.name))?);
+    definition.extend_from_slice(field.name.as_bytes());
+    Ok(definition)
+}
+
+fn struct_implementation(
+    types: &Types,
+    name: &str,
+    value: &Value,
+    commands: &mut Vec<APDUCommand>,
+) -> Result<(), LedgerError> {
+    let fields = types
+        .get(name)
+        .ok_or_else(|| eip712_error(format!("No type definition found for: `{name}`")))?;
+    for field in fields {
+        let value = value.get(&field.name).ok_or_else(|| {
+            eip712_error(format!("Missing value for field `{}` of `{name}`", field.name))
+        })?;
+        field_implementation(types, &field.r#type, value, commands)?;
+    }
+    Ok(())
+}
+
+/// Feeds a field to the device: arrays are announced with their length and then fed element by
+/// element, nested structs are fed field by field
+fn field_implementation(
+    types: &Types,
+    ty: &str,
+    value: &Value,
+    commands: &mut Vec<APDUCommand>,
+) -> Result<(), LedgerError> {
+    // the outermost array level is the last one
+    if let Some((element_type, _)) = ty.strip_suffix(']').and_then(|ty| ty.rsplit_once('[')) {
+        let elements = value.as_array().ok_or_else(|| {
+            eip712_error(format!("Expected array for type `{ty}`, got `{value}`"))
+        })?;
+        commands.push(command(
+            INS::EIP712_SEND_STRUCT_IMPLEMENTATION,
+            P1_COMPLETE,
+            P2_IMPL_ARRAY,
+            &[length_byte(elements.len(), || format!("array of type `{ty}`"))?],
+        ));
+        for element in elements {
+            field_implementation(types, element_type, element, commands)?;
+        }
+        return Ok(())
+    }
+
+    if types.contains_key(ty) {
+        return struct_implementation(types, ty, value, commands)
+    }
+
+    let encoded = encode_primitive(ty, value)?;
+    let len = u16::try_from(encoded.len())
+        .map_err(|_| eip712_error(format!("Value of type `{ty}` exceeds {} bytes", u16::MAX)))?;
+    let mut payload = len.to_be_bytes().to_vec();
+    payload.extend(encoded);
+
+    // values that exceed the APDU size are sent in several parts
+    let mut chunks = payload.chunks(MAX_APDU_DATA).peekable();
+    while let Some(chunk) = chunks.next() {
+        let p1 = if chunks.peek().is_some() { P1_PARTIAL } else { P1_COMPLETE };
+        commands.push(command(INS::EIP712_SEND_STRUCT_IMPLEMENTATION, p1, P2_IMPL_FIELD, chunk));
+    }
+    Ok(())
+}
+
+fn encode_primitive(ty: &str, value: &Value) -> Result<Vec<u8>, LedgerError> {
+    let invalid = |err: &dyn std::fmt::Display| {
+        eip712_error(format!("Invalid value `{value}` for type `{ty}`: {err}"))
+    };
+    let (type_id, size) = primitive_type(ty)?;
+
+    let encoded = match type_id {
+        TYPE_ADDRESS => {
+            let address: Address =
+                serde_json::from_value(value.clone()).map_err(|e| invalid(&e))?;
+            address.as_bytes().to_vec()
+        }
+        TYPE_BOOL => {
+            let b: bool = serde_json::from_value(value.clone()).map_err(|e| invalid(&e))?;
+            vec![b as u8]
+        }
+        TYPE_STRING => {
+            let s: String = serde_json::from_value(value.clone()).map_err(|e| invalid(&e))?;
+            s.into_bytes()
+        }
+        TYPE_FIXED_BYTES | TYPE_DYNAMIC_BYTES => {
+            let bytes: Bytes = serde_json::from_value(value.clone()).map_err(|e| invalid(&e))?;
+            bytes.to_vec()
+        }
+        TYPE_UINT | TYPE_INT => {
+            let raw = if type_id == TYPE_UINT {
+                let n: StringifiedNumeric =
+                    serde_json::from_value(value.clone()).map_err(|e| invalid(&e))?;
+                U256::try_from(n).map_err(|e| invalid(&e))?
+            } else {
+                parse_int(value).ok_or_else(|| invalid(&"not a signed integer"))?.into_raw()
+            };
+            // big-endian two's complement, truncated to the size of the type
+            let mut word = [0u8; 32];
+            raw.to_big_endian(&mut word);
+            let size = (size.unwrap_or(32) as usize).min(32);
+            word[32 - size..].to_vec()
+        }
+        _ => unreachable!("all primitive types are handled"),
+    };
+    Ok(encoded)
+}
+
+fn parse_int(value: &Value) -> Option<I256> {
+    match value {
+        Value::Number(n) => n.as_i64().map(I256::from),
+        Value::String(s) => {
+            let (sign, abs) = s.strip_prefix('-').map(|abs| ("-", abs)).unwrap_or(("", s));
+            match abs.strip_prefix("0x") {
+                Some(hex) => I256::from_hex_str(&format!("{sign}{hex}")).ok(),
+                None => I256::from_dec_str(s).ok(),
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mail() -> TypedData {
+        serde_json::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "chainId", "type": "uint256" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallets", "type": "address[]" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person[]" },
+                    { "name": "value", "type": "int8" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": { "name": "Ether Mail", "chainId": 1 },
+            "message": {
+                "from": { "name": "Cow", "wallets": ["0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"] },
+                "to": [{ "name": "Bob", "wallets": [] }],
+                "value": -2
+            }
+        }))
+        .unwrap()
+    }
+
+    fn data(cmd: &APDUCommand) -> Vec<u8> {
+        cmd.data.to_vec()
+    }
+
+    #[test]
+    fn encodes_field_definitions() {
+        let types = mail().types;
+        let field = |name: &str, ty: &str| Eip712DomainType {
+            name: name.to_string(),
+            r#type: ty.to_string(),
+        };
+
+        assert_eq!(
+            field_definition(&types, &field("value", "int8")).unwrap(),
+            [&[TYPE_SIZE | TYPE_INT, 1, 5][..], b"value"].concat()
+        );
+        assert_eq!(
+            field_definition(&types, &field("to", "Person[]")).unwrap(),
+            [&[TYPE_ARRAY | TYPE_CUSTOM, 6][..], b"Person", &[1, 0, 2], b"to"].concat()
+        );
+        assert_eq!(
+            field_definition(&types, &field("m", "bytes32[2][]")).unwrap(),
+            [&[TYPE_ARRAY | TYPE_SIZE | TYPE_FIXED_BYTES, 32, 2, 1, 2, 0, 1][..], b"m"].concat()
+        );
+        field_definition(&types, &field("x", "Unknown")).unwrap_err();
+        field_definition(&types, &field("x", "uint2048")).unwrap_err();
+        // lengths that do not fit a byte are rejected instead of truncated
+        field_definition(&types, &field(&"x".repeat(256), "bool")).unwrap_err();
+    }
+
+    #[test]
+    fn rejects_arrays_longer_than_255_elements() {
+        let mut typed_data = mail();
+        let wallets = vec!["0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"; 256];
+        typed_data
+            .message
+            .insert("from".to_string(), serde_json::json!({ "name": "Cow", "wallets": wallets }));
+        let err = typed_data_commands(&typed_data).unwrap_err();
+        assert!(err.to_string().contains("Length 256"), "{err}");
+    }
+
+    #[test]
+    fn feeds_nested_structs_and_arrays_field_by_field() {
+        let commands = typed_data_commands(&mail()).unwrap();
+
+        let (definitions, implementations): (Vec<_>, Vec<_>) =
+            commands.iter().partition(|cmd| cmd.ins == INS::EIP712_SEND_STRUCT_DEFINITION as u8);
+        // 3 struct names and 7 fields
+        assert_eq!(definitions.len(), 10);
+
+        let implementations =
+            implementations.into_iter().map(|cmd| (cmd.p1, cmd.p2, data(cmd))).collect::<Vec<_>>();
+        let field = |value: &[u8]| {
+            (P1_COMPLETE, P2_IMPL_FIELD, [&(value.len() as u16).to_be_bytes()[..], value].concat())
+        };
+        let wallet = hex::decode("CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826").unwrap();
+        let mut chain_id = [0u8; 32];
+        chain_id[31] = 1;
+        assert_eq!(
+            implementations,
+            vec![
+                (P1_COMPLETE, P2_IMPL_ROOT, b"EIP712Domain".to_vec()),
+                field(b"Ether Mail"),
+                field(&chain_id),
+                (P1_COMPLETE, P2_IMPL_ROOT, b"Mail".to_vec()),
+                // from
+                field(b"Cow"),
+                (P1_COMPLETE, P2_IMPL_ARRAY, vec![1]),
+                field(&wallet),
+                // to
+                (P1_COMPLETE, P2_IMPL_ARRAY, vec![1]),
+                field(b"Bob"),
+                (P1_COMPLETE, P2_IMPL_ARRAY, vec![0]),
+                // value
+                field(&[0xfe]),
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_large_values() {
+        let mut typed_data = mail();
+        typed_data.message.insert("value".to_string(), 1.into());
+        typed_data.message.insert(
+            "from".to_string(),
+            serde_json::json!({ "name": "a".repeat(300), "wallets": [] }),
+        );
+        let commands = typed_data_commands(&typed_data).unwrap();
+        let partial = commands.iter().position(|cmd| cmd.p1 == P1_PARTIAL).unwrap();
+        let parts = commands[partial..partial + 2]
+            .iter()
+            .map(|cmd| (cmd.p1, cmd.data.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(parts, vec![(P1_PARTIAL, 255), (P1_COMPLETE, 47)]);
+    }
+
+    #[test]
+    fn adds_missing_domain_type() {
+        let mut typed_data = mail();
+        typed_data.types.remove(EIP712_DOMAIN);
+        let commands = typed_data_commands(&typed_data).unwrap();
+        assert_eq!(data(&commands[0]), b"EIP712Domain");
+    }
+}
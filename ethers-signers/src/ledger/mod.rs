@@ -1,4 +1,5 @@
 pub mod app;
+mod eip712;
 pub mod types;
 
 use crate::Signer;
@@ -34,6 +35,9 @@ impl Signer for LedgerEthereum {
     }
 
     /// Signs a EIP712 derived struct
+    ///
+    /// The device only displays the domain separator and struct hash, use
+    /// [`LedgerEthereum::sign_typed_data_full`] to review the whole message on the device.
     async fn sign_typed_data<T: Eip712 + Send + Sync>(
         &self,
         payload: &T,
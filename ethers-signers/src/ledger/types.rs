@@ -69,6 +69,8 @@ pub enum INS {
     GET_APP_CONFIGURATION = 0x06,
     SIGN_PERSONAL_MESSAGE = 0x08,
     SIGN_ETH_EIP_712 = 0x0C,
+    EIP712_SEND_STRUCT_DEFINITION = 0x1A,
+    EIP712_SEND_STRUCT_IMPLEMENTATION = 0x1C,
 }
 
 impl std::fmt::Display for INS {
@@ -79,6 +81,10 @@ impl std::fmt::Display for INS {
             INS::GET_APP_CONFIGURATION => write!(f, "GET_APP_CONFIGURATION"),
             INS::SIGN_PERSONAL_MESSAGE => write!(f, "SIGN_PERSONAL_MESSAGE"),
             INS::SIGN_ETH_EIP_712 => write!(f, "SIGN_ETH_EIP_712"),
+            INS::EIP712_SEND_STRUCT_DEFINITION => write!(f, "EIP712_SEND_STRUCT_DEFINITION"),
+            INS::EIP712_SEND_STRUCT_IMPLEMENTATION => {
+                write!(f, "EIP712_SEND_STRUCT_IMPLEMENTATION")
+            }
         }
     }
 }
@@ -5,6 +5,9 @@
 mod wallet;
 pub use wallet::{MnemonicBuilder, Wallet, WalletError};
 
+mod wallet_signer;
+pub use wallet_signer::{WalletSigner, WalletSignerError};
+
 /// Re-export the BIP-32 crate so that wordlists can be accessed conveniently.
 pub use coins_bip39;
 
@@ -41,16 +44,27 @@ pub use aws::{AwsSigner, AwsSignerError};
 
 use async_trait::async_trait;
 use ethers_core::types::{
-    transaction::{eip2718::TypedTransaction, eip712::Eip712},
-    Address, Signature,
+    transaction::{
+        eip2718::TypedTransaction,
+        eip712::Eip712,
+        eip7702::{Authorization, SignedAuthorization},
+    },
+    Address, Signature, H256,
 };
 use std::error::Error;
+use thiserror::Error;
 
 /// Applies [EIP155](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-155.md)
 pub fn to_eip155_v<T: Into<u8>>(recovery_id: T, chain_id: u64) -> u64 {
     (recovery_id.into() as u64) + 35 + chain_id * 2
 }
 
+/// Error returned by signers that do not support an operation, e.g. by a [`WalletSigner`] backed
+/// by a hardware wallet for the operations of [`HashSigner`] and [`AuthorizationSigner`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+#[error("the signer does not support {0}")]
+pub struct UnsupportedOperation(pub &'static str);
+
 /// Trait for signing transactions and messages
 ///
 /// Implement this trait to support different signing modes, e.g. Ledger, hosted etc.
@@ -84,3 +98,30 @@ pub trait Signer: std::fmt::Debug + Send + Sync {
     #[must_use]
     fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self;
 }
+
+/// A [`Signer`] that can sign raw 32 byte hashes, e.g. a local wallet or an AWS KMS key. Hardware
+/// wallets can not sign raw hashes.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait HashSigner: Signer {
+    /// Signs the 32 byte `hash` as is, without the EIP-191 prefix applied by
+    /// [`sign_message`](Signer::sign_message). The `v` of the signature is 27 or 28.
+    ///
+    /// **Use with care:** the prefix makes sure that a signed message can not be a valid
+    /// transaction or another protocol's payload. Only sign digests whose preimage you know, since
+    /// the signer can not tell what it is signing.
+    async fn sign_hash(&self, hash: H256) -> Result<Signature, Self::Error>;
+}
+
+/// A [`Signer`] that can sign [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorizations,
+/// e.g. a local wallet or an AWS KMS key. Hardware wallets can not sign authorizations.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait AuthorizationSigner: Signer {
+    /// Signs an authorization, which delegates the code of the signer's account to the code at
+    /// `auth.address`
+    async fn sign_authorization(
+        &self,
+        auth: Authorization,
+    ) -> Result<SignedAuthorization, Self::Error>;
+}
@@ -21,6 +21,8 @@ use std::{
 };
 use thiserror::Error;
 
+use crate::UnsupportedOperation;
+
 use super::types::*;
 
 /// A Trezor Ethereum App.
@@ -192,6 +194,10 @@ impl TrezorEthereum {
                 transaction.max_priority_fee_per_gas,
                 transaction.access_list,
             )?,
+            TypedTransaction::Eip4844(_) => return Err(TrezorError::NoBlobTxSupport),
+            TypedTransaction::Eip7702(_) => {
+                return Err(UnsupportedOperation("EIP-7702 transactions").into())
+            }
             #[cfg(feature = "optimism")]
             TypedTransaction::DepositTransaction(tx) => {
                 trezor_client::client::Signature { r: 0.into(), s: 0.into(), v: 0 }
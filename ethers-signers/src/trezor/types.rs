@@ -4,6 +4,8 @@
 use std::fmt;
 use thiserror::Error;
 
+use crate::UnsupportedOperation;
+
 use ethers_core::types::{transaction::eip2718::TypedTransaction, NameOrAddress, U256};
 use trezor_client::client::AccessListItem as Trezor_AccessListItem;
 
@@ -51,6 +53,10 @@ pub enum TrezorError {
     UnsupportedFirmwareVersion(String),
     #[error("Does not support ENS.")]
     NoENSSupport,
+    #[error("Does not support blob transactions.")]
+    NoBlobTxSupport,
+    #[error(transparent)]
+    Unsupported(#[from] UnsupportedOperation),
     #[error("Unable to access trezor cached session.")]
     CacheError(String),
 }
@@ -135,6 +141,10 @@ impl TrezorTransaction {
                     access_list,
                 })
             }
+            TypedTransaction::Eip4844(_) => Err(TrezorError::NoBlobTxSupport),
+            TypedTransaction::Eip7702(_) => {
+                Err(UnsupportedOperation("EIP-7702 transactions").into())
+            }
             #[cfg(feature = "optimism")]
             TypedTransaction::DepositTransaction(_) => Ok(Self {
                 nonce,
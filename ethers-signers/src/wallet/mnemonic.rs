@@ -2,7 +2,7 @@
 //! specifications
 use crate::{Wallet, WalletError};
 
-use coins_bip32::path::DerivationPath;
+use coins_bip32::{path::DerivationPath, prelude::XPriv, xkeys::Parent, BIP32_HARDEN};
 use coins_bip39::{Mnemonic, Wordlist};
 use ethers_core::{
     k256::ecdsa::SigningKey,
@@ -45,6 +45,9 @@ pub enum MnemonicBuilderError {
     /// Error suggests that a phrase (path or words) was not expected but found
     #[error("Unexpected phrase found")]
     UnexpectedPhraseFound,
+    /// Error suggests that a derivation index is not a valid non-hardened index
+    #[error("Derivation index out of range")]
+    IndexOutOfRange,
 }
 
 impl<W: Wordlist> Default for MnemonicBuilder<W> {
@@ -146,14 +149,55 @@ impl<W: Wordlist> MnemonicBuilder<W> {
     /// Builds a `LocalWallet` using the parameters set in mnemonic builder. This method expects
     /// the phrase field to be set.
     pub fn build(&self) -> Result<Wallet<SigningKey>, WalletError> {
-        let mnemonic = match &self.phrase {
+        let mnemonic = self.mnemonic()?;
+        self.mnemonic_to_wallet(&mnemonic)
+    }
+
+    /// Builds the `LocalWallet`s at the indices `start..start + count` of the default derivation
+    /// path, i.e. "m/44'/60'/0'/0/{index}", as used by e.g. MetaMask. The derivation path set in
+    /// the builder is ignored. This method expects the phrase field to be set.
+    ///
+    /// The parent key "m/44'/60'/0'/0" is derived once and reused for all the wallets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_signers::{MnemonicBuilder, coins_bip39::English};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let wallets = MnemonicBuilder::<English>::default()
+    ///     .phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")
+    ///     .derive_range(0, 10)?;
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn derive_range(
+        &self,
+        start: u32,
+        count: u32,
+    ) -> Result<Vec<Wallet<SigningKey>>, WalletError> {
+        let end = start
+            .checked_add(count)
+            .filter(|end| *end <= BIP32_HARDEN)
+            .ok_or(MnemonicBuilderError::IndexOutOfRange)?;
+
+        let mnemonic = self.mnemonic()?;
+        let parent = mnemonic.derive_key(
+            DEFAULT_DERIVATION_PATH_PREFIX.trim_end_matches('/'),
+            self.password.as_deref(),
+        )?;
+        (start..end).map(|index| xpriv_to_wallet(&parent.derive_child(index)?)).collect()
+    }
+
+    fn mnemonic(&self) -> Result<Mnemonic<W>, WalletError> {
+        match &self.phrase {
             Some(path_or_string) => {
                 let phrase = path_or_string.read()?;
-                Mnemonic::<W>::new_from_phrase(&phrase)?
+                Ok(Mnemonic::<W>::new_from_phrase(&phrase)?)
             }
-            None => return Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
-        };
-        self.mnemonic_to_wallet(&mnemonic)
+            None => Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
+        }
     }
 
     /// Builds a `LocalWallet` using the parameters set in the mnemonic builder and constructing
@@ -180,14 +224,18 @@ impl<W: Wordlist> MnemonicBuilder<W> {
     ) -> Result<Wallet<SigningKey>, WalletError> {
         let derived_priv_key =
             mnemonic.derive_key(&self.derivation_path, self.password.as_deref())?;
-        let key: &coins_bip32::prelude::SigningKey = derived_priv_key.as_ref();
-        let signer = SigningKey::from_bytes(&key.to_bytes())?;
-        let address = secret_key_to_address(&signer);
-
-        Ok(Wallet::<SigningKey> { signer, address, chain_id: 1 })
+        xpriv_to_wallet(&derived_priv_key)
     }
 }
 
+fn xpriv_to_wallet(xpriv: &XPriv) -> Result<Wallet<SigningKey>, WalletError> {
+    let key: &coins_bip32::prelude::SigningKey = xpriv.as_ref();
+    let signer = SigningKey::from_bytes(&key.to_bytes())?;
+    let address = secret_key_to_address(&signer);
+
+    Ok(Wallet::<SigningKey> { signer, address, chain_id: 1 })
+}
+
 #[cfg(test)]
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
@@ -279,4 +327,31 @@ mod tests {
 
         dir.close().unwrap();
     }
+
+    #[test]
+    fn mnemonic_derive_range() {
+        let builder = MnemonicBuilder::<English>::default()
+            .phrase("test test test test test test test test test test test junk");
+        let wallets = builder.derive_range(0, 3).unwrap();
+
+        let addresses = wallets.iter().map(|w| to_checksum(&w.address, None)).collect::<Vec<_>>();
+        assert_eq!(
+            addresses,
+            [
+                "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+                "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+                "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+            ]
+        );
+
+        // matches the wallets built for a single index
+        let wallets = builder.derive_range(5, 2).unwrap();
+        for (index, wallet) in (5u32..).zip(wallets) {
+            let expected = builder.clone().index(index).unwrap().build().unwrap();
+            assert_eq!(wallet.address, expected.address);
+        }
+
+        assert!(builder.derive_range(0, 0).unwrap().is_empty());
+        builder.derive_range(BIP32_HARDEN - 1, 2).unwrap_err();
+    }
 }
@@ -7,7 +7,7 @@ pub use private_key::WalletError;
 #[cfg(all(feature = "yubihsm", not(target_arch = "wasm32")))]
 mod yubi;
 
-use crate::{to_eip155_v, Signer};
+use crate::{to_eip155_v, AuthorizationSigner, HashSigner, Signer};
 use ethers_core::{
     k256::{
         ecdsa::{signature::hazmat::PrehashSigner, RecoveryId, Signature as RecoverableSignature},
@@ -15,7 +15,11 @@ use ethers_core::{
         Secp256k1,
     },
     types::{
-        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        transaction::{
+            eip2718::TypedTransaction,
+            eip712::Eip712,
+            eip7702::{Authorization, SignedAuthorization},
+        },
         Address, Signature, H256, U256,
     },
     utils::hash_message,
@@ -127,6 +131,28 @@ impl<D: Sync + Send + PrehashSigner<(RecoverableSignature, RecoveryId)>> Signer
     }
 }
 
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<D: Sync + Send + PrehashSigner<(RecoverableSignature, RecoveryId)>> HashSigner for Wallet<D> {
+    async fn sign_hash(&self, hash: H256) -> Result<Signature, Self::Error> {
+        Wallet::sign_hash(self, hash)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<D: Sync + Send + PrehashSigner<(RecoverableSignature, RecoveryId)>> AuthorizationSigner
+    for Wallet<D>
+{
+    async fn sign_authorization(
+        &self,
+        auth: Authorization,
+    ) -> Result<SignedAuthorization, Self::Error> {
+        let signature = self.sign_hash(auth.signature_hash())?;
+        Ok(auth.into_signed(signature)?)
+    }
+}
+
 impl<D: PrehashSigner<(RecoverableSignature, RecoveryId)>> Wallet<D> {
     /// Synchronously signs the provided transaction, normalizing the signature `v` value with
     /// EIP-155 using the transaction's `chain_id`, or the signer's `chain_id` if the transaction
@@ -159,6 +185,31 @@ impl<D: PrehashSigner<(RecoverableSignature, RecoveryId)>> Wallet<D> {
         Ok(Signature { r, s, v })
     }
 
+    /// Synchronously signs the `messages`, returning the same signatures as signing each message
+    /// with [`Signer::sign_message`], in the order of the messages.
+    ///
+    /// This avoids the overhead of awaiting a future per message when signing many messages.
+    pub fn sign_messages<M: AsRef<[u8]>>(
+        &self,
+        messages: &[M],
+    ) -> Result<Vec<Signature>, WalletError> {
+        messages.iter().map(|message| self.sign_hash(hash_message(message))).collect()
+    }
+
+    /// As [`Self::sign_messages`], but signs the messages in parallel on the rayon thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn par_sign_messages<M: AsRef<[u8]> + Sync>(
+        &self,
+        messages: &[M],
+    ) -> Result<Vec<Signature>, WalletError>
+    where
+        D: Sync,
+    {
+        use rayon::prelude::*;
+
+        messages.par_iter().map(|message| self.sign_hash(hash_message(message))).collect()
+    }
+
     /// Gets the wallet's signer
     pub fn signer(&self) -> &D {
         &self.signer
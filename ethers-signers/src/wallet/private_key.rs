@@ -11,6 +11,7 @@ use eth_keystore::KeystoreError;
 use ethers_core::{
     k256::ecdsa::{self, SigningKey},
     rand::{CryptoRng, Rng},
+    types::SignatureError,
     utils::secret_key_to_address,
 };
 #[cfg(not(target_arch = "wasm32"))]
@@ -46,6 +47,9 @@ pub enum WalletError {
     /// Error type from Eip712Error message
     #[error("error encoding eip712 struct: {0:?}")]
     Eip712Error(String),
+    /// Error when attaching a signature
+    #[error(transparent)]
+    SignatureError(#[from] SignatureError),
 }
 
 impl Wallet<SigningKey> {
@@ -185,7 +189,7 @@ impl TryFrom<String> for Wallet<SigningKey> {
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
     use super::*;
-    use crate::{LocalWallet, Signer};
+    use crate::{AuthorizationSigner, HashSigner, LocalWallet, Signer};
     use ethers_core::types::Address;
     use tempfile::tempdir;
 
@@ -273,6 +277,67 @@ mod tests {
         assert_eq!(recovered2, address);
     }
 
+    #[tokio::test]
+    async fn signs_messages() {
+        let key = Wallet::<SigningKey>::new(&mut rand::thread_rng());
+        let messages: Vec<_> = (0..16u8).map(|i| vec![i; i as usize]).collect();
+
+        let mut expected = Vec::new();
+        for message in &messages {
+            expected.push(key.sign_message(message).await.unwrap());
+        }
+        assert_eq!(key.sign_messages(&messages).unwrap(), expected);
+        #[cfg(feature = "rayon")]
+        assert_eq!(key.par_sign_messages(&messages).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn signs_hash() {
+        let key = Wallet::<SigningKey>::new(&mut rand::thread_rng());
+        let hash = ethers_core::utils::keccak256("Some data").into();
+
+        // the hash is signed as is, without the EIP-191 prefix
+        let signature = HashSigner::sign_hash(&key, hash).await.unwrap();
+        assert_eq!(signature.recover(hash).unwrap(), key.address);
+        assert!(signature.v == 27 || signature.v == 28);
+        assert_ne!(signature, key.sign_message(hash).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn signs_authorization() {
+        use crate::TypedTransaction;
+        use ethers_core::types::{transaction::eip7702::Authorization, Eip7702TransactionRequest};
+
+        let wallet: Wallet<SigningKey> =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let delegate = "F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse::<Address>().unwrap();
+        let auth = Authorization::new(1, delegate, 5u64);
+
+        let signed = wallet.sign_authorization(auth).await.unwrap();
+        assert_eq!(signed.inner, auth);
+        assert!(signed.y_parity.as_u64() <= 1);
+        assert_eq!(signed.recover_authority().unwrap(), wallet.address);
+
+        // the authorization survives the encoding of the transaction it is included in
+        let tx: TypedTransaction = Eip7702TransactionRequest::new()
+            .to(wallet.address)
+            .nonce(4)
+            .gas(100_000)
+            .max_fee_per_gas(2)
+            .max_priority_fee_per_gas(1)
+            .chain_id(1)
+            .authorization(signed)
+            .into();
+        let sig = wallet.sign_transaction(&tx).await.unwrap();
+        let (decoded, _) = TypedTransaction::decode_signed(&ethers_core::utils::rlp::Rlp::new(
+            &tx.rlp_signed(&sig),
+        ))
+        .unwrap();
+        assert_eq!(decoded.from(), Some(&wallet.address));
+        let decoded = decoded.as_eip7702_ref().unwrap();
+        assert_eq!(decoded.authorization_list[0].recover_authority().unwrap(), wallet.address);
+    }
+
     #[tokio::test]
     #[cfg(not(feature = "celo"))]
     async fn signs_tx() {
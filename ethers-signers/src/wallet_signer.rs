@@ -0,0 +1,258 @@
+use crate::{
+    AuthorizationSigner, HashSigner, LocalWallet, Signer, UnsupportedOperation, WalletError,
+};
+use async_trait::async_trait;
+use ethers_core::types::{
+    transaction::{
+        eip2718::TypedTransaction,
+        eip712::Eip712,
+        eip7702::{Authorization, SignedAuthorization},
+    },
+    Address, Signature, H256,
+};
+use thiserror::Error;
+
+#[cfg(feature = "aws")]
+use crate::{AwsSigner, AwsSignerError};
+#[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+use crate::{Ledger, LedgerError};
+#[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+use crate::{Trezor, TrezorError};
+
+/// A [`Signer`] backed by any of the signers of this crate, chosen at runtime.
+///
+/// This allows storing a signer whose kind is only known at runtime, e.g. selected by the user,
+/// without resorting to trait objects, since [`Signer`] is not object safe.
+///
+/// # Example
+///
+/// ```
+/// use ethers_signers::{LocalWallet, Signer, WalletSigner};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let wallet: LocalWallet =
+///     "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse()?;
+/// let signer = WalletSigner::from(wallet).with_chain_id(1u64);
+///
+/// let signature = signer.sign_message("hello world").await?;
+/// signature.verify("hello world", signer.address())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum WalletSigner {
+    /// A wallet with a locally stored private key
+    Local(LocalWallet),
+    /// A Ledger hardware wallet
+    #[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+    Ledger(Ledger),
+    /// A Trezor hardware wallet
+    #[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+    Trezor(Trezor),
+    /// A key stored in AWS KMS
+    #[cfg(feature = "aws")]
+    Aws(AwsSigner),
+}
+
+/// Error thrown by the [`WalletSigner`]
+#[derive(Debug, Error)]
+pub enum WalletSignerError {
+    /// Error of a [`WalletSigner::Local`] signer
+    #[error(transparent)]
+    Local(#[from] WalletError),
+    /// Error of a [`WalletSigner::Ledger`] signer
+    #[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+    #[error(transparent)]
+    Ledger(#[from] LedgerError),
+    /// Error of a [`WalletSigner::Trezor`] signer
+    #[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+    #[error(transparent)]
+    Trezor(#[from] TrezorError),
+    /// Error of a [`WalletSigner::Aws`] signer
+    #[cfg(feature = "aws")]
+    #[error(transparent)]
+    Aws(#[from] AwsSignerError),
+    /// The signer does not support the operation
+    #[error(transparent)]
+    Unsupported(#[from] UnsupportedOperation),
+}
+
+/// Dispatches the expression to the signer of every variant, converting the errors
+macro_rules! dispatch {
+    ($self:ident, $signer:ident => $expr:expr) => {
+        match $self {
+            WalletSigner::Local($signer) => $expr.map_err(WalletSignerError::from),
+            #[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+            WalletSigner::Ledger($signer) => $expr.map_err(WalletSignerError::from),
+            #[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+            WalletSigner::Trezor($signer) => $expr.map_err(WalletSignerError::from),
+            #[cfg(feature = "aws")]
+            WalletSigner::Aws($signer) => $expr.map_err(WalletSignerError::from),
+        }
+    };
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for WalletSigner {
+    type Error = WalletSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        dispatch!(self, signer => signer.sign_message(message).await)
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        dispatch!(self, signer => signer.sign_transaction(tx).await)
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        dispatch!(self, signer => signer.sign_typed_data(payload).await)
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            WalletSigner::Local(signer) => signer.address(),
+            #[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+            WalletSigner::Ledger(signer) => signer.address(),
+            #[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+            WalletSigner::Trezor(signer) => signer.address(),
+            #[cfg(feature = "aws")]
+            WalletSigner::Aws(signer) => signer.address(),
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            WalletSigner::Local(signer) => signer.chain_id(),
+            #[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+            WalletSigner::Ledger(signer) => signer.chain_id(),
+            #[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+            WalletSigner::Trezor(signer) => signer.chain_id(),
+            #[cfg(feature = "aws")]
+            WalletSigner::Aws(signer) => signer.chain_id(),
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        let chain_id = chain_id.into();
+        match self {
+            WalletSigner::Local(signer) => WalletSigner::Local(signer.with_chain_id(chain_id)),
+            #[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+            WalletSigner::Ledger(signer) => WalletSigner::Ledger(signer.with_chain_id(chain_id)),
+            #[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+            WalletSigner::Trezor(signer) => WalletSigner::Trezor(signer.with_chain_id(chain_id)),
+            #[cfg(feature = "aws")]
+            WalletSigner::Aws(signer) => WalletSigner::Aws(signer.with_chain_id(chain_id)),
+        }
+    }
+}
+
+/// Hardware wallets can not sign raw hashes and return an [`UnsupportedOperation`] error.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl HashSigner for WalletSigner {
+    async fn sign_hash(&self, hash: H256) -> Result<Signature, Self::Error> {
+        match self {
+            WalletSigner::Local(signer) => Ok(signer.sign_hash(hash)?),
+            #[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+            WalletSigner::Ledger(_) => Err(UnsupportedOperation("signing raw hashes").into()),
+            #[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+            WalletSigner::Trezor(_) => Err(UnsupportedOperation("signing raw hashes").into()),
+            #[cfg(feature = "aws")]
+            WalletSigner::Aws(signer) => Ok(HashSigner::sign_hash(signer, hash).await?),
+        }
+    }
+}
+
+/// Hardware wallets can not sign authorizations and return an [`UnsupportedOperation`] error.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AuthorizationSigner for WalletSigner {
+    async fn sign_authorization(
+        &self,
+        auth: Authorization,
+    ) -> Result<SignedAuthorization, Self::Error> {
+        match self {
+            WalletSigner::Local(signer) => Ok(signer.sign_authorization(auth).await?),
+            #[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+            WalletSigner::Ledger(_) => {
+                Err(UnsupportedOperation("signing EIP-7702 authorizations").into())
+            }
+            #[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+            WalletSigner::Trezor(_) => {
+                Err(UnsupportedOperation("signing EIP-7702 authorizations").into())
+            }
+            #[cfg(feature = "aws")]
+            WalletSigner::Aws(signer) => Ok(signer.sign_authorization(auth).await?),
+        }
+    }
+}
+
+impl From<LocalWallet> for WalletSigner {
+    fn from(signer: LocalWallet) -> Self {
+        WalletSigner::Local(signer)
+    }
+}
+
+#[cfg(all(feature = "ledger", not(target_arch = "wasm32")))]
+impl From<Ledger> for WalletSigner {
+    fn from(signer: Ledger) -> Self {
+        WalletSigner::Ledger(signer)
+    }
+}
+
+#[cfg(all(feature = "trezor", not(target_arch = "wasm32")))]
+impl From<Trezor> for WalletSigner {
+    fn from(signer: Trezor) -> Self {
+        WalletSigner::Trezor(signer)
+    }
+}
+
+#[cfg(feature = "aws")]
+impl From<AwsSigner> for WalletSigner {
+    fn from(signer: AwsSigner) -> Self {
+        WalletSigner::Aws(signer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::TransactionRequest;
+
+    #[tokio::test]
+    async fn dispatches_to_local_wallet() {
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let wallet = wallet.with_chain_id(5u64);
+        let signer = WalletSigner::from(wallet.clone());
+        assert_eq!(signer.address(), wallet.address());
+        assert_eq!(signer.chain_id(), 5);
+        assert_eq!(signer.with_chain_id(1u64).chain_id(), 1);
+
+        let signer = WalletSigner::from(wallet.clone());
+        assert_eq!(
+            signer.sign_message("hello").await.unwrap(),
+            wallet.sign_message("hello").await.unwrap()
+        );
+        let tx = TransactionRequest::pay(Address::zero(), 100).nonce(0).gas(21_000).into();
+        assert_eq!(
+            signer.sign_transaction(&tx).await.unwrap(),
+            wallet.sign_transaction(&tx).await.unwrap()
+        );
+
+        let hash = H256::repeat_byte(0x11);
+        assert_eq!(signer.sign_hash(hash).await.unwrap(), wallet.sign_hash(hash).unwrap());
+        let auth = Authorization::new(1u64, Address::repeat_byte(0xde), 0u64);
+        assert_eq!(
+            signer.sign_authorization(auth).await.unwrap(),
+            wallet.sign_authorization(auth).await.unwrap()
+        );
+    }
+}